@@ -1,6 +1,7 @@
 //! Actions enable pawns to interact with the world and other entities.
 //! There are two variants of action requests, one is to list all available actions for an entity and the other is to actually execute an action.
 //! Both forms involve a modular process of obtaining one or more available action for the entity that is requesting it, running a prerequisite check and then finalizing the obtained results by prerequisite checkers. This all happens in one single frame. Therefore interacting with actions requires correct systems ordering and labelling.
+//! Actions registered in [timed::TimedActions] are held back after approval and only performed once their duration passed without interruption.
 
 /// The core action module.
 pub mod core;
@@ -10,3 +11,5 @@ pub mod net;
 pub mod networking;
 /// The Bevy plugin.
 pub mod plugin;
+/// Actions that take time to perform and can be interrupted.
+pub mod timed;
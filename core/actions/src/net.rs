@@ -9,6 +9,17 @@ use crate::networking::NetAction;
 
 pub enum ActionsServerMessage {
     TabData(Vec<NetAction>),
+    ActionProgress(ActionProgress),
+    ActionProgressEnded,
+}
+
+/// A timed action the client started performing.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ActionProgress {
+    pub target_entity_option: Option<Entity>,
+    pub target_cell_option: Option<TargetCell>,
+    /// Seconds it takes to perform the action.
+    pub duration: f32,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
@@ -10,6 +10,10 @@ use crate::{
     },
     net::{ActionsClientMessage, ActionsServerMessage},
     networking::incoming_messages,
+    timed::{
+        interrupt_actions, start_timed_actions, tick_actions_in_progress, InterruptAction,
+        TimedActions,
+    },
 };
 pub struct ActionsPlugin;
 
@@ -35,7 +39,20 @@ impl Plugin for ActionsPlugin {
                 .init_resource::<ActionRequests>()
                 .add_system(incoming_messages.in_base_set(CoreSet::PreUpdate))
                 .add_event::<InputListActions>()
-                .add_event::<InputAction>();
+                .add_event::<InputAction>()
+                .init_resource::<TimedActions>()
+                .add_event::<InterruptAction>()
+                .add_system(
+                    tick_actions_in_progress
+                        .after(ActionsLabels::Clear)
+                        .before(ActionsLabels::Init),
+                )
+                .add_system(
+                    start_timed_actions
+                        .after(ActionsLabels::Approve)
+                        .before(ActionsLabels::Action),
+                )
+                .add_system(interrupt_actions.after(ActionsLabels::Action));
         }
 
         register_reliable_message::<ActionsClientMessage>(app, MessageSender::Client);
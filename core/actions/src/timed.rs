@@ -0,0 +1,453 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::{
+    warn, Commands, Component, Entity, EventReader, EventWriter, Query, Res, ResMut, Resource,
+    Transform, Vec3,
+};
+use bevy::time::{Time, Timer, TimerMode};
+use networking::server::{HandleToEntity, OutgoingReliableServerMessage};
+use resources::{grid::TargetCell, math::cell_id_to_world};
+
+use crate::{
+    core::{ActionRequests, BuildingActions, InputAction},
+    net::{ActionProgress, ActionsServerMessage},
+};
+
+/// Duration and interruption rules of an action that takes time to perform.
+#[derive(Clone)]
+
+pub struct TimedActionSettings {
+    /// Seconds it takes to perform the action.
+    pub duration: f32,
+    /// Interrupt once the action taker is further away from the target than this.
+    pub max_distance_option: Option<f32>,
+    pub interrupt_on_damage: bool,
+    /// Interrupt when the action taker switches away from the item the action was started with.
+    pub interrupt_on_item_switch: bool,
+}
+
+/// Resource with the settings of timed actions by action identifier.
+/// Actions not in here resolve in the same frame they get approved.
+
+#[derive(Default, Resource)]
+pub struct TimedActions {
+    pub map: HashMap<String, TimedActionSettings>,
+}
+
+/// Component of action takers performing a timed action.
+#[derive(Component)]
+
+pub struct ActionInProgress {
+    /// Action identifier.
+    pub id: String,
+    pub action_taker_item: Option<Entity>,
+    pub target_entity_option: Option<Entity>,
+    pub target_cell_option: Option<TargetCell>,
+    pub timer: Timer,
+    pub settings: TimedActionSettings,
+    /// Whether the timer finished and the action was requested again to be completed.
+    pub completing: bool,
+}
+
+/// Reasons for interrupting a timed action.
+#[derive(PartialEq)]
+
+pub enum ActionInterruption {
+    Distance,
+    Damage,
+    ItemSwitch,
+    Cancelled,
+}
+
+/// Event to interrupt the action in progress of an action taker.
+/// The action only gets interrupted if its settings are subject to the reason.
+
+pub struct InterruptAction {
+    pub action_taker: Entity,
+    pub reason: ActionInterruption,
+}
+
+/// Tell the client of the action taker the action in progress ended.
+
+fn send_progress_ended(
+    action_taker: Entity,
+    handle_to_entity: &Res<HandleToEntity>,
+    server: &mut EventWriter<OutgoingReliableServerMessage<ActionsServerMessage>>,
+) {
+    match handle_to_entity.inv_map.get(&action_taker) {
+        Some(handle) => {
+            server.send(OutgoingReliableServerMessage {
+                handle: *handle,
+                message: ActionsServerMessage::ActionProgressEnded,
+            });
+        }
+        None => {}
+    }
+}
+
+/// Hold back approved timed actions and start their progress instead.
+/// Actions that finished their progress are let through to their action systems.
+
+pub(crate) fn start_timed_actions(
+    building_actions: Res<BuildingActions>,
+    mut action_requests: ResMut<ActionRequests>,
+    timed_actions: Res<TimedActions>,
+    in_progress: Query<&ActionInProgress>,
+    mut commands: Commands,
+    handle_to_entity: Res<HandleToEntity>,
+    mut server: EventWriter<OutgoingReliableServerMessage<ActionsServerMessage>>,
+) {
+    // Action takers whose finished action got requested again to be completed this frame.
+    let mut completion_requested = HashSet::new();
+    for building in building_actions.list.iter() {
+        match (
+            in_progress.get(building.action_taker),
+            action_requests.list.get(&building.incremented_i),
+        ) {
+            (Ok(progress), Some(action_request)) => {
+                if progress.completing && progress.id == action_request.get_id() {
+                    completion_requested.insert(building.action_taker);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for building in building_actions.list.iter() {
+        let action_request;
+        match action_requests.list.get_mut(&building.incremented_i) {
+            Some(r) => {
+                action_request = r;
+            }
+            None => {
+                continue;
+            }
+        }
+        match in_progress.get(building.action_taker) {
+            Ok(progress) => {
+                if progress.completing {
+                    if progress.id == action_request.get_id() {
+                        commands
+                            .entity(building.action_taker)
+                            .remove::<ActionInProgress>();
+                        send_progress_ended(building.action_taker, &handle_to_entity, &mut server);
+                        continue;
+                    }
+                    if !completion_requested.contains(&building.action_taker) {
+                        // Another request of the action taker replaced the completion request, the finished action is cancelled.
+                        warn!(
+                            "Cancelled completing timed action {} of {:?}, its request was replaced by {}.",
+                            progress.id,
+                            building.action_taker,
+                            action_request.get_id()
+                        );
+                        commands
+                            .entity(building.action_taker)
+                            .remove::<ActionInProgress>();
+                        send_progress_ended(building.action_taker, &handle_to_entity, &mut server);
+                    }
+                }
+            }
+            Err(_) => {}
+        }
+
+        let settings;
+        match timed_actions.map.get(action_request.get_id()) {
+            Some(s) => {
+                settings = s;
+            }
+            None => {
+                continue;
+            }
+        }
+
+        let approved = building.actions.iter().any(|action_data| {
+            action_data.data.id == action_request.get_id() && action_data.approved == Some(true)
+        });
+
+        let id = action_request.get_id().to_string();
+        // Keep the action systems from performing the action this frame.
+        action_request.set_id("".to_string());
+
+        if !approved {
+            continue;
+        }
+
+        commands
+            .entity(building.action_taker)
+            .insert(ActionInProgress {
+                id,
+                action_taker_item: building.action_taker_item,
+                target_entity_option: building.target_entity_option,
+                target_cell_option: building.target_cell_option.clone(),
+                timer: Timer::from_seconds(settings.duration, TimerMode::Once),
+                settings: settings.clone(),
+                completing: false,
+            });
+
+        match handle_to_entity.inv_map.get(&building.action_taker) {
+            Some(handle) => {
+                server.send(OutgoingReliableServerMessage {
+                    handle: *handle,
+                    message: ActionsServerMessage::ActionProgress(ActionProgress {
+                        target_entity_option: building.target_entity_option,
+                        target_cell_option: building.target_cell_option.clone(),
+                        duration: settings.duration,
+                    }),
+                });
+            }
+            None => {}
+        }
+    }
+}
+
+/// Tick actions in progress, interrupt them when out of range and request them again once finished.
+
+pub(crate) fn tick_actions_in_progress(
+    mut in_progress: Query<(Entity, &mut ActionInProgress)>,
+    transforms: Query<&Transform>,
+    time: Res<Time>,
+    mut input_action: EventWriter<InputAction>,
+    mut interrupt: EventWriter<InterruptAction>,
+) {
+    for (action_taker, mut progress) in in_progress.iter_mut() {
+        if progress.completing {
+            continue;
+        }
+
+        match progress.settings.max_distance_option {
+            Some(max_distance) => {
+                let target_position: Option<Vec3>;
+                match progress.target_entity_option {
+                    Some(target) => match transforms.get(target) {
+                        Ok(t) => {
+                            target_position = Some(t.translation);
+                        }
+                        Err(_) => {
+                            target_position = None;
+                        }
+                    },
+                    None => match &progress.target_cell_option {
+                        Some(cell) => {
+                            target_position = Some(cell_id_to_world(cell.id));
+                        }
+                        None => {
+                            target_position = None;
+                        }
+                    },
+                }
+                match (target_position, transforms.get(action_taker)) {
+                    (Some(target_position), Ok(taker_transform)) => {
+                        if taker_transform.translation.distance(target_position) > max_distance {
+                            interrupt.send(InterruptAction {
+                                action_taker,
+                                reason: ActionInterruption::Distance,
+                            });
+                            continue;
+                        }
+                    }
+                    _ => {
+                        interrupt.send(InterruptAction {
+                            action_taker,
+                            reason: ActionInterruption::Cancelled,
+                        });
+                        continue;
+                    }
+                }
+            }
+            None => {}
+        }
+
+        progress.timer.tick(time.delta());
+        if progress.timer.finished() {
+            progress.completing = true;
+            input_action.send(InputAction {
+                fired_action_id: progress.id.clone(),
+                action_taker,
+                action_taker_item: progress.action_taker_item,
+                target_entity_option: progress.target_entity_option,
+                target_cell_option: progress.target_cell_option.clone(),
+            });
+        }
+    }
+}
+
+/// Interrupt actions in progress.
+
+pub(crate) fn interrupt_actions(
+    mut events: EventReader<InterruptAction>,
+    in_progress: Query<&ActionInProgress>,
+    mut commands: Commands,
+    handle_to_entity: Res<HandleToEntity>,
+    mut server: EventWriter<OutgoingReliableServerMessage<ActionsServerMessage>>,
+) {
+    for event in events.iter() {
+        let progress;
+        match in_progress.get(event.action_taker) {
+            Ok(p) => {
+                progress = p;
+            }
+            Err(_) => {
+                continue;
+            }
+        }
+        let interrupts = match event.reason {
+            ActionInterruption::Distance | ActionInterruption::Cancelled => true,
+            ActionInterruption::Damage => progress.settings.interrupt_on_damage,
+            ActionInterruption::ItemSwitch => progress.settings.interrupt_on_item_switch,
+        };
+        if !interrupts || progress.completing {
+            continue;
+        }
+        commands
+            .entity(event.action_taker)
+            .remove::<ActionInProgress>();
+        send_progress_ended(event.action_taker, &handle_to_entity, &mut server);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy::prelude::{Events, IntoSystem, System, World};
+
+    use crate::core::{Action, ActionData, ActionRequest, BuildingAction};
+
+    const TIMED_ID: &str = "actions::test/timed";
+    const OTHER_ID: &str = "actions::test/other";
+
+    fn setup_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<BuildingActions>();
+        world.init_resource::<ActionRequests>();
+        world.init_resource::<HandleToEntity>();
+        world.init_resource::<Events<OutgoingReliableServerMessage<ActionsServerMessage>>>();
+        let mut timed_actions = TimedActions::default();
+        timed_actions.map.insert(
+            TIMED_ID.to_string(),
+            TimedActionSettings {
+                duration: 1.,
+                max_distance_option: None,
+                interrupt_on_damage: false,
+                interrupt_on_item_switch: false,
+            },
+        );
+        world.insert_resource(timed_actions);
+        world
+    }
+
+    fn in_progress(completing: bool) -> ActionInProgress {
+        ActionInProgress {
+            id: TIMED_ID.to_string(),
+            action_taker_item: None,
+            target_entity_option: None,
+            target_cell_option: None,
+            timer: Timer::from_seconds(1., TimerMode::Once),
+            settings: TimedActionSettings {
+                duration: 1.,
+                max_distance_option: None,
+                interrupt_on_damage: false,
+                interrupt_on_item_switch: false,
+            },
+            completing,
+        }
+    }
+
+    fn request_action(world: &mut World, action_taker: Entity, incremented_i: u64, id: &str) {
+        world
+            .resource_mut::<BuildingActions>()
+            .list
+            .push(BuildingAction {
+                actions: vec![ActionData {
+                    data: Action {
+                        id: id.to_string(),
+                        text: "".to_string(),
+                        tab_list_priority: 0,
+                    },
+                    approved: Some(true),
+                }],
+                incremented_i,
+                action_taker,
+                action_taker_item: None,
+                target_entity_option: None,
+                target_cell_option: None,
+            });
+        world
+            .resource_mut::<ActionRequests>()
+            .list
+            .insert(incremented_i, ActionRequest::from_id(id.to_string()));
+    }
+
+    fn run_start_timed_actions(world: &mut World) {
+        let mut system = IntoSystem::into_system(start_timed_actions);
+        system.initialize(world);
+        system.run((), world);
+        system.apply_buffers(world);
+    }
+
+    fn request_id(world: &World, incremented_i: u64) -> String {
+        world
+            .resource::<ActionRequests>()
+            .list
+            .get(&incremented_i)
+            .unwrap()
+            .get_id()
+            .to_string()
+    }
+
+    #[test]
+    fn test_start_timed_action_held_back() {
+        let mut world = setup_world();
+        let action_taker = world.spawn_empty().id();
+        request_action(&mut world, action_taker, 0, TIMED_ID);
+
+        run_start_timed_actions(&mut world);
+
+        assert_eq!(request_id(&world, 0), "");
+        let progress = world.get::<ActionInProgress>(action_taker).unwrap();
+        assert_eq!(progress.id, TIMED_ID);
+        assert!(!progress.completing);
+    }
+
+    #[test]
+    fn test_start_timed_action_completion() {
+        let mut world = setup_world();
+        let action_taker = world.spawn(in_progress(true)).id();
+        request_action(&mut world, action_taker, 0, TIMED_ID);
+
+        run_start_timed_actions(&mut world);
+
+        // The finished action is let through to its action systems.
+        assert_eq!(request_id(&world, 0), TIMED_ID);
+        assert!(world.get::<ActionInProgress>(action_taker).is_none());
+    }
+
+    #[test]
+    fn test_start_timed_action_completion_replaced() {
+        let mut world = setup_world();
+        let action_taker = world.spawn(in_progress(true)).id();
+        request_action(&mut world, action_taker, 0, OTHER_ID);
+
+        run_start_timed_actions(&mut world);
+
+        // The finished action is cancelled and the replacing action performed.
+        assert_eq!(request_id(&world, 0), OTHER_ID);
+        assert!(world.get::<ActionInProgress>(action_taker).is_none());
+    }
+
+    #[test]
+    fn test_start_timed_action_completion_alongside_other_request() {
+        let mut world = setup_world();
+        let action_taker = world.spawn(in_progress(true)).id();
+        request_action(&mut world, action_taker, 0, OTHER_ID);
+        request_action(&mut world, action_taker, 1, TIMED_ID);
+
+        run_start_timed_actions(&mut world);
+
+        // Another request in the same frame doesn't cancel the completion.
+        assert_eq!(request_id(&world, 0), OTHER_ID);
+        assert_eq!(request_id(&world, 1), TIMED_ID);
+        assert!(world.get::<ActionInProgress>(action_taker).is_none());
+    }
+}
@@ -4,6 +4,7 @@ use actions::plugin::ActionsPlugin;
 use airlocks::plugin::AirLocksPlugin;
 use asana::plugin::AsanaPlugin;
 use basic_console_commands::plugin::BasicConsoleCommandsPlugin;
+use battery::plugin::BatteriesPlugin;
 use bevy::app::ScheduleRunnerPlugin;
 use bevy::diagnostic::DiagnosticsPlugin;
 use bevy::log::LogPlugin;
@@ -31,43 +32,57 @@ use bevy::window::WindowPosition;
 use bevy::winit::WinitSettings;
 use bevy::DefaultPlugins;
 use bevy_egui::EguiPlugin;
+use bullet_casing::plugin::BulletCasingPlugin;
 use chat::plugin::ChatPlugin;
 use combat::plugin::CombatPlugin;
 use computers::plugin::ComputersPlugin;
 use console_commands::plugins::ConsoleCommandsPlugin;
 use construction_tool::plugin::ConstructionToolAdminPlugin;
+use content::plugin::ContentPlugin;
 use controller::plugin::ControllerPlugin;
 use counter_windows::plugin::CounterWindowsPlugin;
+use debris::plugin::DebrisPlugin;
 use entity::plugin::EntityPlugin;
 use escape_menu::plugin::EscapeMenuPlugin;
+use generator::plugin::GeneratorsPlugin;
 use graphics::plugin::GraphicsPlugin;
+use gravity::plugin::GravityPlugin;
+use gravity_generator::plugin::GravityGeneratorsPlugin;
 use gridmap::plugin::GridmapPlugin;
 use helmet_security::plugin::HelmetsPlugin;
 use hud::plugin::HudPlugin;
 use human_male::plugin::HumanMalePlugin;
 use humanoid::plugin::HumanoidPlugin;
+use id_card::plugin::IdCardsPlugin;
 use inventory::plugin::InventoryPlugin;
 use jumpsuit_security::plugin::JumpsuitsPlugin;
 use line_arrow::plugin::LineArrowPlugin;
 use line_arrow::plugin::PointArrowPlugin;
+use magazine_b1::plugin::MagazineB1Plugin;
+use magnetic_boots::plugin::MagneticBootsPlugin;
 use main_menu::plugin::MainMenuPlugin;
 use map::plugin::MapPlugin;
 use motd::motd::MOTD;
 use networking::plugin::NetworkingPlugin;
 use pawn::plugin::PawnPlugin;
 use physics::plugin::PhysicsPlugin;
+use pistol_b1::plugin::PistolB1Plugin;
 use pistol_l1::plugin::PistolL1Plugin;
 use player::plugin::PlayerPlugin;
 use point_light::plugin::PointLightPlugin;
+use power::plugin::PowerPlugin;
 use resources::core::ClientInformation;
 use resources::is_server::is_server;
 use resources::labels::StartupLabels;
 use resources::plugin::ResourcesPlugin;
+use round::plugin::RoundPlugin;
+use sectors::plugin::SectorsPlugin;
 use setup_menu::plugin::SetupMenuPlugin;
 use sfx::plugin::SfxPlugin;
 use sounds::plugin::SoundsPlugin;
 use ui::plugin::UiPlugin;
 use world::plugin::WorldPlugin;
+use world_save::plugin::WorldSavePlugin;
 
 /// The function that launches the server on application start.
 fn main() {
@@ -155,6 +170,7 @@ pub(crate) fn configure_and_start() {
         .add_plugin(CombatPlugin)
         .add_plugin(JumpsuitsPlugin)
         .add_plugin(HelmetsPlugin)
+        .add_plugin(IdCardsPlugin)
         .add_plugin(PistolL1Plugin)
         .add_plugin(LineArrowPlugin)
         .add_plugin(PointArrowPlugin)
@@ -165,6 +181,16 @@ pub(crate) fn configure_and_start() {
         .add_plugin(SetupMenuPlugin)
         .add_plugin(PhysicsPlugin)
         .add_plugin(PointLightPlugin)
+        .add_plugin(PowerPlugin)
+        .add_plugin(GeneratorsPlugin)
+        .add_plugin(BatteriesPlugin)
+        .add_plugin(GravityPlugin)
+        .add_plugin(GravityGeneratorsPlugin)
+        .add_plugin(MagneticBootsPlugin)
+        .add_plugin(PistolB1Plugin)
+        .add_plugin(MagazineB1Plugin)
+        .add_plugin(BulletCasingPlugin)
+        .add_plugin(DebrisPlugin)
         .add_plugin(BasicConsoleCommandsPlugin {
             give_all_rcon: true,
         })
@@ -177,6 +203,10 @@ pub(crate) fn configure_and_start() {
         .add_plugin(EscapeMenuPlugin)
         .add_plugin(ControllerPlugin::default())
         .add_plugin(WorldPlugin)
+        .add_plugin(WorldSavePlugin)
+        .add_plugin(RoundPlugin)
+        .add_plugin(SectorsPlugin)
+        .add_plugin(ContentPlugin)
         .add_plugin(HudPlugin)
         .run();
 }
@@ -1,4 +1,5 @@
 use bevy::prelude::{info, EventReader, Resource};
+use console_commands::admin::{AdminConsoleOutput, AdminSession};
 use console_commands::commands::InputConsoleCommand;
 
 use bevy::prelude::{Commands, EventWriter, Res};
@@ -25,26 +26,33 @@ pub fn rcon_entity_console_commands<T: EntityType + Default + Send + Sync + 'sta
     fonts: Res<Fonts>,
 ) {
     for console_command_event in queue.iter() {
-        let player_entity;
-        match connected_players.get(console_command_event.entity) {
-            Ok(s) => {
-                player_entity = s;
-            }
-            Err(_rr) => {
-                continue;
+        // Commands without an entity come from the server admin console.
+        let rcon;
+        match console_command_event.entity_option {
+            Some(entity) => match connected_players.get(entity) {
+                Ok(s) => {
+                    rcon = s.rcon;
+                }
+                Err(_rr) => {
+                    continue;
+                }
+            },
+            None => {
+                rcon = true;
             }
         }
 
-        if console_command_event.input.command == "spawn"
-            && console_command_event.input.command.len() == 3
-        {
-            let entity_name = console_command_event.input.args[0].clone();
+        if console_command_event.input.command == "spawn" {
+            let entity_name = console_command_event.args[0]
+                .string()
+                .unwrap_or_default()
+                .to_string();
 
             if entity_name != T::default().get_identity() {
                 continue;
             }
 
-            if player_entity.rcon == false {
+            if rcon == false {
                 match console_command_event.handle_option {
                     Some(t) => {
                         info!("Sent 1");
@@ -67,23 +75,27 @@ pub fn rcon_entity_console_commands<T: EntityType + Default + Send + Sync + 'sta
                 return;
             }
             let spawn_amount;
-            match console_command_event.input.args[1].parse::<i64>() {
-                Ok(t) => {
+            match console_command_event.args[1].int() {
+                Some(t) => {
                     spawn_amount = t;
                 }
-                Err(_) => {
+                None => {
                     continue;
                 }
             };
 
-            let player_selector = console_command_event.input.args[2].clone();
+            let player_selector = console_command_event.args[2]
+                .string()
+                .unwrap_or_default()
+                .to_string();
 
             rcon_spawn_event.send(RconSpawnEntity {
                 entity_type: T::default(),
                 target_selector: player_selector.to_string(),
                 spawn_amount: spawn_amount,
                 command_executor_handle_option: console_command_event.handle_option,
-                command_executor_entity: console_command_event.entity,
+                command_executor_admin_session_option: console_command_event.admin_session_option,
+                command_executor_entity_option: console_command_event.entity_option,
             });
         }
     }
@@ -100,7 +112,8 @@ pub struct RconSpawnEntity<T: EntityType + Send + Sync + 'static> {
     pub target_selector: String,
     pub spawn_amount: i64,
     pub command_executor_handle_option: Option<u64>,
-    pub command_executor_entity: Entity,
+    pub command_executor_admin_session_option: Option<AdminSession>,
+    pub command_executor_entity_option: Option<Entity>,
 }
 use entity::entity_types::EntityType;
 use entity::spawn::SpawnEntity;
@@ -117,6 +130,7 @@ pub fn rcon_spawn_entity<T: EntityType + Clone + Send + Sync + 'static>(
     mut used_names: ResMut<UsedNames>,
     handle_to_entity: Res<HandleToEntity>,
     mut default_spawner: EventWriter<SpawnEntity<T>>,
+    mut admin: EventWriter<AdminConsoleOutput>,
     fonts: Res<Fonts>,
 ) {
     for event in rcon_spawn_events.iter() {
@@ -148,11 +162,13 @@ pub fn rcon_spawn_entity<T: EntityType + Clone + Send + Sync + 'static>(
         }
 
         for target_entity in player_selector_to_entities(
-            event.command_executor_entity,
+            event.command_executor_entity_option,
             event.command_executor_handle_option,
+            event.command_executor_admin_session_option,
             &event.target_selector,
             &mut used_names,
             &mut server_1,
+            &mut admin,
             &fonts,
         )
         .iter()
@@ -239,7 +255,7 @@ pub struct RconSpawnHeldEntity<T> {
     pub entity_type: T,
     pub target_selector: String,
     pub command_executor_handle_option: Option<u64>,
-    pub command_executor_entity: Entity,
+    pub command_executor_entity_option: Option<Entity>,
 }
 
 use bevy::prelude::Local;
@@ -255,26 +271,29 @@ pub(crate) fn rcon_console_commands(
 ) {
     for console_command_event in console_commands_events.iter() {
         if console_command_event.input.command == "rcon"
-            && console_command_event.input.args.len() == 1
             && console_command_event.handle_option.is_some()
-            && !console_command_event.input.args.is_empty()
+            && console_command_event.entity_option.is_some()
         {
             rcon_authorization(
                 &mut rcon_bruteforce_protection,
                 &mut connected_players,
                 console_command_event.handle_option.unwrap(),
-                console_command_event.entity,
+                console_command_event.entity_option.unwrap(),
                 &mut server,
-                console_command_event.input.args[0].to_string(),
+                console_command_event.args[0]
+                    .string()
+                    .unwrap_or_default()
+                    .to_string(),
                 &fonts,
             );
         } else if console_command_event.input.command == "rconStatus"
             && console_command_event.handle_option.is_some()
+            && console_command_event.entity_option.is_some()
         {
             rcon_status(
                 &mut connected_players,
                 console_command_event.handle_option.unwrap(),
-                console_command_event.entity,
+                console_command_event.entity_option.unwrap(),
                 &mut server,
                 &fonts,
             );
@@ -1,16 +1,27 @@
-use std::{fs::File, io::Write, path::Path};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
 
-use bevy::prelude::{EventReader, EventWriter, Res, ResMut};
+use bevy::prelude::{Color, EventReader, EventWriter, Query, Res, ResMut, Transform, With};
 use console_commands::{
-    commands::{AllConsoleCommands, ConsoleCommand, InputConsoleCommand},
+    admin::{console_write_line, AdminConsoleOutput, AdminSession},
+    commands::{AllConsoleCommands, ConsoleArg, ConsoleCommand, InputConsoleCommand},
     net::{ConsoleCommandsServerMessage, ConsoleLine},
 };
+use entity::{
+    entity_data::{DefaultMapEntity, EntityData},
+    init::RawEntityRon,
+};
 use gridmap::grid::Gridmap;
 use hud::communication::build::CONSOLE_FONT_COLOR;
-use networking::server::OutgoingReliableServerMessage;
+use networking::server::{ConnectedPlayer, ConsoleArgVariant, OutgoingReliableServerMessage};
+use player::{boarding::SpawnPoints, spawn_points::SpawnPointRon};
+use resources::maps::current_map_name;
 use ui::{
     fonts::{Fonts, SOURCECODE_REGULAR_FONT},
-    text::{NetTextSection, COMMUNICATION_FONT_SIZE},
+    text::{NetTextSection, COMMUNICATION_FONT_SIZE, CONSOLE_ERROR_COLOR, CONSOLE_SUCCESS_COLOR},
 };
 
 pub(crate) fn add_export_map_command(mut commands: ResMut<AllConsoleCommands>) {
@@ -19,12 +30,19 @@ pub(crate) fn add_export_map_command(mut commands: ResMut<AllConsoleCommands>) {
         description: "Exports the map to ron.".to_string(),
         args: vec![],
     });
+    commands.list.push(ConsoleCommand {
+        base: "saveMap".to_string(),
+        description: "Saves the cells, entities and spawn points of the map under a map name."
+            .to_string(),
+        args: vec![ConsoleArg::new("map_name", ConsoleArgVariant::String)],
+    });
 }
 
 pub(crate) fn export_map(
     mut queue: EventReader<InputConsoleCommand>,
     gridmap: Res<Gridmap>,
     mut net: EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    mut admin: EventWriter<AdminConsoleOutput>,
     fonts: Res<Fonts>,
 ) {
     for command in queue.iter() {
@@ -34,32 +52,25 @@ pub(crate) fn export_map(
             loop {
                 let path = Path::new("data")
                     .join("maps")
-                    .join("bullseye")
+                    .join(current_map_name())
                     .join(format!("main_export{}.ron", i));
                 if !path.exists() {
                     let mut file = File::create(path.clone()).unwrap();
                     file.write_all(gridmap.export_ron().as_bytes()).unwrap();
-                    match command.handle_option {
-                        Some(handle) => {
-                            net.send(OutgoingReliableServerMessage {
-                                message: ConsoleCommandsServerMessage::ConsoleWriteLine(
-                                    ConsoleLine {
-                                        sections: vec![NetTextSection {
-                                            text: format!("Exported gridmap to {:?}", path),
-                                            font: *fonts
-                                                .inv_map
-                                                .get(SOURCECODE_REGULAR_FONT)
-                                                .unwrap(),
-                                            font_size: COMMUNICATION_FONT_SIZE,
-                                            color: CONSOLE_FONT_COLOR,
-                                        }],
-                                    },
-                                ),
-                                handle,
-                            });
-                        }
-                        None => {}
-                    }
+                    console_write_line(
+                        command.handle_option,
+                        command.admin_session_option,
+                        ConsoleLine {
+                            sections: vec![NetTextSection {
+                                text: format!("Exported gridmap to {:?}", path),
+                                font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+                                font_size: COMMUNICATION_FONT_SIZE,
+                                color: CONSOLE_FONT_COLOR,
+                            }],
+                        },
+                        &mut net,
+                        &mut admin,
+                    );
                     break;
                 } else {
                     i += 1;
@@ -68,3 +79,179 @@ pub(crate) fn export_map(
         }
     }
 }
+
+/// Whether a map name is safe to use as a directory name.
+
+fn valid_map_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Write the map files to data/maps/<map_name>.
+
+fn write_map_files(
+    map_name: &str,
+    gridmap: &Gridmap,
+    entities: &[RawEntityRon],
+    spawn_points: &[SpawnPointRon],
+) -> std::io::Result<()> {
+    let directory = Path::new("data").join("maps").join(map_name);
+    fs::create_dir_all(&directory)?;
+
+    for (file_name, content) in [
+        ("main.ron", gridmap.export_ron()),
+        ("details1.ron", gridmap.export_details1_ron()),
+        (
+            "mainordered.ron",
+            ron::to_string(&gridmap.ordered_main_names).unwrap(),
+        ),
+        (
+            "details1ordered.ron",
+            ron::to_string(&gridmap.ordered_details1_names).unwrap(),
+        ),
+        ("entities.ron", ron::to_string(entities).unwrap()),
+        ("spawnpoints.ron", ron::to_string(spawn_points).unwrap()),
+    ] {
+        File::create(directory.join(file_name))?.write_all(content.as_bytes())?;
+    }
+    Ok(())
+}
+
+fn save_map_reply(
+    handle_option: Option<u64>,
+    admin_session_option: Option<AdminSession>,
+    text: String,
+    color: Color,
+    net: &mut EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    admin: &mut EventWriter<AdminConsoleOutput>,
+    fonts: &Res<Fonts>,
+) {
+    console_write_line(
+        handle_option,
+        admin_session_option,
+        ConsoleLine {
+            sections: vec![NetTextSection {
+                text,
+                font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+                font_size: COMMUNICATION_FONT_SIZE,
+                color,
+            }],
+        },
+        net,
+        admin,
+    );
+}
+
+/// Save the gridmap, map entities and spawn points together under a map name.
+
+pub(crate) fn save_map(
+    mut queue: EventReader<InputConsoleCommand>,
+    gridmap: Res<Gridmap>,
+    map_entities: Query<(&EntityData, &Transform), With<DefaultMapEntity>>,
+    spawn_points: Res<SpawnPoints>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    mut admin: EventWriter<AdminConsoleOutput>,
+    fonts: Res<Fonts>,
+) {
+    for command in queue.iter() {
+        if command.input.command != "saveMap" {
+            continue;
+        }
+
+        match command.handle_option {
+            Some(_) => {
+                let rcon = match command.entity_option {
+                    Some(entity) => match connected_players.get(entity) {
+                        Ok(player) => player.rcon,
+                        Err(_) => false,
+                    },
+                    None => false,
+                };
+                if !rcon {
+                    save_map_reply(
+                        command.handle_option,
+                        command.admin_session_option,
+                        "RCON status denied.".to_string(),
+                        CONSOLE_ERROR_COLOR,
+                        &mut net,
+                        &mut admin,
+                        &fonts,
+                    );
+                    continue;
+                }
+            }
+            None => {}
+        }
+
+        let map_name = command.args[0].string().unwrap_or_default().to_string();
+        if !valid_map_name(&map_name) {
+            save_map_reply(
+                command.handle_option,
+                command.admin_session_option,
+                format!(
+                    "Invalid map name \"{}\", use letters, digits, - and _.",
+                    map_name
+                ),
+                CONSOLE_ERROR_COLOR,
+                &mut net,
+                &mut admin,
+                &fonts,
+            );
+            continue;
+        }
+
+        let entities: Vec<RawEntityRon> = map_entities
+            .iter()
+            .map(|(entity_data, transform)| RawEntityRon {
+                entity_type: entity_data.entity_type.get_identity(),
+                translation: transform.translation,
+                rotation: transform.rotation,
+                scale: transform.scale,
+                data: "".to_string(),
+            })
+            .collect();
+        let points: Vec<SpawnPointRon> = spawn_points
+            .list
+            .iter()
+            .map(|point| SpawnPointRon {
+                point_type: point.point_type.clone(),
+                translation: point.transform.translation,
+                rotation: point.transform.rotation,
+                scale: point.transform.scale,
+            })
+            .collect();
+
+        match write_map_files(&map_name, &gridmap, &entities, &points) {
+            Ok(_) => {
+                save_map_reply(
+                    command.handle_option,
+                    command.admin_session_option,
+                    format!(
+                        "Saved map {} with {} entities and {} spawn points.",
+                        map_name,
+                        entities.len(),
+                        points.len()
+                    ),
+                    CONSOLE_SUCCESS_COLOR,
+                    &mut net,
+                    &mut admin,
+                    &fonts,
+                );
+            }
+            Err(error) => {
+                save_map_reply(
+                    command.handle_option,
+                    command.admin_session_option,
+                    format!("Failed to save map {}: {}", map_name, error),
+                    CONSOLE_ERROR_COLOR,
+                    &mut net,
+                    &mut admin,
+                    &fonts,
+                );
+            }
+        }
+    }
+}
@@ -5,6 +5,8 @@ use bevy::{
 use console_commands::{
     commands::{AllConsoleCommands, ConsoleCommand},
     net::ClientSideConsoleInput,
+    networking::ServerConsoleCommands,
+    parse::console_command_usage,
 };
 use hud::communication::console::DisplayConsoleMessage;
 use ui::{fonts::SOURCECODE_REGULAR_FONT, text::COMMUNICATION_FONT_SIZE};
@@ -20,6 +22,7 @@ pub(crate) fn help_command(
     mut queue: EventReader<ClientSideConsoleInput>,
     mut console: EventWriter<DisplayConsoleMessage>,
     commands: Res<AllConsoleCommands>,
+    server_commands: Res<ServerConsoleCommands>,
     asset_server: Res<AssetServer>,
 ) {
     for input in queue.iter() {
@@ -28,23 +31,27 @@ pub(crate) fn help_command(
         }
         let mut console_message = "".to_string();
 
+        let mut all_commands: Vec<&ConsoleCommand> = commands.list.iter().collect();
+        for command in server_commands.list.iter() {
+            if !all_commands.iter().any(|c| c.base == command.base) {
+                all_commands.push(command);
+            }
+        }
+
         if input.args.len() == 1 {
             let arg = input.args.get(0).unwrap();
             let info_command = arg.clone();
 
-            for command in commands.list.iter() {
+            for command in all_commands.iter() {
                 if command.base == info_command {
                     console_message.push_str(
-                        &(command.description.clone() + "\n Usage: " + &command.base + " "),
+                        &(command.description.clone() + "\n " + &console_command_usage(command)),
                     );
-                    for arg in command.args.iter() {
-                        console_message.push_str(&format!("{}({:?}) ", arg.0, arg.1));
-                    }
                 }
             }
         } else {
             console_message+="For more information about a specific command write help [command].\nAvailable console commands:\n";
-            for command in commands.list.iter() {
+            for command in all_commands.iter() {
                 console_message += &format!("{}: {} \n\n", command.base, command.description);
             }
         }
@@ -5,6 +5,10 @@ pub mod plugin;
 
 pub mod gridmap;
 pub mod help;
+/// Kick, ban and mute commands.
+pub mod moderation;
 /// Select players with special text character-based queries.
 pub mod player_selectors;
 pub mod register;
+/// Move players between the sectors of a galaxy cluster.
+pub mod sectors;
@@ -0,0 +1,328 @@
+use std::time::Duration;
+
+use bevy::prelude::{Color, EventReader, EventWriter, Query, Res, ResMut};
+use console_commands::{
+    admin::{console_write_line, AdminConsoleOutput, AdminSession},
+    commands::{AllConsoleCommands, ConsoleArg, ConsoleCommand, InputConsoleCommand},
+    net::{ConsoleCommandsServerMessage, ConsoleLine},
+};
+use networking::server::{
+    ConnectedPlayer, ConsoleArgVariant, DisconnectClient, DisconnectReason, HandleToEntity,
+    OutgoingReliableServerMessage,
+};
+use player::{
+    account::Accounts,
+    moderation::{unix_now, BanPlayer, Bans, Mute, MuteScope, Mutes},
+    names::UsedNames,
+};
+use ui::{
+    fonts::{Fonts, SOURCECODE_REGULAR_FONT},
+    text::{NetTextSection, COMMUNICATION_FONT_SIZE, CONSOLE_ERROR_COLOR, CONSOLE_SUCCESS_COLOR},
+};
+
+use crate::player_selectors::player_selector_to_entities;
+
+pub(crate) fn add_moderation_commands(mut commands: ResMut<AllConsoleCommands>) {
+    commands.list.push(ConsoleCommand {
+        base: "kick".to_string(),
+        description: "Kick players from the server.".to_string(),
+        args: vec![
+            ConsoleArg::new("player_selector", ConsoleArgVariant::String),
+            ConsoleArg::optional("reason", ConsoleArgVariant::String, "No reason given."),
+        ],
+    });
+    commands.list.push(ConsoleCommand {
+        base: "ban".to_string(),
+        description: "Ban the accounts and addresses of players. 0 minutes is permanent."
+            .to_string(),
+        args: vec![
+            ConsoleArg::new("player_selector", ConsoleArgVariant::String),
+            ConsoleArg::new("minutes", ConsoleArgVariant::Int),
+            ConsoleArg::optional("reason", ConsoleArgVariant::String, "No reason given."),
+        ],
+    });
+    commands.list.push(ConsoleCommand {
+        base: "unban".to_string(),
+        description: "Lift the bans on an account.".to_string(),
+        args: vec![ConsoleArg::new("account_name", ConsoleArgVariant::String)],
+    });
+    commands.list.push(ConsoleCommand {
+        base: "mute".to_string(),
+        description: "Mute players in the global chat or in all chat. 0 minutes is permanent."
+            .to_string(),
+        args: vec![
+            ConsoleArg::new("player_selector", ConsoleArgVariant::String),
+            ConsoleArg::optional("global|all", ConsoleArgVariant::String, "global"),
+            ConsoleArg::optional("minutes", ConsoleArgVariant::Int, "0"),
+        ],
+    });
+    commands.list.push(ConsoleCommand {
+        base: "unmute".to_string(),
+        description: "Unmute players.".to_string(),
+        args: vec![ConsoleArg::new(
+            "player_selector",
+            ConsoleArgVariant::String,
+        )],
+    });
+}
+
+/// Write a line to the console of the command executor.
+
+fn console_reply(
+    handle_option: Option<u64>,
+    admin_session_option: Option<AdminSession>,
+    text: String,
+    color: Color,
+    net: &mut EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    admin: &mut EventWriter<AdminConsoleOutput>,
+    fonts: &Res<Fonts>,
+) {
+    console_write_line(
+        handle_option,
+        admin_session_option,
+        ConsoleLine {
+            sections: vec![NetTextSection {
+                text,
+                font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+                font_size: COMMUNICATION_FONT_SIZE,
+                color,
+            }],
+        },
+        net,
+        admin,
+    );
+}
+
+/// Minutes argument to a duration. Permanent if zero.
+
+fn minutes_to_duration(minutes: i64) -> Result<Option<Duration>, ()> {
+    match minutes {
+        0 => Ok(None),
+        m if m > 0 => Ok(Some(Duration::from_secs(m as u64 * 60))),
+        _ => Err(()),
+    }
+}
+
+/// Process the kick, ban, unban, mute and unmute console commands.
+
+pub(crate) fn moderation_commands(
+    mut queue: EventReader<InputConsoleCommand>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut used_names: ResMut<UsedNames>,
+    handle_to_entity: Res<HandleToEntity>,
+    accounts: Res<Accounts>,
+    mut bans: ResMut<Bans>,
+    mut mutes: ResMut<Mutes>,
+    mut disconnect: EventWriter<DisconnectClient>,
+    mut ban_player: EventWriter<BanPlayer>,
+    mut admin: EventWriter<AdminConsoleOutput>,
+    fonts: Res<Fonts>,
+) {
+    for command in queue.iter() {
+        match command.input.command.as_str() {
+            "kick" | "ban" | "unban" | "mute" | "unmute" => {}
+            _ => {
+                continue;
+            }
+        }
+
+        match command.handle_option {
+            Some(_) => {
+                let rcon = match command.entity_option {
+                    Some(entity) => match connected_players.get(entity) {
+                        Ok(player) => player.rcon,
+                        Err(_) => false,
+                    },
+                    None => false,
+                };
+                if !rcon {
+                    console_reply(
+                        command.handle_option,
+                        command.admin_session_option,
+                        "RCON status denied.".to_string(),
+                        CONSOLE_ERROR_COLOR,
+                        &mut net,
+                        &mut admin,
+                        &fonts,
+                    );
+                    continue;
+                }
+            }
+            None => {}
+        }
+
+        let args = &command.args;
+        let selector = args[0].string().unwrap_or_default().to_string();
+
+        if command.input.command == "unban" {
+            let account_name = selector;
+            let before = bans.list.len();
+            bans.list.retain(|ban| ban.account_name != account_name);
+            if bans.list.len() == before {
+                console_reply(
+                    command.handle_option,
+                    command.admin_session_option,
+                    format!("{} is not banned.", account_name),
+                    CONSOLE_ERROR_COLOR,
+                    &mut net,
+                    &mut admin,
+                    &fonts,
+                );
+            } else {
+                bans.save();
+                console_reply(
+                    command.handle_option,
+                    command.admin_session_option,
+                    format!("Unbanned {}.", account_name),
+                    CONSOLE_SUCCESS_COLOR,
+                    &mut net,
+                    &mut admin,
+                    &fonts,
+                );
+            }
+            continue;
+        }
+
+        let mut duration_option = None;
+        let mut scope = MuteScope::GlobalChat;
+        let mut reason = String::new();
+        let minutes_option;
+        match command.input.command.as_str() {
+            "kick" => {
+                reason = args[1].string().unwrap_or_default().to_string();
+                minutes_option = None;
+            }
+            "ban" => {
+                minutes_option = args[1].int();
+                reason = args[2].string().unwrap_or_default().to_string();
+            }
+            "mute" => {
+                match args[1].string().unwrap_or_default() {
+                    "global" => {}
+                    "all" => {
+                        scope = MuteScope::AllChat;
+                    }
+                    s => {
+                        console_reply(
+                            command.handle_option,
+                            command.admin_session_option,
+                            format!("Invalid mute scope {}, expected global or all.", s),
+                            CONSOLE_ERROR_COLOR,
+                            &mut net,
+                            &mut admin,
+                            &fonts,
+                        );
+                        continue;
+                    }
+                }
+                minutes_option = args[2].int();
+            }
+            _ => {
+                minutes_option = None;
+            }
+        }
+        match minutes_option {
+            Some(minutes) => match minutes_to_duration(minutes) {
+                Ok(d) => {
+                    duration_option = d;
+                }
+                Err(_) => {
+                    console_reply(
+                        command.handle_option,
+                        command.admin_session_option,
+                        format!("Invalid amount of minutes: {}", minutes),
+                        CONSOLE_ERROR_COLOR,
+                        &mut net,
+                        &mut admin,
+                        &fonts,
+                    );
+                    continue;
+                }
+            },
+            None => {}
+        }
+
+        let targets = player_selector_to_entities(
+            command.entity_option,
+            command.handle_option,
+            command.admin_session_option,
+            &selector,
+            &mut used_names,
+            &mut net,
+            &mut admin,
+            &fonts,
+        );
+
+        for target in targets {
+            let handle;
+            match handle_to_entity.inv_map.get(&target) {
+                Some(h) => {
+                    handle = *h;
+                }
+                None => {
+                    continue;
+                }
+            }
+            let account_name;
+            match accounts.list.get(&handle) {
+                Some(n) => {
+                    account_name = n.clone();
+                }
+                None => {
+                    continue;
+                }
+            }
+
+            let text;
+            match command.input.command.as_str() {
+                "kick" => {
+                    disconnect.send(DisconnectClient {
+                        handle,
+                        reason: DisconnectReason::Kicked(reason.clone()),
+                    });
+                    text = format!("Kicked {}.", account_name);
+                }
+                "ban" => {
+                    ban_player.send(BanPlayer {
+                        handle,
+                        duration_option,
+                        reason: reason.clone(),
+                    });
+                    text = format!("Banned {}.", account_name);
+                }
+                "mute" => {
+                    mutes.map.insert(
+                        account_name.clone(),
+                        Mute {
+                            scope: scope.clone(),
+                            expires_option: duration_option
+                                .map(|duration| unix_now() + duration.as_secs()),
+                        },
+                    );
+                    text = format!("Muted {}.", account_name);
+                }
+                "unmute" => match mutes.map.remove(&account_name) {
+                    Some(_) => {
+                        text = format!("Unmuted {}.", account_name);
+                    }
+                    None => {
+                        text = format!("{} is not muted.", account_name);
+                    }
+                },
+                _ => {
+                    continue;
+                }
+            }
+            console_reply(
+                command.handle_option,
+                command.admin_session_option,
+                text,
+                CONSOLE_SUCCESS_COLOR,
+                &mut net,
+                &mut admin,
+                &fonts,
+            );
+        }
+    }
+}
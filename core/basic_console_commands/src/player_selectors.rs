@@ -1,9 +1,12 @@
-use bevy::prelude::{warn, Entity, Res, ResMut};
+use bevy::prelude::{Entity, Res, ResMut};
 
 use player::names::UsedNames;
 
 use bevy::prelude::EventWriter;
-use console_commands::net::{ConsoleCommandsServerMessage, ConsoleLine};
+use console_commands::{
+    admin::{console_write_line, AdminConsoleOutput, AdminSession},
+    net::{ConsoleCommandsServerMessage, ConsoleLine},
+};
 use networking::server::OutgoingReliableServerMessage;
 use ui::{
     fonts::{Fonts, SOURCECODE_REGULAR_FONT},
@@ -13,17 +16,20 @@ use ui::{
 /// Player selector to entities.
 
 pub(crate) fn player_selector_to_entities(
-    command_executor_entity: Entity,
+    command_executor_entity_option: Option<Entity>,
     command_executor_handle_option: Option<u64>,
+    command_executor_admin_session_option: Option<AdminSession>,
     mut player_selector: &str,
     used_names: &mut ResMut<UsedNames>,
     server: &mut EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    admin: &mut EventWriter<AdminConsoleOutput>,
     fonts: &Res<Fonts>,
 ) -> Vec<Entity> {
     if player_selector == "*" {
         return used_names.names.values().copied().collect();
     } else if player_selector == "@me" {
-        return vec![command_executor_entity];
+        // The server admin console has no entity of its own.
+        return command_executor_entity_option.into_iter().collect();
     }
 
     let precise_match = if (player_selector.starts_with('"') && player_selector.ends_with('"'))
@@ -71,25 +77,20 @@ pub(crate) fn player_selector_to_entities(
             format!("Player selector \"{player_selector}\" is not specific enough.\n{names}")
         }
     };
-    match command_executor_handle_option {
-        Some(handle) => {
-            let section = NetTextSection {
-                text: message.to_string(),
-                font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
-                font_size: COMMUNICATION_FONT_SIZE,
-                color: CONSOLE_ERROR_COLOR,
-            };
-
-            server.send(OutgoingReliableServerMessage {
-                handle,
-                message: ConsoleCommandsServerMessage::ConsoleWriteLine(ConsoleLine {
-                    sections: vec![section],
-                }),
-            });
-        }
-        None => {
-            warn!("Couldnt find connection handle.");
-        }
-    }
+    let section = NetTextSection {
+        text: message.to_string(),
+        font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+        font_size: COMMUNICATION_FONT_SIZE,
+        color: CONSOLE_ERROR_COLOR,
+    };
+    console_write_line(
+        command_executor_handle_option,
+        command_executor_admin_session_option,
+        ConsoleLine {
+            sections: vec![section],
+        },
+        server,
+        admin,
+    );
     return vec![];
 }
@@ -4,8 +4,10 @@ use resources::is_server::is_server;
 
 use crate::{
     commands::{rcon_console_commands, GiveAllRCON},
-    gridmap::{add_export_map_command, export_map},
+    gridmap::{add_export_map_command, export_map, save_map},
     help::{add_help_command, help_command},
+    moderation::{add_moderation_commands, moderation_commands},
+    sectors::{add_sector_command, sector_command},
 };
 
 #[derive(Default)]
@@ -20,11 +22,16 @@ impl Plugin for BasicConsoleCommandsPlugin {
                 .insert_resource::<GiveAllRCON>(GiveAllRCON {
                     give: self.give_all_rcon,
                 })
-                .add_system(export_map);
+                .add_system(export_map)
+                .add_system(save_map)
+                .add_system(moderation_commands)
+                .add_system(sector_command);
         } else {
             app.add_startup_system(add_help_command)
                 .add_system(help_command.after(console_input));
         }
-        app.add_startup_system(add_export_map_command);
+        app.add_startup_system(add_export_map_command)
+            .add_startup_system(add_moderation_commands)
+            .add_startup_system(add_sector_command);
     }
 }
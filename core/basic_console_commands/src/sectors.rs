@@ -0,0 +1,105 @@
+use bevy::prelude::{EventReader, EventWriter, Query, Res, ResMut};
+use console_commands::{
+    admin::{console_write_line, AdminConsoleOutput},
+    commands::{AllConsoleCommands, ConsoleArg, ConsoleCommand, InputConsoleCommand},
+    net::{ConsoleCommandsServerMessage, ConsoleLine},
+};
+use networking::server::{
+    ConnectedPlayer, ConsoleArgVariant, HandleToEntity, OutgoingReliableServerMessage,
+};
+use player::names::UsedNames;
+use sectors::transfer::TransferPawn;
+use ui::{
+    fonts::{Fonts, SOURCECODE_REGULAR_FONT},
+    text::{NetTextSection, COMMUNICATION_FONT_SIZE, CONSOLE_ERROR_COLOR, CONSOLE_SUCCESS_COLOR},
+};
+
+use crate::player_selectors::player_selector_to_entities;
+
+pub(crate) fn add_sector_command(mut commands: ResMut<AllConsoleCommands>) {
+    commands.list.push(ConsoleCommand {
+        base: "sector".to_string(),
+        description: "Move players to another sector of the galaxy cluster.".to_string(),
+        args: vec![
+            ConsoleArg::new("player_selector", ConsoleArgVariant::String),
+            ConsoleArg::new("sector", ConsoleArgVariant::String),
+        ],
+    });
+}
+
+/// Process the sector console command.
+
+pub(crate) fn sector_command(
+    mut queue: EventReader<InputConsoleCommand>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut used_names: ResMut<UsedNames>,
+    handle_to_entity: Res<HandleToEntity>,
+    mut transfer: EventWriter<TransferPawn>,
+    mut admin: EventWriter<AdminConsoleOutput>,
+    fonts: Res<Fonts>,
+) {
+    for command in queue.iter() {
+        if command.input.command != "sector" {
+            continue;
+        }
+
+        let text;
+        let color;
+        let rcon = match command.entity_option {
+            Some(entity) => match connected_players.get(entity) {
+                Ok(player) => player.rcon,
+                Err(_) => false,
+            },
+            None => true,
+        };
+        if !rcon {
+            text = "RCON status denied.".to_string();
+            color = CONSOLE_ERROR_COLOR;
+        } else {
+            let selector = command.args[0].string().unwrap_or_default().to_string();
+            let sector = command.args[1].string().unwrap_or_default().to_string();
+
+            let targets = player_selector_to_entities(
+                command.entity_option,
+                command.handle_option,
+                command.admin_session_option,
+                &selector,
+                &mut used_names,
+                &mut net,
+                &mut admin,
+                &fonts,
+            );
+            let mut moved = 0;
+            for target in targets {
+                match handle_to_entity.inv_map.get(&target) {
+                    Some(handle) => {
+                        transfer.send(TransferPawn {
+                            handle: *handle,
+                            sector: sector.clone(),
+                        });
+                        moved += 1;
+                    }
+                    None => {}
+                }
+            }
+            text = format!("Moving {} players to sector {}.", moved, sector);
+            color = CONSOLE_SUCCESS_COLOR;
+        }
+
+        console_write_line(
+            command.handle_option,
+            command.admin_session_option,
+            ConsoleLine {
+                sections: vec![NetTextSection {
+                    text,
+                    font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+                    font_size: COMMUNICATION_FONT_SIZE,
+                    color,
+                }],
+            },
+            &mut net,
+            &mut admin,
+        );
+    }
+}
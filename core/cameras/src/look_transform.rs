@@ -73,7 +73,7 @@ impl Smoother {
         }
     }
 
-    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+    pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
         if self.enabled {
             // To prevent camera jumping from last lerp before disabling to the current position,
@@ -109,7 +109,7 @@ impl Smoother {
     }
 }
 
-fn look_transform_system(
+pub fn look_transform_system(
     mut cameras: Query<(&LookTransform, &mut Transform, Option<&mut Smoother>)>,
 ) {
     for (look_transform, mut scene_transform, smoother) in cameras.iter_mut() {
@@ -3,6 +3,7 @@ use networking::server::{
     ConnectedPlayer, IncomingReliableClientMessage, OutgoingReliableServerMessage,
 };
 use player::account::Accounts;
+use player::moderation::{MuteScope, Mutes};
 use ui::{
     fonts::{Fonts, SOURCECODE_REGULAR_FONT},
     text::{NetTextSection, COMMUNICATION_FONT_SIZE},
@@ -18,10 +19,36 @@ pub struct GlobalChatMessage {
 pub(crate) fn chat_net_input(
     mut net: EventReader<IncomingReliableClientMessage<ChatClientMessage>>,
     mut events: EventWriter<GlobalChatMessage>,
+    accounts: Res<Accounts>,
+    mutes: Res<Mutes>,
+    mut outgoing: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    fonts: Res<Fonts>,
 ) {
     for message in net.iter() {
         match &message.message {
             ChatClientMessage::InputChatMessage(input) => {
+                match accounts.list.get(&message.handle) {
+                    Some(account_name) => {
+                        if mutes.is_muted(account_name, MuteScope::GlobalChat) {
+                            outgoing.send(OutgoingReliableServerMessage {
+                                handle: message.handle,
+                                message: ChatServerMessage::ChatMessage(ChatMessage {
+                                    sections: vec![NetTextSection {
+                                        text: "You are muted.".to_string(),
+                                        font: *fonts
+                                            .inv_map
+                                            .get(SOURCECODE_REGULAR_FONT)
+                                            .expect("Could not get font."),
+                                        font_size: COMMUNICATION_FONT_SIZE,
+                                        color: Color::RED,
+                                    }],
+                                }),
+                            });
+                            continue;
+                        }
+                    }
+                    None => {}
+                }
                 events.send(GlobalChatMessage {
                     message: input.clone(),
                     sender: message.handle,
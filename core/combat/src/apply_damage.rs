@@ -1,6 +1,6 @@
 use bevy::prelude::{warn, Entity, EventReader, EventWriter, Query, Res, ResMut, Resource};
 use entity::health::{HealthComponent, HealthContainer};
-use gridmap::grid::{AddTile, Gridmap};
+use gridmap::{destruction::DamageTile, grid::Gridmap};
 use inventory::server::combat::{DamageModel, MeleeCombat, ProjectileCombat};
 use resources::math::Vec3Int;
 
@@ -160,9 +160,9 @@ pub(crate) fn finalize_apply_damage(
     combat_storage: Res<ActiveAttacks>,
     mut health_entities: Query<&mut HealthComponent>,
     mut health_combat_hit_result: EventWriter<HealthCombatHitResult>,
-    _gridmap_main: Res<Gridmap>,
+    gridmap_main: Res<Gridmap>,
     mut active_applydamage: ResMut<ActiveApplyDamage>,
-    mut _set_cell: EventWriter<AddTile>,
+    mut damage_tile: EventWriter<DamageTile>,
 ) {
     for damage_appler in active_applydamage.list.iter() {
         let attack_cache;
@@ -193,7 +193,7 @@ pub(crate) fn finalize_apply_damage(
         }
 
         let mut entity_hits = vec![];
-        let cell_hits = vec![];
+        let mut cell_hits = vec![];
 
         let mut brute_multiplier = 1.;
         let mut burn_multiplier = 1.;
@@ -275,55 +275,38 @@ pub(crate) fn finalize_apply_damage(
                     }
                 }
             }
-            /*
-            for cell_id in hit_result.cell_hits.iter() {
-                match gridmap_main.get_cell(cell_id.cell) {
-                    Some(cell_data) => match cell_data.clone().health.health_container {
-                        HealthContainer::Structure(_) => {
-                            let (brute_damage, burn_damage, toxin_damage, hit_result) =
-                                calculate_damage(
-                                    &cell_data.health.health_flags,
-                                    &cell_data.health.damage_flags,
-                                    &apply_damage_model.damage_model.brute,
-                                    &apply_damage_model.damage_model.burn,
-                                    &apply_damage_model.damage_model.toxin,
-                                );
-
-                            let mut new_cell_data = cell_data.clone();
-                            match &mut new_cell_data.health.health_container {
-                                HealthContainer::Structure(cont) => {
-                                    cont.brute += brute_damage;
-                                    cont.burn += burn_damage;
-                                    cont.toxin += toxin_damage;
-                                }
-                                _ => (),
-                            }
+            for cell_hit in hit_result.cell_hits.iter() {
+                match gridmap_main.get_cell(cell_hit.cell.clone()) {
+                    Some(cell_data) => {
+                        let (brute_damage, burn_damage, toxin_damage, new_hit_result) =
+                            calculate_damage(
+                                &cell_data.health.health_flags,
+                                &apply_damage_model.damage_model.damage_flags,
+                                &(&apply_damage_model.damage_model.brute * brute_multiplier),
+                                &(&apply_damage_model.damage_model.burn * burn_multiplier),
+                                &(&apply_damage_model.damage_model.toxin * toxin_multiplier),
+                            );
 
-                            set_cell.send(SetCell {
-                                id: cell_id.cell,
-                                data: new_cell_data,
+                        damage_tile.send(DamageTile {
+                            cell: cell_hit.cell.clone(),
+                            brute: brute_damage,
+                            burn: burn_damage,
+                            toxin: toxin_damage,
+                        });
+
+                        if apply_damage_model.signature == "main" {
+                            cell_hits.push(CellHit {
+                                cell_id: cell_hit.cell.id,
+                                hit_result: new_hit_result,
                             });
-
-                            if apply_damage_model.signature == "main" {
-                                cell_hits.push(CellHit {
-                                    cell_id: cell_id.cell,
-                                    hit_result,
-                                });
-                            }
-                        }
-                        HealthContainer::Humanoid(_) => {
-                            info!("struck humanoid instead.");
                         }
-                        HealthContainer::Entity(_) => {
-                            info!("struck entity instead.");
-                        }
-                    },
+                    }
                     None => {
                         warn!("Couldnt find cellid in grid_map main.");
                         continue;
                     }
                 }
-            }*/
+            }
         }
 
         health_combat_hit_result.send(HealthCombatHitResult {
@@ -334,3 +317,26 @@ pub(crate) fn finalize_apply_damage(
     }
     active_applydamage.list.clear();
 }
+
+use actions::timed::{ActionInterruption, InterruptAction};
+
+/// Interrupt the actions in progress of entities that took damage.
+
+pub(crate) fn interrupt_actions_on_damage(
+    mut combat_hit_results: EventReader<HealthCombatHitResult>,
+    mut interrupt: EventWriter<InterruptAction>,
+) {
+    for hit_result in combat_hit_results.iter() {
+        for entity_hit in hit_result.entities_hits.iter() {
+            match entity_hit.hit_result {
+                HitResult::HitSoft => {
+                    interrupt.send(InterruptAction {
+                        action_taker: entity_hit.entity,
+                        reason: ActionInterruption::Damage,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use bevy::{math::Vec3, prelude::Entity};
 use entity::health::{DamageFlag, HealthFlag};
-use resources::math::Vec3Int;
+use resources::{grid::TargetCell, math::Vec3Int};
 
 /// The attack event.
 #[derive(Clone)]
@@ -47,7 +47,7 @@ pub struct EntityHitSimple {
 #[derive(Clone)]
 
 pub struct CellHitSimple {
-    pub cell: Vec3Int,
+    pub cell: TargetCell,
     pub hit_point: Vec3,
 }
 
@@ -120,6 +120,9 @@ pub fn calculate_damage(
     } else if damager_flags.contains(&&DamageFlag::WeakLethalLaser) && is_armour_plated {
         output_burn *= 0.05;
         hit_result = HitResult::Blocked;
+    } else if damager_flags.contains(&&DamageFlag::Ballistic) && is_armour_plated {
+        output_brute *= 0.25;
+        hit_result = HitResult::Blocked;
     }
 
     (output_brute, output_burn, output_toxin, hit_result)
@@ -0,0 +1,408 @@
+use actions::core::{Action, ActionData, ActionRequests, BuildingActions};
+use bevy::{
+    math::Vec3,
+    prelude::{warn, Component, Entity, EventWriter, Query, Res, ResMut, Resource, Transform},
+    time::{Time, Timer},
+};
+use entity::examine::Examinable;
+use inventory::{item::InventoryItem, server::inventory::Inventory};
+use networking::server::{
+    HandleToEntity, NetworkingChatServerMessage, OutgoingReliableServerMessage,
+};
+use text_api::core::FURTHER_ITALIC_FONT;
+
+use crate::{
+    active_attacks::{ActiveAttack, ActiveAttacks},
+    attack::{Attack, QueryCombatHitResult},
+    projectile_queries::ProjectileBlank,
+};
+
+pub const CALIBER_9MM: &str = "9mm";
+
+/// Magazines hold the rounds of ballistic weapons.
+#[derive(Component, Clone, Debug)]
+
+pub struct Magazine {
+    /// Only weapons of the same caliber accept the magazine.
+    pub caliber: String,
+    pub capacity: u16,
+    pub rounds: u16,
+}
+
+impl Magazine {
+    pub fn new_full(caliber: &str, capacity: u16) -> Self {
+        Self {
+            caliber: caliber.to_string(),
+            capacity,
+            rounds: capacity,
+        }
+    }
+}
+
+/// Projectile weapons that consume rounds of their loaded magazine.
+#[derive(Component)]
+
+pub struct BallisticWeapon {
+    /// The loaded magazine.
+    pub magazine: Magazine,
+}
+
+impl BallisticWeapon {
+    /// Consume a round. Returns false when the magazine is empty.
+    pub fn fire_round(&mut self) -> bool {
+        if self.magazine.rounds == 0 {
+            return false;
+        }
+        self.magazine.rounds -= 1;
+        true
+    }
+}
+
+/// Event of a fired round ejecting its spent casing.
+
+pub struct CasingEjected {
+    pub position: Vec3,
+}
+
+/// A fired ballistic round that has yet to reach what it hit.
+
+pub struct InFlightProjectile {
+    pub attack: Attack,
+    pub hit_result: QueryCombatHitResult,
+    /// Hit point of rounds that hit nothing.
+    pub blank_option: Option<Vec3>,
+    pub timer: Timer,
+}
+
+/// Resource with ballistic rounds in flight.
+#[derive(Resource, Default)]
+
+pub struct InFlightProjectiles {
+    pub list: Vec<InFlightProjectile>,
+}
+
+/// Entities that moved further than this from the hit point while the round was in flight are missed.
+const MAX_TARGET_DRIFT: f32 = 1.;
+
+/// Land ballistic rounds once they travelled to their hit point. Re-caches their attack so damage, chat and sfx hooks process the hit this frame.
+
+pub(crate) fn land_ballistic_projectiles(
+    time: Res<Time>,
+    mut in_flight: ResMut<InFlightProjectiles>,
+    mut cached_attacks: ResMut<ActiveAttacks>,
+    transforms: Query<&Transform>,
+    mut query_hit_result: EventWriter<QueryCombatHitResult>,
+    mut blank_writer: EventWriter<ProjectileBlank>,
+) {
+    for projectile in in_flight.list.iter_mut() {
+        projectile.timer.tick(time.delta());
+    }
+
+    let mut i = 0;
+    while i < in_flight.list.len() {
+        if !in_flight.list[i].timer.finished() {
+            i += 1;
+            continue;
+        }
+        let mut projectile = in_flight.list.remove(i);
+
+        let mut blank_option = projectile.blank_option;
+        let mut missed_hit_point = None;
+        projectile.hit_result.entities_hits.retain(|hit| {
+            let hit_transform;
+            match transforms.get(hit.entity) {
+                Ok(t) => {
+                    hit_transform = t;
+                }
+                Err(_) => {
+                    return false;
+                }
+            }
+            let in_place = Vec3::new(
+                hit_transform.translation.x,
+                hit.hit_point.y,
+                hit_transform.translation.z,
+            )
+            .distance(hit.hit_point)
+                <= MAX_TARGET_DRIFT;
+            if !in_place {
+                missed_hit_point = Some(hit_transform.translation);
+            }
+            in_place
+        });
+        if projectile.hit_result.entities_hits.is_empty()
+            && projectile.hit_result.cell_hits.is_empty()
+            && blank_option.is_none()
+        {
+            blank_option = missed_hit_point;
+        }
+
+        let incremented_id = projectile.hit_result.incremented_id;
+        cached_attacks.map.insert(
+            incremented_id,
+            ActiveAttack {
+                attack: projectile.attack,
+                hit_result: Some(projectile.hit_result.clone()),
+                melee: Some(false),
+            },
+        );
+
+        match blank_option {
+            Some(hit_point) => {
+                blank_writer.send(ProjectileBlank {
+                    hit_point,
+                    incremented_id,
+                });
+            }
+            None => {}
+        }
+        query_hit_result.send(projectile.hit_result);
+    }
+}
+
+pub const RELOAD_ACTION_ID: &str = "actions::combat/reload";
+
+/// Build the reload action for ballistic weapons.
+
+pub(crate) fn build_reload_actions(
+    mut building_action_data: ResMut<BuildingActions>,
+    weapons: Query<&BallisticWeapon>,
+) {
+    for building_action in building_action_data.list.iter_mut() {
+        match building_action.target_entity_option {
+            Some(examined_entity) => {
+                if weapons.get(examined_entity).is_err() {
+                    continue;
+                }
+            }
+            None => {
+                continue;
+            }
+        }
+        building_action.actions.push(ActionData {
+            data: Action {
+                id: RELOAD_ACTION_ID.to_string(),
+                text: "Reload".to_string(),
+                tab_list_priority: 60,
+            },
+            approved: None,
+        });
+    }
+}
+
+/// The fullest magazine in the inventory of the holder that fits the weapon and holds more rounds than the loaded one.
+
+fn find_reload_magazine(
+    holder: Entity,
+    weapon: &BallisticWeapon,
+    inventories: &Query<&Inventory>,
+    magazines: &Query<&Magazine>,
+) -> Option<Entity> {
+    let inventory;
+    match inventories.get(holder) {
+        Ok(i) => {
+            inventory = i;
+        }
+        Err(_) => {
+            return None;
+        }
+    }
+    let mut best: Option<(Entity, u16)> = None;
+    for slot in inventory.slots.values() {
+        for item in slot.items.iter() {
+            match magazines.get(item.entity) {
+                Ok(magazine) => {
+                    if magazine.caliber != weapon.magazine.caliber
+                        || magazine.rounds <= weapon.magazine.rounds
+                    {
+                        continue;
+                    }
+                    match best {
+                        Some((_, rounds)) => {
+                            if magazine.rounds <= rounds {
+                                continue;
+                            }
+                        }
+                        None => {}
+                    }
+                    best = Some((item.entity, magazine.rounds));
+                }
+                Err(_) => {}
+            }
+        }
+    }
+    best.map(|(entity, _)| entity)
+}
+
+/// Whether the weapon is held by the pawn.
+
+fn is_held_by(weapon: Entity, pawn: Entity, items: &Query<&InventoryItem>) -> bool {
+    match items.get(weapon) {
+        Ok(item) => item.in_inventory_of_entity == Some(pawn),
+        Err(_) => false,
+    }
+}
+
+/// Prerequisite check of reloading. The weapon has to be held and a magazine with more rounds has to be in the inventory.
+
+pub(crate) fn reload_prerequisite_check(
+    mut building_action_data: ResMut<BuildingActions>,
+    weapons: Query<&BallisticWeapon>,
+    items: Query<&InventoryItem>,
+    inventories: Query<&Inventory>,
+    magazines: Query<&Magazine>,
+) {
+    for building in building_action_data.list.iter_mut() {
+        for action in building.actions.iter_mut() {
+            if action.data.id != RELOAD_ACTION_ID {
+                continue;
+            }
+            let weapon_entity;
+            match building.target_entity_option {
+                Some(w) => {
+                    weapon_entity = w;
+                }
+                None => {
+                    action.do_not_approve();
+                    continue;
+                }
+            }
+            match weapons.get(weapon_entity) {
+                Ok(weapon) => {
+                    if is_held_by(weapon_entity, building.action_taker, &items)
+                        && find_reload_magazine(
+                            building.action_taker,
+                            weapon,
+                            &inventories,
+                            &magazines,
+                        )
+                        .is_some()
+                    {
+                        action.approve();
+                    } else {
+                        action.do_not_approve();
+                    }
+                }
+                Err(_) => {
+                    action.do_not_approve();
+                }
+            }
+        }
+    }
+}
+
+/// Reload ballistic weapons. The loaded magazine is swapped with the fullest fitting magazine of the inventory, which then holds the previously loaded rounds.
+
+pub(crate) fn reload_actions(
+    building_action: Res<BuildingActions>,
+    action_requests: Res<ActionRequests>,
+    mut weapons: Query<(&mut BallisticWeapon, &Examinable)>,
+    inventories: Query<&Inventory>,
+    mut magazines: Query<&mut Magazine>,
+    handle_to_entity: Res<HandleToEntity>,
+    mut server: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+) {
+    for building in building_action.list.iter() {
+        let building_action_id;
+        match action_requests.list.get(&building.incremented_i) {
+            Some(action_request) => {
+                building_action_id = action_request.get_id().clone();
+            }
+            None => {
+                continue;
+            }
+        }
+        for action_data in building.actions.iter() {
+            if !action_data.is_approved()
+                || action_data.data.id != RELOAD_ACTION_ID
+                || action_data.data.id != building_action_id
+            {
+                continue;
+            }
+            let weapon_entity;
+            match building.target_entity_option {
+                Some(w) => {
+                    weapon_entity = w;
+                }
+                None => {
+                    continue;
+                }
+            }
+            match weapons.get_mut(weapon_entity) {
+                Ok((mut weapon, examinable)) => {
+                    let magazine_entity;
+                    match find_reload_magazine(
+                        building.action_taker,
+                        &weapon,
+                        &inventories,
+                        &magazines.to_readonly(),
+                    ) {
+                        Some(m) => {
+                            magazine_entity = m;
+                        }
+                        None => {
+                            continue;
+                        }
+                    }
+                    match magazines.get_mut(magazine_entity) {
+                        Ok(mut magazine) => {
+                            std::mem::swap(&mut weapon.magazine, &mut *magazine);
+                        }
+                        Err(_) => {
+                            warn!("Couldnt find magazine to reload with.");
+                            continue;
+                        }
+                    }
+                    match handle_to_entity.inv_map.get(&building.action_taker) {
+                        Some(handle) => {
+                            server.send(OutgoingReliableServerMessage {
+                                handle: *handle,
+                                message: NetworkingChatServerMessage::ChatMessage(
+                                    "[font=".to_string()
+                                        + FURTHER_ITALIC_FONT
+                                        + "]You reload the "
+                                        + &examinable.name.get_name()
+                                        + ". "
+                                        + &weapon.magazine.rounds.to_string()
+                                        + " rounds loaded.[/font]",
+                                ),
+                            });
+                        }
+                        None => {}
+                    }
+                }
+                Err(_) => {
+                    warn!("Couldnt find weapon to reload.");
+                }
+            }
+        }
+    }
+}
+
+/// Tell the attacker their weapon is empty.
+
+pub(crate) fn dry_fire_message(
+    attacker: Entity,
+    handle_to_entity: &HandleToEntity,
+    server: &mut EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+) {
+    match handle_to_entity.inv_map.get(&attacker) {
+        Some(handle) => {
+            server.send(OutgoingReliableServerMessage {
+                handle: *handle,
+                message: NetworkingChatServerMessage::ChatMessage(
+                    "[font=".to_string()
+                        + FURTHER_ITALIC_FONT
+                        + "]*click* Your weapon is empty.[/font]",
+                ),
+            });
+        }
+        None => {}
+    }
+}
+
+/// Spent casings of magazines eject sideways of the shooter.
+
+pub(crate) fn casing_position(attacker: &Transform, angle: f32) -> Vec3 {
+    attacker.translation + Vec3::new(angle.sin(), 0.5, angle.cos()) * 0.4
+}
@@ -129,9 +129,18 @@ pub fn projectile_laser_visuals<T: Component>(
                 }
             }
 
+            let weapon = active_attack.attack.weapon_option.unwrap();
+
+            match weapon_criteria.get(weapon) {
+                Ok(_) => {}
+                Err(_rr) => {
+                    continue;
+                }
+            }
+
             let projectile_combat_component;
 
-            match projectile_weapons.get(active_attack.attack.weapon_option.unwrap()) {
+            match projectile_weapons.get(weapon) {
                 Ok(c) => {
                     projectile_combat_component = c;
                 }
@@ -203,9 +212,18 @@ pub fn projectile_laser_visuals<T: Component>(
                 }
             }
 
+            let weapon = active_attack.attack.weapon_option.unwrap();
+
+            match weapon_criteria.get(weapon) {
+                Ok(_) => {}
+                Err(_rr) => {
+                    continue;
+                }
+            }
+
             let projectile_combat_component;
 
-            match projectile_weapons.get(active_attack.attack.weapon_option.unwrap()) {
+            match projectile_weapons.get(weapon) {
                 Ok(c) => {
                     projectile_combat_component = c;
                 }
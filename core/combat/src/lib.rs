@@ -10,6 +10,8 @@ pub mod active_attacks;
 mod apply_damage;
 /// The attack event.
 pub mod attack;
+/// Ammunition, reloading and travel time of ballistic projectiles.
+pub mod ballistics;
 /// Hooks text in chat.
 pub mod chat;
 /// Manage visual laser projectiles for clients.
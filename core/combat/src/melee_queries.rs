@@ -13,7 +13,10 @@ use bevy_rapier3d::{
 use entity::{examine::Examinable, health::HealthComponent};
 use gridmap::grid::{Cell, Gridmap};
 use inventory::server::combat::{MeleeCombat, ProjectileCombat};
-use resources::math::{cell_id_to_world, Vec3Int};
+use resources::{
+    grid::TargetCell,
+    math::{cell_id_to_world, Vec3Int},
+};
 
 use crate::{
     active_attacks::ActiveAttacks,
@@ -35,7 +38,7 @@ pub struct AttackResult {
     /// The entity id of the hit entity.
     pub entity_option: Option<Entity>,
     /// The cell id of the hit entity.
-    pub cell_id_option: Option<Vec3Int>,
+    pub cell_id_option: Option<TargetCell>,
     /// The distance between the attacker and hit entity.
     pub distance: f32,
     /// The hit point of the attack.
@@ -249,9 +252,9 @@ pub(crate) fn melee_direct(
                         let mut found = false;
                         let mut first_blocker = None;
                         for attack_result in hit_entities.iter() {
-                            match attack_result.cell_id_option {
+                            match &attack_result.cell_id_option {
                                 Some(cell_id) => {
-                                    if targetted_cell == cell_id {
+                                    if targetted_cell == cell_id.id {
                                         hit_entity = Some(attack_result);
                                         found = true;
                                         break;
@@ -295,11 +298,11 @@ pub(crate) fn melee_direct(
 
         match hit_entity {
             Some(attack_result) => {
-                match attack_result.cell_id_option {
+                match &attack_result.cell_id_option {
                     Some(cell_id) => {
                         cell_hits.push(CellHitSimple {
-                            cell: cell_id,
-                            hit_point: cell_id_to_world(cell_id),
+                            cell: cell_id.clone(),
+                            hit_point: cell_id_to_world(cell_id.id),
                         });
                     }
                     None => {}
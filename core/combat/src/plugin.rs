@@ -1,8 +1,12 @@
 use bevy::prelude::{App, CoreSet, IntoSystemConfig, Plugin};
 use resources::is_server::is_server;
-use resources::labels::{CombatLabels, PostUpdateLabels};
+use resources::labels::{ActionsLabels, CombatLabels, PostUpdateLabels};
 
-use crate::apply_damage::{finalize_apply_damage, ActiveApplyDamage};
+use crate::apply_damage::{finalize_apply_damage, interrupt_actions_on_damage, ActiveApplyDamage};
+use crate::ballistics::{
+    build_reload_actions, land_ballistic_projectiles, reload_actions, reload_prerequisite_check,
+    CasingEjected, InFlightProjectiles,
+};
 use crate::chat::hit_query_chat_cells;
 use crate::health_ui::{health_ui_update, ClientHealthUICache};
 use crate::melee_queries::MeleeBlank;
@@ -31,6 +35,11 @@ impl Plugin for CombatPlugin {
                     .after(CombatLabels::WeaponHandler)
                     .in_set(CombatLabels::Query),
             )
+            .add_system(
+                land_ballistic_projectiles
+                    .after(CombatLabels::CacheAttack)
+                    .in_set(CombatLabels::Query),
+            )
             .add_system(
                 start_apply_damage
                     .in_set(CombatLabels::StartApplyDamage)
@@ -44,6 +53,7 @@ impl Plugin for CombatPlugin {
                     .after(CombatLabels::Query),
             )
             .add_system(hit_query_chat_cells.after(CombatLabels::FinalizeApplyDamage))
+            .add_system(interrupt_actions_on_damage.after(CombatLabels::FinalizeApplyDamage))
             /*.add_system(
                 blanks_chat
                     .after(CombatLabels::FinalizeApplyDamage)
@@ -71,7 +81,24 @@ impl Plugin for CombatPlugin {
                     .in_base_set(CoreSet::PostUpdate)
                     .in_set(PostUpdateLabels::EntityUpdate),
             )
-            .init_resource::<ClientHealthUICache>();
+            .init_resource::<ClientHealthUICache>()
+            .init_resource::<InFlightProjectiles>()
+            .add_event::<CasingEjected>()
+            .add_system(
+                build_reload_actions
+                    .in_set(ActionsLabels::Build)
+                    .after(ActionsLabels::Init),
+            )
+            .add_system(
+                reload_prerequisite_check
+                    .in_set(ActionsLabels::Approve)
+                    .after(ActionsLabels::Build),
+            )
+            .add_system(
+                reload_actions
+                    .in_set(ActionsLabels::Action)
+                    .after(ActionsLabels::Approve),
+            );
         }
     }
 }
@@ -4,6 +4,7 @@ use bevy::{
     prelude::{
         warn, Component, Entity, EventReader, EventWriter, Query, Res, ResMut, Transform, With,
     },
+    time::{Timer, TimerMode},
 };
 use bevy_rapier3d::{
     pipeline::QueryFilter,
@@ -12,13 +13,23 @@ use bevy_rapier3d::{
     rapier::prelude::Ray,
 };
 use entity::{examine::Examinable, health::HealthComponent};
-use gridmap::grid::{Cell, Gridmap};
-use inventory::server::combat::ProjectileCombat;
-use resources::math::{cell_id_to_world, Vec3Int};
+use gridmap::{
+    chunk_collider::{chunk_collider_cells_in_shape, ChunkCollider},
+    grid::Gridmap,
+};
+use inventory::server::combat::{ProjectileCombat, ProjectilePhysics};
+use networking::server::{
+    HandleToEntity, NetworkingChatServerMessage, OutgoingReliableServerMessage,
+};
+use resources::math::Vec3Int;
 
 use crate::{
     active_attacks::ActiveAttacks,
     attack::{Attack, CellHitSimple, EntityHitSimple, QueryCombatHitResult},
+    ballistics::{
+        casing_position, dry_fire_message, BallisticWeapon, CasingEjected, InFlightProjectile,
+        InFlightProjectiles,
+    },
     melee_queries::{AttackResult, ATTACK_HEIGHT},
 };
 
@@ -39,6 +50,33 @@ pub struct ProjectileQuery {
     pub range: f32,
     /// Attack id.
     pub incremented: u64,
+    /// How the projectile travels and what stops it.
+    pub projectile_physics: ProjectilePhysics,
+}
+
+/// Whether a physics query result stops the projectile. Lasers pass through cells that are not laser obstacles, such as windows.
+/// Ballistic rounds penetrate those up to their penetration but are stopped by any other combat obstacle.
+
+fn stops_projectile(
+    result: &AttackResult,
+    projectile_physics: &ProjectilePhysics,
+    penetrated: &mut u8,
+) -> bool {
+    match projectile_physics {
+        ProjectilePhysics::Laser => result.is_combat_obstacle && result.is_laser_obstacle,
+        ProjectilePhysics::Ballistic { penetration, .. } => {
+            if !result.is_combat_obstacle {
+                false
+            } else if result.is_laser_obstacle {
+                true
+            } else if penetrated < penetration {
+                *penetrated += 1;
+                false
+            } else {
+                true
+            }
+        }
+    }
 }
 use physics::physics::{get_bit_masks, ColliderGroup};
 
@@ -50,11 +88,12 @@ pub(crate) fn projectile_attack(
     rapier_context: Res<RapierContext>,
     colliders: Query<&Parent, With<Collider>>,
     mut rigidbody_query: Query<(&mut HealthComponent, &Examinable, &Transform)>,
-    physics_cells: Query<&Cell>,
-    _world_cells: Res<Gridmap>,
+    chunk_colliders: Query<&ChunkCollider>,
+    world_cells: Res<Gridmap>,
     mut query_hit_result: EventWriter<QueryCombatHitResult>,
     mut cached_attacks: ResMut<ActiveAttacks>,
     mut blank_writer: EventWriter<ProjectileBlank>,
+    mut in_flight: ResMut<InFlightProjectiles>,
 ) {
     for attack_event in projectile_events.iter() {
         let direction_additive = Vec3::new(-attack_event.angle.cos(), 0., attack_event.angle.sin());
@@ -111,10 +150,13 @@ pub(crate) fn projectile_attack(
 
         let points_vec = Vec3::new(attack_event.range, cast_vertical_extents, 0.1);
 
+        let projectile_rotation = Quat::from_rotation_y(attack_event.angle);
+        let cast_shape = Collider::cuboid(points_vec.x, points_vec.y, points_vec.z);
+
         rapier_context.intersections_with_shape(
             projectile_rough_end_position,
-            Quat::from_rotation_y(attack_event.angle).into(),
-            &Collider::cuboid(points_vec.x, points_vec.y, points_vec.z),
+            projectile_rotation.into(),
+            &cast_shape,
             query_filter,
             |child_entity| {
                 let collider_entity;
@@ -198,64 +240,73 @@ pub(crate) fn projectile_attack(
                     Err(_rr) => {}
                 }
 
-                match physics_cells.get(collider_entity) {
-                    Ok(cell_component) => {
-                        let position = cell_id_to_world(cell_component.id);
-                        let distance = attacker_transform.translation.distance(position);
-
-                        let ray = Ray::new(
-                            projectile_start_position.into(),
-                            (position - projectile_start_position).into(),
-                        );
-                        let max_toi = distance * 1.2;
-
-                        let _hit_point: Vec3;
-
-                        if let Some((_hit_collider_handle, hit_toi)) = rapier_context.cast_ray(
-                            ray.origin.into(),
-                            ray.dir.into(),
-                            max_toi,
-                            true,
-                            QueryFilter::new().groups(interaction_groups).predicate(
-                                &|child_entity| {
-                                    let colliderz;
+                match chunk_colliders.get(collider_entity) {
+                    Ok(chunk_collider) => {
+                        let tile_collider;
+                        match world_cells
+                            .main_cell_properties
+                            .get(&chunk_collider.tile_type)
+                        {
+                            Some(properties) => {
+                                tile_collider = properties.collider.clone();
+                            }
+                            None => {
+                                return true;
+                            }
+                        }
 
-                                    match colliders.get(child_entity) {
-                                        Ok(parent_entity) => {
-                                            colliderz = parent_entity.get();
-                                        }
-                                        Err(_rr) => {
-                                            colliderz = child_entity;
-                                        }
-                                    }
-                                    colliderz == collider_entity
-                                },
-                            ),
+                        for (cell, collider_transform) in chunk_collider_cells_in_shape(
+                            &world_cells,
+                            chunk_collider,
+                            projectile_rough_end_position,
+                            projectile_rotation,
+                            &cast_shape,
                         ) {
-                            _hit_point = ray.point_at(hit_toi).into();
-                        } else {
-                            _hit_point = position;
-                        };
-
-                        sound_transform.translation = position;
-
-                        /*let cell_data = world_cells.get_cell(cell_component.id).unwrap();
-
-                        let r = AttackResult {
-                            entity_option: None,
-                            cell_id_option: Some(cell_component.id),
-                            distance: attacker_transform.translation.distance(position),
-                            hit_point: hit_point,
-                            collider_handle: collider_entity,
-                            is_combat_obstacle: !world_cells
-                                .non_combat_obstacle_cells_list
-                                .contains(&cell_data.item_0.id),
-                            is_laser_obstacle: !world_cells
-                                .non_laser_obstacle_cells_list
-                                .contains(&cell_data.item_0.id),
-                        };
-
-                        hit_entities_query.push(r);*/
+                            let position = collider_transform.translation;
+                            let distance = attacker_transform.translation.distance(position);
+
+                            let ray = Ray::new(
+                                projectile_start_position.into(),
+                                (position - projectile_start_position).into(),
+                            );
+                            let max_toi = distance * 1.2;
+
+                            let hit_point: Vec3;
+
+                            if let Some(hit_toi) = tile_collider.cast_ray(
+                                collider_transform.translation,
+                                collider_transform.rotation,
+                                ray.origin.into(),
+                                ray.dir.into(),
+                                max_toi,
+                                true,
+                            ) {
+                                hit_point = ray.point_at(hit_toi).into();
+                            } else {
+                                hit_point = position;
+                            };
+
+                            sound_transform.translation = position;
+
+                            match world_cells.get_cell(cell.clone()) {
+                                Some(cell_data) => {
+                                    hit_entities_query.push(AttackResult {
+                                        entity_option: None,
+                                        cell_id_option: Some(cell),
+                                        distance,
+                                        hit_point,
+                                        collider_handle: collider_entity,
+                                        is_combat_obstacle: !world_cells
+                                            .non_combat_obstacle_cells_list
+                                            .contains(&cell_data.tile_type),
+                                        is_laser_obstacle: !world_cells
+                                            .non_laser_obstacle_cells_list
+                                            .contains(&cell_data.tile_type),
+                                    });
+                                }
+                                None => {}
+                            }
+                        }
                     }
                     Err(_rr) => {}
                 }
@@ -268,6 +319,7 @@ pub(crate) fn projectile_attack(
         hit_entities_query.reverse();
 
         let mut hit_results = vec![];
+        let mut penetrated = 0;
 
         match attack_event.targetted_entity {
             //projectile fired and targetted an entity.
@@ -286,7 +338,11 @@ pub(crate) fn projectile_attack(
                         None => {}
                     }
 
-                    if attack_result.is_combat_obstacle && attack_result.is_laser_obstacle {
+                    if stops_projectile(
+                        attack_result,
+                        &attack_event.projectile_physics,
+                        &mut penetrated,
+                    ) {
                         first_blocker = Some(attack_result);
                         break;
                     }
@@ -307,9 +363,9 @@ pub(crate) fn projectile_attack(
                         let mut found = false;
                         let mut first_blocker = None;
                         for attack_result in hit_entities_query.iter() {
-                            match attack_result.cell_id_option {
+                            match &attack_result.cell_id_option {
                                 Some(cell_id) => {
-                                    if targetted_cell == cell_id {
+                                    if targetted_cell == cell_id.id {
                                         hit_results.push(attack_result);
                                         found = true;
                                         break;
@@ -318,7 +374,11 @@ pub(crate) fn projectile_attack(
                                 None => {}
                             }
 
-                            if attack_result.is_combat_obstacle && attack_result.is_laser_obstacle {
+                            if stops_projectile(
+                                attack_result,
+                                &attack_event.projectile_physics,
+                                &mut penetrated,
+                            ) {
                                 first_blocker = Some(attack_result);
                                 break;
                             }
@@ -335,7 +395,11 @@ pub(crate) fn projectile_attack(
                     None => {
                         // Projectile fired without targetting
                         for res in hit_entities_query.iter() {
-                            if res.is_combat_obstacle && res.is_laser_obstacle {
+                            if stops_projectile(
+                                res,
+                                &attack_event.projectile_physics,
+                                &mut penetrated,
+                            ) {
                                 hit_results.push(res);
                                 break;
                             }
@@ -356,10 +420,10 @@ pub(crate) fn projectile_attack(
                         hit_point: hit.hit_point,
                     }
                 }),
-                None => match hit.cell_id_option {
+                None => match &hit.cell_id_option {
                     Some(cell_id) => {
                         hit_cells.push(CellHitSimple {
-                            cell: cell_id,
+                            cell: cell_id.clone(),
                             hit_point: hit.hit_point,
                         });
                     }
@@ -370,12 +434,22 @@ pub(crate) fn projectile_attack(
             }
         }
 
+        // The projectile travels up to the nearest hit.
+        let mut travel_distance = attack_event.range;
+        for hit_point in hit_entities
+            .iter()
+            .map(|hit| hit.hit_point)
+            .chain(hit_cells.iter().map(|hit| hit.hit_point))
+        {
+            travel_distance = travel_distance.min(projectile_start_position.distance(hit_point));
+        }
+
+        let blank_option;
         if hit_entities.len() == 0 && hit_cells.len() == 0 {
             // Blank, we hit nothing but the projectile still fired (visuals etc).
-            blank_writer.send(ProjectileBlank {
-                hit_point: projectile_rough_end_position,
-                incremented_id: attack_event.incremented,
-            });
+            blank_option = Some(projectile_rough_end_position);
+        } else {
+            blank_option = None;
         }
 
         let hit_result = QueryCombatHitResult {
@@ -384,16 +458,49 @@ pub(crate) fn projectile_attack(
             cell_hits: hit_cells,
         };
 
+        let attack_option;
         match cached_attacks.map.get_mut(&attack_event.incremented) {
             Some(c) => {
-                c.hit_result = Some(hit_result.clone());
                 c.melee = Some(false);
+                attack_option = Some(c.attack.clone());
             }
             None => {
                 warn!("Couldnt find cached attack! {}", attack_event.incremented);
+                attack_option = None;
             }
         }
 
+        match (&attack_event.projectile_physics, attack_option) {
+            (ProjectilePhysics::Ballistic { speed, .. }, Some(attack)) => {
+                // Ballistic rounds hit once they travelled to their hit point.
+                in_flight.list.push(InFlightProjectile {
+                    attack,
+                    hit_result,
+                    blank_option,
+                    timer: Timer::from_seconds(travel_distance / speed, TimerMode::Once),
+                });
+                continue;
+            }
+            _ => {}
+        }
+
+        match blank_option {
+            Some(hit_point) => {
+                blank_writer.send(ProjectileBlank {
+                    hit_point,
+                    incremented_id: attack_event.incremented,
+                });
+            }
+            None => {}
+        }
+
+        match cached_attacks.map.get_mut(&attack_event.incremented) {
+            Some(c) => {
+                c.hit_result = Some(hit_result.clone());
+            }
+            None => {}
+        }
+
         query_hit_result.send(hit_result);
     }
 }
@@ -407,12 +514,18 @@ pub struct ProjectileBlank {
     pub incremented_id: u64,
 }
 
-/// Perform projectile attack handler logic.
+/// Perform projectile attack handler logic. Ballistic weapons consume a round per shot and refuse to fire when empty.
 
 pub fn projectile_attack_handler<T: Component>(
     weapon_entities: Query<&ProjectileCombat, With<T>>,
+    mut ballistic_weapons: Query<&mut BallisticWeapon, With<T>>,
+    transforms: Query<&Transform>,
     mut attacks: EventReader<Attack>,
     mut projectile_attack: EventWriter<ProjectileQuery>,
+    mut cached_attacks: ResMut<ActiveAttacks>,
+    mut casings: EventWriter<CasingEjected>,
+    handle_to_entity: Res<HandleToEntity>,
+    mut server: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
 ) {
     for attack in attacks.iter() {
         let combat_component;
@@ -435,6 +548,25 @@ pub fn projectile_attack_handler<T: Component>(
             }
         }
         if !attack.alt_attack_mode {
+            match ballistic_weapons.get_mut(weapon_entity) {
+                Ok(mut ballistic_weapon) => {
+                    if !ballistic_weapon.fire_round() {
+                        // Dry fire, the attack never happened.
+                        cached_attacks.map.remove(&attack.incremented_id);
+                        dry_fire_message(attack.attacker, &handle_to_entity, &mut server);
+                        continue;
+                    }
+                    match transforms.get(attack.attacker) {
+                        Ok(attacker_transform) => {
+                            casings.send(CasingEjected {
+                                position: casing_position(attacker_transform, attack.angle),
+                            });
+                        }
+                        Err(_) => {}
+                    }
+                }
+                Err(_) => {}
+            }
             projectile_attack.send(ProjectileQuery {
                 attacker_entity: attack.attacker,
                 targetted_entity: attack.targetted_entity,
@@ -443,6 +575,7 @@ pub fn projectile_attack_handler<T: Component>(
                 range: combat_component.laser_range,
                 exclude_physics: vec![weapon_entity],
                 incremented: attack.incremented_id,
+                projectile_physics: combat_component.projectile_physics.clone(),
             });
         }
     }
@@ -209,7 +209,7 @@ pub fn attack_sfx<T: Component>(
                 attack_cache = c;
             }
             None => {
-                warn!("Couldnt find attack cache for sfx.");
+                // Cancelled attacks, such as dry fires of empty weapons, are not cached.
                 continue;
             }
         }
@@ -0,0 +1,603 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    env,
+    io::{ErrorKind, Read, Write},
+    net::{IpAddr, Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        mpsc::{channel, Receiver, TryRecvError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::{info, warn, Color, Commands, EventReader, EventWriter, Res, ResMut, Resource};
+use networking::server::OutgoingReliableServerMessage;
+use ui::{
+    fonts::{Fonts, SOURCECODE_REGULAR_FONT},
+    text::{NetTextSection, COMMUNICATION_FONT_SIZE, CONSOLE_ERROR_COLOR},
+};
+
+use crate::{
+    commands::{AllConsoleCommands, InputConsoleCommand},
+    net::{ClientSideConsoleInput, ConsoleCommandsServerMessage, ConsoleLine},
+    parse::{console_command_usage, validate_console_input},
+};
+
+/// Port of the remote admin socket.
+pub const REMOTE_ADMIN_PORT: u16 = 57714;
+/// Environment variable holding the remote admin password. The remote admin socket stays closed if unset.
+pub const REMOTE_ADMIN_PASSWORD_VAR: &str = "SF_ADMIN_PASSWORD";
+/// Environment variable holding the address the remote admin socket binds to. Only local connections are accepted if unset.
+pub const REMOTE_ADMIN_ADDRESS_VAR: &str = "SF_ADMIN_ADDRESS";
+/// Maximum amount of simultaneous remote admin sessions.
+pub const MAX_REMOTE_ADMIN_SESSIONS: usize = 4;
+/// Time a session gets to authenticate before it is closed.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time an authenticated session may stay silent before it is closed.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(900);
+/// Failed authentications after which an address gets locked out.
+const MAX_FAILED_AUTHS: u8 = 3;
+/// Time an address stays locked out after too many failed authentications.
+const FAILED_AUTH_LOCKOUT: Duration = Duration::from_secs(300);
+/// Maximum amount of bytes waiting to be sent to a session before it is closed.
+const MAX_OUTGOING_BYTES: usize = 1 << 20;
+/// Admin session of the stdin of the server.
+pub const STDIN_SESSION: u64 = 0;
+
+/// Packet types of the Source RCON protocol.
+const SERVERDATA_AUTH: i32 = 3;
+const SERVERDATA_AUTH_RESPONSE: i32 = 2;
+const SERVERDATA_EXECCOMMAND: i32 = 2;
+const SERVERDATA_RESPONSE_VALUE: i32 = 0;
+/// Largest packet size the protocol allows.
+const MAX_PACKET_SIZE: i32 = 4096;
+
+/// Admin session and request id of the protocol a command was issued with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+
+pub struct AdminSession {
+    pub session: u64,
+    pub request_id: i32,
+}
+
+/// Console output of commands issued by server admins rather than connected players.
+
+pub struct AdminConsoleOutput {
+    /// Session that issued the command, the output is logged if none.
+    pub session_option: Option<AdminSession>,
+    pub line: ConsoleLine,
+}
+
+/// Write a console line to the player that issued a command, or to the admin console if it was issued on the server itself.
+
+pub fn console_write_line(
+    handle_option: Option<u64>,
+    admin_session_option: Option<AdminSession>,
+    line: ConsoleLine,
+    net: &mut EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    admin: &mut EventWriter<AdminConsoleOutput>,
+) {
+    match handle_option {
+        Some(handle) => {
+            net.send(OutgoingReliableServerMessage {
+                handle,
+                message: ConsoleCommandsServerMessage::ConsoleWriteLine(line),
+            });
+        }
+        None => {
+            admin.send(AdminConsoleOutput {
+                session_option: admin_session_option,
+                line,
+            });
+        }
+    }
+}
+
+/// Lines read from stdin on a separate thread.
+#[derive(Resource)]
+
+pub struct StdinConsole {
+    receiver: Mutex<Receiver<String>>,
+}
+
+/// Start reading commands from stdin.
+
+pub(crate) fn start_stdin_console(mut commands: Commands) {
+    let (sender, receiver) = channel();
+
+    thread::spawn(move || {
+        for line in std::io::stdin().lines() {
+            match line {
+                Ok(l) => {
+                    if sender.send(l).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => {
+                    break;
+                }
+            }
+        }
+    });
+
+    commands.insert_resource(StdinConsole {
+        receiver: Mutex::new(receiver),
+    });
+}
+
+/// Admin commands waiting to be performed. One is performed per frame.
+#[derive(Default, Resource)]
+
+pub struct AdminCommandQueue {
+    /// Session and request id with the command.
+    pub queue: VecDeque<(u64, i32, String)>,
+}
+
+/// Queue commands read from stdin.
+
+pub(crate) fn read_stdin_console(stdin: Res<StdinConsole>, mut queue: ResMut<AdminCommandQueue>) {
+    let receiver;
+    match stdin.receiver.lock() {
+        Ok(r) => {
+            receiver = r;
+        }
+        Err(_) => {
+            return;
+        }
+    }
+    loop {
+        match receiver.try_recv() {
+            Ok(line) => {
+                let line = line.trim().to_string();
+                if !line.is_empty() {
+                    queue.queue.push_back((STDIN_SESSION, 0, line));
+                }
+            }
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => {
+                break;
+            }
+        }
+    }
+}
+
+struct RemoteAdminSession {
+    stream: TcpStream,
+    ip: IpAddr,
+    buffer: Vec<u8>,
+    /// Bytes waiting for the socket to become writable.
+    outgoing: Vec<u8>,
+    authenticated: bool,
+    last_activity: Instant,
+}
+
+impl RemoteAdminSession {
+    /// Write as much of the outgoing bytes as the socket accepts without blocking.
+    fn flush(&mut self) -> std::io::Result<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => {
+                    return Err(ErrorKind::WriteZero.into());
+                }
+                Ok(n) => {
+                    self.outgoing.drain(..n);
+                }
+                Err(err) => match err.kind() {
+                    ErrorKind::WouldBlock => {
+                        break;
+                    }
+                    ErrorKind::Interrupted => {}
+                    _ => {
+                        return Err(err);
+                    }
+                },
+            }
+        }
+        if self.outgoing.len() > MAX_OUTGOING_BYTES {
+            return Err(ErrorKind::OutOfMemory.into());
+        }
+        Ok(())
+    }
+}
+
+/// Remote admin socket speaking the Source RCON protocol.
+#[derive(Default, Resource)]
+
+pub struct RemoteAdmin {
+    listener_option: Option<TcpListener>,
+    password: String,
+    sessions: HashMap<u64, RemoteAdminSession>,
+    next_session: u64,
+    /// Failed authentications and the time of the last one per address.
+    failed_auths: HashMap<IpAddr, (u8, Instant)>,
+}
+
+/// Open the remote admin socket if a password was configured.
+
+pub(crate) fn start_remote_admin(mut remote_admin: ResMut<RemoteAdmin>) {
+    let password;
+    match env::var(REMOTE_ADMIN_PASSWORD_VAR) {
+        Ok(p) => {
+            if p.is_empty() {
+                return;
+            }
+            password = p;
+        }
+        Err(_) => {
+            info!(
+                "Remote admin disabled, set {} to enable it.",
+                REMOTE_ADMIN_PASSWORD_VAR
+            );
+            return;
+        }
+    }
+
+    let address: IpAddr;
+    match env::var(REMOTE_ADMIN_ADDRESS_VAR) {
+        Ok(a) => match a.parse() {
+            Ok(ip) => {
+                address = ip;
+            }
+            Err(_) => {
+                warn!("Invalid remote admin address {}.", a);
+                return;
+            }
+        },
+        Err(_) => {
+            address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        }
+    }
+
+    match TcpListener::bind((address, REMOTE_ADMIN_PORT)) {
+        Ok(listener) => match listener.set_nonblocking(true) {
+            Ok(_) => {
+                info!(
+                    "Remote admin listening on {}:{}.",
+                    address, REMOTE_ADMIN_PORT
+                );
+                remote_admin.listener_option = Some(listener);
+                remote_admin.password = password;
+                remote_admin.next_session = STDIN_SESSION + 1;
+            }
+            Err(err) => {
+                warn!("Couldn't configure remote admin socket: {}", err);
+            }
+        },
+        Err(err) => {
+            warn!("Couldn't open remote admin socket: {}", err);
+        }
+    }
+}
+
+/// Queue a packet to be sent on the next flush of the session.
+
+fn write_packet(outgoing: &mut Vec<u8>, id: i32, kind: i32, body: &str) {
+    let size = 10 + body.len() as i32;
+    outgoing.extend_from_slice(&size.to_le_bytes());
+    outgoing.extend_from_slice(&id.to_le_bytes());
+    outgoing.extend_from_slice(&kind.to_le_bytes());
+    outgoing.extend_from_slice(body.as_bytes());
+    outgoing.extend_from_slice(&[0, 0]);
+}
+
+/// Compare the input against the password in a time that doesn't depend on how much of it matches.
+
+fn password_matches(input: &str, password: &str) -> bool {
+    let input = input.as_bytes();
+    let mut difference = (input.len() != password.len()) as u8;
+    for (i, byte) in password.as_bytes().iter().enumerate() {
+        difference |= byte ^ input.get(i).copied().unwrap_or(0);
+    }
+    difference == 0
+}
+
+/// Take a complete packet from the buffer. Err if the packet is malformed.
+
+fn read_packet(buffer: &mut Vec<u8>) -> Result<Option<(i32, i32, String)>, ()> {
+    if buffer.len() < 4 {
+        return Ok(None);
+    }
+    let size = i32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]);
+    if size < 10 || size > MAX_PACKET_SIZE {
+        return Err(());
+    }
+    let total = size as usize + 4;
+    if buffer.len() < total {
+        return Ok(None);
+    }
+    let packet: Vec<u8> = buffer.drain(..total).collect();
+    let id = i32::from_le_bytes([packet[4], packet[5], packet[6], packet[7]]);
+    let kind = i32::from_le_bytes([packet[8], packet[9], packet[10], packet[11]]);
+    let body_bytes = &packet[12..total - 2];
+    let body_end = body_bytes
+        .iter()
+        .position(|b| *b == 0)
+        .unwrap_or(body_bytes.len());
+    let body = String::from_utf8_lossy(&body_bytes[..body_end]).to_string();
+    Ok(Some((id, kind, body)))
+}
+
+/// Accept remote admin connections, authenticate them and queue their commands.
+
+pub(crate) fn remote_admin_input(
+    mut remote_admin: ResMut<RemoteAdmin>,
+    mut queue: ResMut<AdminCommandQueue>,
+) {
+    let remote_admin = &mut *remote_admin;
+
+    match &remote_admin.listener_option {
+        Some(listener) => loop {
+            match listener.accept() {
+                Ok((stream, address)) => {
+                    remote_admin
+                        .failed_auths
+                        .retain(|_, (_, last)| last.elapsed() < FAILED_AUTH_LOCKOUT);
+                    match remote_admin.failed_auths.get(&address.ip()) {
+                        Some((failed, _)) => {
+                            if *failed >= MAX_FAILED_AUTHS {
+                                warn!(
+                                    "Refused remote admin connection from locked out {}.",
+                                    address
+                                );
+                                continue;
+                            }
+                        }
+                        None => {}
+                    }
+                    if remote_admin.sessions.len() >= MAX_REMOTE_ADMIN_SESSIONS {
+                        warn!(
+                            "Refused remote admin connection from {}, too many sessions.",
+                            address
+                        );
+                        continue;
+                    }
+                    if stream.set_nonblocking(true).is_err() {
+                        continue;
+                    }
+                    info!("Remote admin connection from {}.", address);
+                    remote_admin.sessions.insert(
+                        remote_admin.next_session,
+                        RemoteAdminSession {
+                            stream,
+                            ip: address.ip(),
+                            buffer: vec![],
+                            outgoing: vec![],
+                            authenticated: false,
+                            last_activity: Instant::now(),
+                        },
+                    );
+                    remote_admin.next_session += 1;
+                }
+                Err(err) => {
+                    if err.kind() != ErrorKind::WouldBlock {
+                        warn!("Remote admin accept failed: {}", err);
+                    }
+                    break;
+                }
+            }
+        },
+        None => {
+            return;
+        }
+    }
+
+    let mut closed = vec![];
+
+    for (session_id, session) in remote_admin.sessions.iter_mut() {
+        let mut chunk = [0u8; 1024];
+        loop {
+            match session.stream.read(&mut chunk) {
+                Ok(0) => {
+                    closed.push(*session_id);
+                    break;
+                }
+                Ok(n) => {
+                    session.buffer.extend_from_slice(&chunk[..n]);
+                    session.last_activity = Instant::now();
+                }
+                Err(err) => {
+                    if err.kind() != ErrorKind::WouldBlock {
+                        closed.push(*session_id);
+                    }
+                    break;
+                }
+            }
+        }
+
+        loop {
+            match read_packet(&mut session.buffer) {
+                Ok(Some((id, kind, body))) => {
+                    if !session.authenticated {
+                        if kind != SERVERDATA_AUTH {
+                            closed.push(*session_id);
+                            break;
+                        }
+                        if password_matches(&body, &remote_admin.password) {
+                            session.authenticated = true;
+                            remote_admin.failed_auths.remove(&session.ip);
+                            write_packet(&mut session.outgoing, id, SERVERDATA_RESPONSE_VALUE, "");
+                            write_packet(&mut session.outgoing, id, SERVERDATA_AUTH_RESPONSE, "");
+                        } else {
+                            warn!("Remote admin authentication from {} failed.", session.ip);
+                            let failed = remote_admin
+                                .failed_auths
+                                .entry(session.ip)
+                                .or_insert((0, Instant::now()));
+                            failed.0 = failed.0.saturating_add(1);
+                            failed.1 = Instant::now();
+                            write_packet(&mut session.outgoing, -1, SERVERDATA_AUTH_RESPONSE, "");
+                            let _ = session.flush();
+                            closed.push(*session_id);
+                            break;
+                        }
+                    } else if kind == SERVERDATA_EXECCOMMAND {
+                        let command = body.trim().to_string();
+                        if command.is_empty() {
+                            write_packet(&mut session.outgoing, id, SERVERDATA_RESPONSE_VALUE, "");
+                        } else {
+                            queue.queue.push_back((*session_id, id, command));
+                        }
+                    }
+                }
+                Ok(None) => {
+                    break;
+                }
+                Err(_) => {
+                    closed.push(*session_id);
+                    break;
+                }
+            }
+        }
+
+        let timeout = if session.authenticated {
+            IDLE_TIMEOUT
+        } else {
+            AUTH_TIMEOUT
+        };
+        if session.last_activity.elapsed() > timeout {
+            info!("Remote admin session of {} timed out.", session.ip);
+            closed.push(*session_id);
+            continue;
+        }
+
+        if session.flush().is_err() {
+            closed.push(*session_id);
+        }
+    }
+
+    for session_id in closed {
+        remote_admin.sessions.remove(&session_id);
+    }
+}
+
+/// Perform the next queued admin command.
+
+pub(crate) fn dispatch_admin_command(
+    mut queue: ResMut<AdminCommandQueue>,
+    commands: Res<AllConsoleCommands>,
+    mut console_commands_queue: EventWriter<InputConsoleCommand>,
+    mut output: EventWriter<AdminConsoleOutput>,
+    fonts: Res<Fonts>,
+) {
+    let session;
+    let line;
+    match queue.queue.pop_front() {
+        Some((s, r, l)) => {
+            session = AdminSession {
+                session: s,
+                request_id: r,
+            };
+            line = l;
+        }
+        None => {
+            return;
+        }
+    }
+
+    let input = ClientSideConsoleInput::from_string(line);
+
+    if input.command == "help" {
+        let mut text = "Available console commands:".to_string();
+        for command in commands.list.iter() {
+            text += &format!(
+                "\n{}: {}\n  {}",
+                command.base,
+                command.description,
+                console_command_usage(command)
+            );
+        }
+        output.send(AdminConsoleOutput {
+            session_option: Some(session),
+            line: ConsoleLine {
+                sections: vec![NetTextSection {
+                    text,
+                    font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+                    font_size: COMMUNICATION_FONT_SIZE,
+                    color: Color::WHITE,
+                }],
+            },
+        });
+        return;
+    }
+
+    match validate_console_input(&commands, &input) {
+        Ok(args) => {
+            console_commands_queue.send(InputConsoleCommand {
+                handle_option: None,
+                admin_session_option: Some(session),
+                entity_option: None,
+                input,
+                args,
+            });
+        }
+        Err(err) => {
+            output.send(AdminConsoleOutput {
+                session_option: Some(session),
+                line: ConsoleLine {
+                    sections: vec![NetTextSection {
+                        text: err,
+                        font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+                        font_size: COMMUNICATION_FONT_SIZE,
+                        color: CONSOLE_ERROR_COLOR,
+                    }],
+                },
+            });
+        }
+    }
+}
+
+/// Return admin console output as plain text to the session that issued the command.
+
+pub(crate) fn flush_admin_output(
+    mut output: EventReader<AdminConsoleOutput>,
+    mut remote_admin: ResMut<RemoteAdmin>,
+) {
+    // Output of commands performed over several frames keeps going to the session that issued them.
+    let mut lines: Vec<(AdminSession, Vec<String>)> = vec![];
+    for event in output.iter() {
+        let mut text = String::new();
+        for section in event.line.sections.iter() {
+            text += &section.text;
+        }
+        match event.session_option {
+            Some(session) if session.session != STDIN_SESSION => {
+                match lines.iter_mut().find(|(s, _)| *s == session) {
+                    Some((_, session_lines)) => {
+                        session_lines.push(text);
+                    }
+                    None => {
+                        lines.push((session, vec![text]));
+                    }
+                }
+            }
+            _ => {
+                info!("{}", text);
+            }
+        }
+    }
+
+    for (session, session_lines) in lines {
+        match remote_admin.sessions.get_mut(&session.session) {
+            Some(remote_session) => {
+                let mut body = session_lines.join("\n");
+                // Keep within the maximum packet size.
+                while body.len() > (MAX_PACKET_SIZE - 10) as usize {
+                    body.pop();
+                }
+                write_packet(
+                    &mut remote_session.outgoing,
+                    session.request_id,
+                    SERVERDATA_RESPONSE_VALUE,
+                    &body,
+                );
+                match remote_session.flush() {
+                    Ok(_) => {}
+                    Err(_) => {
+                        remote_admin.sessions.remove(&session.session);
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+}
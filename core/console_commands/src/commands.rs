@@ -3,7 +3,9 @@ use bevy::prelude::{Entity, SystemSet};
 use networking::server::ConsoleArgVariant;
 use serde::{Deserialize, Serialize};
 
+use crate::admin::AdminSession;
 use crate::net::ClientSideConsoleInput;
+use crate::parse::ConsoleArgValue;
 
 /// Resource containing all registered custom console commands.
 #[derive(Default, Resource)]
@@ -15,7 +17,33 @@ pub struct AllConsoleCommands {
 pub struct ConsoleCommand {
     pub base: String,
     pub description: String,
-    pub args: Vec<(String, ConsoleArgVariant)>,
+    pub args: Vec<ConsoleArg>,
+}
+
+/// Declared argument of a console command.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConsoleArg {
+    pub name: String,
+    pub variant: ConsoleArgVariant,
+    /// Value used when the argument is omitted. The argument is required if none.
+    pub default_option: Option<String>,
+}
+
+impl ConsoleArg {
+    pub fn new(name: &str, variant: ConsoleArgVariant) -> Self {
+        Self {
+            name: name.to_string(),
+            variant,
+            default_option: None,
+        }
+    }
+    pub fn optional(name: &str, variant: ConsoleArgVariant, default: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            variant,
+            default_option: Some(default.to_string()),
+        }
+    }
 }
 
 /// Label for systems ordering.
@@ -30,7 +58,11 @@ pub enum ConsoleCommandsLabels {
 pub struct InputConsoleCommand {
     /// The connection handle tied to the entity performing the command.
     pub handle_option: Option<u64>,
-    /// The entity performing the command.
-    pub entity: Entity,
+    /// The admin session the command was issued with, its output is returned there.
+    pub admin_session_option: Option<AdminSession>,
+    /// The entity performing the command. None if it was issued on the server admin console.
+    pub entity_option: Option<Entity>,
     pub input: ClientSideConsoleInput,
+    /// The arguments parsed and validated against the declared [ConsoleCommand::args].
+    pub args: Vec<ConsoleArgValue>,
 }
@@ -1,8 +1,8 @@
 use crate::commands::AllConsoleCommands;
 use crate::net::ConsoleCommandsServerMessage;
-use bevy::prelude::{EventReader, Res};
-use networking::server::OutgoingReliableServerMessage;
-use player::connections::SendServerConfiguration;
+use bevy::prelude::{EventReader, Query, Res};
+use networking::server::{ConnectedPlayer, OutgoingReliableServerMessage};
+use player::{connections::SendServerConfiguration, names::UsedNames};
 
 use bevy::prelude::EventWriter;
 
@@ -10,6 +10,7 @@ pub(crate) fn configure(
     mut config_events: EventReader<SendServerConfiguration>,
     mut server: EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
     console_commands: Res<AllConsoleCommands>,
+    used_names: Res<UsedNames>,
 ) {
     for event in config_events.iter() {
         let console_commands = console_commands.list.clone();
@@ -18,5 +19,33 @@ pub(crate) fn configure(
             handle: event.handle,
             message: ConsoleCommandsServerMessage::ConfigConsoleCommands(console_commands),
         });
+        server.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message: ConsoleCommandsServerMessage::PlayerNames(
+                used_names.names.keys().cloned().collect(),
+            ),
+        });
+    }
+}
+
+/// Send changed character names to connected clients for tab-completion.
+
+pub(crate) fn sync_player_names(
+    used_names: Res<UsedNames>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut server: EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+) {
+    if !used_names.is_changed() {
+        return;
+    }
+    let names: Vec<String> = used_names.names.keys().cloned().collect();
+    for connected_player in connected_players.iter() {
+        if !connected_player.connected {
+            continue;
+        }
+        server.send(OutgoingReliableServerMessage {
+            handle: connected_player.handle,
+            message: ConsoleCommandsServerMessage::PlayerNames(names.clone()),
+        });
     }
 }
@@ -3,6 +3,7 @@ use bevy::prelude::ResMut;
 use networking::server::ConsoleArgVariant;
 
 use crate::commands::AllConsoleCommands;
+use crate::commands::ConsoleArg;
 use crate::commands::ConsoleCommand;
 
 /// Initialize console commands.
@@ -11,7 +12,7 @@ pub fn initialize_console_commands(mut commands: ResMut<AllConsoleCommands>) {
     commands.list.push(ConsoleCommand {
         base: "rcon".to_string(),
         description: "Obtaining rcon status allows for usage of rcon commands".to_string(),
-        args: vec![("password".to_string(), ConsoleArgVariant::String)],
+        args: vec![ConsoleArg::new("password", ConsoleArgVariant::String)],
     });
 
     commands.list.push(ConsoleCommand {
@@ -30,9 +31,9 @@ pub(crate) fn initialize_console_commands_2(mut commands: ResMut<AllConsoleComma
         base: "spawn".to_string(),
         description: "Spawn in entities in proximity.".to_string(),
         args: vec![
-            ("entity_name".to_string(), ConsoleArgVariant::String),
-            ("amount".to_string(), ConsoleArgVariant::Int),
-            ("player_selector".to_string(), ConsoleArgVariant::String),
+            ConsoleArg::new("entity_name", ConsoleArgVariant::String),
+            ConsoleArg::optional("amount", ConsoleArgVariant::Int, "1"),
+            ConsoleArg::optional("player_selector", ConsoleArgVariant::String, "@me"),
         ],
     });
 }
@@ -1,6 +1,8 @@
 //! Console commands are a powerful way to remotely interact with the server as an admin or regular player.
 //! Custom commands can be added and configured server-side.
 
+/// Server admin console on stdin and the remote admin socket.
+pub mod admin;
 /// Resources and initialization.
 pub mod commands;
 /// Send configuration to newly connected clients.
@@ -11,5 +13,7 @@ pub mod init;
 pub mod net;
 /// The networking module of this crate.
 pub mod networking;
+/// Parse and validate console command arguments.
+pub mod parse;
 /// The Bevy plugin of this crate.
 pub mod plugins;
@@ -2,13 +2,18 @@ use serde::{Deserialize, Serialize};
 use typename::TypeName;
 use ui::text::NetTextSection;
 
-use crate::commands::ConsoleCommand;
+use crate::{
+    commands::ConsoleCommand,
+    parse::{quote_console_word, split_console_input},
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
 
 pub enum ConsoleCommandsServerMessage {
     ConsoleWriteLine(ConsoleLine),
     ConfigConsoleCommands(Vec<ConsoleCommand>),
+    /// Character names for tab-completion of player selectors.
+    PlayerNames(Vec<String>),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -31,23 +36,25 @@ pub struct ClientSideConsoleInput {
 
 impl ClientSideConsoleInput {
     pub fn from_string(str: String) -> Self {
-        let mut split: Vec<&str> = str.split(" ").collect();
+        let mut split = split_console_input(&str);
 
-        let base = split.first().unwrap().clone();
-
-        split.remove(0);
-
-        let mut args = vec![];
-        for s in split.iter() {
-            args.push(s.to_string());
+        let base;
+        if split.is_empty() {
+            base = String::new();
+        } else {
+            base = split.remove(0);
         }
 
         ClientSideConsoleInput {
-            command: base.to_string(),
-            args,
+            command: base,
+            args: split,
         }
     }
     pub fn to_string(&self) -> String {
-        format!("{} {}", self.command, self.args.join(" "))
+        let mut words = vec![self.command.clone()];
+        for arg in self.args.iter() {
+            words.push(quote_console_word(arg));
+        }
+        words.join(" ")
     }
 }
@@ -1,10 +1,14 @@
 use bevy::prelude::warn;
 
-use crate::commands::InputConsoleCommand;
-use bevy::prelude::{EventWriter, Res};
-use networking::server::HandleToEntity;
+use crate::commands::{AllConsoleCommands, ConsoleCommand, InputConsoleCommand};
+use crate::parse::validate_console_input;
+use bevy::prelude::{EventWriter, Res, ResMut, Resource};
+use networking::client::IncomingReliableServerMessage;
+use networking::server::{HandleToEntity, OutgoingReliableServerMessage};
+use ui::fonts::{Fonts, SOURCECODE_REGULAR_FONT};
+use ui::text::{NetTextSection, COMMUNICATION_FONT_SIZE, CONSOLE_ERROR_COLOR};
 
-use crate::net::ConsoleCommandsClientMessage;
+use crate::net::{ConsoleCommandsClientMessage, ConsoleCommandsServerMessage, ConsoleLine};
 use bevy::prelude::EventReader;
 use networking::server::IncomingReliableClientMessage;
 
@@ -14,6 +18,9 @@ pub(crate) fn incoming_messages(
     mut server: EventReader<IncomingReliableClientMessage<ConsoleCommandsClientMessage>>,
     handle_to_entity: Res<HandleToEntity>,
     mut console_commands_queue: EventWriter<InputConsoleCommand>,
+    commands: Res<AllConsoleCommands>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    fonts: Res<Fonts>,
 ) {
     for message in server.iter() {
         let client_message = message.message.clone();
@@ -21,13 +28,35 @@ pub(crate) fn incoming_messages(
         match client_message {
             ConsoleCommandsClientMessage::ConsoleCommand(input) => {
                 match handle_to_entity.map.get(&message.handle) {
-                    Some(player_entity) => {
-                        console_commands_queue.send(InputConsoleCommand {
-                            handle_option: Some(message.handle),
-                            entity: *player_entity,
-                            input,
-                        });
-                    }
+                    Some(player_entity) => match validate_console_input(&commands, &input) {
+                        Ok(args) => {
+                            console_commands_queue.send(InputConsoleCommand {
+                                handle_option: Some(message.handle),
+                                admin_session_option: None,
+                                entity_option: Some(*player_entity),
+                                input,
+                                args,
+                            });
+                        }
+                        Err(err) => {
+                            net.send(OutgoingReliableServerMessage {
+                                handle: message.handle,
+                                message: ConsoleCommandsServerMessage::ConsoleWriteLine(
+                                    ConsoleLine {
+                                        sections: vec![NetTextSection {
+                                            text: err,
+                                            font: *fonts
+                                                .inv_map
+                                                .get(SOURCECODE_REGULAR_FONT)
+                                                .unwrap(),
+                                            font_size: COMMUNICATION_FONT_SIZE,
+                                            color: CONSOLE_ERROR_COLOR,
+                                        }],
+                                    },
+                                ),
+                            });
+                        }
+                    },
                     None => {
                         warn!("Couldn't find player_entity belonging to console_command sender handle.");
                     }
@@ -36,3 +65,37 @@ pub(crate) fn incoming_messages(
         }
     }
 }
+
+/// Client resource containing the console commands the server accepts.
+#[derive(Default, Resource)]
+
+pub struct ServerConsoleCommands {
+    pub list: Vec<ConsoleCommand>,
+}
+
+/// Client resource containing character names for tab-completion.
+#[derive(Default, Resource)]
+
+pub struct ConsolePlayerNames {
+    pub names: Vec<String>,
+}
+
+/// Manage incoming network messages from the server.
+
+pub(crate) fn client_incoming_messages(
+    mut client: EventReader<IncomingReliableServerMessage<ConsoleCommandsServerMessage>>,
+    mut server_commands: ResMut<ServerConsoleCommands>,
+    mut player_names: ResMut<ConsolePlayerNames>,
+) {
+    for message in client.iter() {
+        match &message.message {
+            ConsoleCommandsServerMessage::ConfigConsoleCommands(list) => {
+                server_commands.list = list.clone();
+            }
+            ConsoleCommandsServerMessage::PlayerNames(names) => {
+                player_names.names = names.clone();
+            }
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use networking::server::ConsoleArgVariant;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::{AllConsoleCommands, ConsoleCommand},
+    net::ClientSideConsoleInput,
+};
+
+/// Value of a parsed console command argument.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+
+pub enum ConsoleArgValue {
+    Int(i64),
+    String(String),
+    Float(f32),
+    Bool(bool),
+}
+
+impl ConsoleArgValue {
+    pub fn int(&self) -> Option<i64> {
+        match self {
+            ConsoleArgValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+    pub fn string(&self) -> Option<&str> {
+        match self {
+            ConsoleArgValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    pub fn float(&self) -> Option<f32> {
+        match self {
+            ConsoleArgValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+    pub fn bool(&self) -> Option<bool> {
+        match self {
+            ConsoleArgValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+/// Split console input into words. Double quotes group words together, `\"` is a literal quote and `\\` a literal backslash.
+/// An unterminated quote runs until the end of the input.
+
+pub fn split_console_input(input: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'"') || chars.peek() == Some(&'\\') => {
+                word.push(chars.next().unwrap_or('\\'));
+                in_word = true;
+            }
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(word.clone());
+                    word.clear();
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Quote a word if it wouldn't survive [split_console_input] on its own.
+
+pub fn quote_console_word(word: &str) -> String {
+    if !word.is_empty() && !word.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        return word.to_string();
+    }
+    format!("\"{}\"", word.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn variant_name(variant: &ConsoleArgVariant) -> &'static str {
+    match variant {
+        ConsoleArgVariant::Int => "integer",
+        ConsoleArgVariant::String => "text",
+        ConsoleArgVariant::Float => "number",
+        ConsoleArgVariant::Bool => "true/false",
+    }
+}
+
+/// Usage text of a console command, optional arguments in square brackets.
+
+pub fn console_command_usage(command: &ConsoleCommand) -> String {
+    let mut usage = format!("Usage: {}", command.base);
+    for arg in command.args.iter() {
+        match &arg.default_option {
+            Some(default) => {
+                usage += &format!(
+                    " [{}: {} = {}]",
+                    arg.name,
+                    variant_name(&arg.variant),
+                    quote_console_word(default)
+                );
+            }
+            None => {
+                usage += &format!(" <{}: {}>", arg.name, variant_name(&arg.variant));
+            }
+        }
+    }
+    usage
+}
+
+fn parse_value(raw: &str, variant: &ConsoleArgVariant) -> Option<ConsoleArgValue> {
+    match variant {
+        ConsoleArgVariant::Int => raw.parse::<i64>().ok().map(ConsoleArgValue::Int),
+        ConsoleArgVariant::Float => raw.parse::<f32>().ok().map(ConsoleArgValue::Float),
+        ConsoleArgVariant::String => Some(ConsoleArgValue::String(raw.to_string())),
+        ConsoleArgVariant::Bool => match raw.to_lowercase().as_str() {
+            "true" | "1" | "on" | "yes" => Some(ConsoleArgValue::Bool(true)),
+            "false" | "0" | "off" | "no" => Some(ConsoleArgValue::Bool(false)),
+            _ => None,
+        },
+    }
+}
+
+/// Validate arity and types of the input against the declared arguments of the command.
+/// Omitted optional arguments get their default. Surplus words are joined into a trailing text argument.
+
+pub fn parse_console_args(
+    command: &ConsoleCommand,
+    input: &[String],
+) -> Result<Vec<ConsoleArgValue>, String> {
+    let mut raw_args = input.to_vec();
+
+    if raw_args.len() > command.args.len() {
+        match command.args.last() {
+            Some(last) => match last.variant {
+                ConsoleArgVariant::String => {
+                    let rest = raw_args.split_off(command.args.len() - 1);
+                    raw_args.push(rest.join(" "));
+                }
+                _ => {
+                    return Err(format!("Too many arguments for {}.", command.base));
+                }
+            },
+            None => {
+                return Err(format!("{} takes no arguments.", command.base));
+            }
+        }
+    }
+
+    let mut values = vec![];
+    for (i, arg) in command.args.iter().enumerate() {
+        let raw;
+        match raw_args.get(i) {
+            Some(r) => {
+                raw = r.clone();
+            }
+            None => match &arg.default_option {
+                Some(default) => {
+                    raw = default.clone();
+                }
+                None => {
+                    return Err(format!("Missing argument {}.", arg.name));
+                }
+            },
+        }
+        match parse_value(&raw, &arg.variant) {
+            Some(value) => {
+                values.push(value);
+            }
+            None => {
+                return Err(format!(
+                    "Argument {} expects {}, got \"{}\".",
+                    arg.name,
+                    variant_name(&arg.variant),
+                    raw
+                ));
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Find the command of the input and parse its arguments. The error includes the usage text.
+
+pub fn validate_console_input(
+    commands: &AllConsoleCommands,
+    input: &ClientSideConsoleInput,
+) -> Result<Vec<ConsoleArgValue>, String> {
+    match commands
+        .list
+        .iter()
+        .find(|command| command.base == input.command)
+    {
+        Some(command) => match parse_console_args(command, &input.args) {
+            Ok(values) => Ok(values),
+            Err(err) => Err(format!("{}\n{}", err, console_command_usage(command))),
+        },
+        None => Err(format!(
+            "Unknown command {}. Type help for a list of commands.",
+            input.command
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::ConsoleArg;
+
+    fn ban_command() -> ConsoleCommand {
+        ConsoleCommand {
+            base: "ban".to_string(),
+            description: "Ban players.".to_string(),
+            args: vec![
+                ConsoleArg::new("player", ConsoleArgVariant::String),
+                ConsoleArg::optional("minutes", ConsoleArgVariant::Int, "0"),
+                ConsoleArg::optional("reason", ConsoleArgVariant::String, "No reason given."),
+            ],
+        }
+    }
+
+    fn words(input: &[&str]) -> Vec<String> {
+        input.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_console_input_words() {
+        assert_eq!(
+            split_console_input("  spawn   pistol 2 "),
+            words(&["spawn", "pistol", "2"])
+        );
+        assert_eq!(split_console_input(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_split_console_input_quoted_spaces() {
+        assert_eq!(
+            split_console_input("ban \"Wolf 1\" 60 \"being rude\""),
+            words(&["ban", "Wolf 1", "60", "being rude"])
+        );
+        assert_eq!(split_console_input("kick \"\""), words(&["kick", ""]));
+        assert_eq!(split_console_input("ab\"c d\"e"), words(&["abc de"]));
+    }
+
+    #[test]
+    fn test_split_console_input_escapes() {
+        assert_eq!(
+            split_console_input("say \"a \\\"b\\\"\""),
+            words(&["say", "a \"b\""])
+        );
+        assert_eq!(split_console_input("a\\\\b"), words(&["a\\b"]));
+        assert_eq!(split_console_input("C:\\dir"), words(&["C:\\dir"]));
+    }
+
+    #[test]
+    fn test_split_console_input_unterminated_quote() {
+        assert_eq!(
+            split_console_input("kick \"Wolf 1"),
+            words(&["kick", "Wolf 1"])
+        );
+        assert_eq!(split_console_input("kick \""), words(&["kick", ""]));
+    }
+
+    #[test]
+    fn test_parse_console_args_defaults() {
+        assert_eq!(
+            parse_console_args(&ban_command(), &words(&["Wolf"])),
+            Ok(vec![
+                ConsoleArgValue::String("Wolf".to_string()),
+                ConsoleArgValue::Int(0),
+                ConsoleArgValue::String("No reason given.".to_string()),
+            ])
+        );
+        assert_eq!(
+            parse_console_args(&ban_command(), &words(&["Wolf", "60"])),
+            Ok(vec![
+                ConsoleArgValue::String("Wolf".to_string()),
+                ConsoleArgValue::Int(60),
+                ConsoleArgValue::String("No reason given.".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_console_args_quoted_with_spaces() {
+        let input = split_console_input("\"Wolf 1\" 60 \"being rude\"");
+        assert_eq!(
+            parse_console_args(&ban_command(), &input),
+            Ok(vec![
+                ConsoleArgValue::String("Wolf 1".to_string()),
+                ConsoleArgValue::Int(60),
+                ConsoleArgValue::String("being rude".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_console_args_joins_trailing_text() {
+        assert_eq!(
+            parse_console_args(&ban_command(), &words(&["Wolf", "60", "being", "rude"])),
+            Ok(vec![
+                ConsoleArgValue::String("Wolf".to_string()),
+                ConsoleArgValue::Int(60),
+                ConsoleArgValue::String("being rude".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_console_args_missing_required() {
+        assert_eq!(
+            parse_console_args(&ban_command(), &[]),
+            Err("Missing argument player.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_console_args_wrong_types() {
+        assert_eq!(
+            parse_console_args(&ban_command(), &words(&["Wolf", "forever"])),
+            Err("Argument minutes expects integer, got \"forever\".".to_string())
+        );
+
+        let command = ConsoleCommand {
+            base: "godMode".to_string(),
+            description: "Toggle god mode.".to_string(),
+            args: vec![ConsoleArg::new("enabled", ConsoleArgVariant::Bool)],
+        };
+        assert_eq!(
+            parse_console_args(&command, &words(&["yes"])),
+            Ok(vec![ConsoleArgValue::Bool(true)])
+        );
+        assert_eq!(
+            parse_console_args(&command, &words(&["Off"])),
+            Ok(vec![ConsoleArgValue::Bool(false)])
+        );
+        assert!(parse_console_args(&command, &words(&["maybe"])).is_err());
+        assert_eq!(
+            parse_console_args(&command, &words(&["on", "off"])),
+            Err("Too many arguments for godMode.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_console_args_no_arguments() {
+        let command = ConsoleCommand {
+            base: "rconStatus".to_string(),
+            description: "Check RCON status.".to_string(),
+            args: vec![],
+        };
+        assert_eq!(parse_console_args(&command, &[]), Ok(vec![]));
+        assert_eq!(
+            parse_console_args(&command, &words(&["now"])),
+            Err("rconStatus takes no arguments.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_quote_console_word_round_trip() {
+        let cases = [
+            "Wolf",
+            "Wolf 1",
+            "",
+            "say \"hi\"",
+            "\"",
+            "back\\slash",
+            "trailing\\",
+            "trailing \\",
+            "\\\"",
+            "tab\tseparated",
+        ];
+        for word in cases {
+            assert_eq!(
+                split_console_input(&quote_console_word(word)),
+                vec![word.to_string()],
+                "{}",
+                word
+            );
+        }
+        assert_eq!(
+            split_console_input(
+                &cases
+                    .iter()
+                    .map(|word| quote_console_word(word))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
+            words(&cases)
+        );
+        assert_eq!(quote_console_word("Wolf"), "Wolf");
+    }
+}
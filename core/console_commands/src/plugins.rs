@@ -7,11 +7,18 @@ use resources::{
 };
 
 use crate::{
+    admin::{
+        dispatch_admin_command, flush_admin_output, read_stdin_console, remote_admin_input,
+        start_remote_admin, start_stdin_console, AdminCommandQueue, AdminConsoleOutput,
+        RemoteAdmin,
+    },
     commands::{AllConsoleCommands, ConsoleCommandsLabels, InputConsoleCommand},
-    connections::configure,
+    connections::{configure, sync_player_names},
     init::{initialize_console_commands, initialize_console_commands_2},
     net::{ConsoleCommandsClientMessage, ConsoleCommandsServerMessage},
-    networking::incoming_messages,
+    networking::{
+        client_incoming_messages, incoming_messages, ConsolePlayerNames, ServerConsoleCommands,
+    },
 };
 
 #[derive(Default)]
@@ -26,7 +33,26 @@ impl Plugin for ConsoleCommandsPlugin {
                     configure
                         .in_set(ConfigurationLabel::Main)
                         .after(ConfigurationLabel::SpawnEntity),
-                );
+                )
+                .add_system(sync_player_names.in_base_set(CoreSet::PostUpdate))
+                .add_startup_system(start_stdin_console)
+                .add_startup_system(start_remote_admin)
+                .init_resource::<RemoteAdmin>()
+                .init_resource::<AdminCommandQueue>()
+                .add_event::<AdminConsoleOutput>()
+                .add_system(read_stdin_console.in_base_set(CoreSet::PreUpdate))
+                .add_system(remote_admin_input.in_base_set(CoreSet::PreUpdate))
+                .add_system(
+                    dispatch_admin_command
+                        .in_base_set(CoreSet::PreUpdate)
+                        .after(read_stdin_console)
+                        .after(remote_admin_input),
+                )
+                .add_system(flush_admin_output.in_base_set(CoreSet::PostUpdate));
+        } else {
+            app.add_system(client_incoming_messages)
+                .init_resource::<ServerConsoleCommands>()
+                .init_resource::<ConsolePlayerNames>();
         }
         app.add_startup_system(
             initialize_console_commands
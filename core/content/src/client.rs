@@ -0,0 +1,369 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    prelude::{info, warn, EventWriter, Res, ResMut, Resource},
+    time::{Time, Timer, TimerMode},
+};
+use bevy_renet::renet::RenetClient;
+use networking::{
+    client::{ConnectionPreferences, OutgoingReliableClientMessage},
+    plugin::RENET_BLOCKING_CHANNEL_ID,
+};
+use resources::content::ContentAssets;
+
+use crate::{
+    manifest::{
+        content_file_path, content_hash, file_hash, ContentFile, ContentFileId, ContentManifest,
+        MAX_CONTENT_DOWNLOAD_SIZE, MAX_CONTENT_FILE_SIZE,
+    },
+    net::{ContentClientMessage, ContentServerMessage},
+};
+
+/// Seconds the server may stay silent during a download before it is considered failed.
+
+pub const CONTENT_DOWNLOAD_TIMEOUT: f32 = 30.;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub enum ContentStatus {
+    #[default]
+    None,
+    Downloading,
+    /// All content of the server is in the cache.
+    Ready,
+    Failed(String),
+}
+
+/// Resource with the content download of the connected server.
+#[derive(Resource, Default)]
+
+pub struct ContentDownload {
+    pub status: ContentStatus,
+    /// Cache folder of the connected server.
+    pub cache_root: PathBuf,
+    /// All files of the server content.
+    pub files: Vec<ContentFileId>,
+    /// Requested files that have not been received yet.
+    pub pending: HashMap<ContentFileId, ContentFile>,
+    /// Parts received so far of the files being downloaded.
+    pub parts: HashMap<ContentFileId, Vec<u8>>,
+    pub total_bytes: u64,
+    pub received_bytes: u64,
+    /// Reset whenever content arrives.
+    pub timeout: Timer,
+}
+
+impl ContentDownload {
+    /// Path of a downloaded content file to load assets from, none if the server does not provide it.
+    pub fn asset_path(&self, namespace: &str, path: &str) -> Option<PathBuf> {
+        if self.status != ContentStatus::Ready {
+            return None;
+        }
+        match content_file_path(namespace, path) {
+            Some(p) => {
+                let cached = self.cache_root.join(p);
+                if cached.exists() {
+                    Some(cached)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        }
+    }
+    fn fail(&mut self, reason: String) {
+        warn!("Couldnt download server content: {}", reason);
+        self.pending.clear();
+        self.parts.clear();
+        self.status = ContentStatus::Failed(reason);
+    }
+}
+
+/// Cache folder for the content of a server.
+
+pub fn server_cache_directory(server_address: &str) -> PathBuf {
+    let folder: String = server_address
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    // Absolute so the asset server loads cached files outside of the asset folder.
+    let relative = Path::new("data").join("cache").join("content").join(folder);
+    match std::env::current_dir() {
+        Ok(current) => current.join(relative),
+        Err(_) => relative,
+    }
+}
+
+fn start_download(
+    download: &mut ContentDownload,
+    manifest: ContentManifest,
+    server_address: &str,
+    net: &mut EventWriter<OutgoingReliableClientMessage<ContentClientMessage>>,
+) {
+    download.status = ContentStatus::Downloading;
+    download.cache_root = server_cache_directory(server_address);
+    download.files.clear();
+    download.pending.clear();
+    download.parts.clear();
+    download.total_bytes = 0;
+    download.received_bytes = 0;
+    download.timeout = Timer::from_seconds(CONTENT_DOWNLOAD_TIMEOUT, TimerMode::Once);
+
+    // Never trust paths of the server, they could escape the cache folder.
+    for pack in manifest.packs {
+        for file in pack.files {
+            let relative;
+            match content_file_path(&pack.namespace, &file.path) {
+                Some(p) => {
+                    relative = p;
+                }
+                None => {
+                    download.fail(format!(
+                        "Invalid content path {}::{}",
+                        pack.namespace, file.path
+                    ));
+                    return;
+                }
+            }
+            download.files.push(ContentFileId {
+                namespace: pack.namespace.clone(),
+                path: file.path.clone(),
+            });
+            if file.size > MAX_CONTENT_FILE_SIZE {
+                download.fail(format!(
+                    "Content file {}::{} exceeds {} bytes.",
+                    pack.namespace, file.path, MAX_CONTENT_FILE_SIZE
+                ));
+                return;
+            }
+            if file_hash(&download.cache_root.join(relative)).as_ref() == Some(&file.hash) {
+                continue;
+            }
+            download.total_bytes += file.size;
+            download.pending.insert(
+                ContentFileId {
+                    namespace: pack.namespace.clone(),
+                    path: file.path.clone(),
+                },
+                file,
+            );
+        }
+    }
+    if download.total_bytes > MAX_CONTENT_DOWNLOAD_SIZE {
+        let total = download.total_bytes;
+        download.fail(format!(
+            "Server content of {} bytes exceeds {} bytes.",
+            total, MAX_CONTENT_DOWNLOAD_SIZE
+        ));
+        return;
+    }
+
+    info!(
+        "Downloading {} content files of {} bytes.",
+        download.pending.len(),
+        download.total_bytes
+    );
+    for id in download.pending.keys() {
+        net.send(OutgoingReliableClientMessage {
+            message: ContentClientMessage::RequestFile(id.clone()),
+        });
+    }
+}
+
+/// Add a received part to its file and store the file once it is complete.
+
+fn receive_part(
+    download: &mut ContentDownload,
+    id: ContentFileId,
+    offset: u64,
+    data: Vec<u8>,
+) -> Result<(), String> {
+    let size;
+    match download.pending.get(&id) {
+        Some(file) => {
+            size = file.size;
+        }
+        None => {
+            warn!("Received unrequested content {}::{}", id.namespace, id.path);
+            return Ok(());
+        }
+    }
+    let received = download.parts.entry(id.clone()).or_default();
+    if offset != received.len() as u64 || offset + data.len() as u64 > size {
+        return Err(format!(
+            "Content file {}::{} arrived out of order.",
+            id.namespace, id.path
+        ));
+    }
+    received.extend_from_slice(&data);
+    let complete = received.len() as u64 == size;
+    download.received_bytes += data.len() as u64;
+    if !complete {
+        return Ok(());
+    }
+    let data = download.parts.remove(&id).unwrap_or_default();
+    match store_file(download, id, data) {
+        Ok(_) => {}
+        Err(error) => {
+            return Err(error);
+        }
+    }
+    info!(
+        "Downloaded {}/{} bytes of server content.",
+        download.received_bytes, download.total_bytes
+    );
+    Ok(())
+}
+
+fn store_file(
+    download: &mut ContentDownload,
+    id: ContentFileId,
+    data: Vec<u8>,
+) -> Result<(), String> {
+    let file;
+    match download.pending.remove(&id) {
+        Some(f) => {
+            file = f;
+        }
+        None => {
+            warn!("Received unrequested content {}::{}", id.namespace, id.path);
+            return Ok(());
+        }
+    }
+    if data.len() as u64 != file.size || content_hash(&data) != file.hash {
+        return Err(format!(
+            "Content file {}::{} does not match the manifest.",
+            id.namespace, id.path
+        ));
+    }
+    let path;
+    match content_file_path(&id.namespace, &id.path) {
+        Some(p) => {
+            path = download.cache_root.join(p);
+        }
+        None => {
+            return Err(format!(
+                "Invalid content path {}::{}",
+                id.namespace, id.path
+            ));
+        }
+    }
+    match path.parent() {
+        Some(directory) => match fs::create_dir_all(directory) {
+            Ok(_) => {}
+            Err(error) => {
+                return Err(error.to_string());
+            }
+        },
+        None => {}
+    }
+    let temporary = path.with_extension("download");
+    match fs::write(&temporary, &data) {
+        Ok(_) => {}
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    match fs::rename(&temporary, &path) {
+        Ok(_) => {}
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Cached files of the server content to load assets from.
+
+fn content_assets(download: &ContentDownload) -> HashMap<(String, String), PathBuf> {
+    let mut paths = HashMap::new();
+    for id in download.files.iter() {
+        match download.asset_path(&id.namespace, &id.path) {
+            Some(path) => {
+                paths.insert((id.namespace.clone(), id.path.clone()), path);
+            }
+            None => {
+                warn!("Couldnt find cached content {}::{}", id.namespace, id.path);
+            }
+        }
+    }
+    paths
+}
+
+/// Receive the content manifest and files of the server over the chunk channel.
+/// Once all content is cached its files are used for loading assets.
+
+pub(crate) fn receive_content(
+    mut client: ResMut<RenetClient>,
+    mut download: ResMut<ContentDownload>,
+    mut assets: ResMut<ContentAssets>,
+    preferences: Res<ConnectionPreferences>,
+    mut net: EventWriter<OutgoingReliableClientMessage<ContentClientMessage>>,
+    time: Res<Time>,
+) {
+    while let Some(bits) = client.receive_message(RENET_BLOCKING_CHANNEL_ID) {
+        let message;
+        match bincode::deserialize::<ContentServerMessage>(&bits) {
+            Ok(m) => {
+                message = m;
+            }
+            Err(_) => {
+                warn!("Received an invalid content message.");
+                continue;
+            }
+        }
+        match message {
+            ContentServerMessage::Manifest(manifest) => {
+                // Content of a previous server no longer applies.
+                assets.paths.clear();
+                start_download(
+                    &mut download,
+                    manifest,
+                    &preferences.server_address,
+                    &mut net,
+                );
+            }
+            ContentServerMessage::FilePart(id, offset, data) => {
+                if download.status != ContentStatus::Downloading {
+                    continue;
+                }
+                download.timeout.reset();
+                match receive_part(&mut download, id, offset, data) {
+                    Ok(_) => {}
+                    Err(error) => {
+                        download.fail(error);
+                        continue;
+                    }
+                }
+            }
+            ContentServerMessage::Unavailable(id) => {
+                if download.status != ContentStatus::Downloading {
+                    continue;
+                }
+                download.fail(format!(
+                    "The server couldn't send {}::{}.",
+                    id.namespace, id.path
+                ));
+                continue;
+            }
+        }
+        if download.status == ContentStatus::Downloading && download.pending.is_empty() {
+            download.status = ContentStatus::Ready;
+            assets.paths = content_assets(&download);
+            info!("Server content is up to date.");
+            net.send(OutgoingReliableClientMessage {
+                message: ContentClientMessage::Ready,
+            });
+        }
+    }
+
+    if download.status == ContentStatus::Downloading {
+        download.timeout.tick(time.delta());
+        if download.timeout.finished() {
+            download.fail("The server stopped sending content.".to_string());
+        }
+    }
+}
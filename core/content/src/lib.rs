@@ -0,0 +1,16 @@
+//! Namespaced content packs shared by the server with its clients.
+//! Each folder in `data/content/` is a content pack named after its namespace, such as `sf` for `sf::` entity identifiers.
+//! Packs contain models, sounds and tile and entity definitions, the server advertises a manifest with the hash of every file to new clients.
+//! Clients download missing or changed files over the renet chunk channel into a cache per server before they can board.
+//! Cached files take the place of the assets with the same path in the asset folder, see [resources::content::ContentAssets].
+
+/// Downloading content into the client cache.
+pub mod client;
+/// Content manifests, hashing and path sanitisation.
+pub mod manifest;
+/// Content netcode messages.
+pub mod net;
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Serving content packs to clients.
+pub mod server;
@@ -0,0 +1,316 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::warn;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Largest file a content pack may contain in bytes.
+
+pub const MAX_CONTENT_FILE_SIZE: u64 = 32 * 1024 * 1024;
+
+/// Largest amount of bytes a client downloads from a single server.
+
+pub const MAX_CONTENT_DOWNLOAD_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Largest part of a content file sent in a single message.
+
+pub const CONTENT_PART_SIZE: usize = 64 * 1024;
+
+/// Longest path of a file inside a content pack.
+
+pub const MAX_CONTENT_PATH_LENGTH: usize = 256;
+
+/// Longest content pack namespace.
+
+pub const MAX_NAMESPACE_LENGTH: usize = 64;
+
+/// Folder containing the content packs of the server.
+
+pub fn content_directory() -> PathBuf {
+    Path::new("data").join("content")
+}
+
+/// Identifies a file of a content pack.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+
+pub struct ContentFileId {
+    pub namespace: String,
+    /// Path inside the content pack, components separated by `/`.
+    pub path: String,
+}
+
+/// A file of a content pack.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct ContentFile {
+    pub path: String,
+    pub size: u64,
+    /// Hex encoded sha256 of the file.
+    pub hash: String,
+}
+
+/// A namespaced content pack.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct ContentPack {
+    pub namespace: String,
+    pub files: Vec<ContentFile>,
+}
+
+/// All content packs of a server.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+
+pub struct ContentManifest {
+    pub packs: Vec<ContentPack>,
+}
+
+impl ContentManifest {
+    pub fn file(&self, id: &ContentFileId) -> Option<&ContentFile> {
+        match self.packs.iter().find(|p| p.namespace == id.namespace) {
+            Some(pack) => pack.files.iter().find(|f| f.path == id.path),
+            None => None,
+        }
+    }
+}
+
+/// Whether a namespace is safe to use as a directory name.
+
+pub fn valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty()
+        && namespace.len() <= MAX_NAMESPACE_LENGTH
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Relative path of a content file on drive, none if the namespace or path could escape the content folder.
+
+pub fn content_file_path(namespace: &str, path: &str) -> Option<PathBuf> {
+    if !valid_namespace(namespace) || path.is_empty() || path.len() > MAX_CONTENT_PATH_LENGTH {
+        return None;
+    }
+    let mut file_path = PathBuf::from(namespace);
+    for component in path.split('/') {
+        // Also refuses "." and ".." components and hidden files.
+        if component.is_empty()
+            || component.starts_with('.')
+            || !component
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+        {
+            return None;
+        }
+        file_path.push(component);
+    }
+    Some(file_path)
+}
+
+/// Hex encoded sha256 of bytes.
+
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Hash of a file on drive, none if it cannot be read.
+
+pub fn file_hash(path: &Path) -> Option<String> {
+    match fs::read(path) {
+        Ok(data) => Some(content_hash(&data)),
+        Err(_) => None,
+    }
+}
+
+fn collect_pack_files(
+    namespace: &str,
+    directory: &Path,
+    prefix: &str,
+    files: &mut Vec<ContentFile>,
+) {
+    let entries;
+    match fs::read_dir(directory) {
+        Ok(e) => {
+            entries = e;
+        }
+        Err(error) => {
+            warn!("Couldnt read content folder {:?}: {}", directory, error);
+            return;
+        }
+    }
+    for entry_result in entries {
+        let entry;
+        match entry_result {
+            Ok(e) => {
+                entry = e;
+            }
+            Err(_) => {
+                continue;
+            }
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = prefix.to_string() + &name;
+        let entry_path = entry.path();
+        if entry_path.is_dir() {
+            collect_pack_files(namespace, &entry_path, &(path + "/"), files);
+            continue;
+        }
+        if content_file_path(namespace, &path).is_none() {
+            warn!(
+                "Skipped content file with invalid path {}::{}",
+                namespace, path
+            );
+            continue;
+        }
+        let data;
+        match fs::read(&entry_path) {
+            Ok(d) => {
+                data = d;
+            }
+            Err(error) => {
+                warn!("Couldnt read content file {:?}: {}", entry_path, error);
+                continue;
+            }
+        }
+        if data.len() as u64 > MAX_CONTENT_FILE_SIZE {
+            warn!(
+                "Skipped content file {}::{} of {} bytes, the limit is {} bytes.",
+                namespace,
+                path,
+                data.len(),
+                MAX_CONTENT_FILE_SIZE
+            );
+            continue;
+        }
+        files.push(ContentFile {
+            path,
+            size: data.len() as u64,
+            hash: content_hash(&data),
+        });
+    }
+}
+
+/// Build the manifest of the content packs in the content folder.
+
+pub fn build_content_manifest() -> ContentManifest {
+    let mut manifest = ContentManifest::default();
+    let entries;
+    match fs::read_dir(content_directory()) {
+        Ok(e) => {
+            entries = e;
+        }
+        Err(_) => {
+            return manifest;
+        }
+    }
+    for entry_result in entries {
+        let entry;
+        match entry_result {
+            Ok(e) => {
+                entry = e;
+            }
+            Err(_) => {
+                continue;
+            }
+        }
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let namespace = entry.file_name().to_string_lossy().to_string();
+        if !valid_namespace(&namespace) {
+            warn!("Skipped content pack with invalid namespace {}.", namespace);
+            continue;
+        }
+        let mut files = vec![];
+        collect_pack_files(&namespace, &entry.path(), "", &mut files);
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        manifest.packs.push(ContentPack { namespace, files });
+    }
+    manifest.packs.sort_by(|a, b| a.namespace.cmp(&b.namespace));
+    manifest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_namespace() {
+        assert!(valid_namespace("sf"));
+        assert!(valid_namespace("my_pack2"));
+        assert!(valid_namespace(&"a".repeat(MAX_NAMESPACE_LENGTH)));
+
+        assert!(!valid_namespace(""));
+        assert!(!valid_namespace("SF"));
+        assert!(!valid_namespace("my-pack"));
+        assert!(!valid_namespace(".."));
+        assert!(!valid_namespace("sf/models"));
+        assert!(!valid_namespace("/sf"));
+        assert!(!valid_namespace(&"a".repeat(MAX_NAMESPACE_LENGTH + 1)));
+    }
+
+    #[test]
+    fn test_content_file_path_valid() {
+        assert_eq!(
+            content_file_path("sf", "models/cable/cable.glb"),
+            Some(
+                Path::new("sf")
+                    .join("models")
+                    .join("cable")
+                    .join("cable.glb")
+            )
+        );
+        assert_eq!(
+            content_file_path("sf", "tile-1_a.ron"),
+            Some(Path::new("sf").join("tile-1_a.ron"))
+        );
+    }
+
+    #[test]
+    fn test_content_file_path_parent_components() {
+        assert_eq!(content_file_path("sf", ".."), None);
+        assert_eq!(content_file_path("sf", "../secret"), None);
+        assert_eq!(content_file_path("sf", "models/../../secret"), None);
+        assert_eq!(content_file_path("sf", "models/./cable.glb"), None);
+        assert_eq!(content_file_path("sf", "models\\..\\secret"), None);
+    }
+
+    #[test]
+    fn test_content_file_path_absolute() {
+        assert_eq!(content_file_path("sf", "/etc/passwd"), None);
+        assert_eq!(content_file_path("sf", "C:/Windows/win.ini"), None);
+        assert_eq!(content_file_path("sf", "C:\\Windows\\win.ini"), None);
+    }
+
+    #[test]
+    fn test_content_file_path_hidden_files() {
+        assert_eq!(content_file_path("sf", ".hidden"), None);
+        assert_eq!(content_file_path("sf", "models/.git/config"), None);
+    }
+
+    #[test]
+    fn test_content_file_path_empty_components() {
+        assert_eq!(content_file_path("sf", ""), None);
+        assert_eq!(content_file_path("sf", "models//cable.glb"), None);
+        assert_eq!(content_file_path("sf", "models/"), None);
+    }
+
+    #[test]
+    fn test_content_file_path_limits() {
+        let long_path = "a".repeat(MAX_CONTENT_PATH_LENGTH);
+        assert!(content_file_path("sf", &long_path).is_some());
+        let too_long_path = "a".repeat(MAX_CONTENT_PATH_LENGTH + 1);
+        assert_eq!(content_file_path("sf", &too_long_path), None);
+    }
+
+    #[test]
+    fn test_content_file_path_bad_namespace() {
+        assert_eq!(content_file_path("", "models/cable.glb"), None);
+        assert_eq!(content_file_path("..", "models/cable.glb"), None);
+        assert_eq!(content_file_path("sf/..", "models/cable.glb"), None);
+    }
+}
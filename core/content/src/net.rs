@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use typename::TypeName;
+
+use crate::manifest::{ContentFileId, ContentManifest};
+
+/// Gets serialized and sent over the net, this is the client message.
+#[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
+
+pub enum ContentClientMessage {
+    RequestFile(ContentFileId),
+    /// All content of the manifest is in the cache of the client.
+    Ready,
+}
+
+/// Sent over the renet chunk channel, this is the server message.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+
+pub enum ContentServerMessage {
+    Manifest(ContentManifest),
+    /// Part of a requested file starting at the byte offset.
+    FilePart(ContentFileId, u64, Vec<u8>),
+    /// The server couldn't read a requested file.
+    Unavailable(ContentFileId),
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::{resource_exists, App, IntoSystemConfig, Plugin};
+use bevy_renet::renet::RenetClient;
+use networking::messaging::{register_reliable_message, MessageSender};
+use player::plugin::ConfigurationLabel;
+use resources::{content::ContentAssets, is_server::is_server};
+
+use crate::{
+    client::{receive_content, ContentDownload},
+    net::ContentClientMessage,
+    server::{
+        advertise_content_manifest, content_client_disconnected, load_content_manifest,
+        receive_content_requests, send_queued_content, ContentReady, ContentServer,
+    },
+};
+
+pub struct ContentPlugin;
+
+impl Plugin for ContentPlugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.init_resource::<ContentServer>()
+                .init_resource::<ContentReady>()
+                .add_startup_system(load_content_manifest)
+                .add_system(advertise_content_manifest.in_set(ConfigurationLabel::Main))
+                .add_system(receive_content_requests)
+                .add_system(
+                    send_queued_content
+                        .after(advertise_content_manifest)
+                        .after(receive_content_requests),
+                )
+                .add_system(content_client_disconnected);
+        } else {
+            app.init_resource::<ContentDownload>()
+                .init_resource::<ContentAssets>()
+                .add_system(receive_content.run_if(resource_exists::<RenetClient>()));
+        }
+        register_reliable_message::<ContentClientMessage>(app, MessageSender::Client);
+    }
+}
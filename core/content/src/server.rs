@@ -0,0 +1,249 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs,
+};
+
+use bevy::prelude::{info, warn, EventReader, Res, ResMut, Resource};
+use bevy_renet::renet::{RenetServer, ServerEvent};
+use networking::{
+    plugin::{RENET_BLOCKING_CHANNEL_ID, RENET_BLOCKING_MAX_MESSAGE_SIZE},
+    server::IncomingReliableClientMessage,
+};
+use player::connections::SendServerConfiguration;
+
+use crate::{
+    manifest::{
+        build_content_manifest, content_directory, content_file_path, ContentFileId,
+        ContentManifest, CONTENT_PART_SIZE, MAX_CONTENT_DOWNLOAD_SIZE,
+    },
+    net::{ContentClientMessage, ContentServerMessage},
+};
+
+/// A message waiting for room in the chunk channel of a client.
+
+pub enum QueuedContent {
+    Manifest,
+    File(ContentFileId),
+    /// Part of a file read from drive, starting at the byte offset.
+    FilePart(ContentFileId, u64, Vec<u8>),
+}
+
+impl QueuedContent {
+    fn is_file(&self, id: &ContentFileId) -> bool {
+        match self {
+            QueuedContent::Manifest => false,
+            QueuedContent::File(file_id) | QueuedContent::FilePart(file_id, _, _) => file_id == id,
+        }
+    }
+}
+
+/// Resource with the content manifest of the server and what each client is waiting for.
+#[derive(Resource, Default)]
+
+pub struct ContentServer {
+    pub manifest: ContentManifest,
+    pub queues: HashMap<u64, VecDeque<QueuedContent>>,
+    /// Bytes of files each client requested, capped at [MAX_CONTENT_DOWNLOAD_SIZE].
+    pub requested_bytes: HashMap<u64, u64>,
+}
+
+/// Resource with the handles of clients that downloaded all content of the server and may board.
+#[derive(Resource, Default)]
+
+pub struct ContentReady {
+    pub handles: HashSet<u64>,
+}
+
+/// Hash the content packs on drive.
+
+pub(crate) fn load_content_manifest(mut content: ResMut<ContentServer>) {
+    content.manifest = build_content_manifest();
+    let files: usize = content.manifest.packs.iter().map(|p| p.files.len()).sum();
+    info!(
+        "Loaded {} content packs with {} files.",
+        content.manifest.packs.len(),
+        files
+    );
+    match bincode::serialized_size(&ContentServerMessage::Manifest(content.manifest.clone())) {
+        Ok(size) => {
+            if size > RENET_BLOCKING_MAX_MESSAGE_SIZE {
+                warn!(
+                    "Content manifest of {} bytes exceeds the message limit of {} bytes, clients won't receive it.",
+                    size, RENET_BLOCKING_MAX_MESSAGE_SIZE
+                );
+            }
+        }
+        Err(_) => {
+            warn!("Couldnt serialize content manifest.");
+        }
+    }
+}
+
+/// Advertise the content manifest to clients being configured.
+
+pub(crate) fn advertise_content_manifest(
+    mut config_events: EventReader<SendServerConfiguration>,
+    mut content: ResMut<ContentServer>,
+) {
+    for event in config_events.iter() {
+        content
+            .queues
+            .entry(event.handle)
+            .or_default()
+            .push_back(QueuedContent::Manifest);
+    }
+}
+
+/// Queue requested content files and mark clients that finished downloading.
+
+pub(crate) fn receive_content_requests(
+    mut incoming: EventReader<IncomingReliableClientMessage<ContentClientMessage>>,
+    mut content: ResMut<ContentServer>,
+    mut ready: ResMut<ContentReady>,
+) {
+    for message in incoming.iter() {
+        match &message.message {
+            ContentClientMessage::RequestFile(id) => {
+                // Only files of the manifest are served, never arbitrary paths.
+                let size;
+                match content.manifest.file(id) {
+                    Some(file) => {
+                        size = file.size;
+                    }
+                    None => {
+                        warn!(
+                            "[{}] requested unknown content {}::{}",
+                            message.handle, id.namespace, id.path
+                        );
+                        continue;
+                    }
+                }
+                let queue = content.queues.entry(message.handle).or_default();
+                if queue.iter().any(|queued| queued.is_file(id)) {
+                    continue;
+                }
+                let requested = content.requested_bytes.entry(message.handle).or_default();
+                if *requested + size > MAX_CONTENT_DOWNLOAD_SIZE {
+                    warn!(
+                        "[{}] requested more than {} bytes of content.",
+                        message.handle, MAX_CONTENT_DOWNLOAD_SIZE
+                    );
+                    continue;
+                }
+                *requested += size;
+                content
+                    .queues
+                    .entry(message.handle)
+                    .or_default()
+                    .push_back(QueuedContent::File(id.clone()));
+            }
+            ContentClientMessage::Ready => {
+                ready.handles.insert(message.handle);
+            }
+        }
+    }
+}
+
+/// Read a content file and split it into parts that fit the chunk channel.
+
+fn read_file_parts(id: &ContentFileId) -> Option<Vec<QueuedContent>> {
+    let path;
+    match content_file_path(&id.namespace, &id.path) {
+        Some(p) => {
+            path = content_directory().join(p);
+        }
+        None => {
+            return None;
+        }
+    }
+    let data;
+    match fs::read(&path) {
+        Ok(d) => {
+            data = d;
+        }
+        Err(error) => {
+            warn!("Couldnt read content file {:?}: {}", path, error);
+            return None;
+        }
+    }
+    if data.is_empty() {
+        return Some(vec![QueuedContent::FilePart(id.clone(), 0, vec![])]);
+    }
+    Some(
+        data.chunks(CONTENT_PART_SIZE)
+            .enumerate()
+            .map(|(index, part)| {
+                QueuedContent::FilePart(
+                    id.clone(),
+                    (index * CONTENT_PART_SIZE) as u64,
+                    part.to_vec(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Send queued content over the chunk channel as it frees up.
+
+pub(crate) fn send_queued_content(
+    mut content: ResMut<ContentServer>,
+    mut server: ResMut<RenetServer>,
+) {
+    let content = &mut *content;
+    for (handle, queue) in content.queues.iter_mut() {
+        while server.can_send_message(*handle, RENET_BLOCKING_CHANNEL_ID) {
+            let message;
+            match queue.pop_front() {
+                Some(QueuedContent::Manifest) => {
+                    message = ContentServerMessage::Manifest(content.manifest.clone());
+                }
+                Some(QueuedContent::File(id)) => match read_file_parts(&id) {
+                    Some(parts) => {
+                        // Files are only read once they are up, their parts go first.
+                        for part in parts.into_iter().rev() {
+                            queue.push_front(part);
+                        }
+                        continue;
+                    }
+                    None => {
+                        message = ContentServerMessage::Unavailable(id);
+                    }
+                },
+                Some(QueuedContent::FilePart(id, offset, data)) => {
+                    message = ContentServerMessage::FilePart(id, offset, data);
+                }
+                None => {
+                    break;
+                }
+            }
+            match bincode::serialize(&message) {
+                Ok(bits) => {
+                    server.send_message(*handle, RENET_BLOCKING_CHANNEL_ID, bits);
+                }
+                Err(_) => {
+                    warn!("Couldnt serialize content message.");
+                }
+            }
+        }
+    }
+    content.queues.retain(|_, queue| !queue.is_empty());
+}
+
+/// Forget the content state of disconnected clients.
+
+pub(crate) fn content_client_disconnected(
+    mut server_events: EventReader<ServerEvent>,
+    mut content: ResMut<ContentServer>,
+    mut ready: ResMut<ContentReady>,
+) {
+    for event in server_events.iter() {
+        match event {
+            ServerEvent::ClientDisconnected(handle) => {
+                content.queues.remove(handle);
+                content.requested_bytes.remove(handle);
+                ready.handles.remove(handle);
+            }
+            _ => {}
+        }
+    }
+}
@@ -3,6 +3,8 @@
 
 /// Manage client input.
 pub mod input;
+/// Send the look direction of the player to the server.
+pub mod mouse_direction;
 /// Manage netcode.
 pub mod net;
 /// The networking module of this crate.
@@ -0,0 +1,80 @@
+use std::time::SystemTime;
+
+use bevy::prelude::{warn, EventWriter, Local, Query, Res};
+use cameras::{controllers::fps::ActiveCamera, LookTransform};
+use networking::client::OutgoingUnreliableClientMessage;
+use player::{
+    configuration::Boarded,
+    pawn_camera::{PawnCamera, PawnCameraMode},
+};
+
+use crate::net::ControllerUnreliableClientMessage;
+
+/// Smallest change in direction in radians that gets sent to the server.
+
+const MOUSE_DIRECTION_THRESHOLD: f32 = 0.01;
+
+/// Send the direction the pawn camera looks at to the server.
+/// The direction is the yaw on the XZ plane in radians, zero along the positive Z axis.
+
+pub(crate) fn send_mouse_direction(
+    boarded: Res<Boarded>,
+    pawn_camera: Res<PawnCamera>,
+    active_camera: Res<ActiveCamera>,
+    cameras: Query<&LookTransform>,
+    mut net: EventWriter<OutgoingUnreliableClientMessage<ControllerUnreliableClientMessage>>,
+    mut last_direction: Local<Option<f32>>,
+) {
+    if !boarded.boarded {
+        return;
+    }
+    match pawn_camera.active_mode {
+        Some(PawnCameraMode::FirstPerson) | Some(PawnCameraMode::ThirdPerson) => {}
+        _ => {
+            return;
+        }
+    }
+
+    let look_direction;
+    match active_camera
+        .option
+        .and_then(|camera| cameras.get(camera).ok())
+        .and_then(|look_transform| look_transform.look_direction())
+    {
+        Some(d) => {
+            look_direction = d;
+        }
+        None => {
+            return;
+        }
+    }
+    if look_direction.x == 0. && look_direction.z == 0. {
+        return;
+    }
+    let direction = look_direction.x.atan2(look_direction.z);
+
+    match *last_direction {
+        Some(last) => {
+            if (last - direction).abs() < MOUSE_DIRECTION_THRESHOLD {
+                return;
+            }
+        }
+        None => {}
+    }
+
+    let time_stamp;
+    match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(duration) => {
+            time_stamp = duration.as_millis() as u64;
+        }
+        Err(_) => {
+            warn!("System time is before the unix epoch.");
+            return;
+        }
+    }
+
+    net.send(OutgoingUnreliableClientMessage {
+        message: ControllerUnreliableClientMessage::MouseDirectionUpdate(direction, time_stamp),
+    });
+    *last_direction = Some(direction);
+}
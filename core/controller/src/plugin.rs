@@ -6,6 +6,7 @@ use crate::input::{
     InputMouseDirectionUpdate, InputMovementInput, InputSelectBodyPart, InputSprinting,
     InputToggleAutoMove, InputToggleCombatMode,
 };
+use crate::mouse_direction::send_mouse_direction;
 use crate::net::{ControllerClientMessage, ControllerUnreliableClientMessage};
 use crate::networking::incoming_messages;
 use bevy::prelude::{App, CoreSet, IntoSystemConfig, Plugin};
@@ -56,6 +57,10 @@ impl Plugin for ControllerPlugin {
                     .in_set(ConfigurationLabel::Main)
                     .after(ConfigurationLabel::SpawnEntity),
             );
+        } else {
+            app.add_system(
+                send_mouse_direction.run_if(on_fixed_timer(Duration::from_secs_f32(1. / 20.))),
+            );
         }
 
         register_reliable_message::<ControllerClientMessage>(app, MessageSender::Client);
@@ -2,14 +2,29 @@ use bevy::{
     prelude::{warn, AssetServer, Commands, EventReader, Res, Visibility},
     scene::SceneBundle,
 };
+use resources::content::{content_namespace, ContentAssets};
 
 use crate::{
     entity_types::EntityType,
     spawn::{ClientEntityServerEntity, PawnEntityId, SpawnEntity},
 };
 
+/// Path to load a file of the folder of an entity type from, the server content if it provides the file.
+
+pub fn entity_asset_path(
+    content: &ContentAssets,
+    entity_type: &dyn EntityType,
+    file: &str,
+) -> String {
+    content.asset_path(
+        content_namespace(&entity_type.get_identity()),
+        &("entities/".to_string() + &entity_type.get_clean_identity() + "/" + file),
+    )
+}
+
 pub fn link_base_mesh<T: Send + Sync + 'static + EntityType + Default>(
     asset_server: Res<AssetServer>,
+    content: Res<ContentAssets>,
     mut spawner: EventReader<SpawnEntity<T>>,
     mut commands: Commands,
     id: Res<PawnEntityId>,
@@ -22,11 +37,8 @@ pub fn link_base_mesh<T: Send + Sync + 'static + EntityType + Default>(
             continue;
         }
 
-        let mesh = asset_server.load(
-            "entities/".to_string()
-                + &entity_type.get_clean_identity()
-                + "/client_asset.glb#Scene0",
-        );
+        let mesh = asset_server
+            .load(entity_asset_path(&content, &entity_type, "client_asset.glb") + "#Scene0");
 
         let mut visibility = Visibility::default();
 
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use bevy::{
-    prelude::{Changed, Component, Entity, EventWriter, Query, Res, SystemSet, Transform},
+    prelude::{
+        Changed, Component, Entity, EventWriter, Quat, Query, Res, Resource, SystemSet, Transform,
+        Vec3,
+    },
     time::Time,
 };
 use entity_macros::Identity;
@@ -91,6 +94,8 @@ pub struct DefaultMapEntity;
 
 pub struct RawSpawnEvent {
     pub raw_entity: RawEntityRon,
+    /// Entity to build on, a new entity gets spawned if none.
+    pub entity_option: Option<Entity>,
 }
 /// ron entity.
 #[derive(Deserialize, Clone)]
@@ -447,6 +452,7 @@ pub(crate) fn world_mode_update(
 }
 
 /// For entities that are also registered with the gridmap.
+#[derive(Clone)]
 
 pub struct GridItemData {
     pub transform_offset: Transform,
@@ -454,6 +460,33 @@ pub struct GridItemData {
     pub can_be_built_with_grid_item: Vec<String>,
 }
 
+impl GridItemData {
+    /// Transform of the entity when placed at a cell position with a rotation.
+    pub fn placed_transform(&self, position: Vec3, rotation: Quat) -> Transform {
+        Transform {
+            translation: position,
+            rotation,
+            scale: Vec3::ONE,
+        }
+        .mul_transform(self.transform_offset)
+    }
+    /// Position and rotation of the cell an entity was placed at with [GridItemData::placed_transform].
+    pub fn placed_origin(&self, transform: &Transform) -> (Vec3, Quat) {
+        let rotation = transform.rotation * self.transform_offset.rotation.inverse();
+        (
+            transform.translation - rotation * self.transform_offset.translation,
+            rotation,
+        )
+    }
+}
+
 pub trait GridEntity {
     fn get_grid_item_data() -> GridItemData;
 }
+
+/// Resource with the entity types that can be placed on the gridmap by the map editor.
+#[derive(Resource, Default)]
+
+pub struct GridEntityTypes {
+    pub map: HashMap<String, GridItemData>,
+}
@@ -64,6 +64,21 @@ pub fn register_entity_type<T: EntityType + Clone + Default + 'static>(app: &mut
         .add_system((build_raw_entities::<T>).after(BuildingLabels::TriggerBuild));
 }
 
+use crate::entity_data::{GridEntity, GridEntityTypes};
+
+pub fn store_grid_entity_type<T: EntityType + GridEntity + 'static>(
+    mut types: ResMut<GridEntityTypes>,
+) {
+    types
+        .map
+        .insert(T::new().get_identity(), T::get_grid_item_data());
+}
+
+/// Register an entity type that can be placed on the gridmap with the map editor.
+pub fn register_grid_entity_type<T: EntityType + GridEntity + 'static>(app: &mut App) {
+    app.add_startup_system(store_grid_entity_type::<T>.in_set(EntityTypeLabel::Register));
+}
+
 use bevy::prelude::{Commands, EventReader, EventWriter};
 
 use bevy::prelude::Transform;
@@ -84,11 +99,20 @@ pub fn build_raw_entities<T: EntityType + Default + Send + Sync + 'static>(
         let mut entity_transform = Transform::from_translation(spawn_event.raw_entity.translation);
         entity_transform.rotation = spawn_event.raw_entity.rotation;
         entity_transform.scale = spawn_event.raw_entity.scale;
+        let entity;
+        match spawn_event.entity_option {
+            Some(e) => {
+                entity = e;
+            }
+            None => {
+                entity = commands.spawn(()).id();
+            }
+        }
         builder_computer.send(SpawnEntity {
             spawn_data: EntityBuildData {
                 entity_transform: entity_transform,
                 default_map_spawn: true,
-                entity: commands.spawn(()).id(),
+                entity,
                 raw_entity_option: Some(spawn_event.raw_entity.clone()),
                 ..Default::default()
             },
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
 
 /// The data for entities and gridmap cells that have health.
 #[derive(Clone, Debug)]
@@ -37,6 +38,8 @@ pub enum HitSoundSurface {
 pub enum DamageFlag {
     SoftDamage, //Ie fists.
     WeakLethalLaser,
+    /// Bullets and other solid rounds.
+    Ballistic,
     Stun(f32),
     Floor(f32),
 }
@@ -72,7 +75,7 @@ impl Default for Health {
 }
 
 /// Health for each limb of a humanoid entity.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 
 pub struct HumanoidHealth {
     pub head_brute: f32,
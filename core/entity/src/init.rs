@@ -12,6 +12,7 @@ pub struct RawEntityRon {
     pub scale: Vec3,
     pub data: String,
 }
+use resources::maps::current_map_name;
 use std::path::Path;
 
 use bevy::prelude::EventWriter;
@@ -23,7 +24,7 @@ use crate::entity_data::RawSpawnEvent;
 pub(crate) fn load_ron_entities(mut raw_spawner: EventWriter<RawSpawnEvent>) {
     let entities_ron = Path::new("data")
         .join("maps")
-        .join("bullseye")
+        .join(current_map_name())
         .join("entities.ron");
     let current_map_entities_raw_ron: String =
         fs::read_to_string(entities_ron).expect("Error reading map entities.ron file from drive.");
@@ -39,6 +40,7 @@ pub(crate) fn load_ron_entities(mut raw_spawner: EventWriter<RawSpawnEvent>) {
     for raw_entity in current_map_entities_data.iter() {
         raw_spawner.send(RawSpawnEvent {
             raw_entity: raw_entity.clone(),
+            entity_option: None,
         });
     }
     info!("Spawned {} entities.", current_map_entities_data.len());
@@ -27,6 +27,10 @@ pub fn load_entity<T: Send + Sync + 'static + Default + EntityType>(
     for message in client.iter() {
         match &message.message {
             EntityServerMessage::LoadEntity(load_entity) => {
+                // Already loaded, the server resends entities after a reconnect.
+                if map.map.contains_key(&load_entity.entity) {
+                    continue;
+                }
                 let index = types
                     .netcode_types
                     .values()
@@ -75,3 +79,27 @@ pub fn load_entity<T: Send + Sync + 'static + Default + EntityType>(
         }
     }
 }
+
+use bevy::prelude::DespawnRecursiveExt;
+
+/// Client unloads entities that left its senses or got despawned by the server.
+
+pub(crate) fn unload_entity(
+    mut client: EventReader<IncomingReliableServerMessage<EntityServerMessage>>,
+    mut commands: Commands,
+    mut map: ResMut<ClientEntityServerEntity>,
+) {
+    for message in client.iter() {
+        match &message.message {
+            EntityServerMessage::UnloadEntity(server_entity) => {
+                match map.map.remove(server_entity) {
+                    Some(client_entity) => {
+                        commands.entity(client_entity).despawn_recursive();
+                    }
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -5,8 +5,9 @@ use bevy::time::common_conditions::on_fixed_timer;
 use networking::messaging::{register_reliable_message, MessageSender};
 use resources::is_server::is_server;
 use resources::labels::{ActionsLabels, PostUpdateLabels, StartupLabels};
+use resources::load_save::load_save_name;
 
-use crate::entity_data::{world_mode_update, InterpolationSet, RawSpawnEvent};
+use crate::entity_data::{world_mode_update, GridEntityTypes, InterpolationSet, RawSpawnEvent};
 use crate::entity_types::{finalize_register_entity_types, EntityTypeLabel, EntityTypes};
 use crate::examine::{
     examine_entity, examine_entity_health, finalize_entity_examine_input, finalize_examine_entity,
@@ -14,10 +15,11 @@ use crate::examine::{
 };
 use crate::finalize_entity_updates::finalize_entity_updates;
 use crate::init::load_ron_entities;
+use crate::loading::unload_entity;
 use crate::net::{EntityClientMessage, EntityServerMessage};
 use crate::spawn::{ClientEntityServerEntity, PawnEntityId};
 use crate::spawning_events::{despawn_entity, DespawnClientEntity, SpawnClientEntity};
-use crate::visible_checker::visible_checker;
+use crate::visible_checker::{update_sensable_spatial_hash, visible_checker, SensableSpatialHash};
 
 use super::entity_data::broadcast_position_updates;
 
@@ -36,6 +38,13 @@ impl Plugin for EntityPlugin {
                     .in_set(PostUpdateLabels::EntityUpdate)
                     .in_base_set(CoreSet::PostUpdate),
             )
+            .init_resource::<SensableSpatialHash>()
+            .add_system(
+                update_sensable_spatial_hash
+                    .before(PostUpdateLabels::VisibleChecker)
+                    .after(PostUpdateLabels::SendEntityUpdates)
+                    .in_base_set(CoreSet::PostUpdate),
+            )
             .add_system(
                 visible_checker
                     .in_set(PostUpdateLabels::VisibleChecker)
@@ -68,16 +77,20 @@ impl Plugin for EntityPlugin {
             .add_event::<SpawnClientEntity>();
         } else {
             app.init_resource::<PawnEntityId>()
-                .init_resource::<ClientEntityServerEntity>();
+                .init_resource::<ClientEntityServerEntity>()
+                .add_system(unload_entity);
         }
         app.add_event::<RawSpawnEvent>()
             .init_resource::<EntityTypes>()
-            .add_startup_system(finalize_register_entity_types.after(EntityTypeLabel::Register))
-            .add_startup_system(
+            .init_resource::<GridEntityTypes>()
+            .add_startup_system(finalize_register_entity_types.after(EntityTypeLabel::Register));
+        if load_save_name().is_none() {
+            app.add_startup_system(
                 load_ron_entities
                     .after(StartupLabels::BuildGridmap)
                     .in_set(StartupLabels::InitEntities),
             );
+        }
         register_reliable_message::<EntityServerMessage>(app, MessageSender::Server);
         register_reliable_message::<EntityClientMessage>(app, MessageSender::Client);
     }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use bevy::ecs::entity::Entity;
 use bevy::prelude::Component;
 
@@ -7,6 +9,9 @@ use bevy::prelude::Component;
 pub struct Sensable {
     pub is_light: bool,
     pub is_audible: bool,
-    pub sensed_by: Vec<Entity>,
+    pub sensed_by: HashSet<Entity>,
     pub always_sensed: bool,
+    /// Entity holding this one in its inventory. Held entities are sensed together with their holder.
+    /// Inventory items keep it in sync with the inventory they are added to.
+    pub holder_option: Option<Entity>,
 }
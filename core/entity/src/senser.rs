@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use bevy::prelude::{Component, Entity};
 use doryen_fov::FovRecursiveShadowCasting;
 use resources::math::Vec2Int;
@@ -16,23 +18,42 @@ pub enum SensingAbility {
 pub struct Senser {
     pub cell_id: Vec2Int,
     pub fov: FovRecursiveShadowCasting,
-    pub sensing: Vec<Entity>,
-    pub sfx: Vec<Entity>,
+    pub sensing: HashSet<Entity>,
+    pub sfx: HashSet<Entity>,
     pub sensing_abilities: Vec<SensingAbility>,
+    /// Distance in meters within which audible entities are sensed, regardless of FOV.
+    pub hearing_radius: f32,
 }
 
 impl Default for Senser {
     fn default() -> Self {
         Self {
-            cell_id: Vec2Int { x: 0, y: 0 },
+            // Out of range so the FOV gets computed for the first cell.
+            cell_id: Vec2Int {
+                x: i16::MAX,
+                y: i16::MAX,
+            },
             fov: FovRecursiveShadowCasting::new(WORLD_WIDTH_CELLS, WORLD_WIDTH_CELLS),
-            sensing: vec![],
-            sfx: vec![],
+            sensing: HashSet::new(),
+            sfx: HashSet::new(),
             sensing_abilities: vec![],
+            hearing_radius: HEARING_RADIUS,
         }
     }
 }
 
+/// Radius of FOV calculations in cells.
+
+pub const FOV_DISTANCE: usize = 23;
+
+/// Default hearing radius of sensers in meters.
+
+pub const HEARING_RADIUS: f32 = 24.;
+
+/// Distance in meters within which lights are sensed, regardless of FOV.
+
+pub const LIGHT_SENSE_RADIUS: f32 = 48.;
+
 /// Turning up these values drastically increases fov calculation time.
 /// Dividible by 2.
 
@@ -148,6 +148,10 @@ pub fn build_base_entities<T: BaseEntityBuilder<NoData> + 'static>(
                 entity_group: base_entity_bundle.entity_group,
                 default_map_spawn: base_entity_bundle.default_map_spawn,
                 is_item_in_storage: spawn_event.spawn_data.holder_entity_option.is_some(),
+                sensable: Sensable {
+                    holder_option: spawn_event.spawn_data.holder_entity_option,
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             spawn_event.spawn_data.entity,
@@ -1,4 +1,5 @@
 use bevy::prelude::Commands;
+use bevy::prelude::DespawnRecursiveExt;
 use bevy::prelude::Entity;
 use bevy::prelude::EventReader;
 use bevy::prelude::EventWriter;
@@ -22,6 +23,7 @@ pub struct SpawnClientEntity {
     pub loader_handle: u64,
 }
 /// Executes despawn logic for Sensable components.
+/// Children such as colliders and sensors get despawned together with the entity.
 /// Shouldn't be called from the same stage visible_checker.system() runs in.
 
 pub(crate) fn despawn_entity(
@@ -46,11 +48,51 @@ pub(crate) fn despawn_entity(
                     }
                 }
 
-                sensable_component.sensed_by = vec![];
+                sensable_component.sensed_by.clear();
             }
             Err(_) => {}
         }
 
-        commands.entity(event.entity).despawn();
+        commands.entity(event.entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy::prelude::{BuildWorldChildren, Events, IntoSystem, System, World};
+
+    #[test]
+    fn test_despawn_entity_children() {
+        let mut world = World::new();
+        world.init_resource::<HandleToEntity>();
+        world.init_resource::<Events<DespawnClientEntity>>();
+        world.init_resource::<Events<OutgoingReliableServerMessage<EntityServerMessage>>>();
+
+        // Map entities carry their colliders and sensors as children.
+        let mut colliders = vec![];
+        let entity = world
+            .spawn(Sensable::default())
+            .with_children(|children| {
+                colliders.push(children.spawn_empty().id());
+                colliders.push(children.spawn_empty().id());
+            })
+            .id();
+        let other = world.spawn(Sensable::default()).id();
+
+        world
+            .resource_mut::<Events<DespawnClientEntity>>()
+            .send(DespawnClientEntity { entity });
+        let mut system = IntoSystem::into_system(despawn_entity);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        system.apply_buffers(&mut world);
+
+        assert!(world.get_entity(entity).is_none());
+        for collider in colliders {
+            assert!(world.get_entity(collider).is_none());
+        }
+        assert!(world.get_entity(other).is_some());
     }
 }
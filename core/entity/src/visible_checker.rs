@@ -1,143 +1,279 @@
-use bevy::prelude::{Entity, EventWriter, Query, Transform};
+use std::collections::{HashMap, HashSet};
 
-use crate::{sensable::Sensable, senser::Senser};
+use bevy::prelude::{
+    Added, Changed, Entity, EventWriter, Or, Query, RemovedComponents, Res, ResMut, Resource,
+    Transform, Vec3,
+};
+use resources::math::{world_to_cell_id, Vec2Int, CELL_SIZE};
+
+use crate::{
+    sensable::Sensable,
+    senser::{to_doryen_coordinates, Senser, FOV_DISTANCE, LIGHT_SENSE_RADIUS},
+};
 use networking::server::ConnectedPlayer;
 
 use crate::spawning_events::SpawnClientEntity;
 use networking::server::OutgoingReliableServerMessage;
 
 use crate::net::EntityServerMessage;
+
+/// Length of a spatial hash chunk in cells. Matches the gridmap chunk length.
+
+pub const SENSE_CHUNK_LENGTH: i16 = 32;
+
+/// Spatial hash of sensable entities by chunk on the horizontal plane.
+#[derive(Resource, Default)]
+
+pub struct SensableSpatialHash {
+    chunks: HashMap<Vec2Int, HashSet<Entity>>,
+    entity_chunks: HashMap<Entity, Vec2Int>,
+    /// Held entities by holder.
+    held: HashMap<Entity, HashSet<Entity>>,
+    entity_holders: HashMap<Entity, Entity>,
+    always_sensed: HashSet<Entity>,
+}
+
+/// Chunk of a world position.
+
+pub fn sense_chunk(position: Vec3) -> Vec2Int {
+    let cell_id = world_to_cell_id(position);
+    Vec2Int {
+        x: cell_id.x.div_euclid(SENSE_CHUNK_LENGTH),
+        y: cell_id.z.div_euclid(SENSE_CHUNK_LENGTH),
+    }
+}
+
+impl SensableSpatialHash {
+    fn remove(&mut self, entity: Entity) {
+        match self.entity_chunks.remove(&entity) {
+            Some(chunk) => match self.chunks.get_mut(&chunk) {
+                Some(entities) => {
+                    entities.remove(&entity);
+                    if entities.is_empty() {
+                        self.chunks.remove(&chunk);
+                    }
+                }
+                None => {}
+            },
+            None => {}
+        }
+        match self.entity_holders.remove(&entity) {
+            Some(holder) => match self.held.get_mut(&holder) {
+                Some(entities) => {
+                    entities.remove(&entity);
+                    if entities.is_empty() {
+                        self.held.remove(&holder);
+                    }
+                }
+                None => {}
+            },
+            None => {}
+        }
+        self.always_sensed.remove(&entity);
+    }
+
+    fn insert(&mut self, entity: Entity, sensable: &Sensable, position: Vec3) {
+        self.remove(entity);
+        if sensable.always_sensed {
+            self.always_sensed.insert(entity);
+            return;
+        }
+        match sensable.holder_option {
+            Some(holder) => {
+                self.held.entry(holder).or_default().insert(entity);
+                self.entity_holders.insert(entity, holder);
+            }
+            None => {
+                let chunk = sense_chunk(position);
+                self.chunks.entry(chunk).or_default().insert(entity);
+                self.entity_chunks.insert(entity, chunk);
+            }
+        }
+    }
+
+    /// Entities in all chunks overlapping the radius around a position.
+    pub fn entities_near(&self, position: Vec3, radius: f32) -> Vec<Entity> {
+        let center = sense_chunk(position);
+        let chunk_radius = (radius / (SENSE_CHUNK_LENGTH as f32 * CELL_SIZE)).ceil() as i16;
+        let mut entities = vec![];
+        for x in center.x - chunk_radius..center.x + chunk_radius + 1 {
+            for y in center.y - chunk_radius..center.y + chunk_radius + 1 {
+                match self.chunks.get(&Vec2Int { x, y }) {
+                    Some(chunk) => {
+                        entities.extend(chunk.iter());
+                    }
+                    None => {}
+                }
+            }
+        }
+        entities
+    }
+
+    /// Entities held by a holder.
+    pub fn held_by(&self, holder: Entity) -> impl Iterator<Item = &Entity> {
+        self.held.get(&holder).into_iter().flatten()
+    }
+}
+
+/// Keep the spatial hash in sync with moved, added and removed sensable entities.
+
+pub(crate) fn update_sensable_spatial_hash(
+    changed: Query<
+        (Entity, &Sensable, &Transform),
+        Or<(Changed<Transform>, Changed<Sensable>, Added<Sensable>)>,
+    >,
+    mut removed: RemovedComponents<Sensable>,
+    mut hash: ResMut<SensableSpatialHash>,
+) {
+    for entity in removed.iter() {
+        hash.remove(entity);
+    }
+    for (entity, sensable, transform) in changed.iter() {
+        hash.insert(entity, sensable, transform.translation);
+    }
+}
+
+/// Whether a sensable entity at a position is sensed by a senser.
+
+fn is_sensed(senser: &Senser, senser_position: Vec3, sensable: &Sensable, position: Vec3) -> bool {
+    let distance = senser_position.distance(position);
+
+    if sensable.is_audible && distance <= senser.hearing_radius {
+        return true;
+    }
+    if sensable.is_light && distance <= LIGHT_SENSE_RADIUS {
+        return true;
+    }
+    if distance > FOV_DISTANCE as f32 {
+        return false;
+    }
+    let cell_id = world_to_cell_id(position);
+    let coords = to_doryen_coordinates(cell_id.x, cell_id.z);
+    senser.fov.is_in_fov(coords.0, coords.1)
+}
+
 /// Perform FOV checks to see what is and what isn't visible.
 
 pub(crate) fn visible_checker(
-    mut query_visible_entities: Query<(Entity, &mut Sensable, &Transform)>,
+    mut query_visible_entities: Query<(&mut Sensable, &Transform)>,
     mut query_visible_checker_entities_rigid: Query<(
         Entity,
         &mut Senser,
         &Transform,
         Option<&ConnectedPlayer>,
     )>,
+    hash: Res<SensableSpatialHash>,
     mut load_entity_event: EventWriter<SpawnClientEntity>,
     mut server: EventWriter<OutgoingReliableServerMessage<EntityServerMessage>>,
 ) {
     for (
         visible_checker_entity_id,
         mut senser_component,
-        _visible_checker_rigid_body_position_component,
+        visible_checker_transform,
         visible_checker_component_option,
     ) in query_visible_checker_entities_rigid.iter_mut()
     {
-        for (visible_entity_id, mut sensable_component, _visible_transform_component) in
-            query_visible_entities.iter_mut()
-        {
-            let is_sensed = true;
-
-            let sensed_by_contains = sensable_component
-                .sensed_by
-                .contains(&visible_checker_entity_id);
-
-            if is_sensed == false {
-                if sensed_by_contains {
-                    match visible_checker_component_option {
-                        Some(visible_checker_component) => {
-                            if visible_checker_component.connected {
-                                server.send(OutgoingReliableServerMessage {
-                                    handle: visible_checker_component.handle,
-                                    message: EntityServerMessage::UnloadEntity(visible_entity_id),
-                                });
-                            }
-                        }
-                        None => {}
-                    }
+        let senser_position = visible_checker_transform.translation;
+        let radius = (FOV_DISTANCE as f32)
+            .max(senser_component.hearing_radius)
+            .max(LIGHT_SENSE_RADIUS);
 
-                    let index1 = sensable_component
-                        .sensed_by
-                        .iter()
-                        .position(|x| x == &visible_checker_entity_id)
-                        .unwrap();
-                    sensable_component.sensed_by.remove(index1);
-
-                    match senser_component
-                        .sensing
-                        .iter()
-                        .position(|x| x == &visible_checker_entity_id)
-                    {
-                        Some(index) => {
-                            senser_component.sensing.remove(index);
-                        }
-                        None => {}
-                    }
+        let mut sensed = HashSet::new();
+        sensed.extend(hash.always_sensed.iter());
+        sensed.extend(hash.held_by(visible_checker_entity_id));
+
+        for visible_entity_id in hash.entities_near(senser_position, radius) {
+            let is_sensed_entity;
+            match query_visible_entities.get(visible_entity_id) {
+                Ok((sensable_component, visible_transform_component)) => {
+                    is_sensed_entity = visible_entity_id == visible_checker_entity_id
+                        || is_sensed(
+                            &senser_component,
+                            senser_position,
+                            sensable_component,
+                            visible_transform_component.translation,
+                        );
                 }
-            } else {
-                if !senser_component.sensing.contains(&visible_entity_id) {
-                    senser_component.sensing.push(visible_entity_id);
-                    if sensable_component.is_audible {
-                        senser_component.sfx.push(visible_entity_id);
-                    }
+                Err(_) => {
+                    continue;
                 }
+            }
+            if is_sensed_entity {
+                sensed.insert(visible_entity_id);
+                sensed.extend(hash.held_by(visible_entity_id));
+            }
+        }
 
-                if !sensed_by_contains {
-                    sensable_component.sensed_by.push(visible_checker_entity_id);
+        let connected_handle_option = match visible_checker_component_option {
+            Some(visible_checker_component) => match visible_checker_component.connected {
+                true => Some(visible_checker_component.handle),
+                false => None,
+            },
+            None => None,
+        };
 
-                    match visible_checker_component_option {
-                        Some(visible_checker_component) => {
-                            if visible_checker_component.connected {
-                                load_entity_event.send(SpawnClientEntity {
-                                    entity: visible_entity_id,
-                                    loader_handle: visible_checker_component.handle,
-                                });
-                            }
-                        }
-                        None => {}
+        let gone: Vec<Entity> = senser_component
+            .sensing
+            .difference(&sensed)
+            .cloned()
+            .collect();
+
+        for entity in gone {
+            senser_component.sensing.remove(&entity);
+            let was_sfx = senser_component.sfx.remove(&entity);
+
+            match query_visible_entities.get_mut(entity) {
+                Ok((mut sensable_component, _)) => {
+                    sensable_component
+                        .sensed_by
+                        .remove(&visible_checker_entity_id);
+                }
+                Err(_) => {
+                    // Entity has despawned, sfx unload themselves on the client.
+                    if was_sfx {
+                        continue;
                     }
                 }
             }
+            match connected_handle_option {
+                Some(handle) => {
+                    server.send(OutgoingReliableServerMessage {
+                        handle,
+                        message: EntityServerMessage::UnloadEntity(entity),
+                    });
+                }
+                None => {}
+            }
         }
 
-        let mut gone_entities = vec![];
-        let mut gone_sfx_entities = vec![];
+        for visible_entity_id in sensed {
+            if senser_component.sensing.contains(&visible_entity_id) {
+                continue;
+            }
+            match query_visible_entities.get_mut(visible_entity_id) {
+                Ok((mut sensable_component, _)) => {
+                    senser_component.sensing.insert(visible_entity_id);
+                    if sensable_component.is_audible {
+                        senser_component.sfx.insert(visible_entity_id);
+                    }
 
-        let mut i = 0;
-        for entity in senser_component.sensing.iter() {
-            match query_visible_entities.get(*entity) {
-                Ok(_) => {}
-                Err(_) => {
-                    // Entity has despawned.
-                    if !senser_component.sfx.contains(entity) {
-                        match visible_checker_component_option {
-                            Some(connected_component) => {
-                                server.send(OutgoingReliableServerMessage {
-                                    handle: connected_component.handle,
-                                    message: EntityServerMessage::UnloadEntity(*entity),
+                    if sensable_component
+                        .sensed_by
+                        .insert(visible_checker_entity_id)
+                    {
+                        match connected_handle_option {
+                            Some(handle) => {
+                                load_entity_event.send(SpawnClientEntity {
+                                    entity: visible_entity_id,
+                                    loader_handle: handle,
                                 });
                             }
                             None => {}
                         }
-                    } else {
-                        let index = senser_component
-                            .sfx
-                            .iter()
-                            .position(|&r| r == *entity)
-                            .unwrap();
-                        gone_sfx_entities.push(index);
                     }
-
-                    gone_entities.push(i);
                 }
+                Err(_) => {}
             }
-
-            i += 1;
-        }
-
-        gone_entities.reverse();
-        gone_sfx_entities.reverse();
-
-        for i in gone_entities {
-            senser_component.sensing.remove(i);
-        }
-
-        for i in gone_sfx_entities {
-            senser_component.sfx.remove(i);
         }
     }
 }
@@ -0,0 +1,193 @@
+use bevy::{
+    math::Vec3,
+    prelude::{Commands, Component, Entity, EventWriter, Query, Res, ResMut, Resource, Transform},
+};
+use bevy_rapier3d::prelude::{GravityScale, RigidBody};
+use gridmap::grid::Gridmap;
+use inventory::server::inventory::{Inventory, SlotType};
+use networking::server::{
+    HandleToEntity, NetworkingChatServerMessage, OutgoingReliableServerMessage,
+};
+use pawn::pawn::Pawn;
+use physics::rigid_body::RigidBodyStatus;
+use power::power::{is_powered, PowerConsumer};
+use resources::{
+    grid::{CellFace, TargetCell},
+    math::world_to_cell_id,
+};
+use text_api::core::FURTHER_ITALIC_FONT;
+
+/// Entities that generate artificial gravity within a radius while powered.
+#[derive(Component)]
+
+pub struct GravityGenerator {
+    /// Radius of the gravity field in meters.
+    pub radius: f32,
+    pub active: bool,
+}
+
+/// Physics entities and pawns that are currently outside of gravity.
+#[derive(Component)]
+
+pub struct ZeroGravity;
+
+/// Worn items that keep their wearer walking on hull floors in zero-G.
+#[derive(Component)]
+
+pub struct MagneticBoots;
+
+/// A gravity field of an active and powered gravity generator.
+
+pub struct GravityField {
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+/// Resource with the gravity fields of the ship, rebuilt every gravity update.
+#[derive(Resource, Default)]
+
+pub struct GravityFields {
+    pub fields: Vec<GravityField>,
+}
+
+/// How many cells below a position are searched for hull floor.
+const FLOOR_SEARCH_DEPTH: i16 = 2;
+
+/// Whether there is hull floor under a position. Positions without it are in space.
+
+pub fn is_above_floor(position: Vec3, gridmap: &Gridmap) -> bool {
+    let cell_id = world_to_cell_id(position);
+    for depth in 0..FLOOR_SEARCH_DEPTH {
+        let mut id = cell_id;
+        id.y -= depth;
+        match gridmap.get_cell(TargetCell {
+            id,
+            face: CellFace::Floor,
+        }) {
+            Some(_) => {
+                return true;
+            }
+            None => {}
+        }
+    }
+    false
+}
+
+impl GravityFields {
+    /// Whether a position is inside gravity. Space outside of the hull never has gravity.
+    pub fn has_gravity(&self, position: Vec3, gridmap: &Gridmap) -> bool {
+        self.fields
+            .iter()
+            .any(|f| f.position.distance(position) <= f.radius)
+            && is_above_floor(position, gridmap)
+    }
+}
+
+/// Collect the gravity fields of active and powered gravity generators.
+
+pub(crate) fn update_gravity_fields(
+    mut fields: ResMut<GravityFields>,
+    generators: Query<(Entity, &Transform, &GravityGenerator)>,
+    consumers: Query<&PowerConsumer>,
+) {
+    fields.fields.clear();
+    for (entity, transform, generator) in generators.iter() {
+        if !generator.active || !is_powered(entity, &consumers) {
+            continue;
+        }
+        fields.fields.push(GravityField {
+            position: transform.translation,
+            radius: generator.radius,
+        });
+    }
+}
+
+/// Whether the inventory holder wears magnetic boots.
+
+pub fn wears_magnetic_boots(
+    holder: Entity,
+    inventories: &Query<&Inventory>,
+    boots: &Query<&MagneticBoots>,
+) -> bool {
+    match inventories.get(holder) {
+        Ok(inventory) => inventory.slots.values().any(|slot| {
+            slot.slot_type == SlotType::Boots
+                && slot.items.iter().any(|item| boots.get(item.entity).is_ok())
+        }),
+        Err(_) => false,
+    }
+}
+
+/// Insert and remove [ZeroGravity] on enabled dynamic rigid bodies and scale their gravity accordingly. Bodies in zero-G float.
+
+pub(crate) fn update_zero_gravity(
+    fields: Res<GravityFields>,
+    gridmap: Res<Gridmap>,
+    mut bodies: Query<(
+        Entity,
+        &Transform,
+        &RigidBody,
+        &RigidBodyStatus,
+        &mut GravityScale,
+        Option<&ZeroGravity>,
+        Option<&Pawn>,
+    )>,
+    inventories: Query<&Inventory>,
+    boots: Query<&MagneticBoots>,
+    handle_to_entity: Res<HandleToEntity>,
+    mut commands: Commands,
+    mut server: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+) {
+    for (
+        entity,
+        transform,
+        rigid_body,
+        status,
+        mut gravity_scale,
+        zero_gravity_option,
+        pawn_option,
+    ) in bodies.iter_mut()
+    {
+        if !status.enabled || !matches!(rigid_body, RigidBody::Dynamic) {
+            continue;
+        }
+        let mut gravity = fields.has_gravity(transform.translation, &gridmap);
+        if !gravity && pawn_option.is_some() {
+            gravity = wears_magnetic_boots(entity, &inventories, &boots)
+                && is_above_floor(transform.translation, &gridmap);
+        }
+
+        let scale = match gravity {
+            true => 1.,
+            false => 0.,
+        };
+        if gravity_scale.0 != scale {
+            gravity_scale.0 = scale;
+        }
+
+        let text;
+        if gravity && zero_gravity_option.is_some() {
+            commands.entity(entity).remove::<ZeroGravity>();
+            text = "You feel your weight return.";
+        } else if !gravity && zero_gravity_option.is_none() {
+            commands.entity(entity).insert(ZeroGravity);
+            text = "You feel weightless.";
+        } else {
+            continue;
+        }
+        if pawn_option.is_none() {
+            continue;
+        }
+        match handle_to_entity.inv_map.get(&entity) {
+            Some(handle) => {
+                server.send(OutgoingReliableServerMessage {
+                    handle: *handle,
+                    message: NetworkingChatServerMessage::ChatMessage(
+                        "[font=".to_string() + FURTHER_ITALIC_FONT + "]" + text + "[/font]",
+                    ),
+                });
+            }
+            None => {}
+        }
+    }
+}
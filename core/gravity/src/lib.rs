@@ -0,0 +1,9 @@
+//! Artificial gravity. Powered gravity generators create gravity within their radius, but only where there is hull floor to stand on.
+//! Pawns and physics items outside of gravity are given [gravity::ZeroGravity] and float, pawns push off walls to move. Worn magnetic boots restore walking.
+
+/// Gravity components and the gravity fields of the ship.
+pub mod gravity;
+/// Zero-G movement.
+pub mod movement;
+/// The Bevy plugin of this crate.
+pub mod plugin;
@@ -0,0 +1,89 @@
+use bevy::{
+    math::Vec3,
+    prelude::{Query, Res, Transform, With},
+};
+use bevy_rapier3d::prelude::{ExternalImpulse, Velocity};
+use controller::controller::ControllerInput;
+use gridmap::grid::Gridmap;
+use resources::{
+    grid::{CellFace, TargetCell},
+    math::world_to_cell_id,
+};
+
+use crate::gravity::ZeroGravity;
+
+/// Impulse of pushing off a surface in zero-G.
+pub const ZERO_G_PUSH_IMPULSE: f32 = 2.;
+/// Pawns stop gaining speed from pushing off surfaces above this speed.
+pub const ZERO_G_MAX_SPEED: f32 = 3.;
+
+const WALL_FACES: [CellFace; 4] = [
+    CellFace::FrontWall,
+    CellFace::RightWall,
+    CellFace::BackWall,
+    CellFace::LeftWall,
+];
+
+/// Whether a position is close enough to a wall, floor or ceiling to push off it.
+
+pub fn is_bordering_surface(position: Vec3, gridmap: &Gridmap) -> bool {
+    let cell_id = world_to_cell_id(position);
+    for face in [CellFace::Floor, CellFace::Ceiling] {
+        if gridmap.get_cell(TargetCell { id: cell_id, face }).is_some() {
+            return true;
+        }
+    }
+    for x in -1..2 {
+        for z in -1..2 {
+            let mut id = cell_id;
+            id.x += x;
+            id.z += z;
+            for face in WALL_FACES.iter() {
+                if gridmap
+                    .get_cell(TargetCell {
+                        id,
+                        face: face.clone(),
+                    })
+                    .is_some()
+                {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Pawns in zero-G can only move by pushing off nearby surfaces. Without one they keep drifting.
+
+pub(crate) fn zero_gravity_movement(
+    mut pawns: Query<
+        (
+            &ControllerInput,
+            &Transform,
+            &Velocity,
+            &mut ExternalImpulse,
+        ),
+        With<ZeroGravity>,
+    >,
+    gridmap: Res<Gridmap>,
+) {
+    for (input, transform, velocity, mut impulse) in pawns.iter_mut() {
+        let movement_vector = match input.auto_move_enabled && input.movement_vector.length() < 0.1
+        {
+            true => input.auto_move_direction,
+            false => input.movement_vector,
+        };
+        if movement_vector.length() == 0. {
+            continue;
+        }
+        let direction = Vec3::new(-movement_vector.x, 0., movement_vector.y).normalize_or_zero();
+        if velocity.linvel.dot(direction) >= ZERO_G_MAX_SPEED {
+            continue;
+        }
+        if !is_bordering_surface(transform.translation, &gridmap) {
+            continue;
+        }
+        impulse.impulse += direction * ZERO_G_PUSH_IMPULSE;
+    }
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::{App, IntoSystemConfig, Plugin},
+    time::common_conditions::on_fixed_timer,
+};
+use resources::{is_server::is_server, labels::UpdateLabels};
+
+use crate::{
+    gravity::{update_gravity_fields, update_zero_gravity, GravityFields},
+    movement::zero_gravity_movement,
+};
+
+pub struct GravityPlugin;
+
+impl Plugin for GravityPlugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.init_resource::<GravityFields>()
+                .add_system(
+                    update_gravity_fields.run_if(on_fixed_timer(Duration::from_secs_f32(0.25))),
+                )
+                .add_system(
+                    update_zero_gravity
+                        .after(update_gravity_fields)
+                        .run_if(on_fixed_timer(Duration::from_secs_f32(0.25))),
+                )
+                .add_system(zero_gravity_movement.after(UpdateLabels::ProcessMovementInput));
+        }
+    }
+}
@@ -12,7 +12,10 @@ use bevy_rapier3d::{
 use entity::health::HealthComponent;
 use pawn::pawn::REACH_DISTANCE;
 
-use crate::grid::{Cell, Gridmap};
+use crate::{
+    chunk_collider::{cell_at_hit, ChunkCollider},
+    grid::Gridmap,
+};
 
 use physics::physics::ReachResult;
 use physics::physics::{get_bit_masks, ColliderGroup};
@@ -27,8 +30,8 @@ pub fn can_reach_entity(
     target_entity: &Entity,
     reacher_entity: &Entity,
     health_entities_query: &Query<&HealthComponent>,
-    cells_query: &Query<&Cell>,
-    _world_cells: &Res<Gridmap>,
+    chunk_colliders_query: &Query<&ChunkCollider>,
+    world_cells: &Res<Gridmap>,
     no_result_is_valid: bool,
     collider_parents: &Query<&Parent, With<Collider>>,
 ) -> bool {
@@ -70,9 +73,11 @@ pub fn can_reach_entity(
 
             let hit_cell;
 
-            match cells_query.get(parent_entity) {
-                Ok(cell_id) => {
-                    hit_cell = Some(cell_id.id);
+            match chunk_colliders_query.get(parent_entity) {
+                Ok(_) => {
+                    hit_cell =
+                        cell_at_hit(world_cells, ray_intersection.point, ray_intersection.normal)
+                            .map(|cell| cell.id);
                 }
                 Err(_rr) => {
                     hit_cell = None;
@@ -124,7 +129,7 @@ pub fn can_reach_entity(
             None => {
                 match reach_result.hit_cell {
                     Some(cell_id) => {
-                        // Assume all gridmap main wall items are blockers, work with _world_cells and _gridmap_data if you want to change this.
+                        // Assume all gridmap main wall items are blockers, work with world_cells if you want to change this.
                         if cell_id.y == 0 {
                             if no_result_is_valid && collided_entities_length - 1 == this_i {
                                 in_reach = true;
@@ -0,0 +1,546 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{
+    prelude::{
+        warn, Commands, Component, DespawnRecursiveExt, Entity, EventReader, Quat, Res, ResMut,
+        Resource, Transform, Vec3,
+    },
+    transform::TransformBundle,
+};
+use bevy_rapier3d::{
+    prelude::{CoefficientCombineRule, Collider, CollisionGroups, Friction, Group, RigidBody},
+    rapier::{parry::query::intersection_test, prelude::Isometry},
+};
+use physics::physics::{get_bit_masks, ColliderGroup};
+use resources::{
+    grid::{CellFace, TargetCell},
+    math::{world_to_cell_id, Vec3Int},
+};
+
+use crate::grid::{AddTile, CellIndexes, CellItem, GridCell, Gridmap, RemoveTile, TileProperties};
+
+/// How far a hit point is moved into the hit surface to find the tile it belongs to.
+
+const HIT_POINT_DEPTH: f32 = 0.01;
+
+/// Resource with the merged collider bodies of the gridmap chunks.
+#[derive(Resource, Default)]
+
+pub struct ChunkColliders {
+    /// Collider body per chunk index and tile type.
+    pub bodies: HashMap<(usize, u16), Entity>,
+    /// Chunks whose colliders are outdated.
+    pub dirty: HashSet<usize>,
+}
+
+/// A fixed body with the merged colliders of all tiles of one type in a gridmap chunk.
+/// Use [cell_at_hit] to find the tile that got hit.
+#[derive(Component)]
+
+pub struct ChunkCollider {
+    pub chunk: usize,
+    pub tile_type: u16,
+}
+
+/// World transform of the collider of a tile.
+
+pub fn tile_collider_transform(
+    gridmap: &Gridmap,
+    cell: TargetCell,
+    orientation: u8,
+    properties: &TileProperties,
+) -> Transform {
+    gridmap
+        .get_cell_transform(cell, orientation)
+        .mul_transform(properties.collider_position)
+}
+
+/// The main tiles of a cell with the faces they are on.
+
+fn cell_items(cell: &GridCell) -> [(Option<&CellItem>, CellFace); 4] {
+    [
+        (cell.floor.as_ref(), CellFace::Floor),
+        (cell.front_wall.as_ref(), CellFace::FrontWall),
+        (cell.right_wall.as_ref(), CellFace::RightWall),
+        (cell.center.as_ref(), CellFace::Center),
+    ]
+}
+
+/// All main tiles of a chunk with their world collider transforms.
+
+fn chunk_tiles(gridmap: &Gridmap, chunk_index: usize) -> Vec<(TargetCell, &CellItem, Transform)> {
+    let mut tiles = vec![];
+
+    let chunk;
+    match gridmap.grid.get(chunk_index) {
+        Some(Some(c)) => {
+            chunk = c;
+        }
+        _ => {
+            return tiles;
+        }
+    }
+
+    for (cell_index, cell_option) in chunk.cells.iter().enumerate() {
+        let cell;
+        match cell_option {
+            Some(c) => {
+                cell = c;
+            }
+            None => {
+                continue;
+            }
+        }
+        let id;
+        match gridmap.get_id(CellIndexes {
+            chunk: chunk_index,
+            cell: cell_index,
+        }) {
+            Some(i) => {
+                id = i;
+            }
+            None => {
+                continue;
+            }
+        }
+        for (item_option, face) in cell_items(cell) {
+            match item_option {
+                Some(item) => match gridmap.main_cell_properties.get(&item.tile_type) {
+                    Some(properties) => {
+                        let target = TargetCell { id, face };
+                        let transform = tile_collider_transform(
+                            gridmap,
+                            target.clone(),
+                            item.orientation,
+                            properties,
+                        );
+                        tiles.push((target, item, transform));
+                    }
+                    None => {
+                        warn!(
+                            "Unknown cellid {}. Initialization of gridmap cell in startup gridmap systems missing.",
+                            item.tile_type
+                        );
+                    }
+                },
+                None => {}
+            }
+        }
+    }
+
+    tiles
+}
+
+/// The tiles of a chunk collider body that intersect a shape, with their world collider transforms.
+/// Physics queries report a merged body once, this splits such a hit up into the individual tiles.
+
+pub fn chunk_collider_cells_in_shape(
+    gridmap: &Gridmap,
+    chunk_collider: &ChunkCollider,
+    shape_position: Vec3,
+    shape_rotation: Quat,
+    shape: &Collider,
+) -> Vec<(TargetCell, Transform)> {
+    let mut cells = vec![];
+
+    let properties;
+    match gridmap.main_cell_properties.get(&chunk_collider.tile_type) {
+        Some(p) => {
+            properties = p;
+        }
+        None => {
+            return cells;
+        }
+    }
+
+    let shape_isometry = Isometry::new(
+        shape_position.into(),
+        shape_rotation.to_scaled_axis().into(),
+    );
+
+    for (target, item, transform) in chunk_tiles(gridmap, chunk_collider.chunk) {
+        if item.tile_type != chunk_collider.tile_type {
+            continue;
+        }
+        let tile_isometry = Isometry::new(
+            transform.translation.into(),
+            transform.rotation.to_scaled_axis().into(),
+        );
+        match intersection_test(
+            &shape_isometry,
+            &*shape.raw,
+            &tile_isometry,
+            &*properties.collider.raw,
+        ) {
+            Ok(true) => {
+                cells.push((target, transform));
+            }
+            _ => {}
+        }
+    }
+
+    cells
+}
+
+/// Find the tile that a ray or shape cast hit from the hit point and the surface normal.
+
+pub fn cell_at_hit(gridmap: &Gridmap, point: Vec3, normal: Vec3) -> Option<TargetCell> {
+    let inside = point - normal.normalize_or_zero() * HIT_POINT_DEPTH;
+    let center = world_to_cell_id(inside);
+
+    let mut closest: Option<(f32, TargetCell)> = None;
+    for x in -1..2 {
+        for y in -1..2 {
+            for z in -1..2 {
+                let id = Vec3Int {
+                    x: center.x + x,
+                    y: center.y + y,
+                    z: center.z + z,
+                };
+                let indexes = gridmap.get_indexes(id);
+                let cell;
+                match gridmap.grid.get(indexes.chunk) {
+                    Some(Some(chunk)) => match chunk.cells.get(indexes.cell) {
+                        Some(Some(c)) => {
+                            cell = c;
+                        }
+                        _ => {
+                            continue;
+                        }
+                    },
+                    _ => {
+                        continue;
+                    }
+                }
+                for (item_option, face) in cell_items(cell) {
+                    let item;
+                    match item_option {
+                        Some(i) => {
+                            item = i;
+                        }
+                        None => {
+                            continue;
+                        }
+                    }
+                    let properties;
+                    match gridmap.main_cell_properties.get(&item.tile_type) {
+                        Some(p) => {
+                            properties = p;
+                        }
+                        None => {
+                            continue;
+                        }
+                    }
+                    let target = TargetCell { id, face };
+                    let transform = tile_collider_transform(
+                        gridmap,
+                        target.clone(),
+                        item.orientation,
+                        properties,
+                    );
+                    let distance = properties.collider.distance_to_point(
+                        transform.translation,
+                        transform.rotation,
+                        inside,
+                        true,
+                    );
+                    match &closest {
+                        Some((closest_distance, _)) => {
+                            if distance >= *closest_distance {
+                                continue;
+                            }
+                        }
+                        None => {}
+                    }
+                    closest = Some((distance, target));
+                }
+            }
+        }
+    }
+
+    closest.map(|(_, target)| target)
+}
+
+/// Mark the chunks of added and removed tiles as outdated.
+
+pub(crate) fn mark_dirty_chunk_colliders(
+    mut add_events: EventReader<AddTile>,
+    mut remove_events: EventReader<RemoveTile>,
+    gridmap: Res<Gridmap>,
+    mut chunk_colliders: ResMut<ChunkColliders>,
+) {
+    for event in add_events.iter() {
+        let strict = gridmap.get_strict_cell(TargetCell {
+            id: event.id,
+            face: event.face.clone(),
+        });
+        chunk_colliders
+            .dirty
+            .insert(gridmap.get_indexes(strict.id).chunk);
+    }
+    for event in remove_events.iter() {
+        let strict = gridmap.get_strict_cell(event.cell.clone());
+        chunk_colliders
+            .dirty
+            .insert(gridmap.get_indexes(strict.id).chunk);
+    }
+}
+
+/// Rebuild the merged colliders of outdated chunks.
+
+pub(crate) fn build_chunk_colliders(
+    mut chunk_colliders: ResMut<ChunkColliders>,
+    gridmap: Res<Gridmap>,
+    mut commands: Commands,
+) {
+    if chunk_colliders.dirty.is_empty() {
+        return;
+    }
+    let dirty: Vec<usize> = chunk_colliders.dirty.drain().collect();
+
+    for chunk_index in dirty {
+        let mut shapes: HashMap<u16, Vec<(Vec3, Quat, Collider)>> = HashMap::new();
+
+        for (_, item, transform) in chunk_tiles(&gridmap, chunk_index) {
+            match gridmap.main_cell_properties.get(&item.tile_type) {
+                Some(properties) => {
+                    shapes.entry(item.tile_type).or_default().push((
+                        transform.translation,
+                        transform.rotation,
+                        properties.collider.clone(),
+                    ));
+                }
+                None => {}
+            }
+        }
+
+        let removed: Vec<(usize, u16)> = chunk_colliders
+            .bodies
+            .keys()
+            .filter(|(chunk, tile_type)| *chunk == chunk_index && !shapes.contains_key(tile_type))
+            .cloned()
+            .collect();
+        for key in removed {
+            match chunk_colliders.bodies.remove(&key) {
+                Some(entity) => {
+                    commands.entity(entity).despawn_recursive();
+                }
+                None => {}
+            }
+        }
+
+        for (tile_type, tile_shapes) in shapes {
+            let collider = Collider::compound(tile_shapes);
+
+            match chunk_colliders
+                .bodies
+                .get(&(chunk_index, tile_type))
+                .copied()
+            {
+                Some(entity) => {
+                    commands.entity(entity).insert(collider);
+                }
+                None => {
+                    let friction;
+                    let combine_rule;
+                    match gridmap.main_cell_properties.get(&tile_type) {
+                        Some(properties) => {
+                            friction = properties.friction;
+                            combine_rule = properties.combine_rule;
+                        }
+                        None => {
+                            friction = 0.;
+                            combine_rule = CoefficientCombineRule::Min;
+                        }
+                    }
+                    let mut friction_component = Friction::coefficient(friction);
+                    friction_component.combine_rule = combine_rule;
+
+                    let masks = get_bit_masks(ColliderGroup::Standard);
+
+                    let entity = commands
+                        .spawn(RigidBody::Fixed)
+                        .insert(TransformBundle::default())
+                        .insert(collider)
+                        .insert(friction_component)
+                        .insert(CollisionGroups::new(
+                            Group::from_bits(masks.0).unwrap(),
+                            Group::from_bits(masks.1).unwrap(),
+                        ))
+                        .insert(ChunkCollider {
+                            chunk: chunk_index,
+                            tile_type,
+                        })
+                        .id();
+                    chunk_colliders
+                        .bodies
+                        .insert((chunk_index, tile_type), entity);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::set_tile;
+
+    const FLOOR: u16 = 1;
+    const WALL: u16 = 2;
+    const CENTER: u16 = 3;
+
+    fn test_gridmap() -> Gridmap {
+        let mut gridmap = Gridmap::default();
+        gridmap.main_cell_properties.insert(
+            FLOOR,
+            TileProperties {
+                id: FLOOR,
+                collider: Collider::cuboid(0.5, 0.05, 0.5),
+                ..Default::default()
+            },
+        );
+        gridmap.main_cell_properties.insert(
+            WALL,
+            TileProperties {
+                id: WALL,
+                collider: Collider::cuboid(0.5, 0.5, 0.05),
+                ..Default::default()
+            },
+        );
+        // Fills the cell from the top of the floor to the ceiling.
+        gridmap.main_cell_properties.insert(
+            CENTER,
+            TileProperties {
+                id: CENTER,
+                collider: Collider::cuboid(0.5, 0.45, 0.5),
+                ..Default::default()
+            },
+        );
+        gridmap
+    }
+
+    fn target(x: i16, y: i16, z: i16, face: CellFace) -> Option<TargetCell> {
+        Some(TargetCell {
+            id: Vec3Int { x, y, z },
+            face,
+        })
+    }
+
+    #[test]
+    fn test_cell_at_hit_empty() {
+        let gridmap = test_gridmap();
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(0.2, 0.05, 0.2), Vec3::Y),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cell_at_hit_floor() {
+        let mut gridmap = test_gridmap();
+        set_tile(
+            &mut gridmap,
+            Vec3Int { x: 2, y: 0, z: 3 },
+            CellFace::Floor,
+            FLOOR,
+        );
+
+        // From above.
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(2.3, 0.05, 3.2), Vec3::Y),
+            target(2, 0, 3, CellFace::Floor)
+        );
+        // From below, the hit point lies in the cell underneath the floor.
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(2.3, -0.05, 3.2), Vec3::NEG_Y),
+            target(2, 0, 3, CellFace::Floor)
+        );
+        // The floor reaches half a cell into the neighbouring cell ids.
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(1.7, 0.05, 2.6), Vec3::Y),
+            target(2, 0, 3, CellFace::Floor)
+        );
+    }
+
+    #[test]
+    fn test_cell_at_hit_walls() {
+        let mut gridmap = test_gridmap();
+        let id = Vec3Int { x: 0, y: 0, z: 0 };
+        set_tile(&mut gridmap, id, CellFace::Floor, FLOOR, 0);
+        set_tile(&mut gridmap, id, CellFace::FrontWall, WALL, 0);
+        set_tile(&mut gridmap, id, CellFace::RightWall, WALL, 0);
+
+        // Both sides of the front wall.
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(0.1, 0.6, 0.45), Vec3::NEG_Z),
+            target(0, 0, 0, CellFace::FrontWall)
+        );
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(0.1, 0.6, 0.55), Vec3::Z),
+            target(0, 0, 0, CellFace::FrontWall)
+        );
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(0.45, 0.6, -0.2), Vec3::NEG_X),
+            target(0, 0, 0, CellFace::RightWall)
+        );
+        // The floor below the walls.
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(0.1, 0.05, 0.1), Vec3::Y),
+            target(0, 0, 0, CellFace::Floor)
+        );
+    }
+
+    #[test]
+    fn test_cell_at_hit_chunk_border() {
+        let mut gridmap = test_gridmap();
+        let inside = Vec3Int { x: 0, y: 0, z: 0 };
+        let outside = Vec3Int { x: -1, y: 0, z: 0 };
+        assert_ne!(
+            gridmap.get_indexes(inside).chunk,
+            gridmap.get_indexes(outside).chunk
+        );
+        set_tile(&mut gridmap, inside, CellFace::Floor, FLOOR, 0);
+        set_tile(&mut gridmap, outside, CellFace::Floor, FLOOR, 0);
+
+        // The hit point lies in the cell id of the other chunk.
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(-0.3, 0.05, 0.2), Vec3::Y),
+            target(0, 0, 0, CellFace::Floor)
+        );
+        assert_eq!(
+            cell_at_hit(&gridmap, Vec3::new(-0.7, 0.05, 0.2), Vec3::Y),
+            target(-1, 0, 0, CellFace::Floor)
+        );
+    }
+
+    #[test]
+    fn test_cell_at_hit_depth() {
+        let mut gridmap = test_gridmap();
+        let id = Vec3Int { x: 0, y: 0, z: 0 };
+        set_tile(&mut gridmap, id, CellFace::Floor, FLOOR, 0);
+        set_tile(&mut gridmap, id, CellFace::Center, CENTER, 0);
+
+        // The floor and center tile touch, the hit point is moved behind the hit surface.
+        let point = Vec3::new(0.2, 0.05, 0.1);
+        assert_eq!(
+            cell_at_hit(&gridmap, point, Vec3::Y),
+            target(0, 0, 0, CellFace::Floor)
+        );
+        assert_eq!(
+            cell_at_hit(&gridmap, point, Vec3::NEG_Y),
+            target(0, 0, 0, CellFace::Center)
+        );
+        // Normals are normalized before the offset is applied.
+        assert_eq!(
+            cell_at_hit(&gridmap, point, Vec3::Y * 5.),
+            target(0, 0, 0, CellFace::Floor)
+        );
+        // A hit point slightly above the floor within the depth still belongs to it.
+        assert_eq!(
+            cell_at_hit(&gridmap, point + Vec3::Y * HIT_POINT_DEPTH * 0.5, Vec3::Y),
+            target(0, 0, 0, CellFace::Floor)
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{Mesh, Transform, Vec3},
+    render::{
+        mesh::{Indices, VertexAttributeValues},
+        render_resource::PrimitiveTopology,
+    },
+};
+use resources::grid::{CellFace, TargetCell};
+
+use crate::grid::{CellIndexes, Gridmap};
+
+/// Transforms of the tiles of a gridmap chunk grouped by tile type.
+
+pub fn chunk_tile_transforms(
+    gridmap: &Gridmap,
+    chunk_index: usize,
+) -> HashMap<u16, Vec<Transform>> {
+    let mut batches: HashMap<u16, Vec<Transform>> = HashMap::new();
+
+    let chunk;
+    match gridmap.grid.get(chunk_index) {
+        Some(Some(c)) => {
+            chunk = c;
+        }
+        _ => {
+            return batches;
+        }
+    }
+
+    for (cell_index, cell_option) in chunk.cells.iter().enumerate() {
+        let cell;
+        match cell_option {
+            Some(c) => {
+                cell = c;
+            }
+            None => {
+                continue;
+            }
+        }
+        let id;
+        match gridmap.get_id(CellIndexes {
+            chunk: chunk_index,
+            cell: cell_index,
+        }) {
+            Some(i) => {
+                id = i;
+            }
+            None => {
+                continue;
+            }
+        }
+
+        for (item_option, face) in [
+            (&cell.floor, CellFace::Floor),
+            (&cell.front_wall, CellFace::FrontWall),
+            (&cell.right_wall, CellFace::RightWall),
+            (&cell.center, CellFace::Center),
+        ] {
+            match item_option {
+                Some(item) => {
+                    batches.entry(item.tile_type).or_default().push(
+                        gridmap.get_cell_transform(TargetCell { id, face }, item.orientation),
+                    );
+                }
+                None => {}
+            }
+        }
+    }
+
+    batches
+}
+
+/// Merge copies of a tile mesh placed at the given transforms into a single triangle list mesh.
+/// Positions, normals, uvs and tangents are carried over. Returns none for meshes that cannot be batched.
+
+pub fn merge_tile_meshes(mesh: &Mesh, transforms: &[Transform]) -> Option<Mesh> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList || transforms.is_empty() {
+        return None;
+    }
+
+    let positions;
+    match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(p)) => {
+            positions = p;
+        }
+        _ => {
+            return None;
+        }
+    }
+    let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float32x3(n)) => Some(n),
+        _ => None,
+    };
+    let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+        Some(VertexAttributeValues::Float32x2(u)) => Some(u),
+        _ => None,
+    };
+    let tangents = match mesh.attribute(Mesh::ATTRIBUTE_TANGENT) {
+        Some(VertexAttributeValues::Float32x4(t)) => Some(t),
+        _ => None,
+    };
+    let indices: Vec<u32> = match mesh.indices() {
+        Some(Indices::U16(i)) => i.iter().map(|index| *index as u32).collect(),
+        Some(Indices::U32(i)) => i.clone(),
+        None => (0..positions.len() as u32).collect(),
+    };
+
+    let vertex_count = positions.len();
+    let total_vertices = vertex_count * transforms.len();
+
+    let mut merged_positions: Vec<[f32; 3]> = Vec::with_capacity(total_vertices);
+    let mut merged_normals: Vec<[f32; 3]> = Vec::with_capacity(total_vertices);
+    let mut merged_uvs: Vec<[f32; 2]> = Vec::with_capacity(total_vertices);
+    let mut merged_tangents: Vec<[f32; 4]> = Vec::with_capacity(total_vertices);
+    let mut merged_indices: Vec<u32> = Vec::with_capacity(indices.len() * transforms.len());
+
+    for (copy, transform) in transforms.iter().enumerate() {
+        let offset = (copy * vertex_count) as u32;
+
+        for position in positions.iter() {
+            merged_positions.push(transform.transform_point(Vec3::from(*position)).into());
+        }
+        match normals {
+            Some(normals) => {
+                for normal in normals.iter() {
+                    merged_normals.push((transform.rotation * Vec3::from(*normal)).into());
+                }
+            }
+            None => {}
+        }
+        match uvs {
+            Some(uvs) => {
+                merged_uvs.extend(uvs.iter());
+            }
+            None => {}
+        }
+        match tangents {
+            Some(tangents) => {
+                for tangent in tangents.iter() {
+                    let rotated =
+                        transform.rotation * Vec3::new(tangent[0], tangent[1], tangent[2]);
+                    merged_tangents.push([rotated.x, rotated.y, rotated.z, tangent[3]]);
+                }
+            }
+            None => {}
+        }
+        merged_indices.extend(indices.iter().map(|index| index + offset));
+    }
+
+    let mut merged = Mesh::new(PrimitiveTopology::TriangleList);
+    merged.insert_attribute(Mesh::ATTRIBUTE_POSITION, merged_positions);
+    if normals.is_some() {
+        merged.insert_attribute(Mesh::ATTRIBUTE_NORMAL, merged_normals);
+    }
+    if uvs.is_some() {
+        merged.insert_attribute(Mesh::ATTRIBUTE_UV_0, merged_uvs);
+    }
+    if tangents.is_some() {
+        merged.insert_attribute(Mesh::ATTRIBUTE_TANGENT, merged_tangents);
+    }
+    merged.set_indices(Some(Indices::U32(merged_indices)));
+    Some(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+    use bevy::prelude::Quat;
+    use resources::math::Vec3Int;
+
+    use crate::test_utils::set_tile;
+
+    const PI: f32 = std::f32::consts::PI;
+
+    fn quad_mesh() -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0., 0., 0.], [1., 0., 0.], [1., 0., 1.], [0., 0., 1.]],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[1., 0., 0.]; 4]);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, vec![[1., 0., 0., -1.]; 4]);
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2, 0, 2, 3])));
+        mesh
+    }
+
+    fn positions(mesh: &Mesh) -> Vec<[f32; 3]> {
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(p)) => p.clone(),
+            _ => panic!("Merged mesh has no positions."),
+        }
+    }
+
+    fn indices(mesh: &Mesh) -> Vec<u32> {
+        match mesh.indices() {
+            Some(Indices::U32(i)) => i.clone(),
+            _ => panic!("Merged mesh has no u32 indices."),
+        }
+    }
+
+    #[test]
+    fn test_merge_counts_and_offsets() {
+        let transforms = vec![
+            Transform::from_xyz(0., 0., 0.),
+            Transform::from_xyz(2., 0., 0.),
+            Transform::from_xyz(0., 2., 4.),
+        ];
+        let merged = merge_tile_meshes(&quad_mesh(), &transforms).unwrap();
+
+        let positions = positions(&merged);
+        let indices = indices(&merged);
+        assert_eq!(positions.len(), 12);
+        assert_eq!(indices.len(), 18);
+        assert_eq!(&indices[0..6], &[0, 1, 2, 0, 2, 3]);
+        assert_eq!(&indices[6..12], &[4, 5, 6, 4, 6, 7]);
+        assert_eq!(&indices[12..18], &[8, 9, 10, 8, 10, 11]);
+
+        assert_eq!(positions[5], [3., 0., 0.]);
+        assert_eq!(positions[10], [1., 2., 5.]);
+
+        match merged.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => {
+                assert_eq!(uvs.len(), 12);
+                assert_eq!(uvs[6], [1., 1.]);
+            }
+            _ => panic!("Merged mesh has no uvs."),
+        }
+    }
+
+    #[test]
+    fn test_merge_unindexed() {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0., 0., 0.], [1., 0., 0.], [0., 0., 1.]],
+        );
+        let merged = merge_tile_meshes(&mesh, &[Transform::IDENTITY, Transform::IDENTITY]).unwrap();
+        assert_eq!(indices(&merged), vec![0, 1, 2, 3, 4, 5]);
+        assert!(merged.attribute(Mesh::ATTRIBUTE_NORMAL).is_none());
+    }
+
+    #[test]
+    fn test_merge_rotates_normals_and_tangents() {
+        let transform = Transform::from_xyz(5., 0., 0.)
+            .with_rotation(Quat::from_rotation_y(0.5 * PI))
+            .with_scale(Vec3::splat(2.));
+        let merged = merge_tile_meshes(&quad_mesh(), &[transform]).unwrap();
+
+        match merged.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(normals)) => {
+                // Normals are only rotated, never translated or scaled.
+                assert_relative_eq!(normals[0][0], 0., epsilon = 1e-6);
+                assert_relative_eq!(normals[0][1], 0., epsilon = 1e-6);
+                assert_relative_eq!(normals[0][2], -1., epsilon = 1e-6);
+            }
+            _ => panic!("Merged mesh has no normals."),
+        }
+        match merged.attribute(Mesh::ATTRIBUTE_TANGENT) {
+            Some(VertexAttributeValues::Float32x4(tangents)) => {
+                assert_relative_eq!(tangents[0][2], -1., epsilon = 1e-6);
+                assert_eq!(tangents[0][3], -1.);
+            }
+            _ => panic!("Merged mesh has no tangents."),
+        }
+
+        let positions = positions(&merged);
+        assert_relative_eq!(positions[1][0], 5., epsilon = 1e-6);
+        assert_relative_eq!(positions[1][2], -2., epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_merge_cannot_batch() {
+        assert!(merge_tile_meshes(&quad_mesh(), &[]).is_none());
+
+        let mut lines = Mesh::new(PrimitiveTopology::LineList);
+        lines.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0., 0., 0.], [1., 0., 0.]]);
+        assert!(merge_tile_meshes(&lines, &[Transform::IDENTITY]).is_none());
+
+        let no_positions = Mesh::new(PrimitiveTopology::TriangleList);
+        assert!(merge_tile_meshes(&no_positions, &[Transform::IDENTITY]).is_none());
+    }
+
+    #[test]
+    fn test_chunk_tile_transforms() {
+        let mut gridmap = Gridmap::default();
+        let first = Vec3Int { x: 0, y: 0, z: 0 };
+        let second = Vec3Int { x: 1, y: 0, z: 3 };
+        let other_type = Vec3Int { x: 2, y: 0, z: 0 };
+        // Across the chunk border on the x axis.
+        let other_chunk = Vec3Int { x: 40, y: 0, z: 0 };
+        set_tile(&mut gridmap, first, CellFace::Floor, 1, 0);
+        set_tile(&mut gridmap, second, CellFace::Floor, 1, 16);
+        set_tile(&mut gridmap, other_type, CellFace::Floor, 2, 0);
+        set_tile(&mut gridmap, other_chunk, CellFace::Floor, 1, 0);
+
+        let chunk_index = gridmap.get_indexes(first).chunk;
+        assert_ne!(chunk_index, gridmap.get_indexes(other_chunk).chunk);
+
+        let batches = chunk_tile_transforms(&gridmap, chunk_index);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches.get(&1).unwrap().len(), 2);
+        assert_eq!(batches.get(&2).unwrap().len(), 1);
+
+        let expected = gridmap.get_cell_transform(
+            TargetCell {
+                id: second,
+                face: CellFace::Floor,
+            },
+            16,
+        );
+        let found = batches
+            .get(&1)
+            .unwrap()
+            .iter()
+            .any(|transform| *transform == expected);
+        assert!(found);
+
+        let other = chunk_tile_transforms(&gridmap, gridmap.get_indexes(other_chunk).chunk);
+        assert_eq!(other.get(&1).unwrap().len(), 1);
+
+        let empty_chunk = gridmap.get_indexes(Vec3Int { x: 0, y: 64, z: 0 }).chunk;
+        assert!(chunk_tile_transforms(&gridmap, empty_chunk).is_empty());
+    }
+}
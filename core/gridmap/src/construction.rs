@@ -13,10 +13,16 @@ use bevy_rapier3d::prelude::{
     Collider, CollisionGroups, Group, QueryFilter, RapierContext, RigidBody,
 };
 use cameras::{controllers::fps::ActiveCamera, LookTransform};
+use entity::{
+    base_mesh::entity_asset_path,
+    entity_data::{GridEntityTypes, GridItemData},
+    entity_types::EntityTypes,
+};
 use networking::client::{IncomingReliableServerMessage, OutgoingReliableClientMessage};
 use physics::physics::{get_bit_masks, ColliderGroup};
 use resources::{
     binds::{KeyBind, KeyBinds},
+    content::ContentAssets,
     grid::{CellFace, TargetCell},
     hud::HudState,
 };
@@ -26,8 +32,11 @@ use resources::{
 };
 
 use crate::{
-    grid::{Gridmap, Orthogonal, OrthogonalBases},
-    net::{ConstructCell, DeconstructCell, GridmapClientMessage, GridmapServerMessage},
+    editor::{nearest_orientation, EditorTool, MapEditorState},
+    grid::{Gridmap, GridmapLayer, Orthogonal, OrthogonalBases},
+    net::{
+        ConstructCell, DeconstructCell, GridmapClientMessage, GridmapServerMessage, PlaceEntity,
+    },
 };
 
 #[derive(Component)]
@@ -74,6 +83,7 @@ pub fn create_select_cell_cam_state(mut commands: Commands, asset_server: Res<As
         is_constructing: false,
         y_plane_position: Vec2Int { x: 0, y: 0 },
         ghost_tile: None,
+        ghost_entity_type: None,
         ghost_entity,
         ghost_rotation: 0,
         ghost_face: CellFace::default(),
@@ -88,14 +98,27 @@ pub struct GridmapConstructionState {
     pub is_constructing: bool,
     pub y_plane_position: Vec2Int,
     pub ghost_tile: Option<GhostTile>,
+    /// Entity type that is being placed instead of a tile.
+    pub ghost_entity_type: Option<String>,
     pub ghost_entity: Entity,
     pub ghost_rotation: u8,
     pub ghost_face: CellFace,
 }
 
+/// Transform of a grid entity placed on a cell with an orientation.
+
+pub fn grid_entity_transform(data: &GridItemData, cell: Vec3Int, orientation: u8) -> Transform {
+    data.placed_transform(
+        cell_id_to_world(cell),
+        OrthogonalBases::default().bases[orientation as usize],
+    )
+}
+
 pub struct GhostTile {
     /// Id of tile type.
     pub tile_type: u16,
+    /// Layer the tile type belongs to.
+    pub layer: GridmapLayer,
 }
 
 pub struct ShowYLevelPlane {
@@ -280,6 +303,7 @@ pub(crate) fn input_ghost_rotation(
     mut ghost_query: Query<&mut Transform, With<GhostTileComponent>>,
     mut events: EventReader<ConstructionCellSelectionChanged>,
     binds: Res<KeyBinds>,
+    grid_entity_types: Res<GridEntityTypes>,
 ) {
     if !state.is_constructing {
         return;
@@ -307,6 +331,38 @@ pub(crate) fn input_ghost_rotation(
         return;
     }
 
+    match state.ghost_entity_type.clone() {
+        Some(entity_type) => {
+            let mut rotation = OrthogonalBases::default().bases[state.ghost_rotation as usize];
+            if keys.just_pressed(binds.bind(ROTATE_CONSTRUCTION_LEFT_BIND)) {
+                rotation = Quat::from_axis_angle(Vec3::Y, PI / 2.) * rotation;
+            } else if keys.just_pressed(binds.bind(ROTATE_CONSTRUCTION_RIGHT_BIND)) {
+                rotation = Quat::from_axis_angle(Vec3::Y, -PI / 2.) * rotation;
+            }
+            state.ghost_rotation = nearest_orientation(rotation);
+
+            match state.selected {
+                Some(selected_id) => match grid_entity_types.map.get(&entity_type) {
+                    Some(data) => match ghost_query.get_mut(state.ghost_entity) {
+                        Ok(mut ghost_transform) => {
+                            *ghost_transform =
+                                grid_entity_transform(data, selected_id, state.ghost_rotation);
+                        }
+                        Err(_) => {
+                            warn!("Couldnt find ghost transform.");
+                        }
+                    },
+                    None => {
+                        warn!("Couldnt find grid entity type {}.", entity_type);
+                    }
+                },
+                None => {}
+            }
+            return;
+        }
+        None => {}
+    }
+
     match &state.ghost_tile {
         Some(ghost_tile) => match ghost_query.get_mut(state.ghost_entity) {
             Ok(mut ghost_transform) => match state.selected {
@@ -546,6 +602,10 @@ pub(crate) fn update_ghost_cell(
     gridmap: Res<Gridmap>,
     mut events: EventReader<ConstructionCellSelectionChanged>,
     mut ghost_tile: Query<(&mut Transform, &mut Handle<Scene>), With<GhostTileComponent>>,
+    asset_server: Res<AssetServer>,
+    content: Res<ContentAssets>,
+    entity_types: Res<EntityTypes>,
+    grid_entity_types: Res<GridEntityTypes>,
 ) {
     if !select_state.is_constructing {
         return;
@@ -555,40 +615,69 @@ pub(crate) fn update_ghost_cell(
         match select_state.selected {
             Some(selected_id) => match ghost_tile.get_mut(select_state.ghost_entity) {
                 Ok((mut transform, mut scene)) => match &select_state.ghost_tile {
-                    Some(ghost) => match gridmap.main_cell_properties.get(&ghost.tile_type) {
-                        Some(properties) => {
-                            *scene = properties.mesh_option.clone().unwrap();
-                            let face;
-                            if event.changed_tile_type {
-                                select_state.ghost_rotation = 0;
-                                match properties.cell_type {
-                                    crate::grid::CellType::Wall => {
-                                        face = CellFace::FrontWall;
-                                    }
-                                    crate::grid::CellType::Floor => {
-                                        face = CellFace::Floor;
-                                    }
-                                    crate::grid::CellType::Center => {
-                                        face = CellFace::Center;
+                    Some(ghost) => {
+                        match gridmap.get_cell_properties(ghost.layer, ghost.tile_type) {
+                            Some(properties) => {
+                                *scene = properties.mesh_option.clone().unwrap();
+                                let face;
+                                if event.changed_tile_type {
+                                    select_state.ghost_rotation = 0;
+                                    match properties.cell_type {
+                                        crate::grid::CellType::Wall => {
+                                            face = CellFace::FrontWall;
+                                        }
+                                        crate::grid::CellType::Floor => {
+                                            face = CellFace::Floor;
+                                        }
+                                        crate::grid::CellType::Center => {
+                                            face = CellFace::Center;
+                                        }
                                     }
+                                    select_state.ghost_face = face.clone();
+                                } else {
+                                    face = select_state.ghost_face.clone();
                                 }
-                                select_state.ghost_face = face.clone();
-                            } else {
-                                face = select_state.ghost_face.clone();
+                                *transform = gridmap.get_cell_transform(
+                                    TargetCell {
+                                        id: selected_id,
+                                        face: face,
+                                    },
+                                    select_state.ghost_rotation,
+                                );
+                            }
+                            None => {
+                                warn!("Coudlnt find tile.");
                             }
-                            *transform = gridmap.get_cell_transform(
-                                TargetCell {
-                                    id: selected_id,
-                                    face: face,
-                                },
-                                select_state.ghost_rotation,
-                            );
-                        }
-                        None => {
-                            warn!("Coudlnt find tile.");
                         }
+                    }
+                    None => match &select_state.ghost_entity_type {
+                        Some(entity_type) => match (
+                            entity_types.types.get(entity_type),
+                            grid_entity_types.map.get(entity_type),
+                        ) {
+                            (Some(boxed_type), Some(data)) => {
+                                if event.changed_tile_type {
+                                    *scene = asset_server.load(
+                                        entity_asset_path(
+                                            &content,
+                                            boxed_type.as_ref(),
+                                            "client_asset.glb",
+                                        ) + "#Scene0",
+                                    );
+                                    select_state.ghost_rotation = 0;
+                                }
+                                *transform = grid_entity_transform(
+                                    data,
+                                    selected_id,
+                                    select_state.ghost_rotation,
+                                );
+                            }
+                            _ => {
+                                warn!("Couldnt find grid entity type {}.", entity_type);
+                            }
+                        },
+                        None => {}
                     },
-                    None => {}
                 },
                 Err(_) => {
                     warn!("Couldnt query ghost tile.");
@@ -609,7 +698,26 @@ pub(crate) fn change_ghost_tile_request(
             GridmapServerMessage::GhostCellType(type_id) => {
                 select_state.ghost_tile = Some(GhostTile {
                     tile_type: *type_id,
+                    layer: GridmapLayer::Main,
+                });
+                select_state.ghost_entity_type = None;
+                events.send(ConstructionCellSelectionChanged {
+                    changed_tile_type: true,
+                });
+            }
+            GridmapServerMessage::GhostDetails1CellType(type_id) => {
+                select_state.ghost_tile = Some(GhostTile {
+                    tile_type: *type_id,
+                    layer: GridmapLayer::Details1,
+                });
+                select_state.ghost_entity_type = None;
+                events.send(ConstructionCellSelectionChanged {
+                    changed_tile_type: true,
                 });
+            }
+            GridmapServerMessage::GhostEntityType(entity_type) => {
+                select_state.ghost_tile = None;
+                select_state.ghost_entity_type = Some(entity_type.clone());
                 events.send(ConstructionCellSelectionChanged {
                     changed_tile_type: true,
                 });
@@ -624,8 +732,35 @@ pub(crate) fn client_mouse_click_input(
     state: Res<GridmapConstructionState>,
     mut net: EventWriter<OutgoingReliableClientMessage<GridmapClientMessage>>,
     hud_state: Res<HudState>,
+    editor: Res<MapEditorState>,
 ) {
-    if !state.is_constructing || hud_state.expanded {
+    if !state.is_constructing || hud_state.expanded || editor.tool != EditorTool::Single {
+        return;
+    }
+
+    if state.ghost_entity_type.is_some() {
+        let cell_id;
+        match state.selected {
+            Some(c) => {
+                cell_id = c;
+            }
+            None => {
+                return;
+            }
+        }
+        if buttons.just_pressed(MouseButton::Left) {
+            net.send(OutgoingReliableClientMessage {
+                message: GridmapClientMessage::PlaceEntity(PlaceEntity {
+                    cell: cell_id,
+                    orientation: state.ghost_rotation,
+                }),
+            });
+        }
+        if buttons.just_pressed(MouseButton::Right) {
+            net.send(OutgoingReliableClientMessage {
+                message: GridmapClientMessage::RemoveEntity(cell_id),
+            });
+        }
         return;
     }
 
@@ -0,0 +1,188 @@
+use bevy::prelude::{info, warn, Commands, EventReader, EventWriter, Res, ResMut, Resource, Vec3};
+use entity::health::HealthContainer;
+use resources::grid::{CellFace, TargetCell};
+
+use crate::grid::{AddTile, Gridmap, RemoveTile};
+
+/// Event to damage a gridmap tile.
+
+pub struct DamageTile {
+    pub cell: TargetCell,
+    pub brute: f32,
+    pub burn: f32,
+    pub toxin: f32,
+}
+
+/// Event of a gridmap tile that broke from damage.
+
+pub struct TileBroken {
+    pub cell: TargetCell,
+    /// Tile type of the tile before it broke.
+    pub tile_type: u16,
+    /// World position of the broken tile.
+    pub position: Vec3,
+}
+
+/// Event of a floor that broke away and exposes the ship to space.
+
+pub struct HullBreach {
+    pub cell: TargetCell,
+}
+
+/// Event of a hull breach that got sealed by a newly constructed floor.
+
+pub struct HullBreachSealed {
+    pub cell: TargetCell,
+}
+
+/// Resource with the cells that are currently breached.
+#[derive(Resource, Default)]
+
+pub struct HullBreaches {
+    pub cells: Vec<TargetCell>,
+}
+
+/// Apply damage to gridmap tiles. Tiles that exceed their damage threshold are replaced with their broken tile or get removed.
+
+pub(crate) fn damage_tiles(
+    mut events: EventReader<DamageTile>,
+    mut gridmap: ResMut<Gridmap>,
+    mut remove_tile: EventWriter<RemoveTile>,
+    mut add_tile: EventWriter<AddTile>,
+    mut tile_broken: EventWriter<TileBroken>,
+    mut hull_breach: EventWriter<HullBreach>,
+    mut breaches: ResMut<HullBreaches>,
+    mut commands: Commands,
+) {
+    let mut broken = vec![];
+    for event in events.iter() {
+        let tile_type;
+        let orientation;
+        let damage;
+
+        match gridmap.get_cell_mut(event.cell.clone()) {
+            Some(cell_item) => {
+                match &mut cell_item.health.health_container {
+                    HealthContainer::Structure(structure) => {
+                        structure.brute += event.brute;
+                        structure.burn += event.burn;
+                        structure.toxin += event.toxin;
+                        damage = structure.brute + structure.burn;
+                    }
+                    _ => {
+                        warn!("Gridmap tile without structure health.");
+                        continue;
+                    }
+                }
+                tile_type = cell_item.tile_type;
+                orientation = cell_item.orientation;
+            }
+            None => {
+                continue;
+            }
+        }
+
+        let properties;
+        match gridmap.main_cell_properties.get(&tile_type) {
+            Some(p) => {
+                properties = p.clone();
+            }
+            None => {
+                warn!("Couldnt find properties of damaged tile {}.", tile_type);
+                continue;
+            }
+        }
+
+        match properties.damage_threshold {
+            Some(threshold) => {
+                if damage < threshold {
+                    continue;
+                }
+            }
+            None => {
+                continue;
+            }
+        }
+
+        // The tile stays in the resource until the removal is processed.
+        if broken.contains(&event.cell) {
+            continue;
+        }
+        broken.push(event.cell.clone());
+
+        remove_tile.send(RemoveTile {
+            cell: event.cell.clone(),
+        });
+
+        match properties.broken_tile_option {
+            Some(broken_tile) => {
+                add_tile.send(AddTile {
+                    id: event.cell.id,
+                    tile_type: broken_tile,
+                    orientation,
+                    face: event.cell.face.clone(),
+                    group_instance_id_option: None,
+                    entity: commands.spawn(()).id(),
+                    default_map_spawn: false,
+                });
+            }
+            None => {
+                if properties.floor_cell {
+                    match event.cell.face {
+                        CellFace::Floor | CellFace::Ceiling => {
+                            info!("Hull breach at {:?}.", event.cell.id);
+                            breaches.cells.push(event.cell.clone());
+                            hull_breach.send(HullBreach {
+                                cell: event.cell.clone(),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        tile_broken.send(TileBroken {
+            cell: event.cell.clone(),
+            tile_type,
+            position: gridmap
+                .get_cell_transform(event.cell.clone(), orientation)
+                .translation,
+        });
+    }
+}
+
+/// Breaches are sealed when a floor is constructed over them.
+
+pub(crate) fn seal_hull_breaches(
+    mut events: EventReader<AddTile>,
+    gridmap: Res<Gridmap>,
+    mut breaches: ResMut<HullBreaches>,
+    mut sealed_events: EventWriter<HullBreachSealed>,
+) {
+    for event in events.iter() {
+        match gridmap.main_cell_properties.get(&event.tile_type) {
+            Some(properties) => {
+                if !properties.floor_cell {
+                    continue;
+                }
+            }
+            None => {
+                continue;
+            }
+        }
+        let sealed = gridmap.get_strict_cell(TargetCell {
+            id: event.id,
+            face: event.face.clone(),
+        });
+        breaches.cells.retain(|cell| {
+            let strict = gridmap.get_strict_cell(cell.clone());
+            if strict.id == sealed.id && strict.face == sealed.face {
+                sealed_events.send(HullBreachSealed { cell: cell.clone() });
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
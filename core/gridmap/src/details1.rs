@@ -0,0 +1,237 @@
+use bevy::{
+    prelude::{
+        warn, Commands, DespawnRecursiveExt, Entity, EventReader, EventWriter, Query, Res, ResMut,
+        Without,
+    },
+    scene::SceneBundle,
+};
+use networking::{
+    client::IncomingReliableServerMessage,
+    server::{ConnectedPlayer, OutgoingReliableServerMessage},
+};
+use player::boarding::SoftPlayer;
+use resources::{
+    grid::{CellFace, TargetCell},
+    math::Vec3Int,
+};
+
+use crate::{
+    grid::{AddedUpdate, CellItem, Gridmap, GridmapUpdate},
+    net::{GridmapServerMessage, NewCell, NewDetails1Cell},
+};
+
+/// Event to add a tile to the details1 layer.
+pub struct AddDetails1Tile {
+    pub id: Vec3Int,
+    /// Id of details1 tile type.
+    pub tile_type: u16,
+    /// Rotation.
+    pub orientation: u8,
+    pub entity: Entity,
+    pub default_map_spawn: bool,
+}
+
+/// Event to remove a tile from the details1 layer.
+pub struct RemoveDetails1Tile {
+    pub id: Vec3Int,
+}
+
+pub(crate) fn add_details1_tile(
+    mut events: EventReader<AddDetails1Tile>,
+    mut gridmap: ResMut<Gridmap>,
+    mut commands: Commands,
+) {
+    for event in events.iter() {
+        if !gridmap
+            .details1_cell_properties
+            .contains_key(&event.tile_type)
+        {
+            warn!("Unknown details1 cellid {}.", event.tile_type);
+            continue;
+        }
+        let old_item_option = gridmap.details1.insert(
+            event.id,
+            CellItem {
+                tile_type: event.tile_type,
+                entity: Some(event.entity),
+                orientation: event.orientation,
+                ..Default::default()
+            },
+        );
+        match old_item_option {
+            Some(old_item) => match old_item.entity {
+                Some(old_entity) => {
+                    commands.entity(old_entity).despawn_recursive();
+                }
+                None => {}
+            },
+            None => {}
+        }
+    }
+}
+
+pub(crate) fn remove_details1_tile(
+    mut events: EventReader<RemoveDetails1Tile>,
+    mut gridmap: ResMut<Gridmap>,
+    mut commands: Commands,
+) {
+    for event in events.iter() {
+        match gridmap.details1.remove(&event.id) {
+            Some(old_item) => match old_item.entity {
+                Some(old_entity) => {
+                    commands.entity(old_entity).despawn_recursive();
+                }
+                None => {}
+            },
+            None => {}
+        }
+    }
+}
+
+/// Send details1 layer changes to connected players, including players that connect later on.
+
+pub(crate) fn details1_tile_net(
+    mut add_events: EventReader<AddDetails1Tile>,
+    mut remove_events: EventReader<RemoveDetails1Tile>,
+    connected_players: Query<&ConnectedPlayer, Without<SoftPlayer>>,
+    mut net: EventWriter<OutgoingReliableServerMessage<GridmapServerMessage>>,
+    mut gridmap: ResMut<Gridmap>,
+) {
+    for event in add_events.iter() {
+        if event.default_map_spawn {
+            continue;
+        }
+        let new_cell = NewDetails1Cell {
+            id: event.id,
+            orientation: event.orientation,
+            tile_type: event.tile_type,
+        };
+        let mut received = vec![];
+        for connected_player in connected_players.iter() {
+            if !connected_player.connected {
+                continue;
+            }
+            net.send(OutgoingReliableServerMessage {
+                handle: connected_player.handle,
+                message: GridmapServerMessage::AddDetails1Cell(new_cell.clone()),
+            });
+            received.push(connected_player.handle);
+        }
+        gridmap.details1_updates.insert(
+            event.id,
+            AddedUpdate {
+                cell: GridmapUpdate::Added(NewCell {
+                    cell: TargetCell {
+                        id: event.id,
+                        face: CellFace::Floor,
+                    },
+                    orientation: event.orientation,
+                    tile_type: event.tile_type,
+                }),
+                players_received: received,
+            },
+        );
+    }
+    for event in remove_events.iter() {
+        let mut received = vec![];
+        for connected_player in connected_players.iter() {
+            if !connected_player.connected {
+                continue;
+            }
+            net.send(OutgoingReliableServerMessage {
+                handle: connected_player.handle,
+                message: GridmapServerMessage::RemoveDetails1Cell(event.id),
+            });
+            received.push(connected_player.handle);
+        }
+        gridmap.details1_updates.insert(
+            event.id,
+            AddedUpdate {
+                cell: GridmapUpdate::Removed,
+                players_received: received,
+            },
+        );
+    }
+
+    for connected_player in connected_players.iter() {
+        if !connected_player.connected {
+            continue;
+        }
+        for (id, update) in gridmap.details1_updates.iter_mut() {
+            if update.players_received.contains(&connected_player.handle) {
+                continue;
+            }
+            update.players_received.push(connected_player.handle);
+            let message = match &update.cell {
+                GridmapUpdate::Added(add) => {
+                    GridmapServerMessage::AddDetails1Cell(NewDetails1Cell {
+                        id: *id,
+                        orientation: add.orientation,
+                        tile_type: add.tile_type,
+                    })
+                }
+                GridmapUpdate::Removed => GridmapServerMessage::RemoveDetails1Cell(*id),
+            };
+            net.send(OutgoingReliableServerMessage {
+                handle: connected_player.handle,
+                message,
+            });
+        }
+    }
+}
+
+pub(crate) fn details1_cell_client(
+    mut net: EventReader<IncomingReliableServerMessage<GridmapServerMessage>>,
+    mut add_events: EventWriter<AddDetails1Tile>,
+    mut remove_events: EventWriter<RemoveDetails1Tile>,
+    mut commands: Commands,
+) {
+    for message in net.iter() {
+        match &message.message {
+            GridmapServerMessage::AddDetails1Cell(new) => {
+                add_events.send(AddDetails1Tile {
+                    id: new.id,
+                    tile_type: new.tile_type,
+                    orientation: new.orientation,
+                    entity: commands.spawn(()).id(),
+                    default_map_spawn: false,
+                });
+            }
+            GridmapServerMessage::RemoveDetails1Cell(id) => {
+                remove_events.send(RemoveDetails1Tile { id: *id });
+            }
+            _ => (),
+        }
+    }
+}
+
+pub(crate) fn set_details1_cell_graphics(
+    mut events: EventReader<AddDetails1Tile>,
+    gridmap: Res<Gridmap>,
+    mut commands: Commands,
+) {
+    for event in events.iter() {
+        match gridmap.details1_cell_properties.get(&event.tile_type) {
+            Some(properties) => match &properties.mesh_option {
+                Some(mesh) => {
+                    let transform = gridmap.get_cell_transform(
+                        TargetCell {
+                            id: event.id,
+                            face: CellFace::Floor,
+                        },
+                        event.orientation,
+                    );
+                    commands.entity(event.entity).insert(SceneBundle {
+                        scene: mesh.clone(),
+                        transform,
+                        ..Default::default()
+                    });
+                }
+                None => {}
+            },
+            None => {
+                warn!("Couldnt find details1 cell properties!");
+            }
+        }
+    }
+}
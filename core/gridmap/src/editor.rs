@@ -0,0 +1,1132 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    f32::consts::PI,
+};
+
+use bevy::prelude::{
+    info, Commands, EventReader, EventWriter, Input, KeyCode, MouseButton, Quat, Res, ResMut,
+    Resource,
+};
+use networking::client::OutgoingReliableClientMessage;
+use resources::{
+    binds::{KeyBind, KeyBinds},
+    grid::{CellFace, TargetCell},
+    hud::HudState,
+    math::Vec3Int,
+    ui::TextInput,
+};
+
+use crate::{
+    construction::GridmapConstructionState,
+    grid::{AddTile, CellType, Gridmap, OrthogonalBases, RemoveTile},
+    net::{
+        EditorCopy, EditorFill, EditorFillShape, EditorFloodFill, EditorPaste, GridmapClientMessage,
+    },
+};
+
+/// Most cells a single editor operation may touch.
+
+pub const MAX_EDITOR_CELLS: usize = 4096;
+/// Amount of operations kept in the undo history of each editor.
+
+pub const EDITOR_HISTORY_LENGTH: usize = 64;
+
+/// A tile as it is placed in a cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+
+pub struct PlacedTile {
+    /// Id of tile type.
+    pub tile_type: u16,
+    /// Rotation.
+    pub orientation: u8,
+}
+
+/// A change to a single cell that can be undone.
+#[derive(Clone, Debug)]
+
+pub struct TileEdit {
+    pub cell: TargetCell,
+    pub before: Option<PlacedTile>,
+    pub after: Option<PlacedTile>,
+}
+
+impl TileEdit {
+    pub fn inverse(&self) -> Self {
+        Self {
+            cell: self.cell.clone(),
+            before: self.after,
+            after: self.before,
+        }
+    }
+}
+
+/// A copied tile relative to the lowest corner of the copied box.
+#[derive(Clone, Debug)]
+
+pub struct ClipboardTile {
+    pub offset: Vec3Int,
+    pub face: CellFace,
+    pub tile: PlacedTile,
+}
+
+/// Undo and redo history and clipboard of an editor.
+#[derive(Default)]
+
+pub struct EditorHistory {
+    pub undo: Vec<Vec<TileEdit>>,
+    pub redo: Vec<Vec<TileEdit>>,
+    pub clipboard: Vec<ClipboardTile>,
+}
+
+/// Server resource with the editor histories by connection handle.
+#[derive(Resource, Default)]
+
+pub struct MapEditorHistories {
+    pub map: HashMap<u64, EditorHistory>,
+}
+
+/// Event to apply and record edits of an editor.
+
+pub struct ApplyMapEdits {
+    pub handle: u64,
+    pub edits: Vec<TileEdit>,
+}
+/// Event to undo the last edits of an editor.
+
+pub struct UndoMapEdit {
+    pub handle: u64,
+}
+/// Event to redo the last undone edits of an editor.
+
+pub struct RedoMapEdit {
+    pub handle: u64,
+}
+
+fn placed_tile(gridmap: &Gridmap, cell: &TargetCell) -> Option<PlacedTile> {
+    gridmap.get_cell(cell.clone()).map(|item| PlacedTile {
+        tile_type: item.tile_type,
+        orientation: item.orientation,
+    })
+}
+
+/// The edit that sets a cell to a tile or clears it. None if the cell already matches.
+
+fn tile_edit(gridmap: &Gridmap, cell: TargetCell, after: Option<PlacedTile>) -> Option<TileEdit> {
+    let before = placed_tile(gridmap, &cell);
+    if before == after {
+        return None;
+    }
+    Some(TileEdit {
+        cell,
+        before,
+        after,
+    })
+}
+
+/// Client supplied orientations index [OrthogonalBases].
+
+fn valid_orientation(orientation: u8) -> Result<(), String> {
+    match (orientation as usize) < OrthogonalBases::default().bases.len() {
+        true => Ok(()),
+        false => Err(format!("Invalid orientation {}.", orientation)),
+    }
+}
+
+/// Client supplied cells have to lie within the map limits.
+
+fn valid_cell(gridmap: &Gridmap, id: Vec3Int) -> Result<(), String> {
+    match gridmap.within_limits(id) {
+        true => Ok(()),
+        false => Err(format!("Cell {:?} is outside of the map.", id)),
+    }
+}
+
+fn box_cells(from: Vec3Int, to: Vec3Int) -> Result<Vec<Vec3Int>, String> {
+    let min = Vec3Int {
+        x: from.x.min(to.x),
+        y: from.y.min(to.y),
+        z: from.z.min(to.z),
+    };
+    let max = Vec3Int {
+        x: from.x.max(to.x),
+        y: from.y.max(to.y),
+        z: from.z.max(to.z),
+    };
+    // Spans are computed in i64, client supplied coordinates can span the whole i16 range.
+    let span = |min: i16, max: i16| (max as i64 - min as i64 + 1) as u64;
+    let amount = span(min.x, max.x) * span(min.y, max.y) * span(min.z, max.z);
+    if amount > MAX_EDITOR_CELLS as u64 {
+        return Err(format!(
+            "Selection of {} cells exceeds the limit of {}.",
+            amount, MAX_EDITOR_CELLS
+        ));
+    }
+
+    let mut cells = Vec::with_capacity(amount as usize);
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            for z in min.z..=max.z {
+                cells.push(Vec3Int { x, y, z });
+            }
+        }
+    }
+    Ok(cells)
+}
+
+fn line_cells(from: Vec3Int, to: Vec3Int) -> Result<Vec<Vec3Int>, String> {
+    let dx = to.x as i32 - from.x as i32;
+    let dy = to.y as i32 - from.y as i32;
+    let dz = to.z as i32 - from.z as i32;
+    let steps = dx.abs().max(dy.abs()).max(dz.abs());
+    if steps as usize + 1 > MAX_EDITOR_CELLS {
+        return Err(format!(
+            "Line of {} cells exceeds the limit of {}.",
+            steps + 1,
+            MAX_EDITOR_CELLS
+        ));
+    }
+
+    let mut cells = vec![];
+    for step in 0..steps + 1 {
+        let t = match steps {
+            0 => 0.,
+            _ => step as f32 / steps as f32,
+        };
+        let cell = Vec3Int {
+            x: from.x + (dx as f32 * t).round() as i16,
+            y: from.y + (dy as f32 * t).round() as i16,
+            z: from.z + (dz as f32 * t).round() as i16,
+        };
+        if !cells.contains(&cell) {
+            cells.push(cell);
+        }
+    }
+    Ok(cells)
+}
+
+/// Edits of a box or line fill with a tile type.
+
+pub fn fill_edits(
+    gridmap: &Gridmap,
+    fill: &EditorFill,
+    tile_type: u16,
+) -> Result<Vec<TileEdit>, String> {
+    valid_orientation(fill.orientation)?;
+    valid_cell(gridmap, fill.from)?;
+    valid_cell(gridmap, fill.to)?;
+    let cells = match fill.shape {
+        EditorFillShape::Box => box_cells(fill.from, fill.to)?,
+        EditorFillShape::Line => line_cells(fill.from, fill.to)?,
+    };
+
+    let after = match fill.remove {
+        true => None,
+        false => Some(PlacedTile {
+            tile_type,
+            orientation: fill.orientation,
+        }),
+    };
+
+    let mut edits = vec![];
+    for id in cells {
+        let cell = TargetCell {
+            id,
+            face: fill.face.clone(),
+        };
+        match tile_edit(gridmap, cell, after) {
+            Some(edit) => {
+                edits.push(edit);
+            }
+            None => {}
+        }
+    }
+    Ok(edits)
+}
+
+/// Edits that fill the floors of all cells connected to the start cell on its y-level that have the same floor as the start cell.
+/// Walls bound the area, an area that is not enclosed fails once it exceeds the cell limit.
+
+pub fn flood_fill_edits(
+    gridmap: &Gridmap,
+    fill: &EditorFloodFill,
+    tile_type: u16,
+) -> Result<Vec<TileEdit>, String> {
+    valid_orientation(fill.orientation)?;
+    valid_cell(gridmap, fill.start)?;
+    match gridmap.main_cell_properties.get(&tile_type) {
+        Some(properties) => match properties.cell_type {
+            CellType::Floor => {}
+            _ => {
+                return Err("Flood fill only supports floor tiles.".to_string());
+            }
+        },
+        None => {
+            return Err(format!("Unknown tile type {}.", tile_type));
+        }
+    }
+
+    let floor = |id: Vec3Int| {
+        placed_tile(
+            gridmap,
+            &TargetCell {
+                id,
+                face: CellFace::Floor,
+            },
+        )
+    };
+    let replaced = floor(fill.start).map(|tile| tile.tile_type);
+    if replaced == Some(tile_type) {
+        return Ok(vec![]);
+    }
+
+    let after = Some(PlacedTile {
+        tile_type,
+        orientation: fill.orientation,
+    });
+
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    let mut edits = vec![];
+    visited.insert(fill.start);
+    queue.push_back(fill.start);
+
+    while let Some(id) = queue.pop_front() {
+        if edits.len() >= MAX_EDITOR_CELLS {
+            return Err(format!(
+                "Flood fill exceeds the limit of {} cells, the area is not enclosed by walls.",
+                MAX_EDITOR_CELLS
+            ));
+        }
+        match tile_edit(
+            gridmap,
+            TargetCell {
+                id,
+                face: CellFace::Floor,
+            },
+            after,
+        ) {
+            Some(edit) => {
+                edits.push(edit);
+            }
+            None => {}
+        }
+
+        for (face, dx, dz) in [
+            (CellFace::FrontWall, 0, 1),
+            (CellFace::BackWall, 0, -1),
+            (CellFace::RightWall, 1, 0),
+            (CellFace::LeftWall, -1, 0),
+        ] {
+            if gridmap.get_cell(TargetCell { id, face }).is_some() {
+                continue;
+            }
+            let neighbour;
+            match (id.x.checked_add(dx), id.z.checked_add(dz)) {
+                (Some(x), Some(z)) => {
+                    neighbour = Vec3Int { x, y: id.y, z };
+                }
+                _ => {
+                    continue;
+                }
+            }
+            if visited.contains(&neighbour)
+                || !gridmap.within_limits(neighbour)
+                || floor(neighbour).map(|tile| tile.tile_type) != replaced
+            {
+                continue;
+            }
+            visited.insert(neighbour);
+            queue.push_back(neighbour);
+        }
+    }
+    Ok(edits)
+}
+
+/// Copy the tiles of a box.
+
+pub fn copy_region(
+    gridmap: &Gridmap,
+    from: Vec3Int,
+    to: Vec3Int,
+) -> Result<Vec<ClipboardTile>, String> {
+    valid_cell(gridmap, from)?;
+    valid_cell(gridmap, to)?;
+    let cells = box_cells(from, to)?;
+    let min = Vec3Int {
+        x: from.x.min(to.x),
+        y: from.y.min(to.y),
+        z: from.z.min(to.z),
+    };
+
+    let mut clipboard = vec![];
+    for id in cells {
+        let indexes = gridmap.get_indexes(id);
+        match gridmap.grid.get(indexes.chunk) {
+            Some(Some(chunk)) => match chunk.cells.get(indexes.cell) {
+                Some(Some(cell)) => {
+                    for (item, face) in cell.get_items() {
+                        clipboard.push(ClipboardTile {
+                            offset: Vec3Int {
+                                x: id.x - min.x,
+                                y: id.y - min.y,
+                                z: id.z - min.z,
+                            },
+                            face,
+                            tile: PlacedTile {
+                                tile_type: item.tile_type,
+                                orientation: item.orientation,
+                            },
+                        });
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    Ok(clipboard)
+}
+
+/// Base rotation of tiles on a face, see [Gridmap::get_cell_transform].
+
+fn face_rotation(face: &CellFace) -> Quat {
+    match face {
+        CellFace::FrontWall | CellFace::BackWall => Quat::from_rotation_y(PI),
+        CellFace::RightWall | CellFace::LeftWall => Quat::from_rotation_y(0.5 * PI),
+        _ => Quat::IDENTITY,
+    }
+}
+
+/// The orthogonal basis closest to a rotation.
+
+pub(crate) fn nearest_orientation(rotation: Quat) -> u8 {
+    let mut nearest = 0;
+    let mut nearest_dot = 0.;
+    for (i, basis) in OrthogonalBases::default().bases.iter().enumerate() {
+        let dot = basis.dot(rotation).abs();
+        if dot > nearest_dot {
+            nearest = i as u8;
+            nearest_dot = dot;
+        }
+    }
+    nearest
+}
+
+/// Rotate a clipboard tile a quarter turn around the y-axis.
+
+fn rotate_clipboard_tile(tile: &ClipboardTile) -> ClipboardTile {
+    let face = match tile.face {
+        CellFace::FrontWall => CellFace::RightWall,
+        CellFace::RightWall => CellFace::BackWall,
+        CellFace::BackWall => CellFace::LeftWall,
+        CellFace::LeftWall => CellFace::FrontWall,
+        _ => tile.face.clone(),
+    };
+    let rotation = face_rotation(&face).inverse()
+        * Quat::from_rotation_y(0.5 * PI)
+        * face_rotation(&tile.face)
+        * OrthogonalBases::default().bases[tile.tile.orientation as usize];
+    ClipboardTile {
+        offset: Vec3Int {
+            x: tile.offset.z,
+            y: tile.offset.y,
+            z: -tile.offset.x,
+        },
+        face,
+        tile: PlacedTile {
+            tile_type: tile.tile.tile_type,
+            orientation: nearest_orientation(rotation),
+        },
+    }
+}
+
+/// Edits that paste a clipboard at an origin, rotated in quarter turns around the y-axis.
+
+pub fn paste_edits(
+    gridmap: &Gridmap,
+    clipboard: &[ClipboardTile],
+    paste: &EditorPaste,
+) -> Vec<TileEdit> {
+    let mut edits = vec![];
+    for tile in clipboard.iter() {
+        let mut rotated = tile.clone();
+        for _ in 0..paste.rotation % 4 {
+            rotated = rotate_clipboard_tile(&rotated);
+        }
+        let id;
+        match (
+            paste.origin.x.checked_add(rotated.offset.x),
+            paste.origin.y.checked_add(rotated.offset.y),
+            paste.origin.z.checked_add(rotated.offset.z),
+        ) {
+            (Some(x), Some(y), Some(z)) => {
+                id = Vec3Int { x, y, z };
+            }
+            _ => {
+                continue;
+            }
+        }
+        if !gridmap.within_limits(id) {
+            continue;
+        }
+        match tile_edit(
+            gridmap,
+            TargetCell {
+                id,
+                face: rotated.face,
+            },
+            Some(rotated.tile),
+        ) {
+            Some(edit) => {
+                edits.push(edit);
+            }
+            None => {}
+        }
+    }
+    edits
+}
+
+fn send_edits(
+    edits: &[TileEdit],
+    gridmap: &Gridmap,
+    add: &mut EventWriter<AddTile>,
+    remove: &mut EventWriter<RemoveTile>,
+    commands: &mut Commands,
+) {
+    for edit in edits.iter() {
+        // The cell may have changed since the edit was recorded.
+        if gridmap.get_cell(edit.cell.clone()).is_some() {
+            remove.send(RemoveTile {
+                cell: edit.cell.clone(),
+            });
+        }
+        match edit.after {
+            Some(tile) => {
+                add.send(AddTile {
+                    id: edit.cell.id,
+                    tile_type: tile.tile_type,
+                    orientation: tile.orientation,
+                    face: edit.cell.face.clone(),
+                    group_instance_id_option: None,
+                    entity: commands.spawn(()).id(),
+                    default_map_spawn: false,
+                });
+            }
+            None => {}
+        }
+    }
+}
+
+/// Apply editor edits and manage their undo and redo history.
+
+pub(crate) fn apply_map_edits(
+    mut apply_events: EventReader<ApplyMapEdits>,
+    mut undo_events: EventReader<UndoMapEdit>,
+    mut redo_events: EventReader<RedoMapEdit>,
+    mut histories: ResMut<MapEditorHistories>,
+    gridmap: Res<Gridmap>,
+    mut add: EventWriter<AddTile>,
+    mut remove: EventWriter<RemoveTile>,
+    mut commands: Commands,
+) {
+    for event in apply_events.iter() {
+        if event.edits.is_empty() {
+            continue;
+        }
+        send_edits(&event.edits, &gridmap, &mut add, &mut remove, &mut commands);
+        let history = histories.map.entry(event.handle).or_default();
+        history.undo.push(event.edits.clone());
+        if history.undo.len() > EDITOR_HISTORY_LENGTH {
+            history.undo.remove(0);
+        }
+        history.redo.clear();
+    }
+    for event in undo_events.iter() {
+        let history = histories.map.entry(event.handle).or_default();
+        match history.undo.pop() {
+            Some(edits) => {
+                let inverse = edits.iter().rev().map(|edit| edit.inverse()).collect();
+                send_edits(&inverse, &gridmap, &mut add, &mut remove, &mut commands);
+                history.redo.push(edits);
+            }
+            None => {}
+        }
+    }
+    for event in redo_events.iter() {
+        let history = histories.map.entry(event.handle).or_default();
+        match history.redo.pop() {
+            Some(edits) => {
+                send_edits(&edits, &gridmap, &mut add, &mut remove, &mut commands);
+                history.undo.push(edits);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Tools of the map editor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+
+pub enum EditorTool {
+    /// Construct and deconstruct one tile per click.
+    #[default]
+    Single,
+    /// Fill a box between two clicked cells.
+    Box,
+    /// Fill a line between two clicked cells.
+    Line,
+    /// Fill the floors of an area enclosed by walls.
+    FloodFill,
+    /// Select a box to copy.
+    Select,
+}
+
+impl EditorTool {
+    pub fn next(&self) -> Self {
+        match self {
+            EditorTool::Single => EditorTool::Box,
+            EditorTool::Box => EditorTool::Line,
+            EditorTool::Line => EditorTool::FloodFill,
+            EditorTool::FloodFill => EditorTool::Select,
+            EditorTool::Select => EditorTool::Single,
+        }
+    }
+}
+
+/// Client resource with the state of the map editor.
+#[derive(Resource, Default)]
+
+pub struct MapEditorState {
+    pub tool: EditorTool,
+    /// First cell of a box, line or selection in progress.
+    pub anchor: Option<Vec3Int>,
+    /// Selected box to copy.
+    pub selection: Option<(Vec3Int, Vec3Int)>,
+    /// Quarter turns around the y-axis of pasted regions.
+    pub paste_rotation: u8,
+}
+
+pub const EDITOR_CYCLE_TOOL_BIND: &str = "mapEditorCycleTool";
+pub const EDITOR_UNDO_BIND: &str = "mapEditorUndo";
+pub const EDITOR_REDO_BIND: &str = "mapEditorRedo";
+pub const EDITOR_COPY_BIND: &str = "mapEditorCopy";
+pub const EDITOR_PASTE_BIND: &str = "mapEditorPaste";
+pub const EDITOR_ROTATE_PASTE_BIND: &str = "mapEditorRotatePaste";
+
+pub(crate) fn register_editor_input(mut binds: ResMut<KeyBinds>) {
+    binds.list.insert(
+        EDITOR_CYCLE_TOOL_BIND.to_string(),
+        KeyBind {
+            key_code: KeyCode::T,
+            description: "Cycles through the map editor tools.".to_string(),
+            name: "Map Editor Cycle Tool".to_string(),
+        },
+    );
+    binds.list.insert(
+        EDITOR_UNDO_BIND.to_string(),
+        KeyBind {
+            key_code: KeyCode::Z,
+            description: "Undoes the last map edit.".to_string(),
+            name: "Map Editor Undo".to_string(),
+        },
+    );
+    binds.list.insert(
+        EDITOR_REDO_BIND.to_string(),
+        KeyBind {
+            key_code: KeyCode::Y,
+            description: "Redoes the last undone map edit.".to_string(),
+            name: "Map Editor Redo".to_string(),
+        },
+    );
+    binds.list.insert(
+        EDITOR_COPY_BIND.to_string(),
+        KeyBind {
+            key_code: KeyCode::C,
+            description: "Copies the selected region of the map.".to_string(),
+            name: "Map Editor Copy".to_string(),
+        },
+    );
+    binds.list.insert(
+        EDITOR_PASTE_BIND.to_string(),
+        KeyBind {
+            key_code: KeyCode::P,
+            description: "Pastes the copied region at the selected cell.".to_string(),
+            name: "Map Editor Paste".to_string(),
+        },
+    );
+    binds.list.insert(
+        EDITOR_ROTATE_PASTE_BIND.to_string(),
+        KeyBind {
+            key_code: KeyCode::R,
+            description: "Rotates the pasted region.".to_string(),
+            name: "Map Editor Rotate Paste".to_string(),
+        },
+    );
+}
+
+pub(crate) fn editor_key_input(
+    keys: Res<Input<KeyCode>>,
+    binds: Res<KeyBinds>,
+    focus: Res<TextInput>,
+    construction_state: Res<GridmapConstructionState>,
+    mut state: ResMut<MapEditorState>,
+    mut net: EventWriter<OutgoingReliableClientMessage<GridmapClientMessage>>,
+) {
+    if !construction_state.is_constructing || focus.focused_input.is_some() {
+        return;
+    }
+
+    if keys.just_pressed(binds.bind(EDITOR_CYCLE_TOOL_BIND)) {
+        state.tool = state.tool.next();
+        state.anchor = None;
+        info!("Map editor tool: {:?}", state.tool);
+    }
+    if keys.just_pressed(binds.bind(EDITOR_UNDO_BIND)) {
+        net.send(OutgoingReliableClientMessage {
+            message: GridmapClientMessage::EditorUndo,
+        });
+    }
+    if keys.just_pressed(binds.bind(EDITOR_REDO_BIND)) {
+        net.send(OutgoingReliableClientMessage {
+            message: GridmapClientMessage::EditorRedo,
+        });
+    }
+    if keys.just_pressed(binds.bind(EDITOR_COPY_BIND)) {
+        match state.selection {
+            Some((from, to)) => {
+                net.send(OutgoingReliableClientMessage {
+                    message: GridmapClientMessage::EditorCopy(EditorCopy { from, to }),
+                });
+            }
+            None => {}
+        }
+    }
+    if keys.just_pressed(binds.bind(EDITOR_ROTATE_PASTE_BIND)) {
+        state.paste_rotation = (state.paste_rotation + 1) % 4;
+        info!(
+            "Map editor paste rotation: {} degrees",
+            state.paste_rotation as u16 * 90
+        );
+    }
+    if keys.just_pressed(binds.bind(EDITOR_PASTE_BIND)) {
+        match construction_state.selected {
+            Some(origin) => {
+                net.send(OutgoingReliableClientMessage {
+                    message: GridmapClientMessage::EditorPaste(EditorPaste {
+                        origin,
+                        rotation: state.paste_rotation,
+                    }),
+                });
+            }
+            None => {}
+        }
+    }
+}
+
+pub(crate) fn editor_mouse_click_input(
+    buttons: Res<Input<MouseButton>>,
+    construction_state: Res<GridmapConstructionState>,
+    mut state: ResMut<MapEditorState>,
+    hud_state: Res<HudState>,
+    mut net: EventWriter<OutgoingReliableClientMessage<GridmapClientMessage>>,
+) {
+    if !construction_state.is_constructing || hud_state.expanded || state.tool == EditorTool::Single
+    {
+        return;
+    }
+
+    let left = buttons.just_pressed(MouseButton::Left);
+    let right = buttons.just_pressed(MouseButton::Right);
+    if !left && !right {
+        return;
+    }
+
+    let selected;
+    match construction_state.selected {
+        Some(c) => {
+            selected = c;
+        }
+        None => {
+            return;
+        }
+    }
+
+    match state.tool {
+        EditorTool::Box | EditorTool::Line => match state.anchor {
+            Some(anchor) => {
+                state.anchor = None;
+                if !right && construction_state.ghost_tile.is_none() {
+                    return;
+                }
+                let shape = match state.tool {
+                    EditorTool::Line => EditorFillShape::Line,
+                    _ => EditorFillShape::Box,
+                };
+                net.send(OutgoingReliableClientMessage {
+                    message: GridmapClientMessage::EditorFill(EditorFill {
+                        from: anchor,
+                        to: selected,
+                        shape,
+                        face: construction_state.ghost_face.clone(),
+                        orientation: construction_state.ghost_rotation,
+                        remove: right,
+                    }),
+                });
+            }
+            None => {
+                state.anchor = Some(selected);
+            }
+        },
+        EditorTool::FloodFill => {
+            if left && construction_state.ghost_tile.is_some() {
+                net.send(OutgoingReliableClientMessage {
+                    message: GridmapClientMessage::EditorFloodFill(EditorFloodFill {
+                        start: selected,
+                        orientation: construction_state.ghost_rotation,
+                    }),
+                });
+            }
+        }
+        EditorTool::Select => {
+            if right {
+                state.anchor = None;
+                state.selection = None;
+                return;
+            }
+            match state.anchor {
+                Some(anchor) => {
+                    state.selection = Some((anchor, selected));
+                    state.anchor = None;
+                }
+                None => {
+                    state.anchor = Some(selected);
+                }
+            }
+        }
+        EditorTool::Single => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{grid::TileProperties, test_utils::set_tile};
+
+    const FLOOR: u16 = 1;
+    const NEW_FLOOR: u16 = 2;
+    const WALL: u16 = 3;
+
+    fn id(x: i16, y: i16, z: i16) -> Vec3Int {
+        Vec3Int { x, y, z }
+    }
+
+    fn test_gridmap() -> Gridmap {
+        let mut gridmap = Gridmap::default();
+        for (tile_type, cell_type) in [
+            (FLOOR, CellType::Floor),
+            (NEW_FLOOR, CellType::Floor),
+            (WALL, CellType::Wall),
+        ] {
+            gridmap.main_cell_properties.insert(
+                tile_type,
+                TileProperties {
+                    id: tile_type,
+                    cell_type,
+                    ..Default::default()
+                },
+            );
+        }
+        gridmap
+    }
+
+    fn flood_fill(start: Vec3Int) -> EditorFloodFill {
+        EditorFloodFill {
+            start,
+            orientation: 0,
+        }
+    }
+
+    fn edited_cells(edits: &[TileEdit]) -> HashSet<Vec3Int> {
+        edits.iter().map(|edit| edit.cell.id).collect()
+    }
+
+    #[test]
+    fn test_box_cells() {
+        let cells = box_cells(id(1, 0, 2), id(-1, 1, 3)).unwrap();
+        assert_eq!(cells.len(), 12);
+        assert!(cells.contains(&id(-1, 0, 2)));
+        assert!(cells.contains(&id(1, 1, 3)));
+        assert!(!cells.contains(&id(2, 0, 2)));
+
+        assert_eq!(
+            box_cells(id(4, 4, 4), id(4, 4, 4)).unwrap(),
+            vec![id(4, 4, 4)]
+        );
+        // Corners at the end of the coordinate range.
+        assert_eq!(
+            box_cells(id(i16::MAX, 0, 0), id(i16::MAX - 1, 0, 0))
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_box_cells_limit() {
+        assert_eq!(box_cells(id(0, 0, 0), id(15, 15, 15)).unwrap().len(), 4096);
+        assert!(box_cells(id(0, 0, 0), id(16, 15, 15)).is_err());
+        // Spans that overflow i16 and products that overflow usize are rejected.
+        assert!(box_cells(id(i16::MIN, 0, 0), id(i16::MAX - 1, 0, 0)).is_err());
+        assert!(box_cells(id(i16::MIN, 0, 0), id(i16::MAX, 0, 0)).is_err());
+        assert!(box_cells(
+            id(i16::MIN, i16::MIN, i16::MIN),
+            id(i16::MAX, i16::MAX, i16::MAX)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_line_cells() {
+        assert_eq!(
+            line_cells(id(2, 0, 2), id(2, 0, 2)).unwrap(),
+            vec![id(2, 0, 2)]
+        );
+        assert_eq!(
+            line_cells(id(0, 0, 0), id(3, 0, 0)).unwrap(),
+            vec![id(0, 0, 0), id(1, 0, 0), id(2, 0, 0), id(3, 0, 0)]
+        );
+        assert_eq!(
+            line_cells(id(0, 0, 0), id(-2, 0, -2)).unwrap(),
+            vec![id(0, 0, 0), id(-1, 0, -1), id(-2, 0, -2)]
+        );
+        // The longest axis decides the amount of cells.
+        let cells = line_cells(id(0, 0, 0), id(1, 0, 4)).unwrap();
+        assert_eq!(cells.len(), 5);
+        assert_eq!(cells[0], id(0, 0, 0));
+        assert_eq!(cells[4], id(1, 0, 4));
+    }
+
+    #[test]
+    fn test_line_cells_limit() {
+        assert_eq!(
+            line_cells(id(0, 0, 0), id(MAX_EDITOR_CELLS as i16 - 1, 0, 0))
+                .unwrap()
+                .len(),
+            MAX_EDITOR_CELLS
+        );
+        assert!(line_cells(id(0, 0, 0), id(MAX_EDITOR_CELLS as i16, 0, 0)).is_err());
+        assert!(line_cells(id(i16::MIN, 0, 0), id(i16::MAX, 0, 0)).is_err());
+        assert!(line_cells(id(i16::MAX, i16::MIN, 0), id(i16::MIN, i16::MAX, 0)).is_err());
+    }
+
+    #[test]
+    fn test_fill_edits_map_limits() {
+        let gridmap = test_gridmap();
+        let mut fill = EditorFill {
+            from: id(-512, 0, 0),
+            to: id(511, 0, 0),
+            shape: EditorFillShape::Line,
+            face: CellFace::Floor,
+            orientation: 0,
+            remove: false,
+        };
+        assert_eq!(fill_edits(&gridmap, &fill, FLOOR).unwrap().len(), 1024);
+        fill.to = id(512, 0, 0);
+        assert!(fill_edits(&gridmap, &fill, FLOOR).is_err());
+        fill.from = id(i16::MIN, i16::MIN, i16::MIN);
+        fill.to = id(i16::MAX, i16::MAX, i16::MAX);
+        fill.shape = EditorFillShape::Box;
+        assert!(fill_edits(&gridmap, &fill, FLOOR).is_err());
+        assert!(copy_region(&gridmap, fill.from, fill.to).is_err());
+    }
+
+    #[test]
+    fn test_fill_edits_orientation() {
+        let gridmap = test_gridmap();
+        let mut fill = EditorFill {
+            from: id(0, 0, 0),
+            to: id(1, 0, 0),
+            shape: EditorFillShape::Box,
+            face: CellFace::Floor,
+            orientation: 23,
+            remove: false,
+        };
+        assert_eq!(fill_edits(&gridmap, &fill, FLOOR).unwrap().len(), 2);
+        fill.orientation = 24;
+        assert!(fill_edits(&gridmap, &fill, FLOOR).is_err());
+    }
+
+    #[test]
+    fn test_flood_fill_edits() {
+        let mut gridmap = test_gridmap();
+        for x in 0..3 {
+            for z in 0..2 {
+                set_tile(&mut gridmap, id(x, 0, z), CellFace::Floor, FLOOR, 0);
+            }
+        }
+        // A different floor is not part of the area.
+        set_tile(&mut gridmap, id(2, 0, 1), CellFace::Floor, NEW_FLOOR, 0);
+
+        let edits = flood_fill_edits(&gridmap, &flood_fill(id(0, 0, 0)), NEW_FLOOR).unwrap();
+        assert_eq!(
+            edited_cells(&edits),
+            HashSet::from([
+                id(0, 0, 0),
+                id(1, 0, 0),
+                id(2, 0, 0),
+                id(0, 0, 1),
+                id(1, 0, 1)
+            ])
+        );
+        assert!(edits.iter().all(|edit| edit.before
+            == Some(PlacedTile {
+                tile_type: FLOOR,
+                orientation: 0
+            })));
+
+        // Walls bound the area.
+        set_tile(&mut gridmap, id(0, 0, 0), CellFace::RightWall, WALL, 0);
+        set_tile(&mut gridmap, id(0, 0, 0), CellFace::FrontWall, WALL, 0);
+        let edits = flood_fill_edits(&gridmap, &flood_fill(id(0, 0, 0)), NEW_FLOOR).unwrap();
+        assert_eq!(edited_cells(&edits), HashSet::from([id(0, 0, 0)]));
+
+        // Walls of the neighbour facing the cell bound it too.
+        let edits = flood_fill_edits(&gridmap, &flood_fill(id(1, 0, 0)), NEW_FLOOR).unwrap();
+        assert!(!edited_cells(&edits).contains(&id(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_flood_fill_edits_rejected() {
+        let mut gridmap = test_gridmap();
+        set_tile(&mut gridmap, id(0, 0, 0), CellFace::Floor, FLOOR, 0);
+
+        assert!(flood_fill_edits(&gridmap, &flood_fill(id(0, 0, 0)), WALL).is_err());
+        assert!(flood_fill_edits(&gridmap, &flood_fill(id(0, 0, 0)), 99).is_err());
+        assert!(flood_fill_edits(&gridmap, &flood_fill(id(0, 0, 0)), FLOOR)
+            .unwrap()
+            .is_empty());
+        // Empty space is never enclosed.
+        assert!(flood_fill_edits(&gridmap, &flood_fill(id(5, 0, 5)), NEW_FLOOR).is_err());
+    }
+
+    #[test]
+    fn test_flood_fill_edits_map_limits() {
+        let mut gridmap = test_gridmap();
+        // The corner cell of the map, open towards the map edges.
+        set_tile(&mut gridmap, id(511, 0, -512), CellFace::Floor, FLOOR, 0);
+        set_tile(&mut gridmap, id(510, 0, -512), CellFace::Floor, FLOOR, 0);
+        let edits = flood_fill_edits(&gridmap, &flood_fill(id(511, 0, -512)), NEW_FLOOR).unwrap();
+        assert_eq!(
+            edited_cells(&edits),
+            HashSet::from([id(511, 0, -512), id(510, 0, -512)])
+        );
+
+        assert!(
+            flood_fill_edits(&gridmap, &flood_fill(id(i16::MAX, 0, i16::MIN)), NEW_FLOOR).is_err()
+        );
+    }
+
+    #[test]
+    fn test_nearest_orientation() {
+        for (i, basis) in OrthogonalBases::default().bases.iter().enumerate() {
+            assert_eq!(nearest_orientation(*basis), i as u8);
+        }
+    }
+
+    #[test]
+    fn test_rotate_clipboard_tile() {
+        let tile = ClipboardTile {
+            offset: id(1, 2, 3),
+            face: CellFace::FrontWall,
+            tile: PlacedTile {
+                tile_type: WALL,
+                orientation: 0,
+            },
+        };
+        let rotated = rotate_clipboard_tile(&tile);
+        assert_eq!(rotated.offset, id(3, 2, -1));
+        assert_eq!(rotated.face, CellFace::RightWall);
+        assert_eq!(rotated.tile.tile_type, WALL);
+
+        let mut faces = vec![];
+        let mut rotated = tile.clone();
+        for _ in 0..4 {
+            rotated = rotate_clipboard_tile(&rotated);
+            faces.push(rotated.face.clone());
+        }
+        assert_eq!(
+            faces,
+            vec![
+                CellFace::RightWall,
+                CellFace::BackWall,
+                CellFace::LeftWall,
+                CellFace::FrontWall
+            ]
+        );
+        assert_eq!(rotated.offset, tile.offset);
+        assert_eq!(rotated.tile, tile.tile);
+    }
+
+    #[test]
+    fn test_rotate_clipboard_floor_orientations() {
+        for orientation in 0..24 {
+            let tile = ClipboardTile {
+                offset: id(0, 0, 0),
+                face: CellFace::Floor,
+                tile: PlacedTile {
+                    tile_type: FLOOR,
+                    orientation,
+                },
+            };
+            let mut rotated = tile.clone();
+            for _ in 0..4 {
+                rotated = rotate_clipboard_tile(&rotated);
+            }
+            assert_eq!(rotated.face, CellFace::Floor);
+            assert_eq!(rotated.tile, tile.tile);
+        }
+    }
+
+    #[test]
+    fn test_paste_edits_map_limits() {
+        let gridmap = test_gridmap();
+        let clipboard = vec![
+            ClipboardTile {
+                offset: id(0, 0, 0),
+                face: CellFace::Floor,
+                tile: PlacedTile {
+                    tile_type: FLOOR,
+                    orientation: 0,
+                },
+            },
+            ClipboardTile {
+                offset: id(1, 0, 0),
+                face: CellFace::Floor,
+                tile: PlacedTile {
+                    tile_type: FLOOR,
+                    orientation: 0,
+                },
+            },
+        ];
+        let edits = paste_edits(
+            &gridmap,
+            &clipboard,
+            &EditorPaste {
+                origin: id(511, 0, 0),
+                rotation: 0,
+            },
+        );
+        assert_eq!(edited_cells(&edits), HashSet::from([id(511, 0, 0)]));
+
+        let edits = paste_edits(
+            &gridmap,
+            &clipboard,
+            &EditorPaste {
+                origin: id(i16::MAX, 0, 0),
+                rotation: 0,
+            },
+        );
+        assert!(edits.is_empty());
+    }
+}
@@ -237,6 +237,51 @@ pub(crate) fn examine_map_health(
     }
 }
 
+/// Examine the details1 layer of a cell.
+
+pub(crate) fn examine_map_details1(
+    mut examine_map_events: ResMut<GridmapExamineMessages>,
+    gridmap_main: Res<Gridmap>,
+    senser_entities: Query<&Senser>,
+) {
+    for examine_event in examine_map_events.messages.iter_mut() {
+        let examiner_senser_component;
+
+        match senser_entities.get(examine_event.entity) {
+            Ok(examiner_senser) => {
+                examiner_senser_component = examiner_senser;
+            }
+            Err(_rr) => {
+                warn!("Couldn't find examiner entity in &Senser query.");
+                continue;
+            }
+        }
+
+        let coords = to_doryen_coordinates(
+            examine_event.gridmap_cell_id.x,
+            examine_event.gridmap_cell_id.z,
+        );
+        if !examiner_senser_component.fov.is_in_fov(coords.0, coords.1) {
+            continue;
+        }
+
+        match gridmap_main.details1.get(&examine_event.gridmap_cell_id) {
+            Some(detail) => match gridmap_main.details1_text_names.get(&detail.tile_type) {
+                Some(name) => {
+                    examine_event.message = examine_event.message.clone()
+                        + "[font="
+                        + FURTHER_ITALIC_FONT
+                        + "]There is "
+                        + &name.get_a_name()
+                        + " here.[/font]\n";
+                }
+                None => {}
+            },
+            None => {}
+        }
+    }
+}
+
 /// Examine gridmap.
 
 pub(crate) fn examine_map_abilities(
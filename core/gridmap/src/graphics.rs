@@ -1,39 +1,266 @@
-use bevy::prelude::Commands;
-use bevy::prelude::EventReader;
-
-use bevy::scene::SceneBundle;
+use std::collections::{HashMap, HashSet};
 
+use bevy::asset::LoadState;
+use bevy::gltf::GltfMesh;
+use bevy::pbr::PbrBundle;
 use bevy::prelude::warn;
+use bevy::prelude::AssetServer;
+use bevy::prelude::Assets;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::DespawnRecursiveExt;
+use bevy::prelude::DetectChanges;
+use bevy::prelude::Entity;
+use bevy::prelude::EventReader;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
 use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use resources::content::{ContentAssets, SF_CONTENT_NAMESPACE};
 use resources::grid::TargetCell;
 
+use crate::chunk_mesh::{chunk_tile_transforms, merge_tile_meshes};
 use crate::grid::AddTile;
 use crate::grid::Gridmap;
+use crate::grid::RemoveTile;
+
+/// Mesh of a tile model that gets batched per gridmap chunk.
+/// Each primitive of the mesh is batched separately with its own material.
+#[derive(Clone)]
+
+pub struct TileMesh {
+    pub mesh: Handle<GltfMesh>,
+}
+
+/// Load the first mesh of a tile model with all of its primitives.
 
-pub(crate) fn set_cell_graphics(
-    mut events: EventReader<AddTile>,
-    gridmap_main: Res<Gridmap>,
+pub fn load_tile_mesh(assets: &AssetServer, model: &str) -> TileMesh {
+    TileMesh {
+        mesh: assets.load(model.to_string() + "#Mesh0"),
+    }
+}
+
+/// Client resource with the batched mesh entities of the gridmap chunks.
+#[derive(Resource, Default)]
+
+pub struct ChunkMeshes {
+    /// Batched mesh entity per chunk index, tile type and mesh primitive.
+    pub batches: HashMap<(usize, u16, usize), Entity>,
+    /// Chunks whose batched meshes are outdated.
+    pub dirty: HashSet<usize>,
+}
+
+/// A merged mesh primitive of all tiles of one type in a gridmap chunk.
+#[derive(Component)]
+
+pub struct ChunkMesh {
+    pub chunk: usize,
+    pub tile_type: u16,
+    pub primitive: usize,
+}
+
+/// Reload the meshes of tile types from the server content once it is downloaded.
+
+pub(crate) fn load_content_tile_meshes(
+    content: Res<ContentAssets>,
+    assets: Res<AssetServer>,
+    mut gridmap: ResMut<Gridmap>,
+    mut chunk_meshes: ResMut<ChunkMeshes>,
     mut commands: Commands,
 ) {
-    for set_cell in events.iter() {
-        match gridmap_main.main_cell_properties.get(&set_cell.tile_type) {
-            Some(properties) => {
-                let transform = gridmap_main.get_cell_transform(
-                    TargetCell {
-                        id: set_cell.id,
-                        face: set_cell.face.clone(),
-                    },
-                    set_cell.orientation,
-                );
-                commands.entity(set_cell.entity).insert(SceneBundle {
-                    scene: properties.mesh_option.clone().unwrap(),
-                    transform,
-                    ..Default::default()
-                });
+    if !content.is_changed() || content.is_added() {
+        return;
+    }
+    let gridmap = &mut *gridmap;
+    for properties in gridmap
+        .main_cell_properties
+        .values_mut()
+        .chain(gridmap.details1_cell_properties.values_mut())
+    {
+        let model;
+        match &properties.model_option {
+            Some(m) => {
+                model = content.asset_path(SF_CONTENT_NAMESPACE, m);
             }
             None => {
-                warn!("Couldnt find maincellproperties!");
+                continue;
             }
         }
+        properties.mesh_option = Some(assets.load(model.clone() + "#Scene0"));
+        if properties.tile_mesh_option.is_some() {
+            properties.tile_mesh_option = Some(load_tile_mesh(&assets, &model));
+        }
+    }
+
+    // Batches are rebuilt from scratch so they pick up the materials of the new models too.
+    let batches: Vec<((usize, u16, usize), Entity)> = chunk_meshes.batches.drain().collect();
+    for ((chunk, _, _), entity) in batches {
+        commands.entity(entity).despawn_recursive();
+        chunk_meshes.dirty.insert(chunk);
+    }
+}
+
+/// Mark the chunks of added and removed tiles as outdated.
+
+pub(crate) fn mark_dirty_chunks(
+    mut add_events: EventReader<AddTile>,
+    mut remove_events: EventReader<RemoveTile>,
+    gridmap: Res<Gridmap>,
+    mut chunk_meshes: ResMut<ChunkMeshes>,
+) {
+    for event in add_events.iter() {
+        let strict = gridmap.get_strict_cell(TargetCell {
+            id: event.id,
+            face: event.face.clone(),
+        });
+        chunk_meshes
+            .dirty
+            .insert(gridmap.get_indexes(strict.id).chunk);
+    }
+    for event in remove_events.iter() {
+        let strict = gridmap.get_strict_cell(event.cell.clone());
+        chunk_meshes
+            .dirty
+            .insert(gridmap.get_indexes(strict.id).chunk);
+    }
+}
+
+/// Rebuild the batched meshes of outdated chunks.
+
+pub(crate) fn build_chunk_meshes(
+    mut chunk_meshes: ResMut<ChunkMeshes>,
+    gridmap: Res<Gridmap>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    gltf_meshes: Res<Assets<GltfMesh>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    if chunk_meshes.dirty.is_empty() {
+        return;
+    }
+    let dirty: Vec<usize> = chunk_meshes.dirty.drain().collect();
+
+    for chunk_index in dirty {
+        let batches = chunk_tile_transforms(&gridmap, chunk_index);
+
+        let removed: Vec<(usize, u16, usize)> = chunk_meshes
+            .batches
+            .keys()
+            .filter(|(chunk, tile_type, _)| {
+                *chunk == chunk_index && !batches.contains_key(tile_type)
+            })
+            .cloned()
+            .collect();
+        for key in removed {
+            match chunk_meshes.batches.remove(&key) {
+                Some(entity) => {
+                    commands.entity(entity).despawn_recursive();
+                }
+                None => {}
+            }
+        }
+
+        let mut loaded = true;
+        for (tile_type, transforms) in batches.iter() {
+            let tile_mesh;
+            match gridmap.main_cell_properties.get(tile_type) {
+                Some(properties) => match &properties.tile_mesh_option {
+                    Some(t) => {
+                        tile_mesh = t.clone();
+                    }
+                    None => {
+                        continue;
+                    }
+                },
+                None => {
+                    warn!("Couldnt find maincellproperties!");
+                    continue;
+                }
+            }
+
+            let gltf_mesh;
+            match gltf_meshes.get(&tile_mesh.mesh) {
+                Some(m) => {
+                    gltf_mesh = m;
+                }
+                None => {
+                    match asset_server.get_load_state(&tile_mesh.mesh) {
+                        LoadState::Failed => {
+                            warn!("Mesh of tile type {} failed to load.", tile_type);
+                        }
+                        _ => {
+                            // Retry once the tile model has loaded.
+                            loaded = false;
+                        }
+                    }
+                    continue;
+                }
+            }
+
+            for (primitive_index, primitive) in gltf_mesh.primitives.iter().enumerate() {
+                let merged;
+                match meshes.get(&primitive.mesh) {
+                    Some(source) => match merge_tile_meshes(source, transforms) {
+                        Some(m) => {
+                            merged = m;
+                        }
+                        None => {
+                            warn!(
+                                "Primitive {} of tile type {} cannot be batched.",
+                                primitive_index, tile_type
+                            );
+                            continue;
+                        }
+                    },
+                    None => {
+                        loaded = false;
+                        continue;
+                    }
+                }
+
+                // Bounds are computed once by Bevy, so they are replaced on rebuilds to keep frustum culling correct.
+                let aabb = merged.compute_aabb();
+                let handle = meshes.add(merged);
+                let key = (chunk_index, *tile_type, primitive_index);
+
+                match chunk_meshes.batches.get(&key).copied() {
+                    Some(entity) => {
+                        let mut builder = commands.entity(entity);
+                        builder.insert(handle);
+                        match aabb {
+                            Some(aabb) => {
+                                builder.insert(aabb);
+                            }
+                            None => {}
+                        }
+                    }
+                    None => {
+                        let mut builder = commands.spawn(PbrBundle {
+                            mesh: handle,
+                            material: primitive.material.clone().unwrap_or_default(),
+                            ..Default::default()
+                        });
+                        builder.insert(ChunkMesh {
+                            chunk: chunk_index,
+                            tile_type: *tile_type,
+                            primitive: primitive_index,
+                        });
+                        match aabb {
+                            Some(aabb) => {
+                                builder.insert(aabb);
+                            }
+                            None => {}
+                        }
+                        let entity = builder.id();
+                        chunk_meshes.batches.insert(key, entity);
+                    }
+                }
+            }
+        }
+
+        if !loaded {
+            chunk_meshes.dirty.insert(chunk_index);
+        }
     }
 }
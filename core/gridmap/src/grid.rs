@@ -2,29 +2,28 @@ use std::{collections::HashMap, f32::consts::PI};
 
 use bevy::{
     prelude::{
-        warn, BuildChildren, Commands, Component, DespawnRecursiveExt, Entity, EventWriter, Handle,
-        Mat3, Quat, Query, Res, Resource, Transform, Without,
+        warn, Commands, Component, DespawnRecursiveExt, Entity, EventWriter, Handle, Mat3, Quat,
+        Query, Res, Resource, Transform, Without,
     },
     scene::Scene,
     transform::TransformBundle,
 };
-use bevy_rapier3d::prelude::{
-    CoefficientCombineRule, Collider, CollisionGroups, Friction, Group, RigidBody,
-};
+use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider};
 use entity::{examine::RichName, health::Health};
 use networking::{
     client::IncomingReliableServerMessage,
     server::{ConnectedPlayer, OutgoingReliableServerMessage},
 };
-use physics::physics::{get_bit_masks, ColliderGroup};
 use player::boarding::SoftPlayer;
-use resources::{grid::CellFace, is_server::is_server};
+use resources::grid::CellFace;
 use resources::{
     grid::TargetCell,
     math::{cell_id_to_world, Vec3Int},
 };
 use serde::{Deserialize, Serialize};
 
+use crate::graphics::TileMesh;
+
 /// Gridmap maximum limits as cube dimensions in chunks.
 pub struct MapLimits {
     /// Full length of the cube as chunks.
@@ -64,7 +63,15 @@ pub struct TileProperties {
     pub combine_rule: CoefficientCombineRule,
     /// Always available on client. Never available on server.
     pub mesh_option: Option<Handle<Scene>>,
+    /// Mesh that gets batched per chunk. Always available on client. Never available on server.
+    pub tile_mesh_option: Option<TileMesh>,
+    /// Path of the model in the `sf` content pack, the meshes get reloaded from the server content when it provides the model.
+    pub model_option: Option<String>,
     pub cell_type: CellType,
+    /// Brute and burn damage at which the tile breaks. Indestructible if none.
+    pub damage_threshold: Option<f32>,
+    /// Tile type that replaces this tile once it breaks. The tile is removed if none.
+    pub broken_tile_option: Option<u16>,
 }
 
 impl Default for TileProperties {
@@ -86,7 +93,11 @@ impl Default for TileProperties {
             friction: 0.,
             combine_rule: CoefficientCombineRule::Min,
             mesh_option: None,
+            tile_mesh_option: None,
+            model_option: None,
             cell_type: CellType::Wall,
+            damage_threshold: None,
+            broken_tile_option: None,
         }
     }
 }
@@ -239,6 +250,10 @@ pub struct Gridmap {
     pub details1_text_names: HashMap<u16, RichName>,
     pub main_text_examine_desc: HashMap<u16, String>,
     pub details1_text_examine_desc: HashMap<u16, String>,
+    /// Cells of the details1 layer. Each cell holds at most one details1 item.
+    pub details1: HashMap<Vec3Int, CellItem>,
+    pub details1_cell_properties: HashMap<u16, TileProperties>,
+    pub details1_updates: HashMap<Vec3Int, AddedUpdate>,
     pub blackcell_id: u16,
     pub blackcell_blocking_id: u16,
     pub main_cell_properties: HashMap<u16, TileProperties>,
@@ -294,6 +309,26 @@ impl Gridmap {
         }
         ron::to_string(&data).unwrap()
     }
+    /// Export the details1 layer to ron.
+    pub fn export_details1_ron(&self) -> String {
+        let mut data = vec![];
+        for (id, item) in self.details1.iter() {
+            match self.details1_id_name_map.get(&item.tile_type) {
+                Some(name) => {
+                    data.push(CellDataRon {
+                        id: *id,
+                        item: RonItem::Cell(name.clone()),
+                        orientation: item.orientation,
+                        face: CellFace::Floor,
+                    });
+                }
+                None => {
+                    warn!("Couldnt find details1 item {}", item.tile_type);
+                }
+            }
+        }
+        ron::to_string(&data).unwrap()
+    }
 }
 
 const EMPTY_CHUNK: Option<GridmapChunk> = None;
@@ -317,6 +352,9 @@ impl Default for Gridmap {
             details1_text_names: HashMap::default(),
             main_text_examine_desc: HashMap::default(),
             details1_text_examine_desc: HashMap::default(),
+            details1: HashMap::default(),
+            details1_cell_properties: HashMap::default(),
+            details1_updates: HashMap::default(),
             blackcell_id: 0,
             blackcell_blocking_id: 0,
             main_cell_properties: HashMap::default(),
@@ -328,6 +366,15 @@ impl Default for Gridmap {
         }
     }
 }
+/// The gridmap layers cells can be placed on.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+
+pub enum GridmapLayer {
+    #[default]
+    Main,
+    Details1,
+}
+
 /// Result for [get_indexes].
 #[derive(Clone, Copy, Debug)]
 pub struct CellIndexes {
@@ -335,7 +382,7 @@ pub struct CellIndexes {
     pub cell: usize,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
 pub enum StrictCellFace {
     #[default]
     FrontWall,
@@ -427,6 +474,14 @@ impl Gridmap {
 
         Some(id)
     }
+    /// Whether a cell lies within the map limits, [Gridmap::get_indexes] only supports these.
+    pub fn within_limits(&self, id: Vec3Int) -> bool {
+        let map_half_length = ((self.map_length_limit.length as f32 * CHUNK_CUBIC_LENGTH as f32)
+            * 0.5)
+            .floor() as i16;
+        let within = |c: i16| c >= -map_half_length && c < map_half_length;
+        within(id.x) && within(id.y) && within(id.z)
+    }
     pub fn get_strict_cell(&self, cell: TargetCell) -> StrictCell {
         let mut adjusted_id = cell.id.clone();
         let adjusted_face;
@@ -482,6 +537,37 @@ impl Gridmap {
         }
     }
 
+    pub fn get_cell_mut(&mut self, cell: TargetCell) -> Option<&mut CellItem> {
+        let strict = self.get_strict_cell(cell);
+
+        let indexes = self.get_indexes(strict.id);
+
+        match self.grid.get_mut(indexes.chunk) {
+            Some(Some(chunk)) => match chunk.cells.get_mut(indexes.cell) {
+                Some(Some(items)) => match strict.face {
+                    StrictCellFace::FrontWall => items.front_wall.as_mut(),
+                    StrictCellFace::RightWall => items.right_wall.as_mut(),
+                    StrictCellFace::Floor => items.floor.as_mut(),
+                    StrictCellFace::Center => items.center.as_mut(),
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Properties of a tile type of a layer.
+    pub fn get_cell_properties(
+        &self,
+        layer: GridmapLayer,
+        tile_type: u16,
+    ) -> Option<&TileProperties> {
+        match layer {
+            GridmapLayer::Main => self.main_cell_properties.get(&tile_type),
+            GridmapLayer::Details1 => self.details1_cell_properties.get(&tile_type),
+        }
+    }
+
     pub fn get_cell_transform(&self, cell: TargetCell, orientation: u8) -> Transform {
         let strict = self.get_strict_cell(cell);
 
@@ -557,12 +643,14 @@ pub struct CellUpdate {
 
 pub struct Cell {
     pub id: Vec3Int,
+    pub face: CellFace,
 }
 
 impl Default for Cell {
     fn default() -> Self {
         Self {
             id: Vec3Int { x: 0, y: 0, z: 0 },
+            face: CellFace::default(),
         }
     }
 }
@@ -835,11 +923,19 @@ pub(crate) fn remove_tile_net(
 pub(crate) fn add_cell_client(
     mut net: EventReader<IncomingReliableServerMessage<GridmapServerMessage>>,
     mut event: EventWriter<AddTile>,
+    mut remove: EventWriter<RemoveTile>,
+    gridmap: Res<Gridmap>,
     mut commands: Commands,
 ) {
     for message in net.iter() {
         match &message.message {
             GridmapServerMessage::AddCell(new) => {
+                // Replaced tiles, like broken walls, arrive without a prior removal for late joiners.
+                if gridmap.get_cell(new.cell.clone()).is_some() {
+                    remove.send(RemoveTile {
+                        cell: new.cell.clone(),
+                    });
+                }
                 event.send(AddTile {
                     id: new.cell.id,
                     tile_type: new.tile_type,
@@ -867,23 +963,14 @@ pub(crate) fn remove_cell_client(
     }
 }
 
-pub(crate) fn add_tile_collision(
+/// Give cell entities their cell data and position. Their collision is part of the merged [crate::chunk_collider::ChunkCollider] bodies.
+
+pub(crate) fn add_tile_entity(
     mut events: EventReader<AddTile>,
     mut commands: Commands,
     gridmap_data: Res<Gridmap>,
 ) {
     for event in events.iter() {
-        let cell_properties;
-        match gridmap_data.main_cell_properties.get(&event.tile_type) {
-            Some(x) => {
-                cell_properties = x;
-            }
-            None => {
-                warn!("Unknown cellid {}. Initialization of gridmap cell in startup gridmap systems missing.", event.tile_type);
-                return;
-            }
-        }
-
         let world_position = gridmap_data.get_cell_transform(
             TargetCell {
                 id: event.id,
@@ -891,37 +978,17 @@ pub(crate) fn add_tile_collision(
             },
             event.orientation,
         );
-        let mut collider_position = Transform::IDENTITY;
-        collider_position.translation += cell_properties.collider_position.translation;
-        collider_position.rotation *= cell_properties.collider_position.rotation;
 
-        let mut entity_builder = commands.entity(event.entity);
-        entity_builder
-            .insert(RigidBody::Fixed)
-            .insert(Cell { id: event.id });
-
-        if is_server() {
-            entity_builder.insert(TransformBundle {
+        commands
+            .entity(event.entity)
+            .insert(Cell {
+                id: event.id,
+                face: event.face.clone(),
+            })
+            .insert(TransformBundle {
                 local: world_position,
                 ..Default::default()
             });
-        }
-
-        let masks = get_bit_masks(ColliderGroup::Standard);
-
-        let mut friction_component = Friction::coefficient(cell_properties.friction);
-        friction_component.combine_rule = cell_properties.combine_rule;
-
-        entity_builder.with_children(|children| {
-            children
-                .spawn(cell_properties.collider.clone())
-                .insert(collider_position)
-                .insert(friction_component)
-                .insert(CollisionGroups::new(
-                    Group::from_bits(masks.0).unwrap(),
-                    Group::from_bits(masks.1).unwrap(),
-                ));
-        });
     }
 }
 
@@ -3,9 +3,12 @@ use std::{collections::HashMap, fs, path::Path};
 use bevy::prelude::{info, warn, AssetServer, Commands, EventWriter, Res, ResMut, Transform};
 use bevy_rapier3d::plugin::{RapierConfiguration, TimestepMode};
 use entity::examine::RichName;
+use resources::maps::current_map_name;
 use resources::math::Vec3Int;
 use resources::{core::TickRate, grid::CellFace, is_server::is_server};
 
+use crate::details1::AddDetails1Tile;
+use crate::graphics::load_tile_mesh;
 use crate::grid::{AddGroup, AddTile, CellType, Gridmap, TileProperties};
 
 /// Physics friction on placeable item surfaces.
@@ -28,10 +31,13 @@ pub(crate) fn startup_map_tile_properties(
     default_isometry.translation.y = -0.5;
 
     let mesh_option;
+    let tile_mesh_option;
     if !is_server() {
         mesh_option = Some(assets.load("models/wall/wall.glb#Scene0"));
+        tile_mesh_option = Some(load_tile_mesh(&assets, "models/wall/wall.glb"));
     } else {
         mesh_option = None;
+        tile_mesh_option = None;
     }
     main_cells_data.push(TileProperties {
         id: *gridmap_data.main_name_id_map.get("generic_wall_1").unwrap(),
@@ -43,7 +49,11 @@ pub(crate) fn startup_map_tile_properties(
         description: "A generic wall tile.".to_string(),
         constructable: true,
         mesh_option,
+        model_option: Some("models/wall/wall.glb".to_string()),
+        tile_mesh_option,
         cell_type: CellType::Wall,
+        damage_threshold: Some(150.),
+        broken_tile_option: Some(*gridmap_data.main_name_id_map.get("girder_1").unwrap()),
         ..Default::default()
     });
     let mut wall_group = HashMap::new();
@@ -63,10 +73,13 @@ pub(crate) fn startup_map_tile_properties(
         .id_group_map
         .insert(0, "generic_wall_group_1".to_string());
     let mesh_option;
+    let tile_mesh_option;
     if !is_server() {
         mesh_option = Some(assets.load("models/floor/floor.glb#Scene0"));
+        tile_mesh_option = Some(load_tile_mesh(&assets, "models/floor/floor.glb"));
     } else {
         mesh_option = None;
+        tile_mesh_option = None;
     }
     main_cells_data.push(TileProperties {
         id: *gridmap_data
@@ -82,7 +95,76 @@ pub(crate) fn startup_map_tile_properties(
         constructable: true,
         floor_cell: true,
         mesh_option,
+        model_option: Some("models/floor/floor.glb".to_string()),
+        tile_mesh_option,
         cell_type: CellType::Floor,
+        damage_threshold: Some(150.),
+        broken_tile_option: Some(
+            *gridmap_data
+                .main_name_id_map
+                .get("floor_plating_1")
+                .unwrap(),
+        ),
+        ..Default::default()
+    });
+
+    let mesh_option;
+    let tile_mesh_option;
+    if !is_server() {
+        mesh_option = Some(assets.load("models/wall/wall.glb#Scene0"));
+        tile_mesh_option = Some(load_tile_mesh(&assets, "models/wall/wall.glb"));
+    } else {
+        mesh_option = None;
+        tile_mesh_option = None;
+    }
+    main_cells_data.push(TileProperties {
+        id: *gridmap_data.main_name_id_map.get("girder_1").unwrap(),
+        name: RichName {
+            name: "girder".to_string(),
+            n: false,
+            the: false,
+        },
+        description: "The bare frame of a destroyed wall.".to_string(),
+        constructable: true,
+        combat_obstacle: false,
+        laser_combat_obstacle: false,
+        atmospherics_blocker: false,
+        non_fov_blocker: true,
+        mesh_option,
+        model_option: Some("models/wall/wall.glb".to_string()),
+        tile_mesh_option,
+        cell_type: CellType::Wall,
+        damage_threshold: Some(100.),
+        ..Default::default()
+    });
+
+    let mesh_option;
+    let tile_mesh_option;
+    if !is_server() {
+        mesh_option = Some(assets.load("models/floor/floor.glb#Scene0"));
+        tile_mesh_option = Some(load_tile_mesh(&assets, "models/floor/floor.glb"));
+    } else {
+        mesh_option = None;
+        tile_mesh_option = None;
+    }
+    main_cells_data.push(TileProperties {
+        id: *gridmap_data
+            .main_name_id_map
+            .get("floor_plating_1")
+            .unwrap(),
+        name: RichName {
+            name: "floor plating".to_string(),
+            n: false,
+            the: false,
+        },
+        description: "Exposed plating of a destroyed floor.".to_string(),
+        constructable: true,
+        floor_cell: true,
+        mesh_option,
+        model_option: Some("models/floor/floor.glb".to_string()),
+        tile_mesh_option,
+        cell_type: CellType::Floor,
+        damage_threshold: Some(100.),
         ..Default::default()
     });
 
@@ -126,6 +208,53 @@ pub(crate) fn startup_map_tile_properties(
     }
 
     info!("Loaded {} gridmap cell types.", main_cells_data.len());
+
+    let mut details1_cells_data = vec![];
+
+    let mesh_option;
+    if !is_server() {
+        mesh_option = Some(assets.load("models/cable/cable.glb#Scene0"));
+    } else {
+        mesh_option = None;
+    }
+    details1_cells_data.push(TileProperties {
+        id: *gridmap_data
+            .details1_name_id_map
+            .get("power_cable_1")
+            .unwrap(),
+        name: RichName {
+            name: "power cable".to_string(),
+            n: false,
+            the: false,
+        },
+        description: "A cable that carries electricity between power devices.".to_string(),
+        constructable: true,
+        combat_obstacle: false,
+        laser_combat_obstacle: false,
+        atmospherics_blocker: false,
+        non_fov_blocker: true,
+        mesh_option,
+        model_option: Some("models/cable/cable.glb".to_string()),
+        cell_type: CellType::Floor,
+        ..Default::default()
+    });
+
+    for cell_properties in details1_cells_data.iter() {
+        gridmap_data
+            .details1_text_names
+            .insert(cell_properties.id, cell_properties.name.clone());
+        gridmap_data
+            .details1_text_examine_desc
+            .insert(cell_properties.id, cell_properties.description.clone());
+        gridmap_data
+            .details1_cell_properties
+            .insert(cell_properties.id, cell_properties.clone());
+    }
+
+    info!(
+        "Loaded {} gridmap details1 cell types.",
+        details1_cells_data.len()
+    );
 }
 use player::spawn_points::SpawnPointRon;
 
@@ -147,7 +276,7 @@ pub(crate) fn startup_misc_resources(
 
     let mainordered_cells_ron = Path::new("data")
         .join("maps")
-        .join("bullseye")
+        .join(current_map_name())
         .join("mainordered.ron");
     let current_map_mainordered_cells_raw_ron: String = fs::read_to_string(mainordered_cells_ron)
         .expect("Error reading map mainordered.ron drive.");
@@ -166,9 +295,31 @@ pub(crate) fn startup_misc_resources(
 
     gridmap_data.ordered_main_names = current_map_mainordered_cells;
 
+    let details1ordered_cells_ron = Path::new("data")
+        .join("maps")
+        .join(current_map_name())
+        .join("details1ordered.ron");
+    let current_map_details1ordered_cells_raw_ron: String =
+        fs::read_to_string(details1ordered_cells_ron)
+            .expect("Error reading map details1ordered.ron drive.");
+    let current_map_details1ordered_cells: Vec<String> =
+        ron::from_str(&current_map_details1ordered_cells_raw_ron)
+            .expect("Error parsing map details1ordered.ron String.");
+
+    for (i, name) in current_map_details1ordered_cells.iter().rev().enumerate() {
+        gridmap_data
+            .details1_name_id_map
+            .insert(name.to_string(), i as u16);
+        gridmap_data
+            .details1_id_name_map
+            .insert(i as u16, name.to_string());
+    }
+
+    gridmap_data.ordered_details1_names = current_map_details1ordered_cells;
+
     let spawnpoints_ron = Path::new("data")
         .join("maps")
-        .join("bullseye")
+        .join(current_map_name())
         .join("spawnpoints.ron");
     let current_map_spawn_points_raw_ron: String =
         fs::read_to_string(spawnpoints_ron).expect("Error reading map spawnpoints.ron from drive.");
@@ -191,12 +342,13 @@ pub(crate) fn load_ron_gridmap(
     gridmap_data: Res<Gridmap>,
     mut set_cell: EventWriter<AddTile>,
     mut set_group: EventWriter<AddGroup>,
+    mut set_details1: EventWriter<AddDetails1Tile>,
     mut commands: Commands,
 ) {
     // Load map json data into real static bodies.
     let main_ron = Path::new("data")
         .join("maps")
-        .join("bullseye")
+        .join(current_map_name())
         .join("main.ron");
     let current_map_main_raw_ron: String = fs::read_to_string(main_ron)
         .expect("startup_build_map() Error reading map main.ron file from drive.");
@@ -259,6 +411,48 @@ pub(crate) fn load_ron_gridmap(
     }
 
     info!("Spawned {} map cells.", current_map_main_data.len());
+
+    let details1_ron = Path::new("data")
+        .join("maps")
+        .join(current_map_name())
+        .join("details1.ron");
+    let current_map_details1_raw_ron: String = fs::read_to_string(details1_ron)
+        .expect("startup_build_map() Error reading map details1.ron file from drive.");
+
+    if current_map_details1_raw_ron.len() == 0 {
+        warn!("Empty details1.ron map file.");
+        return;
+    }
+
+    let current_map_details1_data: Vec<CellDataRon> = ron::from_str(&current_map_details1_raw_ron)
+        .expect("startup_build_map() Error parsing map details1.ron String.");
+
+    for cell_data in current_map_details1_data.iter() {
+        match &cell_data.item {
+            RonItem::Cell(item) => match gridmap_data.details1_name_id_map.get(item) {
+                Some(cell_item_id) => {
+                    set_details1.send(AddDetails1Tile {
+                        id: cell_data.id,
+                        tile_type: *cell_item_id,
+                        orientation: cell_data.orientation,
+                        entity: commands.spawn(()).id(),
+                        default_map_spawn: true,
+                    });
+                }
+                None => {
+                    warn!("Couldnt find details1 item {}", item);
+                }
+            },
+            RonItem::Group(item) => {
+                warn!("Groups are not supported on the details1 layer: {}", item);
+            }
+        }
+    }
+
+    info!(
+        "Spawned {} map details1 cells.",
+        current_map_details1_data.len()
+    );
 }
 
 use player::boarding::{SpawnPoint, SpawnPoints};
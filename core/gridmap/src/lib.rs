@@ -9,9 +9,19 @@
 
 /// Check if an entity can reach another entity.
 pub mod can_reach_entity;
+/// Merged physics colliders of gridmap chunks.
+pub mod chunk_collider;
+/// Merge tile meshes of gridmap chunks on the CPU.
+pub mod chunk_mesh;
 /// Configuration to send to newly connected clients.
 pub mod connections;
 pub mod construction;
+/// Damage and destruction of gridmap tiles.
+pub mod destruction;
+/// The details1 layer of the gridmap.
+pub mod details1;
+/// Map editor operations with undo history.
+pub mod editor;
 /// Manage gridmap exmination.
 pub mod examine;
 /// Manage gridmap FOV.
@@ -29,4 +39,7 @@ pub mod net;
 pub mod plugin;
 /// Manage sensing authorization for gridmap examining.
 mod sensing_ability;
+/// Fixtures shared by the tests of this crate.
+#[cfg(test)]
+mod test_utils;
 pub mod wall;
@@ -1,5 +1,6 @@
 use bevy::prelude::Vec3;
-use resources::grid::TargetCell;
+use resources::grid::{CellFace, TargetCell};
+use resources::math::Vec3Int;
 use serde::Deserialize;
 use serde::Serialize;
 use typename::TypeName;
@@ -11,6 +12,20 @@ pub enum GridmapClientMessage {
     ExamineMap(i16, i16, i16),
     ConstructCell(ConstructCell),
     DeconstructCell(DeconstructCell),
+    EditorFill(EditorFill),
+    EditorFloodFill(EditorFloodFill),
+    EditorCopy(EditorCopy),
+    EditorPaste(EditorPaste),
+    EditorUndo,
+    EditorRedo,
+    PlaceEntity(PlaceEntity),
+    RemoveEntity(Vec3Int),
+}
+/// Place the entity type selected with the construction tool on a cell.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlaceEntity {
+    pub cell: Vec3Int,
+    pub orientation: u8,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ConstructCell {
@@ -23,10 +38,53 @@ pub struct NewCell {
     pub orientation: u8,
     pub tile_type: u16,
 }
+/// A new cell on the details1 layer.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewDetails1Cell {
+    pub id: Vec3Int,
+    pub orientation: u8,
+    pub tile_type: u16,
+}
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DeconstructCell {
     pub cell: TargetCell,
 }
+/// Shape of an editor fill between two cells.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorFillShape {
+    Box,
+    Line,
+}
+/// Construct or deconstruct a tile on every cell of a box or line.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EditorFill {
+    pub from: Vec3Int,
+    pub to: Vec3Int,
+    pub shape: EditorFillShape,
+    pub face: CellFace,
+    pub orientation: u8,
+    /// Deconstruct the tiles instead.
+    pub remove: bool,
+}
+/// Fill the floors of the area enclosed by walls around a cell.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EditorFloodFill {
+    pub start: Vec3Int,
+    pub orientation: u8,
+}
+/// Copy the tiles of a box to the clipboard of the editor.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EditorCopy {
+    pub from: Vec3Int,
+    pub to: Vec3Int,
+}
+/// Paste the clipboard of the editor.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EditorPaste {
+    pub origin: Vec3Int,
+    /// Quarter turns around the y-axis.
+    pub rotation: u8,
+}
 
 /// Gets serialized and sent over the net, this is the server message.
 #[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
@@ -34,6 +92,8 @@ pub struct DeconstructCell {
 pub enum GridmapServerMessage {
     RemoveCell(TargetCell),
     AddCell(NewCell),
+    RemoveDetails1Cell(Vec3Int),
+    AddDetails1Cell(NewDetails1Cell),
     FireProjectile(ProjectileData),
     ConfigBlackCellID(u16, u16),
     ConfigOrderedCellsMain(Vec<String>),
@@ -41,6 +101,8 @@ pub enum GridmapServerMessage {
     ConfigPlaceableItemsSurfaces(Vec<u16>),
     ConfigNonBlockingCells(Vec<u16>),
     GhostCellType(u16),
+    GhostDetails1CellType(u16),
+    GhostEntityType(String),
 }
 
 /// Contains information about the projectile and its visual graphics.
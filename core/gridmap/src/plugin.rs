@@ -12,6 +12,7 @@ use resources::{
 };
 
 use crate::{
+    chunk_collider::{build_chunk_colliders, mark_dirty_chunk_colliders, ChunkColliders},
     connections::configure,
     construction::{
         change_ghost_tile_request, client_mouse_click_input, create_select_cell_cam_state,
@@ -19,16 +20,28 @@ use crate::{
         select_cell_in_front_camera, set_yplane_position, show_ylevel_plane, update_ghost_cell,
         ConstructionCellSelectionChanged, GhostTileLabel, SetYPlanePosition,
     },
+    destruction::{
+        damage_tiles, seal_hull_breaches, DamageTile, HullBreach, HullBreachSealed, HullBreaches,
+        TileBroken,
+    },
+    details1::{
+        add_details1_tile, details1_cell_client, details1_tile_net, remove_details1_tile,
+        set_details1_cell_graphics, AddDetails1Tile, RemoveDetails1Tile,
+    },
+    editor::{
+        apply_map_edits, editor_key_input, editor_mouse_click_input, register_editor_input,
+        ApplyMapEdits, MapEditorHistories, MapEditorState, RedoMapEdit, UndoMapEdit,
+    },
     examine::{
-        examine_grid, examine_map, examine_map_abilities, examine_map_health, finalize_examine_map,
-        finalize_grid_examine_input, incoming_messages, set_action_header_name,
-        GridmapExamineMessages, InputExamineMap,
+        examine_grid, examine_map, examine_map_abilities, examine_map_details1, examine_map_health,
+        finalize_examine_map, finalize_grid_examine_input, incoming_messages,
+        set_action_header_name, GridmapExamineMessages, InputExamineMap,
     },
     fov::ProjectileFOV,
-    graphics::set_cell_graphics,
+    graphics::{build_chunk_meshes, load_content_tile_meshes, mark_dirty_chunks, ChunkMeshes},
     grid::{
-        add_cell_client, add_tile, add_tile_collision, add_tile_net, remove_cell_client,
-        remove_tile, remove_tile_net, AddGroup, AddTile, Gridmap, RemoveTile,
+        add_cell_client, add_tile, add_tile_entity, add_tile_net, remove_cell_client, remove_tile,
+        remove_tile_net, AddGroup, AddTile, Gridmap, RemoveTile,
     },
     init::{load_ron_gridmap, startup_map_tile_properties, startup_misc_resources},
     net::{GridmapClientMessage, GridmapServerMessage},
@@ -56,6 +69,7 @@ impl Plugin for GridmapPlugin {
                 .add_system(examine_map.after(ActionsLabels::Action))
                 .add_system(examine_map_health.after(ActionsLabels::Action))
                 .add_system(examine_map_abilities.after(ActionsLabels::Action))
+                .add_system(examine_map_details1.after(ActionsLabels::Action))
                 .add_event::<ProjectileFOV>()
                 .add_system(finalize_grid_examine_input.in_base_set(CoreSet::PreUpdate))
                 .add_system(incoming_messages.in_base_set(CoreSet::PreUpdate))
@@ -72,10 +86,32 @@ impl Plugin for GridmapPlugin {
                         .in_set(ConfigurationLabel::Main)
                         .after(ConfigurationLabel::SpawnEntity),
                 )
-                .add_system(add_tile_net)
-                .add_system(remove_tile_net);
+                .add_system(add_tile_net.after(remove_tile_net))
+                .add_system(remove_tile_net)
+                .add_system(details1_tile_net)
+                .add_system(damage_tiles.before(remove_tile))
+                .add_system(seal_hull_breaches)
+                .add_event::<DamageTile>()
+                .add_event::<TileBroken>()
+                .add_event::<HullBreach>()
+                .add_event::<HullBreachSealed>()
+                .init_resource::<HullBreaches>()
+                .init_resource::<MapEditorHistories>()
+                .add_event::<ApplyMapEdits>()
+                .add_event::<UndoMapEdit>()
+                .add_event::<RedoMapEdit>()
+                .add_system(apply_map_edits.before(remove_tile));
         } else {
-            app.add_system(set_cell_graphics)
+            app.init_resource::<ChunkMeshes>()
+                .add_system(mark_dirty_chunks)
+                .add_system(load_content_tile_meshes)
+                .add_system(
+                    build_chunk_meshes
+                        .after(mark_dirty_chunks)
+                        .after(load_content_tile_meshes)
+                        .after(add_tile)
+                        .after(remove_tile),
+                )
                 .add_startup_system(create_select_cell_cam_state)
                 .add_event::<SetYPlanePosition>()
                 .add_system(show_ylevel_plane)
@@ -91,9 +127,15 @@ impl Plugin for GridmapPlugin {
                 .add_system(change_ghost_tile_request)
                 .add_system(input_ghost_rotation.after(GhostTileLabel::Update))
                 .add_system(client_mouse_click_input)
-                .add_system(add_cell_client)
-                .add_system(remove_cell_client)
-                .add_startup_system(register_input);
+                .add_system(add_cell_client.before(remove_tile))
+                .add_system(remove_cell_client.before(remove_tile))
+                .add_system(details1_cell_client)
+                .add_system(set_details1_cell_graphics)
+                .add_startup_system(register_input)
+                .init_resource::<MapEditorState>()
+                .add_startup_system(register_editor_input)
+                .add_system(editor_key_input)
+                .add_system(editor_mouse_click_input);
         }
 
         app.add_startup_system(startup_misc_resources.in_set(StartupLabels::MiscResources))
@@ -110,13 +152,25 @@ impl Plugin for GridmapPlugin {
             )
             .init_resource::<Gridmap>()
             .init_resource::<DoryenMap>()
-            .add_system(add_tile)
+            .add_system(add_tile.after(remove_tile))
             .add_event::<AddTile>()
             .add_event::<AddGroup>()
             .add_system(add_wall_group)
-            .add_system(add_tile_collision)
+            .add_system(add_tile_entity)
+            .init_resource::<ChunkColliders>()
+            .add_system(mark_dirty_chunk_colliders)
+            .add_system(
+                build_chunk_colliders
+                    .after(mark_dirty_chunk_colliders)
+                    .after(add_tile)
+                    .after(remove_tile),
+            )
             .add_system(remove_tile)
-            .add_event::<RemoveTile>();
+            .add_event::<RemoveTile>()
+            .add_system(add_details1_tile)
+            .add_system(remove_details1_tile)
+            .add_event::<AddDetails1Tile>()
+            .add_event::<RemoveDetails1Tile>();
 
         register_reliable_message::<GridmapClientMessage>(app, MessageSender::Client);
         register_reliable_message::<GridmapServerMessage>(app, MessageSender::Server);
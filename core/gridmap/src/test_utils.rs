@@ -0,0 +1,40 @@
+use resources::{
+    grid::{CellFace, TargetCell},
+    math::Vec3Int,
+};
+
+use crate::grid::{CellItem, GridCell, Gridmap, GridmapChunk, StrictCellFace};
+
+/// Place a tile in a cell of the main layer.
+
+pub(crate) fn set_tile(
+    gridmap: &mut Gridmap,
+    id: Vec3Int,
+    face: CellFace,
+    tile_type: u16,
+    orientation: u8,
+) {
+    let strict = gridmap.get_strict_cell(TargetCell { id, face });
+    let indexes = gridmap.get_indexes(strict.id);
+    let chunk = gridmap.grid[indexes.chunk].get_or_insert_with(GridmapChunk::default);
+    let cell = chunk.cells[indexes.cell].get_or_insert_with(GridCell::default);
+    let item = Some(CellItem {
+        tile_type,
+        orientation,
+        ..Default::default()
+    });
+    match strict.face {
+        StrictCellFace::Floor => {
+            cell.floor = item;
+        }
+        StrictCellFace::FrontWall => {
+            cell.front_wall = item;
+        }
+        StrictCellFace::RightWall => {
+            cell.right_wall = item;
+        }
+        StrictCellFace::Center => {
+            cell.center = item;
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use actions::net::{ActionProgress, ActionsServerMessage};
+use bevy::{
+    prelude::{
+        BuildChildren, Camera, Color, Commands, Component, EventReader, GlobalTransform,
+        NodeBundle, Query, Res, ResMut, Resource, Vec3, With, Without,
+    },
+    time::{Time, Timer, TimerMode},
+    ui::{Display, PositionType, Size, Style, UiRect, Val},
+};
+use cameras::controllers::fps::ActiveCamera;
+use entity::spawn::ClientEntityServerEntity;
+use networking::client::IncomingReliableServerMessage;
+use resources::math::cell_id_to_world;
+
+/// Width of the progress bar in pixels.
+
+const PROGRESS_BAR_WIDTH: f32 = 80.;
+/// Height of the progress bar in pixels.
+
+const PROGRESS_BAR_HEIGHT: f32 = 8.;
+
+/// The progress bar shown over the target of a timed action.
+#[derive(Component)]
+
+pub struct ActionProgressBar;
+
+/// The filling part of [ActionProgressBar].
+#[derive(Component)]
+
+pub struct ActionProgressFill;
+
+/// Resource with the timed action the player is currently performing.
+#[derive(Resource, Default)]
+
+pub struct ActionProgressState {
+    pub progress_option: Option<ActionProgress>,
+    pub timer: Timer,
+}
+
+pub(crate) fn build_action_progress_bar(mut commands: Commands) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Px(PROGRESS_BAR_WIDTH), Val::Px(PROGRESS_BAR_HEIGHT)),
+                display: Display::None,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.6).into(),
+            ..Default::default()
+        })
+        .insert(ActionProgressBar)
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(0.), Val::Percent(100.)),
+                        ..Default::default()
+                    },
+                    background_color: Color::rgb(0.26, 0.72, 0.32).into(),
+                    ..Default::default()
+                })
+                .insert(ActionProgressFill);
+        });
+}
+
+/// Start and stop showing action progress from the server.
+
+pub(crate) fn receive_action_progress(
+    mut net: EventReader<IncomingReliableServerMessage<ActionsServerMessage>>,
+    mut state: ResMut<ActionProgressState>,
+) {
+    for message in net.iter() {
+        match &message.message {
+            ActionsServerMessage::ActionProgress(progress) => {
+                state.timer = Timer::from_seconds(progress.duration, TimerMode::Once);
+                state.progress_option = Some(progress.clone());
+            }
+            ActionsServerMessage::ActionProgressEnded => {
+                state.progress_option = None;
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Fill the progress bar and keep it over the target of the action.
+
+pub(crate) fn update_action_progress_bar(
+    mut state: ResMut<ActionProgressState>,
+    time: Res<Time>,
+    active_camera: Res<ActiveCamera>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    transforms: Query<&GlobalTransform, Without<Camera>>,
+    map: Res<ClientEntityServerEntity>,
+    mut bars: Query<&mut Style, With<ActionProgressBar>>,
+    mut fills: Query<&mut Style, (With<ActionProgressFill>, Without<ActionProgressBar>)>,
+) {
+    let mut bar_style;
+    match bars.get_single_mut() {
+        Ok(s) => {
+            bar_style = s;
+        }
+        Err(_) => {
+            return;
+        }
+    }
+
+    let progress;
+    match &state.progress_option {
+        Some(p) => {
+            progress = p.clone();
+        }
+        None => {
+            bar_style.display = Display::None;
+            return;
+        }
+    }
+    state.timer.tick(time.delta());
+
+    let target_position: Vec3;
+    match progress.target_entity_option {
+        Some(server_entity) => match map.map.get(&server_entity) {
+            Some(client_entity) => match transforms.get(*client_entity) {
+                Ok(t) => {
+                    target_position = t.translation();
+                }
+                Err(_) => {
+                    bar_style.display = Display::None;
+                    return;
+                }
+            },
+            None => {
+                bar_style.display = Display::None;
+                return;
+            }
+        },
+        None => match &progress.target_cell_option {
+            Some(cell) => {
+                target_position = cell_id_to_world(cell.id);
+            }
+            None => {
+                bar_style.display = Display::None;
+                return;
+            }
+        },
+    }
+
+    let viewport_position;
+    match active_camera.option {
+        Some(camera_entity) => match cameras.get(camera_entity) {
+            Ok((camera, camera_transform)) => {
+                viewport_position = camera.world_to_viewport(camera_transform, target_position);
+            }
+            Err(_) => {
+                return;
+            }
+        },
+        None => {
+            return;
+        }
+    }
+
+    match viewport_position {
+        Some(position) => {
+            bar_style.display = Display::Flex;
+            bar_style.position = UiRect {
+                left: Val::Px(position.x - PROGRESS_BAR_WIDTH / 2.),
+                bottom: Val::Px(position.y + PROGRESS_BAR_HEIGHT * 2.),
+                ..Default::default()
+            };
+        }
+        None => {
+            bar_style.display = Display::None;
+        }
+    }
+
+    for mut fill_style in fills.iter_mut() {
+        fill_style.size.width = Val::Percent(state.timer.percent() * 100.);
+    }
+}
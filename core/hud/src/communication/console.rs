@@ -10,11 +10,16 @@ use bevy::{
     text::{TextSection, TextStyle},
     ui::{FlexDirection, Size, Style, Val},
 };
-use console_commands::net::{
-    ClientSideConsoleInput, ConsoleCommandsClientMessage, ConsoleCommandsServerMessage,
+use console_commands::{
+    commands::AllConsoleCommands,
+    net::{ClientSideConsoleInput, ConsoleCommandsClientMessage, ConsoleCommandsServerMessage},
+    networking::ServerConsoleCommands,
 };
 use networking::client::{IncomingReliableServerMessage, OutgoingReliableClientMessage};
-use ui::fonts::{Fonts, SOURCECODE_REGULAR_FONT};
+use ui::{
+    fonts::{Fonts, SOURCECODE_REGULAR_FONT},
+    text::CONSOLE_ERROR_COLOR,
+};
 
 use super::build::{HudCommunicationState, CONSOLE_FONT_COLOR, MESSAGES_DEFAULT_MAX_WIDTH};
 
@@ -23,6 +28,8 @@ pub fn console_input(
     mut net: EventWriter<OutgoingReliableClientMessage<ConsoleCommandsClientMessage>>,
     mut display: EventWriter<DisplayConsoleMessage>,
     asset_server: Res<AssetServer>,
+    local_commands: Res<AllConsoleCommands>,
+    server_commands: Res<ServerConsoleCommands>,
 ) {
     for input in events.iter() {
         let source = asset_server.load(SOURCECODE_REGULAR_FONT);
@@ -30,7 +37,7 @@ pub fn console_input(
         let section = TextSection::new(
             input.to_string(),
             TextStyle {
-                font: source,
+                font: source.clone(),
                 font_size: 12.0,
                 color: CONSOLE_FONT_COLOR,
             },
@@ -40,6 +47,34 @@ pub fn console_input(
             sections: vec![section],
         });
 
+        if !server_commands
+            .list
+            .iter()
+            .any(|command| command.base == input.command)
+        {
+            // Commands only known to the client are handled locally.
+            if !local_commands
+                .list
+                .iter()
+                .any(|command| command.base == input.command)
+            {
+                display.send(DisplayConsoleMessage {
+                    sections: vec![TextSection::new(
+                        format!(
+                            "Unknown command {}. Type help for a list of commands.",
+                            input.command
+                        ),
+                        TextStyle {
+                            font: source,
+                            font_size: 12.0,
+                            color: CONSOLE_ERROR_COLOR,
+                        },
+                    )],
+                });
+            }
+            continue;
+        }
+
         net.send(OutgoingReliableClientMessage {
             message: ConsoleCommandsClientMessage::ConsoleCommand(input.clone()),
         });
@@ -1,19 +1,32 @@
 use bevy::{
-    prelude::{warn, Children, EventWriter, Input, KeyCode, Query, Res},
-    text::Text,
+    prelude::{warn, AssetServer, Children, EventWriter, Input, KeyCode, Query, Res},
+    text::{Text, TextSection, TextStyle},
 };
 use chat::net::ChatClientMessage;
-use console_commands::net::ClientSideConsoleInput;
+use console_commands::{
+    commands::{AllConsoleCommands, ConsoleCommand},
+    net::ClientSideConsoleInput,
+    networking::{ConsolePlayerNames, ServerConsoleCommands},
+    parse::{quote_console_word, split_console_input},
+};
+use entity::entity_types::EntityTypes;
 use networking::client::OutgoingReliableClientMessage;
 use resources::{binds::KeyBinds, hud::HudState, ui::TextInput};
-use ui::text_input::{FocusTextInput, TextInputNode, UnfocusTextInput};
+use ui::{
+    fonts::SOURCECODE_REGULAR_FONT,
+    text::COMMUNICATION_FONT_SIZE,
+    text_input::{FocusTextInput, SetText, TextInputNode, UnfocusTextInput},
+};
 
 use crate::{
     input::binds::{SUBMIT_CONSOLE_BIND, TOGGLE_CHAT},
     inventory::build::OpenHud,
 };
 
-use super::build::HudCommunicationState;
+use super::{
+    build::{HudCommunicationState, CONSOLE_FONT_COLOR},
+    console::DisplayConsoleMessage,
+};
 
 pub(crate) fn text_input(
     keyboard: Res<Input<KeyCode>>,
@@ -75,8 +88,13 @@ pub(crate) fn tab_communication_input_toggle(
     mut unfocus_event: EventWriter<UnfocusTextInput>,
     hud_state: Res<HudState>,
     binds: Res<KeyBinds>,
+    text_input_state: Res<TextInput>,
+    input_query: Query<&TextInputNode>,
 ) {
     if keys.just_pressed(binds.bind(TOGGLE_CHAT)) {
+        if is_completing_console_input(&state, &text_input_state, &input_query) {
+            return;
+        }
         let is_focused = hud_state.expanded;
 
         if is_focused {
@@ -92,3 +110,151 @@ pub(crate) fn tab_communication_input_toggle(
         open_hud.send(OpenHud { open: !is_focused });
     }
 }
+
+/// Whether the toggle chat bind should tab-complete the console input instead.
+
+fn is_completing_console_input(
+    state: &HudCommunicationState,
+    text_input_state: &TextInput,
+    input_query: &Query<&TextInputNode>,
+) -> bool {
+    if !state.is_displaying_console
+        || text_input_state.focused_input != Some(state.communication_input_node)
+    {
+        return false;
+    }
+    match input_query.get(state.communication_input_node) {
+        Ok(input_node) => !input_node.placeholder_active && !input_node.input.is_empty(),
+        Err(_) => false,
+    }
+}
+
+/// Tab-complete command names, entity types and player names in the console input.
+
+pub(crate) fn console_tab_completion(
+    keys: Res<Input<KeyCode>>,
+    binds: Res<KeyBinds>,
+    state: Res<HudCommunicationState>,
+    text_input_state: Res<TextInput>,
+    input_query: Query<&TextInputNode>,
+    local_commands: Res<AllConsoleCommands>,
+    server_commands: Res<ServerConsoleCommands>,
+    entity_types: Res<EntityTypes>,
+    player_names: Res<ConsolePlayerNames>,
+    mut set_text: EventWriter<SetText>,
+    mut display: EventWriter<DisplayConsoleMessage>,
+    asset_server: Res<AssetServer>,
+) {
+    if !keys.just_pressed(binds.bind(TOGGLE_CHAT))
+        || !is_completing_console_input(&state, &text_input_state, &input_query)
+    {
+        return;
+    }
+    let input;
+    match input_query.get(state.communication_input_node) {
+        Ok(input_node) => {
+            input = input_node.input.clone();
+        }
+        Err(_) => {
+            return;
+        }
+    }
+
+    let mut commands: Vec<&ConsoleCommand> = local_commands.list.iter().collect();
+    for command in server_commands.list.iter() {
+        if !commands.iter().any(|c| c.base == command.base) {
+            commands.push(command);
+        }
+    }
+
+    let mut words = split_console_input(&input);
+    if input.ends_with(char::is_whitespace) || words.is_empty() {
+        words.push(String::new());
+    }
+    let current = words.pop().unwrap();
+    let current_lower = current.to_lowercase();
+
+    let mut options: Vec<String> = vec![];
+    if words.is_empty() {
+        for command in commands.iter() {
+            options.push(command.base.clone());
+        }
+    } else {
+        match commands.iter().find(|c| c.base == words[0]) {
+            Some(command) => match command.args.get(words.len() - 1) {
+                Some(arg) => match arg.name.as_str() {
+                    "entity_name" => {
+                        for identifier in entity_types.types.keys() {
+                            options.push(identifier.clone());
+                        }
+                    }
+                    "player_selector" => {
+                        options.push("*".to_string());
+                        options.push("@me".to_string());
+                        for name in player_names.names.iter() {
+                            options.push(name.clone());
+                        }
+                    }
+                    _ => {}
+                },
+                None => {}
+            },
+            None => {}
+        }
+    }
+
+    let mut candidates: Vec<String> = options
+        .into_iter()
+        .filter(|option| option.to_lowercase().starts_with(&current_lower))
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+
+    if candidates.is_empty() {
+        return;
+    }
+
+    let completed;
+    if candidates.len() == 1 {
+        completed = quote_console_word(&candidates[0]) + " ";
+    } else {
+        let mut prefix = candidates[0].clone();
+        for candidate in candidates.iter().skip(1) {
+            while !candidate.to_lowercase().starts_with(&prefix.to_lowercase()) {
+                prefix.pop();
+            }
+        }
+        if prefix.len() < current.len() {
+            prefix = current.clone();
+        }
+        if prefix.contains(char::is_whitespace) {
+            // Leave the quote open so the word keeps growing with the next completion.
+            completed = format!("\"{}", prefix);
+        } else {
+            completed = prefix;
+        }
+
+        display.send(DisplayConsoleMessage {
+            sections: vec![TextSection::new(
+                candidates.join("  "),
+                TextStyle {
+                    font: asset_server.load(SOURCECODE_REGULAR_FONT),
+                    font_size: COMMUNICATION_FONT_SIZE,
+                    color: CONSOLE_FONT_COLOR,
+                },
+            )],
+        });
+    }
+
+    let mut text = String::new();
+    for word in words.iter() {
+        text += &quote_console_word(word);
+        text += " ";
+    }
+    text += &completed;
+
+    set_text.send(SetText {
+        entity: state.communication_input_node,
+        text,
+    });
+}
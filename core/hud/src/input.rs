@@ -1,2 +1,3 @@
 pub mod binds;
+pub mod text_input_request;
 pub mod text_tree_selection;
@@ -5,6 +5,7 @@ pub const TOGGLE_CONSOLE_BIND: &str = "toggleConsole";
 pub const SUBMIT_CONSOLE_BIND: &str = "submitConsoleInput";
 pub const TOGGLE_CHAT: &str = "toggleChat";
 pub const TOGGLE_INVENTORY: &str = "toggleInventoryHud";
+pub const TOGGLE_MAP: &str = "toggleMapHud";
 
 pub(crate) fn register_input(mut binds: ResMut<KeyBinds>) {
     binds.list.insert(
@@ -39,4 +40,12 @@ pub(crate) fn register_input(mut binds: ResMut<KeyBinds>) {
             name: "Toggle Inventory HUD".to_string(),
         },
     );
+    binds.list.insert(
+        TOGGLE_MAP.to_string(),
+        KeyBind {
+            key_code: KeyCode::M,
+            description: "Toggles the map of the ship.".to_string(),
+            name: "Toggle Map".to_string(),
+        },
+    );
 }
@@ -0,0 +1,256 @@
+use bevy::{
+    prelude::{
+        warn, AssetServer, BuildChildren, Button, ButtonBundle, Changed, Color, Commands,
+        Component, DespawnRecursiveExt, Entity, EventReader, EventWriter, NodeBundle, Query, Res,
+        ResMut, TextBundle, With,
+    },
+    text::TextStyle,
+    ui::{AlignItems, FlexDirection, Interaction, JustifyContent, Size, Style, Val},
+};
+use networking::client::{IncomingReliableServerMessage, OutgoingReliableClientMessage};
+use resources::hud::HudState;
+use ui::{
+    fonts::{ARIZONE_FONT, EMPIRE_FONT},
+    net::{TextInputRequest, TextInputResponse, UiClientMessage, UiServerMessage},
+    text_input::{CharacterFilter, TextInputNode},
+};
+
+use crate::{
+    inventory::{
+        actions::{ACTIONS_HUD_BG_COLOR, INVENTORY_HUD_BG_COLOR},
+        build::{InventoryHudState, OpenInventoryHud},
+    },
+    style::button::ButtonSelectionStyle,
+};
+
+use super::text_tree_selection::TextTreeInputSelectionState;
+
+/// The text input node of a server input request.
+#[derive(Component)]
+pub struct TextInputRequestNode;
+
+/// Submit button of a server input request.
+#[derive(Component)]
+pub struct TextInputRequestSubmitButton {
+    pub request: TextInputRequest,
+    pub input_node: Entity,
+}
+
+/// Open the hud when the server sends a menu or an input request.
+
+pub(crate) fn open_hud_for_server_ui(
+    mut events: EventReader<IncomingReliableServerMessage<UiServerMessage>>,
+    state: Option<Res<InventoryHudState>>,
+    mut open: EventWriter<OpenInventoryHud>,
+) {
+    let open_state;
+    match state {
+        Some(s) => {
+            open_state = s.open;
+        }
+        None => {
+            return;
+        }
+    }
+    for message in events.iter() {
+        match &message.message {
+            UiServerMessage::TextTreeSelection(_) | UiServerMessage::UIRequestInput(_) => {
+                if !open_state {
+                    open.send(OpenInventoryHud { open: true });
+                }
+                break;
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Build the input request UI. Shares its node slot with text tree selections.
+
+pub(crate) fn create_text_input_request(
+    mut events: EventReader<IncomingReliableServerMessage<UiServerMessage>>,
+    hud_state: Res<HudState>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut state: ResMut<TextTreeInputSelectionState>,
+) {
+    for message in events.iter() {
+        match &message.message {
+            UiServerMessage::UIRequestInput(request) => {
+                let arizone_font = asset_server.load(ARIZONE_FONT);
+                let empire_font = asset_server.load(EMPIRE_FONT);
+
+                match state.entity {
+                    Some(old_entity) => {
+                        commands.entity(old_entity).despawn_recursive();
+                    }
+                    None => {}
+                }
+
+                commands
+                    .entity(hud_state.right_content_node)
+                    .with_children(|parent| {
+                        let root = parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                background_color: INVENTORY_HUD_BG_COLOR.into(),
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                parent
+                                    .spawn(NodeBundle {
+                                        style: Style {
+                                            size: Size::new(Val::Percent(100.), Val::Percent(3.)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..Default::default()
+                                        },
+                                        ..Default::default()
+                                    })
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            request.text.clone(),
+                                            TextStyle {
+                                                font_size: 13.0,
+                                                color: Color::WHITE,
+                                                font: arizone_font.clone(),
+                                            },
+                                        ));
+                                    });
+                                parent.spawn(NodeBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Percent(100.), Val::Percent(8.)),
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                });
+                                let placeholder = "...".to_string();
+                                let input_node = parent
+                                    .spawn(NodeBundle {
+                                        style: Style {
+                                            size: Size::new(Val::Percent(65.), Val::Percent(4.)),
+                                            align_items: AlignItems::Center,
+                                            ..Default::default()
+                                        },
+                                        background_color: ACTIONS_HUD_BG_COLOR.into(),
+                                        ..Default::default()
+                                    })
+                                    .insert((
+                                        TextInputNode {
+                                            placeholder_active: true,
+                                            character_filter_option: Some(CharacterFilter::Chat),
+                                            placeholder_text_option: Some(placeholder.clone()),
+                                            ..Default::default()
+                                        },
+                                        Interaction::default(),
+                                        TextInputRequestNode,
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            placeholder,
+                                            TextStyle {
+                                                font_size: 13.0,
+                                                color: Color::WHITE,
+                                                font: empire_font.clone(),
+                                            },
+                                        ));
+                                    })
+                                    .id();
+                                parent.spawn(NodeBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Percent(100.), Val::Percent(2.)),
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                });
+                                parent
+                                    .spawn(ButtonBundle {
+                                        style: Style {
+                                            size: Size::new(Val::Percent(65.), Val::Percent(3.)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..Default::default()
+                                        },
+                                        background_color: ACTIONS_HUD_BG_COLOR.into(),
+                                        ..Default::default()
+                                    })
+                                    .insert(ButtonSelectionStyle::default())
+                                    .insert(TextInputRequestSubmitButton {
+                                        request: request.clone(),
+                                        input_node,
+                                    })
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            "Submit",
+                                            TextStyle {
+                                                font_size: 13.0,
+                                                color: Color::WHITE,
+                                                font: empire_font.clone(),
+                                            },
+                                        ));
+                                    });
+                            })
+                            .id();
+                        state.entity = Some(root);
+                    });
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Submit the text of an input request to the server and close it.
+
+pub(crate) fn text_input_request_submit_button(
+    interaction_query: Query<
+        (&Interaction, &TextInputRequestSubmitButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    input_nodes: Query<&TextInputNode, With<TextInputRequestNode>>,
+    mut state: ResMut<TextTreeInputSelectionState>,
+    mut commands: Commands,
+    mut net: EventWriter<OutgoingReliableClientMessage<UiClientMessage>>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        match interaction {
+            Interaction::Clicked => {
+                let input;
+                match input_nodes.get(button.input_node) {
+                    Ok(node) => {
+                        if node.placeholder_active {
+                            continue;
+                        }
+                        input = node.input.clone();
+                    }
+                    Err(_) => {
+                        warn!("Couldnt find input request text node.");
+                        continue;
+                    }
+                }
+                if input.trim().is_empty() {
+                    continue;
+                }
+                net.send(OutgoingReliableClientMessage {
+                    message: UiClientMessage::TextInput(TextInputResponse {
+                        entity: button.request.entity,
+                        id: button.request.id.clone(),
+                        input,
+                    }),
+                });
+                match state.entity {
+                    Some(entity) => {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                    None => {}
+                }
+                state.entity = None;
+            }
+            _ => (),
+        }
+    }
+}
@@ -51,10 +51,13 @@ pub(crate) fn create_text_tree_selection(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut state: ResMut<TextTreeInputSelectionState>,
+    mut selection_state: ResMut<TextTreeSelectionState>,
 ) {
     for message in events.iter() {
         match &message.message {
             UiServerMessage::TextTreeSelection(selection) => {
+                // Selections of the previous menu would otherwise be submitted again.
+                selection_state.selected = None;
                 let arizone_font = asset_server.load(ARIZONE_FONT);
                 let empire_font = asset_server.load(EMPIRE_FONT);
 
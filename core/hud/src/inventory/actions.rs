@@ -173,6 +173,7 @@ pub(crate) fn slot_item_actions(
                         });
                 });
             }
+            _ => (),
         }
     }
 }
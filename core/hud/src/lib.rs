@@ -1,9 +1,12 @@
+pub mod action_progress;
 pub mod communication;
 pub mod expand;
 pub mod hud;
 pub mod input;
 pub mod inventory;
+pub mod map;
 pub mod mouse;
 pub mod plugin;
 pub mod server_stats;
 pub mod style;
+pub mod world_picking;
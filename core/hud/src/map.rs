@@ -0,0 +1,3 @@
+pub mod build;
+pub mod input;
+pub mod render;
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{
+        AssetServer, Assets, BuildChildren, ButtonBundle, ChildBuilder, Color, Commands, Component,
+        Entity, Handle, Image, ImageBundle, NodeBundle, Res, ResMut, Resource, TextBundle, Vec2,
+    },
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+    text::{Font, TextStyle},
+    ui::{
+        AlignItems, Display, FlexDirection, JustifyContent, PositionType, RelativeCursorPosition,
+        Size, Style, UiImage, UiRect, Val,
+    },
+};
+use resources::{hud::HudState, math::Vec2Int};
+use ui::fonts::{ARIZONE_FONT, EMPIRE_FONT};
+
+use crate::{
+    inventory::actions::{ACTIONS_HUD_BG_COLOR, INVENTORY_HUD_BG_COLOR},
+    style::button::ButtonSelectionStyle,
+};
+
+/// Width and height of the map images in pixels.
+
+pub const MAP_IMAGE_SIZE: u32 = 128;
+/// View range of the mini-map in cells.
+
+pub const MINI_MAP_VIEW_RANGE: f32 = 12.;
+/// Default view range of the map panel in cells.
+
+pub const MAP_DEFAULT_VIEW_RANGE: f32 = 20.;
+
+/// Resource with the nodes and images of the mini-map and the map panel.
+#[derive(Resource)]
+
+pub struct MapHudState {
+    pub open: bool,
+    pub mini_map_node: Entity,
+    pub mini_map_image: Handle<Image>,
+    pub panel_node: Entity,
+    pub panel_image: Handle<Image>,
+    /// Parent node of the display mode buttons.
+    pub display_modes_node: Entity,
+    pub deck_text: Entity,
+    pub hover_text: Entity,
+}
+
+/// Resource with the client-side view of the map panel and the overlay data received from the server.
+#[derive(Resource)]
+
+pub struct MapView {
+    /// Map camera position in cells. X is the world x axis, y the world z axis.
+    pub camera_position: Vec2,
+    /// View distance in cells from the center to the edge.
+    pub view_range: f32,
+    /// The y level of the cells that are drawn.
+    pub deck: i16,
+    /// Whether the camera and deck follow the pawn.
+    pub follow: bool,
+    pub display_mode: String,
+    pub display_modes: Vec<(String, String)>,
+    /// Overlay of the active display mode.
+    pub overlay: HashMap<Vec2Int, i16>,
+    /// Permanent map markers like airlocks, counters and hull breaches.
+    pub default_tiles: HashMap<Vec2Int, i16>,
+    pub hovered_cell: Option<Vec2Int>,
+    pub hover_data: String,
+    /// Camera position and view range the server last received.
+    pub sent_camera: Option<(Vec2, f32)>,
+}
+
+impl Default for MapView {
+    fn default() -> Self {
+        Self {
+            camera_position: Vec2::ZERO,
+            view_range: MAP_DEFAULT_VIEW_RANGE,
+            deck: 0,
+            follow: true,
+            display_mode: "standard".to_string(),
+            display_modes: vec![],
+            overlay: HashMap::new(),
+            default_tiles: HashMap::new(),
+            hovered_cell: None,
+            hover_data: "".to_string(),
+            sent_camera: None,
+        }
+    }
+}
+
+/// The image node of the map panel.
+#[derive(Component)]
+
+pub struct MapPanelImage;
+
+/// Button that switches the display mode of the map.
+#[derive(Component)]
+
+pub struct MapDisplayModeButton {
+    pub id: String,
+}
+
+/// Button that moves the map a deck up or down.
+#[derive(Component)]
+
+pub struct MapDeckButton {
+    pub delta: i16,
+}
+
+/// Button that makes the map follow the pawn again.
+#[derive(Component)]
+
+pub struct MapFollowButton;
+
+pub(crate) fn new_map_image() -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: MAP_IMAGE_SIZE,
+            height: MAP_IMAGE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.sampler_descriptor = ImageSampler::nearest();
+    image
+}
+
+fn spawn_map_button<T: Component>(
+    parent: &mut ChildBuilder,
+    text: &str,
+    component: T,
+    font: Handle<Font>,
+) {
+    parent
+        .spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Px(22.)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                margin: UiRect::bottom(Val::Px(2.)),
+                ..Default::default()
+            },
+            background_color: ACTIONS_HUD_BG_COLOR.into(),
+            ..Default::default()
+        })
+        .insert(component)
+        .insert(ButtonSelectionStyle::default())
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 12.0,
+                    color: Color::WHITE,
+                    font,
+                },
+            ));
+        });
+}
+
+/// Spawn a display mode button of the map panel.
+
+pub(crate) fn spawn_display_mode_button(
+    parent: &mut ChildBuilder,
+    name: &str,
+    id: &str,
+    font: Handle<Font>,
+) {
+    spawn_map_button(
+        parent,
+        name,
+        MapDisplayModeButton { id: id.to_string() },
+        font,
+    );
+}
+
+pub(crate) fn create_map_hud(
+    mut commands: Commands,
+    hud: Res<HudState>,
+    mut images: ResMut<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+) {
+    let arizone_font = asset_server.load(ARIZONE_FONT);
+    let empire_font = asset_server.load(EMPIRE_FONT);
+
+    let mini_map_image = images.add(new_map_image());
+    let panel_image = images.add(new_map_image());
+
+    let mut mini_map_node = Entity::from_bits(0);
+    let mut panel_node = Entity::from_bits(0);
+    let mut display_modes_node = Entity::from_bits(0);
+    let mut deck_text = Entity::from_bits(0);
+    let mut hover_text = Entity::from_bits(0);
+
+    commands.entity(hud.root_entity).with_children(|parent| {
+        mini_map_node = parent
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Percent(1.),
+                        top: Val::Percent(11.),
+                        ..Default::default()
+                    },
+                    size: Size::new(Val::Px(160.), Val::Px(160.)),
+                    padding: UiRect::all(Val::Px(2.)),
+                    ..Default::default()
+                },
+                background_color: INVENTORY_HUD_BG_COLOR.into(),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent.spawn(ImageBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                        ..Default::default()
+                    },
+                    image: UiImage::from(mini_map_image.clone()),
+                    ..Default::default()
+                });
+            })
+            .id();
+
+        panel_node = parent
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Percent(10.),
+                        top: Val::Percent(10.),
+                        ..Default::default()
+                    },
+                    size: Size::new(Val::Percent(80.), Val::Percent(80.)),
+                    flex_direction: FlexDirection::Row,
+                    display: Display::None,
+                    padding: UiRect::all(Val::Px(4.)),
+                    ..Default::default()
+                },
+                background_color: INVENTORY_HUD_BG_COLOR.into(),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(25.), Val::Percent(100.)),
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::right(Val::Px(4.)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Map",
+                            TextStyle {
+                                font_size: 16.0,
+                                color: Color::WHITE,
+                                font: arizone_font.clone(),
+                            },
+                        ));
+                        deck_text = parent
+                            .spawn(TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font_size: 12.0,
+                                    color: Color::WHITE,
+                                    font: empire_font.clone(),
+                                },
+                            ))
+                            .id();
+                        spawn_map_button(
+                            parent,
+                            "Deck up",
+                            MapDeckButton { delta: 1 },
+                            empire_font.clone(),
+                        );
+                        spawn_map_button(
+                            parent,
+                            "Deck down",
+                            MapDeckButton { delta: -1 },
+                            empire_font.clone(),
+                        );
+                        spawn_map_button(parent, "Follow", MapFollowButton, empire_font.clone());
+                        parent.spawn(TextBundle::from_section(
+                            "Display modes",
+                            TextStyle {
+                                font_size: 12.0,
+                                color: Color::WHITE,
+                                font: arizone_font.clone(),
+                            },
+                        ));
+                        display_modes_node = parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Percent(100.), Val::Auto),
+                                    flex_direction: FlexDirection::Column,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .id();
+                        hover_text = parent
+                            .spawn(TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font_size: 12.0,
+                                    color: Color::WHITE,
+                                    font: empire_font.clone(),
+                                },
+                            ))
+                            .id();
+                    });
+                parent
+                    .spawn(ImageBundle {
+                        style: Style {
+                            size: Size::new(Val::Auto, Val::Percent(100.)),
+                            aspect_ratio: Some(1.),
+                            ..Default::default()
+                        },
+                        image: UiImage::from(panel_image.clone()),
+                        ..Default::default()
+                    })
+                    .insert(RelativeCursorPosition::default())
+                    .insert(MapPanelImage);
+            })
+            .id();
+    });
+
+    commands.insert_resource(MapHudState {
+        open: false,
+        mini_map_node,
+        mini_map_image,
+        panel_node,
+        panel_image,
+        display_modes_node,
+        deck_text,
+        hover_text,
+    });
+}
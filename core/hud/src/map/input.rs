@@ -0,0 +1,361 @@
+use bevy::{
+    input::mouse::{MouseMotion, MouseWheel},
+    prelude::{
+        warn, AssetServer, BuildChildren, Button, Changed, Commands, DespawnRecursiveExt,
+        DetectChanges, EventReader, EventWriter, Input, KeyCode, MouseButton, Query, Res, ResMut,
+        Vec2, Vec3, With,
+    },
+    text::Text,
+    ui::{Display, Interaction, Node, RelativeCursorPosition, Style},
+};
+use cameras::{controllers::fps::ActiveCamera, LookTransform};
+use map::net::{MapReliableClientMessage, MapServerMessage, MapUnreliableClientMessage};
+use networking::client::{
+    IncomingReliableServerMessage, OutgoingReliableClientMessage, OutgoingUnreliableClientMessage,
+};
+use player::configuration::Boarded;
+use resources::{
+    binds::KeyBinds,
+    math::{world_to_cell_id, Vec2Int},
+    ui::TextInput,
+};
+use ui::fonts::EMPIRE_FONT;
+
+use crate::{
+    input::binds::TOGGLE_MAP,
+    mouse::{GrabCursor, ReleaseCursor},
+};
+
+use super::build::{
+    spawn_display_mode_button, MapDeckButton, MapDisplayModeButton, MapFollowButton, MapHudState,
+    MapPanelImage, MapView,
+};
+
+/// Approximate height of pawn eyes above their floor.
+
+const EYE_HEIGHT: f32 = 1.;
+/// View range limits of the map panel in cells.
+
+const MIN_VIEW_RANGE: f32 = 4.;
+const MAX_VIEW_RANGE: f32 = 64.;
+
+/// Event to open or close the map panel.
+
+pub struct OpenMapHud {
+    pub open: bool,
+}
+
+pub(crate) fn map_hud_key_press(
+    keys: Res<Input<KeyCode>>,
+    mut events: EventWriter<OpenMapHud>,
+    state: Res<MapHudState>,
+    focus: Res<TextInput>,
+    binds: Res<KeyBinds>,
+) {
+    if keys.just_pressed(binds.bind(TOGGLE_MAP)) && focus.focused_input.is_none() {
+        events.send(OpenMapHud { open: !state.open });
+    }
+}
+
+pub(crate) fn open_map_hud(
+    boarded: Res<Boarded>,
+    mut events: EventReader<OpenMapHud>,
+    mut state: ResMut<MapHudState>,
+    mut view: ResMut<MapView>,
+    mut styles: Query<&mut Style>,
+    mut grab: EventWriter<GrabCursor>,
+    mut release: EventWriter<ReleaseCursor>,
+    mut net: EventWriter<OutgoingReliableClientMessage<MapReliableClientMessage>>,
+) {
+    for event in events.iter() {
+        if !boarded.boarded {
+            continue;
+        }
+        state.open = event.open;
+        match styles.get_mut(state.panel_node) {
+            Ok(mut style) => {
+                style.display = match state.open {
+                    true => Display::Flex,
+                    false => Display::None,
+                };
+            }
+            Err(_) => {
+                warn!("Couldnt find map panel node.");
+            }
+        }
+        match styles.get_mut(state.mini_map_node) {
+            Ok(mut style) => {
+                style.display = match state.open {
+                    true => Display::None,
+                    false => Display::Flex,
+                };
+            }
+            Err(_) => {
+                warn!("Couldnt find mini-map node.");
+            }
+        }
+        if state.open {
+            release.send(ReleaseCursor);
+            net.send(OutgoingReliableClientMessage {
+                message: MapReliableClientMessage::MapRequestDisplayModes,
+            });
+            // Make sure the server receives our camera again.
+            view.sent_camera = None;
+        } else {
+            grab.send(GrabCursor);
+        }
+    }
+}
+
+/// The deck a position is on, which is the floor below the eyes of pawns.
+
+pub(crate) fn deck_of(position: Vec3) -> i16 {
+    world_to_cell_id(position - Vec3::Y * EYE_HEIGHT).y
+}
+
+/// Keep the map centered on the pawn while following.
+
+pub(crate) fn map_follow_pawn(
+    mut view: ResMut<MapView>,
+    active_camera: Res<ActiveCamera>,
+    cameras: Query<&LookTransform>,
+) {
+    if !view.follow {
+        return;
+    }
+    match active_camera.option {
+        Some(camera_entity) => match cameras.get(camera_entity) {
+            Ok(look_transform) => {
+                let position = Vec2::new(look_transform.eye.x, look_transform.eye.z);
+                if view.camera_position != position {
+                    view.camera_position = position;
+                }
+                let deck = deck_of(look_transform.eye);
+                if view.deck != deck {
+                    view.deck = deck;
+                }
+            }
+            Err(_) => {}
+        },
+        None => {}
+    }
+}
+
+pub(crate) fn map_hud_buttons(
+    mode_buttons: Query<
+        (&Interaction, &MapDisplayModeButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    deck_buttons: Query<(&Interaction, &MapDeckButton), (Changed<Interaction>, With<Button>)>,
+    follow_buttons: Query<&Interaction, (Changed<Interaction>, With<MapFollowButton>)>,
+    mut view: ResMut<MapView>,
+    mut net: EventWriter<OutgoingReliableClientMessage<MapReliableClientMessage>>,
+) {
+    for (interaction, button) in mode_buttons.iter() {
+        match interaction {
+            Interaction::Clicked => {
+                view.display_mode = button.id.clone();
+                view.overlay.clear();
+                view.hover_data = "".to_string();
+                net.send(OutgoingReliableClientMessage {
+                    message: MapReliableClientMessage::MapChangeDisplayMode(button.id.clone()),
+                });
+            }
+            _ => {}
+        }
+    }
+    for (interaction, button) in deck_buttons.iter() {
+        match interaction {
+            Interaction::Clicked => {
+                view.follow = false;
+                view.deck += button.delta;
+            }
+            _ => {}
+        }
+    }
+    for interaction in follow_buttons.iter() {
+        match interaction {
+            Interaction::Clicked => {
+                view.follow = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Pan the map by dragging, zoom with the mouse wheel and report the hovered cell.
+
+pub(crate) fn map_mouse_input(
+    state: Res<MapHudState>,
+    mut view: ResMut<MapView>,
+    images: Query<(&RelativeCursorPosition, &Node), With<MapPanelImage>>,
+    buttons: Res<Input<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+    mut net: EventWriter<OutgoingUnreliableClientMessage<MapUnreliableClientMessage>>,
+) {
+    if !state.open {
+        motion.clear();
+        wheel.clear();
+        return;
+    }
+    let cursor;
+    let node_size;
+    match images.get_single() {
+        Ok((c, node)) => {
+            cursor = c;
+            node_size = node.size();
+        }
+        Err(_) => {
+            return;
+        }
+    }
+    let normalized;
+    match cursor.normalized {
+        Some(n) => {
+            if !cursor.mouse_over() {
+                motion.clear();
+                wheel.clear();
+                return;
+            }
+            normalized = n;
+        }
+        None => {
+            motion.clear();
+            wheel.clear();
+            return;
+        }
+    }
+
+    let cells_per_pixel = view.view_range * 2. / node_size.x.max(1.);
+
+    if buttons.pressed(MouseButton::Left) {
+        let mut delta = Vec2::ZERO;
+        for event in motion.iter() {
+            delta += event.delta;
+        }
+        if delta != Vec2::ZERO {
+            view.follow = false;
+            view.camera_position -= delta * cells_per_pixel;
+        }
+    } else {
+        motion.clear();
+    }
+
+    for event in wheel.iter() {
+        view.view_range =
+            (view.view_range * (1. - event.y.signum() * 0.1)).clamp(MIN_VIEW_RANGE, MAX_VIEW_RANGE);
+    }
+
+    let hovered = view.camera_position + (normalized - Vec2::splat(0.5)) * view.view_range * 2.;
+    let hovered_cell = Vec2Int {
+        x: hovered.x.floor() as i16,
+        y: hovered.y.floor() as i16,
+    };
+    if view.hovered_cell != Some(hovered_cell) {
+        view.hovered_cell = Some(hovered_cell);
+        net.send(OutgoingUnreliableClientMessage {
+            message: MapUnreliableClientMessage::MapOverlayMouseHoverCell(
+                hovered_cell.x,
+                hovered_cell.y,
+            ),
+        });
+    }
+}
+
+/// Tell the server where the map camera is so it sends the right overlay.
+
+pub(crate) fn send_map_camera(
+    state: Res<MapHudState>,
+    mut view: ResMut<MapView>,
+    mut reliable: EventWriter<OutgoingReliableClientMessage<MapReliableClientMessage>>,
+    mut unreliable: EventWriter<OutgoingUnreliableClientMessage<MapUnreliableClientMessage>>,
+) {
+    if !state.open {
+        return;
+    }
+    let camera = (view.camera_position, view.view_range);
+    if view.sent_camera == Some(camera) {
+        return;
+    }
+    reliable.send(OutgoingReliableClientMessage {
+        message: MapReliableClientMessage::MapCameraPosition(camera.0),
+    });
+    unreliable.send(OutgoingUnreliableClientMessage {
+        message: MapUnreliableClientMessage::MapViewRange(camera.1),
+    });
+    view.sent_camera = Some(camera);
+}
+
+pub(crate) fn receive_map_messages(
+    mut net: EventReader<IncomingReliableServerMessage<MapServerMessage>>,
+    mut view: ResMut<MapView>,
+    state: Res<MapHudState>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    for message in net.iter() {
+        match &message.message {
+            MapServerMessage::MapSendDisplayModes(modes) => {
+                view.display_modes = modes.clone();
+                let empire_font = asset_server.load(EMPIRE_FONT);
+                let mut node = commands.entity(state.display_modes_node);
+                node.despawn_descendants();
+                node.with_children(|parent| {
+                    for (name, id) in modes.iter() {
+                        spawn_display_mode_button(parent, name, id, empire_font.clone());
+                    }
+                });
+            }
+            MapServerMessage::MapOverlayUpdate(tiles) => {
+                view.overlay.clear();
+                for (x, y, item) in tiles.iter() {
+                    view.overlay.insert(Vec2Int { x: *x, y: *y }, *item);
+                }
+            }
+            MapServerMessage::MapOverlayHoverData(text) => {
+                view.hover_data = text.clone();
+            }
+            MapServerMessage::MapDefaultAddition(x, y, item) => {
+                view.default_tiles.insert(Vec2Int { x: *x, y: *y }, *item);
+            }
+            MapServerMessage::MapDefaultRemoval(x, y) => {
+                view.default_tiles.remove(&Vec2Int { x: *x, y: *y });
+            }
+        }
+    }
+}
+
+pub(crate) fn update_map_texts(
+    view: Res<MapView>,
+    state: Res<MapHudState>,
+    mut texts: Query<&mut Text>,
+) {
+    if !view.is_changed() {
+        return;
+    }
+    match texts.get_mut(state.deck_text) {
+        Ok(mut text) => {
+            let follow = match view.follow {
+                true => " (following)",
+                false => "",
+            };
+            text.sections[0].value = format!("Deck {}{}", view.deck, follow);
+        }
+        Err(_) => {
+            warn!("Couldnt find map deck text.");
+        }
+    }
+    match texts.get_mut(state.hover_text) {
+        Ok(mut text) => {
+            let cell = match view.hovered_cell {
+                Some(cell) => format!("({}, {})\n", cell.x, cell.y),
+                None => "".to_string(),
+            };
+            text.sections[0].value = cell + &view.hover_data;
+        }
+        Err(_) => {
+            warn!("Couldnt find map hover text.");
+        }
+    }
+}
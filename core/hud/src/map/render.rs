@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{Assets, Color, Image, Query, Res, ResMut, Vec2};
+use cameras::{controllers::fps::ActiveCamera, LookTransform};
+use gridmap::grid::{GridCell, Gridmap};
+use player::configuration::Boarded;
+use resources::math::{Vec2Int, Vec3Int};
+
+use super::{
+    build::{MapHudState, MapView, MAP_IMAGE_SIZE, MINI_MAP_VIEW_RANGE},
+    input::deck_of,
+};
+
+const EMPTY_COLOR: Color = Color::rgba(0., 0., 0., 0.6);
+const FLOOR_COLOR: Color = Color::rgba(0.3, 0.3, 0.35, 1.);
+const WALL_COLOR: Color = Color::rgba(0.75, 0.75, 0.8, 1.);
+const PAWN_COLOR: Color = Color::rgba(1., 1., 1., 1.);
+
+/// Color of map tile items sent by the server. See [map::map::get_overlay_tile_item].
+
+fn map_item_color(item: i16) -> Color {
+    match item {
+        0 => Color::rgba(0., 0.8, 0., 1.),
+        1 => Color::rgba(1., 0.5, 0., 1.),
+        2 => Color::rgba(0.9, 0., 0., 1.),
+        3 => Color::rgba(0.9, 0.9, 0., 1.),
+        4 => Color::rgba(0., 0.7, 0.9, 1.),
+        _ => Color::rgba(1., 0., 1., 1.),
+    }
+}
+
+fn grid_cell(gridmap: &Gridmap, id: Vec3Int) -> Option<&GridCell> {
+    let indexes = gridmap.get_indexes(id);
+    match gridmap.grid.get(indexes.chunk) {
+        Some(Some(chunk)) => match chunk.cells.get(indexes.cell) {
+            Some(Some(cell)) => Some(cell),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Top-down color of a cell of the given deck.
+
+fn cell_color(gridmap: &Gridmap, x: i16, deck: i16, z: i16) -> Color {
+    let mut color = EMPTY_COLOR;
+    match grid_cell(gridmap, Vec3Int { x, y: deck, z }) {
+        Some(cell) => {
+            if cell.floor.is_some() {
+                color = FLOOR_COLOR;
+            }
+            if cell.front_wall.is_some() || cell.right_wall.is_some() || cell.center.is_some() {
+                return WALL_COLOR;
+            }
+        }
+        None => {}
+    }
+    // Back and left walls are stored as the front and right walls of the neighbouring cells.
+    match grid_cell(
+        gridmap,
+        Vec3Int {
+            x,
+            y: deck,
+            z: z - 1,
+        },
+    ) {
+        Some(cell) => {
+            if cell.front_wall.is_some() {
+                return WALL_COLOR;
+            }
+        }
+        None => {}
+    }
+    match grid_cell(
+        gridmap,
+        Vec3Int {
+            x: x - 1,
+            y: deck,
+            z,
+        },
+    ) {
+        Some(cell) => {
+            if cell.right_wall.is_some() {
+                return WALL_COLOR;
+            }
+        }
+        None => {}
+    }
+    color
+}
+
+/// Draw the gridmap top-down into a map image.
+
+fn draw_map(
+    image: &mut Image,
+    gridmap: &Gridmap,
+    center: Vec2,
+    view_range: f32,
+    deck: i16,
+    overlay: Option<&HashMap<Vec2Int, i16>>,
+    default_tiles: &HashMap<Vec2Int, i16>,
+    pawn_position: Option<Vec2>,
+) {
+    let cells_per_pixel = view_range * 2. / MAP_IMAGE_SIZE as f32;
+    let pawn_pixel = pawn_position.map(|position| {
+        let pixel = (position - center) / cells_per_pixel + Vec2::splat(MAP_IMAGE_SIZE as f32 / 2.);
+        (pixel.x.floor() as i32, pixel.y.floor() as i32)
+    });
+    let mut colors: HashMap<Vec2Int, [u8; 4]> = HashMap::new();
+
+    for row in 0..MAP_IMAGE_SIZE {
+        for column in 0..MAP_IMAGE_SIZE {
+            let position = center
+                + (Vec2::new(column as f32, row as f32) - Vec2::splat(MAP_IMAGE_SIZE as f32 / 2.))
+                    * cells_per_pixel;
+            let id = Vec2Int {
+                x: position.x.floor() as i16,
+                y: position.y.floor() as i16,
+            };
+
+            let mut color = match colors.get(&id) {
+                Some(c) => *c,
+                None => {
+                    let mut color = cell_color(gridmap, id.x, deck, id.y);
+                    match overlay.and_then(|o| o.get(&id)) {
+                        Some(item) => {
+                            color = map_item_color(*item);
+                        }
+                        None => match default_tiles.get(&id) {
+                            Some(item) => {
+                                color = map_item_color(*item);
+                            }
+                            None => {}
+                        },
+                    }
+                    let c = color.as_rgba_u8();
+                    colors.insert(id, c);
+                    c
+                }
+            };
+
+            match pawn_pixel {
+                Some((x, y)) => {
+                    if (column as i32 - x).abs() <= 1 && (row as i32 - y).abs() <= 1 {
+                        color = PAWN_COLOR.as_rgba_u8();
+                    }
+                }
+                None => {}
+            }
+
+            let index = ((row * MAP_IMAGE_SIZE + column) * 4) as usize;
+            image.data[index..index + 4].copy_from_slice(&color);
+        }
+    }
+}
+
+/// Redraw the mini-map and the opened map panel.
+
+pub(crate) fn render_map_hud(
+    state: Res<MapHudState>,
+    view: Res<MapView>,
+    gridmap: Res<Gridmap>,
+    boarded: Res<Boarded>,
+    mut images: ResMut<Assets<Image>>,
+    active_camera: Res<ActiveCamera>,
+    cameras: Query<&LookTransform>,
+) {
+    if !boarded.boarded {
+        return;
+    }
+    let mut pawn_deck = view.deck;
+    let pawn_position = match active_camera.option {
+        Some(camera_entity) => match cameras.get(camera_entity) {
+            Ok(look_transform) => {
+                pawn_deck = deck_of(look_transform.eye);
+                Some(Vec2::new(look_transform.eye.x, look_transform.eye.z))
+            }
+            Err(_) => None,
+        },
+        None => None,
+    };
+
+    if state.open {
+        match images.get_mut(&state.panel_image) {
+            Some(image) => {
+                draw_map(
+                    image,
+                    &gridmap,
+                    view.camera_position,
+                    view.view_range,
+                    view.deck,
+                    Some(&view.overlay),
+                    &view.default_tiles,
+                    pawn_position,
+                );
+            }
+            None => {}
+        }
+    } else {
+        match pawn_position {
+            Some(position) => match images.get_mut(&state.mini_map_image) {
+                Some(image) => {
+                    draw_map(
+                        image,
+                        &gridmap,
+                        position,
+                        MINI_MAP_VIEW_RANGE,
+                        pawn_deck,
+                        None,
+                        &view.default_tiles,
+                        pawn_position,
+                    );
+                }
+                None => {}
+            },
+            None => {}
+        }
+    }
+}
@@ -1,20 +1,32 @@
-use bevy::prelude::{not, resource_exists, App, IntoSystemConfig, Plugin, StartupSet};
+use std::time::Duration;
+
+use bevy::{
+    prelude::{not, resource_exists, App, IntoSystemConfig, Plugin, StartupSet},
+    time::common_conditions::on_fixed_timer,
+};
 use console_commands::net::ClientSideConsoleInput;
 use resources::is_server::is_server;
 
 use crate::{
+    action_progress::{
+        build_action_progress_bar, receive_action_progress, update_action_progress_bar,
+        ActionProgressState,
+    },
     communication::{
         build::{build_communication_ui, console_welcome_message, toggle_console_button},
         chat::{display_chat_message, receive_chat_message, DisplayChatMessage},
         console::{
             console_input, display_console_message, receive_console_message, DisplayConsoleMessage,
         },
-        input::{tab_communication_input_toggle, text_input},
+        input::{console_tab_completion, tab_communication_input_toggle, text_input},
     },
     expand::{expand_inventory_hud, ExpandInventoryHud},
     hud::{create_hud, show_hud, ExpandedLeftContentHud},
     input::{
         binds::register_input,
+        text_input_request::{
+            create_text_input_request, open_hud_for_server_ui, text_input_request_submit_button,
+        },
         text_tree_selection::{
             create_text_tree_selection, hide_text_tree_selection, text_tree_select_button,
             text_tree_select_submit_button, TextTreeInputSelectionState, TextTreeSelectionState,
@@ -36,12 +48,25 @@ use crate::{
         },
         slots::{scale_slots, update_inventory_hud_slot, HudAddInventorySlot, InventoryHudLabels},
     },
+    map::{
+        build::{create_map_hud, MapView},
+        input::{
+            map_follow_pawn, map_hud_buttons, map_hud_key_press, map_mouse_input, open_map_hud,
+            receive_map_messages, send_map_camera, update_map_texts, OpenMapHud,
+        },
+        render::render_map_hud,
+    },
     mouse::{
         focus_state, grab_cursor, grab_mouse_hud_expand, grab_mouse_on_board, release_cursor,
         window_unfocus_event, FocusState, GrabCursor, ReleaseCursor,
     },
     server_stats::{build_server_stats, update_server_stats, ServerStatsState},
     style::button::{button_style_events, changed_focus},
+    world_picking::{
+        build_hover_marker, build_world_context_menu, dismiss_world_context_menu,
+        pick_world_target, update_hover_marker, world_context_menu_button_events,
+        world_target_mouse_input, WorldPickingState,
+    },
 };
 
 pub struct HudPlugin;
@@ -85,6 +110,9 @@ impl Plugin for HudPlugin {
                 .init_resource::<TextTreeSelectionState>()
                 .add_system(changed_focus)
                 .add_system(text_tree_select_submit_button)
+                .add_system(open_hud_for_server_ui)
+                .add_system(create_text_input_request)
+                .add_system(text_input_request_submit_button)
                 .add_system(grab_mouse_on_board)
                 .add_system(grab_mouse_hud_expand)
                 .add_event::<ExpandedLeftContentHud>()
@@ -96,6 +124,7 @@ impl Plugin for HudPlugin {
                 .add_system(text_input)
                 .add_system(receive_chat_message)
                 .add_system(tab_communication_input_toggle)
+                .add_system(console_tab_completion)
                 .add_system(open_inventory_hud.after(open_hud))
                 .add_event::<OpenInventoryHud>()
                 .add_system(toggle_console_button)
@@ -115,7 +144,37 @@ impl Plugin for HudPlugin {
                 .add_system(display_chat_message)
                 .add_system(update_server_stats)
                 .init_resource::<ServerStatsState>()
-                .add_startup_system(register_input);
+                .add_startup_system(register_input)
+                .add_startup_system(build_action_progress_bar)
+                .init_resource::<ActionProgressState>()
+                .add_system(receive_action_progress)
+                .add_system(update_action_progress_bar.after(receive_action_progress))
+                .init_resource::<WorldPickingState>()
+                .add_startup_system(build_hover_marker)
+                .add_system(pick_world_target)
+                .add_system(update_hover_marker.after(pick_world_target))
+                .add_system(world_target_mouse_input.after(pick_world_target))
+                .add_system(build_world_context_menu)
+                .add_system(world_context_menu_button_events)
+                .add_system(dismiss_world_context_menu.after(world_target_mouse_input))
+                .add_startup_system(create_map_hud.in_base_set(StartupSet::PostStartup))
+                .init_resource::<MapView>()
+                .add_event::<OpenMapHud>()
+                .add_system(map_hud_key_press)
+                .add_system(open_map_hud.after(map_hud_key_press))
+                .add_system(map_follow_pawn)
+                .add_system(map_hud_buttons)
+                .add_system(map_mouse_input.after(map_follow_pawn))
+                .add_system(
+                    send_map_camera
+                        .after(map_mouse_input)
+                        .run_if(on_fixed_timer(Duration::from_secs_f32(1. / 8.))),
+                )
+                .add_system(receive_map_messages)
+                .add_system(update_map_texts)
+                .add_system(
+                    render_map_hud.run_if(on_fixed_timer(Duration::from_secs_f32(1. / 4.))),
+                );
         }
     }
 }
@@ -0,0 +1,521 @@
+use actions::{
+    net::{ActionsClientMessage, ActionsServerMessage, TabData, TabPressed},
+    networking::NetAction,
+};
+use bevy::{
+    hierarchy::Parent,
+    prelude::{
+        warn, AssetServer, BuildChildren, Button, ButtonBundle, Camera, Changed, Color, Commands,
+        Component, DespawnRecursiveExt, Entity, EventReader, EventWriter, GlobalTransform, Input,
+        KeyCode, MouseButton, NodeBundle, Query, Res, ResMut, Resource, TextBundle, Vec3, With,
+    },
+    text::TextStyle,
+    ui::{
+        AlignItems, Display, FlexDirection, Interaction, JustifyContent, PositionType, Size, Style,
+        UiRect, Val,
+    },
+};
+use bevy_rapier3d::prelude::{Collider, CollisionGroups, Group, QueryFilter, RapierContext};
+use cameras::{controllers::fps::ActiveCamera, LookTransform};
+use entity::{
+    net::EntityClientMessage,
+    spawn::{ClientEntityServerEntity, PawnEntityId},
+};
+use gridmap::{
+    chunk_collider::{cell_at_hit, ChunkCollider},
+    grid::Gridmap,
+    net::GridmapClientMessage,
+};
+use networking::client::{IncomingReliableServerMessage, OutgoingReliableClientMessage};
+use physics::physics::{get_bit_masks, ColliderGroup};
+use resources::{grid::TargetCell, hud::HudState, math::cell_id_to_world};
+use ui::fonts::{ARIZONE_FONT, EMPIRE_FONT};
+
+use crate::{
+    inventory::{
+        actions::{ACTIONS_HUD_BG_COLOR, INVENTORY_HUD_BG_COLOR},
+        build::InventoryHudState,
+    },
+    mouse::{GrabCursor, ReleaseCursor},
+    style::button::ButtonSelectionStyle,
+};
+
+/// How far away from the camera entities and cells can be picked.
+
+pub const PICKING_DISTANCE: f32 = 8.;
+/// Size of the hover marker in pixels.
+
+const HOVER_MARKER_SIZE: f32 = 14.;
+
+/// An entity or cell in the world picked with the camera.
+#[derive(Clone)]
+
+pub struct PickedTarget {
+    /// Server entity.
+    pub entity_option: Option<Entity>,
+    pub cell_option: Option<TargetCell>,
+    pub position: Vec3,
+}
+
+/// Resource with the world target under the crosshair and the state of its context menu.
+#[derive(Resource, Default)]
+
+pub struct WorldPickingState {
+    pub hovered: Option<PickedTarget>,
+    /// Whether we requested the actions of the hovered target and are waiting for them.
+    pub awaiting_actions: bool,
+    pub menu_open: bool,
+}
+
+/// The marker highlighting the hovered world target.
+#[derive(Component)]
+
+pub struct HoverMarker;
+
+/// Root node of the world context menu.
+#[derive(Component)]
+
+pub struct WorldContextMenu;
+
+/// Button of an action in the world context menu.
+#[derive(Component)]
+
+pub struct WorldContextMenuButton {
+    pub data: NetAction,
+}
+
+pub(crate) fn build_hover_marker(mut commands: Commands) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Px(HOVER_MARKER_SIZE), Val::Px(HOVER_MARKER_SIZE)),
+                display: Display::None,
+                ..Default::default()
+            },
+            background_color: Color::rgba(1., 1., 1., 0.35).into(),
+            ..Default::default()
+        })
+        .insert(HoverMarker);
+}
+
+/// Raycast from the camera to find the entity or cell under the crosshair.
+
+pub(crate) fn pick_world_target(
+    mut state: ResMut<WorldPickingState>,
+    camera_query: Query<&LookTransform>,
+    active_camera: Res<ActiveCamera>,
+    rapier_context: Res<RapierContext>,
+    colliders: Query<&Parent, With<Collider>>,
+    chunk_colliders: Query<&ChunkCollider>,
+    gridmap: Res<Gridmap>,
+    transforms: Query<&GlobalTransform>,
+    map: Res<ClientEntityServerEntity>,
+    pawn: Res<PawnEntityId>,
+    inventory_state: Res<InventoryHudState>,
+    hud_state: Res<HudState>,
+) {
+    if state.menu_open {
+        return;
+    }
+    if inventory_state.open && hud_state.expanded {
+        state.hovered = None;
+        return;
+    }
+
+    let camera_look_transform;
+    match active_camera.option {
+        Some(camera_entity) => match camera_query.get(camera_entity) {
+            Ok(transform) => {
+                camera_look_transform = transform;
+            }
+            Err(_) => {
+                state.hovered = None;
+                return;
+            }
+        },
+        None => {
+            state.hovered = None;
+            return;
+        }
+    }
+    let ray_dir;
+    match camera_look_transform.look_direction() {
+        Some(dir) => {
+            ray_dir = dir;
+        }
+        None => {
+            state.hovered = None;
+            return;
+        }
+    }
+
+    let collider_groups = get_bit_masks(ColliderGroup::Standard);
+    let mut filter = QueryFilter::new().groups(CollisionGroups::new(
+        Group::from_bits(collider_groups.0).unwrap(),
+        Group::from_bits(collider_groups.1).unwrap(),
+    ));
+    match pawn.option {
+        Some(server_pawn) => match map.map.get(&server_pawn) {
+            Some(client_pawn) => {
+                filter = filter.exclude_rigid_body(*client_pawn);
+            }
+            None => {}
+        },
+        None => {}
+    }
+
+    let hit_collider;
+    let intersection;
+    match rapier_context.cast_ray_and_get_normal(
+        camera_look_transform.eye,
+        ray_dir,
+        PICKING_DISTANCE,
+        true,
+        filter,
+    ) {
+        Some((collider_entity, ray_intersection)) => {
+            hit_collider = collider_entity;
+            intersection = ray_intersection;
+        }
+        None => {
+            state.hovered = None;
+            return;
+        }
+    }
+
+    let hit_entity;
+    match colliders.get(hit_collider) {
+        Ok(parent) => {
+            hit_entity = parent.get();
+        }
+        Err(_) => {
+            hit_entity = hit_collider;
+        }
+    }
+
+    match chunk_colliders.get(hit_entity) {
+        Ok(_) => {
+            match cell_at_hit(&gridmap, intersection.point, intersection.normal) {
+                Some(cell) => {
+                    state.hovered = Some(PickedTarget {
+                        entity_option: None,
+                        position: cell_id_to_world(cell.id),
+                        cell_option: Some(cell),
+                    });
+                }
+                None => {
+                    state.hovered = None;
+                }
+            }
+            return;
+        }
+        Err(_) => {}
+    }
+
+    let mut server_entity_option = None;
+    for (server_entity, client_entity) in map.map.iter() {
+        if *client_entity == hit_entity {
+            server_entity_option = Some(*server_entity);
+            break;
+        }
+    }
+    match (server_entity_option, transforms.get(hit_entity)) {
+        (Some(server_entity), Ok(transform)) => {
+            state.hovered = Some(PickedTarget {
+                entity_option: Some(server_entity),
+                cell_option: None,
+                position: transform.translation(),
+            });
+        }
+        _ => {
+            state.hovered = None;
+        }
+    }
+}
+
+/// Keep the hover marker over the hovered world target.
+
+pub(crate) fn update_hover_marker(
+    state: Res<WorldPickingState>,
+    active_camera: Res<ActiveCamera>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut markers: Query<&mut Style, With<HoverMarker>>,
+) {
+    let mut marker_style;
+    match markers.get_single_mut() {
+        Ok(s) => {
+            marker_style = s;
+        }
+        Err(_) => {
+            return;
+        }
+    }
+
+    let target_position;
+    match &state.hovered {
+        Some(target) => {
+            target_position = target.position;
+        }
+        None => {
+            marker_style.display = Display::None;
+            return;
+        }
+    }
+
+    let viewport_position;
+    match active_camera.option {
+        Some(camera_entity) => match cameras.get(camera_entity) {
+            Ok((camera, camera_transform)) => {
+                viewport_position = camera.world_to_viewport(camera_transform, target_position);
+            }
+            Err(_) => {
+                return;
+            }
+        },
+        None => {
+            return;
+        }
+    }
+
+    match viewport_position {
+        Some(position) => {
+            marker_style.display = Display::Flex;
+            marker_style.position = UiRect {
+                left: Val::Px(position.x - HOVER_MARKER_SIZE / 2.),
+                bottom: Val::Px(position.y - HOVER_MARKER_SIZE / 2.),
+                ..Default::default()
+            };
+        }
+        None => {
+            marker_style.display = Display::None;
+        }
+    }
+}
+
+/// Request the actions of the hovered target on right-click and examine it on shift-click.
+
+pub(crate) fn world_target_mouse_input(
+    buttons: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    mut state: ResMut<WorldPickingState>,
+    mut actions_net: EventWriter<OutgoingReliableClientMessage<ActionsClientMessage>>,
+    mut entity_net: EventWriter<OutgoingReliableClientMessage<EntityClientMessage>>,
+    mut gridmap_net: EventWriter<OutgoingReliableClientMessage<GridmapClientMessage>>,
+) {
+    if state.menu_open {
+        return;
+    }
+    let target;
+    match &state.hovered {
+        Some(t) => {
+            target = t.clone();
+        }
+        None => {
+            return;
+        }
+    }
+
+    if buttons.just_pressed(MouseButton::Right) {
+        actions_net.send(OutgoingReliableClientMessage {
+            message: ActionsClientMessage::TabData(TabData {
+                action_taker_item: None,
+                target_cell_option: target.cell_option.clone(),
+                target_entity_option: target.entity_option,
+            }),
+        });
+        state.awaiting_actions = true;
+    }
+
+    let shift = keys.pressed(KeyCode::LShift) || keys.pressed(KeyCode::RShift);
+    if shift && buttons.just_pressed(MouseButton::Left) {
+        match target.entity_option {
+            Some(entity) => {
+                entity_net.send(OutgoingReliableClientMessage {
+                    message: EntityClientMessage::ExamineEntity(entity.to_bits()),
+                });
+            }
+            None => match &target.cell_option {
+                Some(cell) => {
+                    gridmap_net.send(OutgoingReliableClientMessage {
+                        message: GridmapClientMessage::ExamineMap(cell.id.x, cell.id.y, cell.id.z),
+                    });
+                }
+                None => {}
+            },
+        }
+    }
+}
+
+/// Show the actions the server approved for the picked target in a context menu.
+
+pub(crate) fn build_world_context_menu(
+    mut net: EventReader<IncomingReliableServerMessage<ActionsServerMessage>>,
+    mut state: ResMut<WorldPickingState>,
+    mut commands: Commands,
+    menus: Query<Entity, With<WorldContextMenu>>,
+    asset_server: Res<AssetServer>,
+    mut release: EventWriter<ReleaseCursor>,
+) {
+    for message in net.iter() {
+        match &message.message {
+            ActionsServerMessage::TabData(data) => {
+                if !state.awaiting_actions {
+                    continue;
+                }
+                state.awaiting_actions = false;
+
+                if data.len() == 0 {
+                    continue;
+                }
+
+                for menu in menus.iter() {
+                    commands.entity(menu).despawn_recursive();
+                }
+
+                let arizone_font = asset_server.load(ARIZONE_FONT);
+                let empire_font = asset_server.load(EMPIRE_FONT);
+
+                let item_name = data.get(0).unwrap().item_name.clone();
+
+                let mut sorted_data = data.clone();
+                sorted_data.sort_by_key(|d| d.tab_list_priority);
+                sorted_data.reverse();
+
+                commands
+                    .spawn(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            position: UiRect {
+                                left: Val::Percent(52.),
+                                top: Val::Percent(45.),
+                                ..Default::default()
+                            },
+                            size: Size::new(Val::Px(180.), Val::Auto),
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        background_color: INVENTORY_HUD_BG_COLOR.into(),
+                        ..Default::default()
+                    })
+                    .insert(WorldContextMenu)
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            item_name,
+                            TextStyle {
+                                font_size: 13.0,
+                                color: Color::WHITE,
+                                font: arizone_font.clone(),
+                            },
+                        ));
+                        for net_action in sorted_data.iter() {
+                            parent
+                                .spawn(ButtonBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Percent(100.), Val::Px(24.)),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: ACTIONS_HUD_BG_COLOR.into(),
+                                    ..Default::default()
+                                })
+                                .insert(WorldContextMenuButton {
+                                    data: net_action.clone(),
+                                })
+                                .insert(ButtonSelectionStyle::default())
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        net_action.text.clone(),
+                                        TextStyle {
+                                            font_size: 13.0,
+                                            color: Color::WHITE,
+                                            font: empire_font.clone(),
+                                        },
+                                    ));
+                                });
+                        }
+                    });
+                state.menu_open = true;
+                release.send(ReleaseCursor);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Close the world context menu.
+
+fn close_world_context_menu(
+    state: &mut ResMut<WorldPickingState>,
+    commands: &mut Commands,
+    menus: &Query<Entity, With<WorldContextMenu>>,
+    grab: &mut EventWriter<GrabCursor>,
+) {
+    for menu in menus.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+    state.menu_open = false;
+    grab.send(GrabCursor);
+}
+
+/// Perform the pressed action of the world context menu.
+
+pub(crate) fn world_context_menu_button_events(
+    interaction_query: Query<
+        (&Interaction, &WorldContextMenuButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut net: EventWriter<OutgoingReliableClientMessage<ActionsClientMessage>>,
+    pawn: Res<PawnEntityId>,
+    mut state: ResMut<WorldPickingState>,
+    mut commands: Commands,
+    menus: Query<Entity, With<WorldContextMenu>>,
+    mut grab: EventWriter<GrabCursor>,
+) {
+    for (interaction, component) in interaction_query.iter() {
+        match interaction {
+            Interaction::Clicked => {
+                match pawn.option {
+                    Some(action_taker) => {
+                        net.send(OutgoingReliableClientMessage {
+                            message: ActionsClientMessage::TabPressed(TabPressed {
+                                id: component.data.id.clone(),
+                                action_taker,
+                                action_taker_item: component.data.action_taker_item,
+                                target_cell_option: component.data.target_cell_option.clone(),
+                                target_entity_option: component.data.target_entity_option,
+                            }),
+                        });
+                    }
+                    None => {
+                        warn!("Pawn not yet initialized.");
+                    }
+                }
+                close_world_context_menu(&mut state, &mut commands, &menus, &mut grab);
+                return;
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Close the world context menu on escape or right-click.
+
+pub(crate) fn dismiss_world_context_menu(
+    buttons: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    mut state: ResMut<WorldPickingState>,
+    mut commands: Commands,
+    menus: Query<Entity, With<WorldContextMenu>>,
+    mut grab: EventWriter<GrabCursor>,
+) {
+    if !state.menu_open {
+        return;
+    }
+    if keys.just_pressed(KeyCode::Escape) || buttons.just_pressed(MouseButton::Right) {
+        close_world_context_menu(&mut state, &mut commands, &menus, &mut grab);
+    }
+}
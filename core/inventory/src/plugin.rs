@@ -14,8 +14,8 @@ use crate::{
     server::{
         entity_update_item::inventory_item_update,
         inventory::{
-            add_item_to_slot, add_slot_to_inventory, added_item_to_slot, AddItemToSlot, AddSlot,
-            Inventory, InventorySlotLabel, ItemAddedToSlot, SpawnItemLabel,
+            add_item_to_slot, add_slot_to_inventory, added_item_to_slot, sync_sensable_holders,
+            AddItemToSlot, AddSlot, Inventory, InventorySlotLabel, ItemAddedToSlot, SpawnItemLabel,
         },
         set_active_item::process_request_set_active_item,
     },
@@ -39,6 +39,7 @@ impl Plugin for InventoryPlugin {
             )
             .add_event::<ItemAddedToSlot>()
             .add_system(added_item_to_slot)
+            .add_system(sync_sensable_holders.after(added_item_to_slot))
             .add_system(add_slot_to_inventory.in_set(InventorySlotLabel::AddSlotToInventory))
             .add_system(process_request_set_active_item)
             .add_system(spawn_entity_for_client);
@@ -62,15 +63,15 @@ impl Plugin for InventoryPlugin {
 use networking::server::ConsoleArgVariant;
 
 use bevy::prelude::ResMut;
-use console_commands::commands::AllConsoleCommands;
+use console_commands::commands::{AllConsoleCommands, ConsoleArg};
 
 pub fn initialize_console_commands(mut commands: ResMut<AllConsoleCommands>) {
     commands.list.push(ConsoleCommand {
         base: "spawnHeld".to_string(),
         description: "Spawn in held entities in hands or in proximity.".to_string(),
         args: vec![
-            ("entity_name".to_string(), ConsoleArgVariant::String),
-            ("player_selector".to_string(), ConsoleArgVariant::String),
+            ConsoleArg::new("entity_name", ConsoleArgVariant::String),
+            ConsoleArg::optional("player_selector", ConsoleArgVariant::String, "@me"),
         ],
     });
 }
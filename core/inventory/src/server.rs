@@ -1,3 +1,5 @@
+/// Read the access of ID cards held in inventories.
+pub mod access;
 pub mod combat;
 pub mod entity_update;
 pub mod entity_update_item;
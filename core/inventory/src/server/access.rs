@@ -0,0 +1,79 @@
+use bevy::prelude::{Entity, Query};
+use pawn::access::IdCard;
+
+use super::inventory::{Inventory, SlotType};
+
+/// Access levels of the ID card in the ID slot of an inventory holder.
+
+pub fn worn_card_access(
+    holder: Entity,
+    inventories: &Query<&Inventory>,
+    cards: &Query<&IdCard>,
+) -> Vec<String> {
+    let mut access = vec![];
+    match inventories.get(holder) {
+        Ok(inventory) => {
+            for slot in inventory.slots.values() {
+                if slot.slot_type != SlotType::Id {
+                    continue;
+                }
+                for item in slot.items.iter() {
+                    match cards.get(item.entity) {
+                        Ok(card) => {
+                            for level in card.access.iter() {
+                                if !access.contains(level) {
+                                    access.push(level.clone());
+                                }
+                            }
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+        }
+        Err(_) => {}
+    }
+    access
+}
+
+/// Access levels of the ID card in the active hand and the ID slot of an inventory holder.
+
+pub fn card_access(
+    holder: Entity,
+    inventories: &Query<&Inventory>,
+    cards: &Query<&IdCard>,
+) -> Vec<String> {
+    let mut access = worn_card_access(holder, inventories, cards);
+    match inventories.get(holder) {
+        Ok(inventory) => match inventory.active_item {
+            Some(active) => match cards.get(active) {
+                Ok(card) => {
+                    for level in card.access.iter() {
+                        if !access.contains(level) {
+                            access.push(level.clone());
+                        }
+                    }
+                }
+                Err(_) => {}
+            },
+            None => {}
+        },
+        Err(_) => {}
+    }
+    access
+}
+
+/// Whether the cards of an inventory holder grant any of the required access levels. Anything is accessible if no levels are required.
+
+pub fn has_access(
+    holder: Entity,
+    required: &[String],
+    inventories: &Query<&Inventory>,
+    cards: &Query<&IdCard>,
+) -> bool {
+    if required.is_empty() {
+        return true;
+    }
+    let access = card_access(holder, inventories, cards);
+    required.iter().any(|r| access.contains(r))
+}
@@ -46,6 +46,18 @@ pub struct ProjectileCombat {
     pub combat_projectile_text_set: Vec<String>,
     /// The words used for chat hooks.
     pub trigger_projectile_text_set: Vec<String>,
+    /// How the projectiles travel and what stops them.
+    pub projectile_physics: ProjectilePhysics,
+}
+
+/// How projectiles of a [ProjectileCombat] weapon travel.
+#[derive(Clone, Debug)]
+
+pub enum ProjectilePhysics {
+    /// Hits instantly and passes through cells that are not laser combat obstacles, such as windows.
+    Laser,
+    /// Travels at speed in meters per second. Can penetrate this many combat obstacles that lasers pass through.
+    Ballistic { speed: f32, penetration: u8 },
 }
 
 impl Default for ProjectileCombat {
@@ -58,6 +70,7 @@ impl Default for ProjectileCombat {
             laser_range: 50.,
             combat_projectile_text_set: get_default_laser_words(),
             trigger_projectile_text_set: get_default_trigger_weapon_words(),
+            projectile_physics: ProjectilePhysics::Laser,
         }
     }
 }
@@ -84,6 +97,10 @@ pub fn get_default_laser_words() -> Vec<String> {
     vec!["shot".to_string(), "hit".to_string(), "beamed".to_string()]
 }
 
+pub fn get_default_ballistic_words() -> Vec<String> {
+    vec!["shot".to_string(), "hit".to_string()]
+}
+
 pub fn get_default_trigger_weapon_words() -> Vec<String> {
     vec!["fired".to_string(), "shot".to_string()]
 }
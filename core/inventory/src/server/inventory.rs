@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use bevy::prelude::{
-    warn, Component, Entity, EventReader, EventWriter, Query, Resource, SystemSet,
+    warn, Changed, Component, Entity, EventReader, EventWriter, Query, Resource, SystemSet,
 };
+use entity::sensable::Sensable;
 use networking::server::{ConnectedPlayer, OutgoingReliableServerMessage};
 use resources::math::Vec2Int;
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,9 @@ pub enum SlotType {
     Helmet,
     Jumpsuit,
     Holster,
+    /// Holds the ID card that doors and devices read.
+    Id,
+    Boots,
 }
 
 /// An inventory slot, an inventory can contain many of these.
@@ -237,8 +241,17 @@ pub(crate) fn added_item_to_slot(
     mut events: EventReader<ItemAddedToSlot>,
     connected_players: Query<&ConnectedPlayer>,
     mut net: EventWriter<OutgoingReliableServerMessage<InventoryServerMessage>>,
+    mut items: Query<&mut InventoryItem>,
 ) {
     for event in events.iter() {
+        match items.get_mut(event.item_entity) {
+            Ok(mut item) => {
+                item.in_inventory_of_entity = Some(event.inventory_entity);
+            }
+            Err(_) => {
+                warn!("Couldnt find inventory item.");
+            }
+        }
         match connected_players.get(event.inventory_entity) {
             Ok(player) => {
                 net.send(OutgoingReliableServerMessage {
@@ -250,3 +263,15 @@ pub(crate) fn added_item_to_slot(
         }
     }
 }
+
+/// Keep the holder of sensable items in sync with the inventory they are in, held items are sensed together with their holder.
+
+pub(crate) fn sync_sensable_holders(
+    mut items: Query<(&InventoryItem, &mut Sensable), Changed<InventoryItem>>,
+) {
+    for (item, mut sensable) in items.iter_mut() {
+        if sensable.holder_option != item.in_inventory_of_entity {
+            sensable.holder_option = item.in_inventory_of_entity;
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use actions::timed::{ActionInterruption, InterruptAction};
 use bevy::prelude::{warn, EventReader, EventWriter, Query, Res};
 use networking::server::{
     HandleToEntity, IncomingReliableClientMessage, OutgoingReliableServerMessage,
@@ -12,6 +13,7 @@ pub(crate) fn process_request_set_active_item(
     mut o_net: EventWriter<OutgoingReliableServerMessage<InventoryServerMessage>>,
     mut inventory_query: Query<&mut Inventory>,
     handle_to_entity: Res<HandleToEntity>,
+    mut interrupt: EventWriter<InterruptAction>,
 ) {
     for event in net.iter() {
         match event.message {
@@ -19,6 +21,12 @@ pub(crate) fn process_request_set_active_item(
                 match handle_to_entity.map.get(&event.handle) {
                     Some(pawn_entity) => match inventory_query.get_mut(*pawn_entity) {
                         Ok(mut inventory_component) => {
+                            if inventory_component.active_item != Some(requested_active_item) {
+                                interrupt.send(InterruptAction {
+                                    action_taker: *pawn_entity,
+                                    reason: ActionInterruption::ItemSwitch,
+                                });
+                            }
                             inventory_component.active_item = Some(requested_active_item);
                             o_net.send(OutgoingReliableServerMessage {
                                 handle: event.handle,
@@ -0,0 +1,246 @@
+use bevy::prelude::{
+    AssetServer, BuildChildren, ButtonBundle, Changed, Color, Commands, Component,
+    DespawnRecursiveExt, Entity, EventWriter, Local, NodeBundle, Query, Res, ResMut, Resource,
+    TextBundle, With,
+};
+use bevy::text::TextStyle;
+use bevy::ui::{
+    AlignItems, FlexDirection, Interaction, JustifyContent, PositionType, Size, Style, UiRect, Val,
+};
+use networking::client::{Connection, ConnectionStatus};
+use ui::button::{SFButton, HOVERED_BUTTON};
+use ui::fonts::ARIZONE_FONT;
+use ui::text_input::INPUT_TEXT_BG_HOVER;
+
+use crate::build::{
+    EnableMainMenu, MainMainMenuRoot, MainMenuState, SIDEBAR_COLOR, SUB_MENU_HEADER_COLOR,
+    TEXT_COLOR, TEXT_INPUT_COLOR,
+};
+
+/// Connection dialog state.
+#[derive(Default, Resource)]
+
+pub struct ConnectionDialogState {
+    /// Description waiting for the main menu to be built before it can be shown.
+    pub pending: Option<String>,
+    pub root: Option<Entity>,
+}
+
+#[derive(Component)]
+
+pub struct ConnectionDialog;
+
+#[derive(Component)]
+
+pub struct ConnectionDialogCloseButton;
+
+/// Queue a dialog when the connection ended and bring back the main menu if we were in-game.
+
+pub(crate) fn connection_failed(
+    connection: Res<Connection>,
+    mut last_status: Local<ConnectionStatus>,
+    mut state: ResMut<ConnectionDialogState>,
+    main_menu_state: Res<MainMenuState>,
+    mut enable_menu: EventWriter<EnableMainMenu>,
+) {
+    if *last_status == connection.status {
+        return;
+    }
+    *last_status = connection.status.clone();
+
+    match connection.status.failure_description() {
+        Some(description) => {
+            state.pending = Some(description);
+            if !main_menu_state.enabled {
+                enable_menu.send(EnableMainMenu { enable: true });
+            }
+        }
+        None => {}
+    }
+}
+
+/// Build the connection dialog on top of the main menu.
+
+pub(crate) fn show_connection_dialog(
+    mut state: ResMut<ConnectionDialogState>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    root_node_query: Query<Entity, With<MainMainMenuRoot>>,
+) {
+    if state.pending.is_none() {
+        return;
+    }
+    let mut root_node_option = None;
+    for root in root_node_query.iter() {
+        root_node_option = Some(root);
+        break;
+    }
+    let root_node;
+    match root_node_option {
+        Some(n) => {
+            root_node = n;
+        }
+        None => {
+            // Main menu is still being built.
+            return;
+        }
+    }
+
+    match state.root {
+        Some(old) => {
+            commands.entity(old).despawn_recursive();
+        }
+        None => {}
+    }
+
+    let description = state.pending.take().unwrap();
+    let arizone_font = asset_server.load(ARIZONE_FONT);
+
+    let entity = commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.5).into(),
+            ..Default::default()
+        })
+        .insert(ConnectionDialog)
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(40.), Val::Percent(25.)),
+                        flex_direction: FlexDirection::ColumnReverse,
+                        ..Default::default()
+                    },
+                    background_color: SIDEBAR_COLOR.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    // Close button.
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(100.), Val::Percent(20.)),
+                                justify_content: JustifyContent::Center,
+                                margin: UiRect::new(
+                                    Val::Undefined,
+                                    Val::Undefined,
+                                    Val::Undefined,
+                                    Val::Percent(3.),
+                                ),
+                                ..Default::default()
+                            },
+                            background_color: SIDEBAR_COLOR.into(),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn(ButtonBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Percent(25.), Val::Percent(100.)),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                })
+                                .insert((
+                                    SFButton {
+                                        pressed_color: Color::BLUE,
+                                        default_color_option: Some(HOVERED_BUTTON),
+                                        default_parent_color: HOVERED_BUTTON,
+                                        hovered_color: INPUT_TEXT_BG_HOVER,
+                                        color_parent: false,
+                                        ..Default::default()
+                                    },
+                                    ConnectionDialogCloseButton,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Ok",
+                                        TextStyle {
+                                            font: arizone_font.clone(),
+                                            font_size: 10.,
+                                            color: TEXT_INPUT_COLOR,
+                                        },
+                                    ));
+                                });
+                        });
+                    // Body.
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(100.), Val::Percent(55.)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                padding: UiRect::all(Val::Percent(3.)),
+                                ..Default::default()
+                            },
+                            background_color: SIDEBAR_COLOR.into(),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                description,
+                                TextStyle {
+                                    font: arizone_font.clone(),
+                                    font_size: 11.,
+                                    color: TEXT_COLOR,
+                                },
+                            ));
+                        });
+                    // Header.
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(100.), Val::Percent(20.)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: SUB_MENU_HEADER_COLOR.into(),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Disconnected",
+                                TextStyle {
+                                    font: arizone_font.clone(),
+                                    font_size: 12.,
+                                    color: TEXT_COLOR,
+                                },
+                            ));
+                        });
+                });
+        })
+        .id();
+
+    commands.entity(root_node).add_child(entity);
+    state.root = Some(entity);
+}
+
+/// Close the connection dialog.
+
+pub(crate) fn close_connection_dialog(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<ConnectionDialogCloseButton>)>,
+    mut state: ResMut<ConnectionDialogState>,
+    mut commands: Commands,
+) {
+    for interaction in button_query.iter() {
+        match interaction {
+            Interaction::Clicked => match state.root {
+                Some(root) => {
+                    commands.entity(root).despawn_recursive();
+                    state.root = None;
+                }
+                None => {}
+            },
+            _ => {}
+        }
+    }
+}
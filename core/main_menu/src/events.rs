@@ -216,6 +216,10 @@ pub(crate) fn connect_to_server_button(
                     continue;
                 }
 
+                // Resume tokens are only valid on the server that handed them out.
+                if preferences.server_address != server_address {
+                    preferences.resume_token = None;
+                }
                 preferences.account_name = account_name;
                 preferences.server_address = server_address;
                 connect.send(ConnectToServer);
@@ -3,13 +3,16 @@ use bevy::prelude::{Commands, EventReader};
 use bevy::prelude::{DespawnRecursiveExt, ResMut};
 use networking::client::IncomingReliableServerMessage;
 
-use crate::build::{EnableMainMenu, MainMenuState};
+use crate::build::{EnableMainMenu, MainMenuState, PlayMenuState};
+use crate::dialog::ConnectionDialogState;
 
 /// System that toggles the visiblity of the main menu based on an event.
 
 pub(crate) fn hide_main_menu(
     mut enable_events: EventReader<EnableMainMenu>,
     mut state: ResMut<MainMenuState>,
+    mut play_menu_state: ResMut<PlayMenuState>,
+    mut dialog_state: ResMut<ConnectionDialogState>,
     mut commands: Commands,
 ) {
     for event in enable_events.iter() {
@@ -22,6 +25,10 @@ pub(crate) fn hide_main_menu(
             state.root = None;
             commands.entity(state.camera.unwrap()).despawn_recursive();
             state.camera = None;
+            // Sub menus are despawned along with the root, allow them to be built again.
+            play_menu_state.enabled = false;
+            play_menu_state.root = None;
+            dialog_state.root = None;
         }
     }
 }
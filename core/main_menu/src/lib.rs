@@ -2,6 +2,8 @@
 
 /// Builds the main menu.
 pub mod build;
+/// Dialog explaining why the connection ended.
+pub mod dialog;
 /// Main menu UI events.
 pub mod events;
 /// Hides the main menu.
@@ -7,6 +7,9 @@ use crate::{
         startup_show_menu, AutoFillConnectSubMenu, EnableMainMenu, EnablePlayMenu, MainMenuLabel,
         MainMenuState, PlayMenuState, MAIN_BG_COLOR,
     },
+    dialog::{
+        close_connection_dialog, connection_failed, show_connection_dialog, ConnectionDialogState,
+    },
     events::{button_presses, connect_to_server_button, space_frontiers_link, starwolves_link},
     hide::{confirm_connection, hide_main_menu},
 };
@@ -38,7 +41,11 @@ impl Plugin for MainMenuPlugin {
                 .add_system(auto_fill_connect_menu)
                 .add_event::<AutoFillConnectSubMenu>()
                 .add_system(on_submenu_connect_creation)
-                .add_system(confirm_connection);
+                .add_system(confirm_connection)
+                .init_resource::<ConnectionDialogState>()
+                .add_system(connection_failed)
+                .add_system(show_connection_dialog.after(MainMenuLabel::BuildMainMenu))
+                .add_system(close_connection_dialog);
         }
     }
 }
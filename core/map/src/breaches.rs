@@ -0,0 +1,114 @@
+use bevy::prelude::{EventReader, EventWriter, Query, Res, ResMut};
+use entity::senser::{to_doryen_coordinates, WORLD_WIDTH_CELLS};
+use gridmap::destruction::{HullBreach, HullBreachSealed, HullBreaches};
+use networking::server::{ConnectedPlayer, OutgoingReliableServerMessage};
+use resources::math::Vec2Int;
+
+use crate::{
+    map::{get_overlay_tile_item, MapHolders, OverlayTile},
+    map_input::MapData,
+    net::MapServerMessage,
+};
+
+/// Index of a mini-map cell in the atmospherics cache of a map holder.
+
+fn atmospherics_cache_index(cell_id: Vec2Int) -> usize {
+    let (x, y) = to_doryen_coordinates(cell_id.x, cell_id.y);
+    x + y * WORLD_WIDTH_CELLS
+}
+
+/// Mark hull breaches on the mini-map.
+
+pub(crate) fn map_hull_breaches(
+    mut events: EventReader<HullBreach>,
+    mut map_data: ResMut<MapData>,
+    mut map_holders: ResMut<MapHolders>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut server: EventWriter<OutgoingReliableServerMessage<MapServerMessage>>,
+) {
+    for event in events.iter() {
+        let cell_id = Vec2Int {
+            x: event.cell.id.x,
+            y: event.cell.id.z,
+        };
+        let item = get_overlay_tile_item(&OverlayTile::Red);
+        map_data.data.insert(cell_id, item);
+
+        for connected_player in connected_players.iter() {
+            if !connected_player.connected {
+                continue;
+            }
+            server.send(OutgoingReliableServerMessage {
+                handle: connected_player.handle,
+                message: MapServerMessage::MapDefaultAddition(cell_id.x, cell_id.y, item),
+            });
+        }
+        let index = atmospherics_cache_index(cell_id);
+        for holder in map_holders.holders.values_mut() {
+            match holder.cache.get_mut(index) {
+                Some(cache) => {
+                    cache.tile_color = Some(OverlayTile::Red);
+                }
+                None => {}
+            }
+            holder.reset_cache = true;
+        }
+    }
+}
+
+/// Remove sealed hull breaches from the mini-map.
+
+pub(crate) fn map_sealed_hull_breaches(
+    mut events: EventReader<HullBreachSealed>,
+    breaches: Res<HullBreaches>,
+    mut map_data: ResMut<MapData>,
+    mut map_holders: ResMut<MapHolders>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut server: EventWriter<OutgoingReliableServerMessage<MapServerMessage>>,
+) {
+    for event in events.iter() {
+        let cell_id = Vec2Int {
+            x: event.cell.id.x,
+            y: event.cell.id.z,
+        };
+        // Breaches on other decks share the same mini-map tile.
+        if breaches
+            .cells
+            .iter()
+            .any(|cell| cell.id.x == cell_id.x && cell.id.z == cell_id.y)
+        {
+            continue;
+        }
+        match map_data.data.get(&cell_id) {
+            Some(item) => {
+                if *item != get_overlay_tile_item(&OverlayTile::Red) {
+                    continue;
+                }
+            }
+            None => {
+                continue;
+            }
+        }
+        map_data.data.remove(&cell_id);
+
+        for connected_player in connected_players.iter() {
+            if !connected_player.connected {
+                continue;
+            }
+            server.send(OutgoingReliableServerMessage {
+                handle: connected_player.handle,
+                message: MapServerMessage::MapDefaultRemoval(cell_id.x, cell_id.y),
+            });
+        }
+        let index = atmospherics_cache_index(cell_id);
+        for holder in map_holders.holders.values_mut() {
+            match holder.cache.get_mut(index) {
+                Some(cache) => {
+                    cache.tile_color = None;
+                }
+                None => {}
+            }
+            holder.reset_cache = true;
+        }
+    }
+}
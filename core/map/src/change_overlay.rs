@@ -53,6 +53,14 @@ pub(crate) fn change_map_overlay(
                     None => {}
                 }
             }
+        } else if event.display_mode == "power" {
+            if data_link_component
+                .links
+                .contains(&DataLinkType::ShipEngineeringKnowledge)
+            {
+                map_component.display_mode = Some(event.display_mode.clone());
+            }
+            display_atmos_state.holders.remove(&event.entity);
         } else {
             display_atmos_state.holders.remove(&event.entity);
         }
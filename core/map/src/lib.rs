@@ -1,5 +1,7 @@
 //! The 2D mini-map that can display various data with overlays.
 
+/// Mark and clear hull breaches on the mini-map.
+mod breaches;
 /// Manage overlay changes.
 mod change_overlay;
 /// Manage connection events.
@@ -177,36 +177,38 @@ pub(crate) fn incoming_messages(
                 }
             }
         }
+    }
 
-        for message in u_server.iter() {
-            let client_message = message.message.clone();
-            match client_message {
-                MapUnreliableClientMessage::MapViewRange(range_x) => {
-                    match handle_to_entity.map.get(&message.handle) {
-                        Some(player_entity) => {
-                            input_map_view_range.send(InputMap {
-                                handle: message.handle,
-                                entity: *player_entity,
-                                input: MapInput::Range(range_x),
-                            });
-                        }
-                        None => {
-                            warn!("Couldn't find player_entity belonging to MapViewRange sender handle.");
-                        }
+    for message in u_server.iter() {
+        let client_message = message.message.clone();
+        match client_message {
+            MapUnreliableClientMessage::MapViewRange(range_x) => {
+                match handle_to_entity.map.get(&message.handle) {
+                    Some(player_entity) => {
+                        input_map_view_range.send(InputMap {
+                            handle: message.handle,
+                            entity: *player_entity,
+                            input: MapInput::Range(range_x),
+                        });
+                    }
+                    None => {
+                        warn!(
+                            "Couldn't find player_entity belonging to MapViewRange sender handle."
+                        );
                     }
                 }
-                MapUnreliableClientMessage::MapOverlayMouseHoverCell(idx, idy) => {
-                    match handle_to_entity.map.get(&message.handle) {
-                        Some(player_entity) => {
-                            input_map_view_range.send(InputMap {
-                                handle: message.handle,
-                                entity: *player_entity,
-                                input: MapInput::MouseCell(idx, idy),
-                            });
-                        }
-                        None => {
-                            warn!("Couldn't find player_entity belonging to MapMouseHoverCell sender handle.");
-                        }
+            }
+            MapUnreliableClientMessage::MapOverlayMouseHoverCell(idx, idy) => {
+                match handle_to_entity.map.get(&message.handle) {
+                    Some(player_entity) => {
+                        input_map_view_range.send(InputMap {
+                            handle: message.handle,
+                            entity: *player_entity,
+                            input: MapInput::MouseCell(idx, idy),
+                        });
+                    }
+                    None => {
+                        warn!("Couldn't find player_entity belonging to MapMouseHoverCell sender handle.");
                     }
                 }
             }
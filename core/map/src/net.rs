@@ -21,6 +21,7 @@ pub enum MapServerMessage {
     MapOverlayUpdate(Vec<(i16, i16, i16)>),
     MapOverlayHoverData(String),
     MapDefaultAddition(i16, i16, i16),
+    MapDefaultRemoval(i16, i16),
 }
 
 /// This message gets sent at high intervals.
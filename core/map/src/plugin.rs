@@ -6,6 +6,7 @@ use player::plugin::ConfigurationLabel;
 use resources::{is_server::is_server, labels::MapLabels};
 
 use crate::{
+    breaches::{map_hull_breaches, map_sealed_hull_breaches},
     connections::configure,
     map::MapHolders,
     map_input::{
@@ -26,6 +27,8 @@ impl Plugin for MapPlugin {
             app.init_resource::<MapData>()
                 .add_system(change_map_overlay.in_set(MapLabels::ChangeMode))
                 .add_system(request_map_overlay)
+                .add_system(map_hull_breaches)
+                .add_system(map_sealed_hull_breaches)
                 .add_system(map_input.in_set(MapLabels::ChangeMode))
                 .init_resource::<MapHolders>()
                 .add_system(incoming_messages.in_base_set(CoreSet::PreUpdate))
@@ -5,10 +5,13 @@ use std::{
 
 use bevy::prelude::{info, Resource};
 use bevy_renet::renet::{
-    ChannelConfig, ClientAuthentication, ReliableChannelConfig, RenetClient, RenetConnectionConfig,
+    ChannelConfig, ChunkChannelConfig, ClientAuthentication, ReliableChannelConfig, RenetClient,
+    RenetConnectionConfig,
 };
 
-use crate::server::PROTOCOL_ID;
+use crate::plugin::RENET_BLOCKING_MAX_MESSAGE_SIZE;
+use crate::server::{RejectionReason, NETCODE_VERSION, PROTOCOL_ID};
+use bevy::time::{Timer, TimerMode};
 
 /// Resource containing needed for the server.
 
@@ -16,6 +19,10 @@ use crate::server::PROTOCOL_ID;
 pub struct ConnectionPreferences {
     pub account_name: String,
     pub server_address: String,
+    /// Token to claim a pawn transferred from another sector.
+    pub transfer_token: Option<u64>,
+    /// Token to resume control over the pawn held by the server after a lost connection.
+    pub resume_token: Option<u64>,
 }
 
 /// Event that triggers a new server connection.
@@ -36,6 +43,134 @@ use bevy_renet::renet::ConnectToken;
 
 use crate::server::PRIV_KEY;
 
+/// Maximum amount of seconds to wait for the server to confirm a new connection.
+
+pub const CONNECTION_TIMEOUT: f32 = 10.;
+
+/// Amount of automatic reconnect attempts after losing the connection.
+
+pub const MAX_RECONNECT_ATTEMPTS: u8 = 5;
+
+/// Seconds to wait before the first reconnect attempt, doubled with each failed attempt.
+
+pub const RECONNECT_BASE_DELAY: f32 = 1.;
+
+/// Create a new renet client for the preferred server address.
+
+fn new_renet_client(preferences: &ConnectionPreferences) -> Result<RenetClient, String> {
+    let address;
+    let port;
+
+    match preferences.server_address.split_once(":") {
+        Some((ip, port_str)) => {
+            address = ip;
+            match port_str.parse::<u16>() {
+                Ok(p) => {
+                    port = p;
+                }
+                Err(_rr) => {
+                    return Err("Couldn't parse port.".to_string());
+                }
+            };
+        }
+        None => {
+            address = &preferences.server_address;
+            port = SERVER_PORT
+        }
+    }
+
+    let ip_address;
+
+    match address.parse::<IpAddr>() {
+        Ok(add) => {
+            ip_address = add;
+        }
+        Err(_) => {
+            return Err("Invalid server address.".to_string());
+        }
+    }
+
+    let socket_address: SocketAddr = SocketAddr::new(ip_address, port as u16);
+
+    // Sectors of a cluster hosted on the same machine are reached over loopback.
+    let local_address;
+    if ip_address.is_loopback() {
+        local_address = "127.0.0.1:0".to_string();
+    } else {
+        local_address = local_ipaddress::get().unwrap_or_default() + ":0";
+    }
+    let socket;
+    match UdpSocket::bind(local_address) {
+        Ok(s) => {
+            socket = s;
+        }
+        Err(err) => {
+            return Err(format!("Failed to bind udp socket: {}", err));
+        }
+    }
+
+    let channels_config = vec![
+        ChannelConfig::Reliable(ReliableChannelConfig {
+            packet_budget: 6000,
+            max_message_size: 5900,
+            ..Default::default()
+        }),
+        ChannelConfig::Unreliable(Default::default()),
+        ChannelConfig::Chunk(ChunkChannelConfig {
+            max_message_size: RENET_BLOCKING_MAX_MESSAGE_SIZE,
+            ..Default::default()
+        }),
+    ];
+
+    let connection_config = RenetConnectionConfig {
+        send_channels_config: channels_config.clone(),
+        receive_channels_config: channels_config,
+
+        ..Default::default()
+    };
+    let current_time;
+    match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(t) => {
+            current_time = t;
+        }
+        Err(err) => {
+            return Err(format!("Invalid system time: {}", err));
+        }
+    }
+    let client_id = current_time.as_millis() as u64;
+
+    info!("Connecting to {}...", socket_address);
+
+    let connect_token;
+    match ConnectToken::generate(
+        current_time,
+        PROTOCOL_ID,
+        120,
+        client_id,
+        120,
+        vec![socket_address],
+        None,
+        &PRIV_KEY,
+    ) {
+        Ok(t) => {
+            connect_token = t;
+        }
+        Err(err) => {
+            return Err(format!("Token generation failed: {:?}", err));
+        }
+    }
+
+    match RenetClient::new(
+        current_time,
+        socket,
+        connection_config,
+        ClientAuthentication::Secure { connect_token },
+    ) {
+        Ok(c) => Ok(c),
+        Err(err) => Err(format!("Failed to create client: {:?}", err)),
+    }
+}
+
 pub(crate) fn connect_to_server(
     mut event: EventReader<ConnectToServer>,
     mut commands: Commands,
@@ -45,123 +180,81 @@ pub(crate) fn connect_to_server(
 ) {
     for _ in event.iter() {
         match connection_state.status {
-            ConnectionStatus::None => {
-                let address;
-                let port;
-
-                match preferences.server_address.split_once(":") {
-                    Some((ip, port_str)) => {
-                        address = ip;
-                        match port_str.parse::<u16>() {
-                            Ok(p) => {
-                                port = p;
-                            }
-                            Err(_rr) => {
-                                warn!("Couldn't connect: couldn't parse port.");
-                                continue;
-                            }
-                        };
-                    }
-                    None => {
-                        address = &preferences.server_address;
-                        port = SERVER_PORT
-                    }
-                }
-
-                let ip_address;
-
-                match address.parse::<IpAddr>() {
-                    Ok(add) => {
-                        ip_address = add;
-                    }
-                    Err(_) => {
-                        warn!("Couldn't connect: invalid server address.");
-                        continue;
-                    }
-                }
-
-                let socket_address: SocketAddr = SocketAddr::new(ip_address, port as u16);
+            ConnectionStatus::Connecting | ConnectionStatus::Connected => {
+                continue;
+            }
+            ConnectionStatus::Reconnecting => {}
+            _ => {
+                connection_state.reconnect_attempts = 0;
+            }
+        }
 
-                let socket;
-                match UdpSocket::bind(local_ipaddress::get().unwrap_or_default() + ":0") {
-                    Ok(s) => {
-                        socket = s;
-                    }
-                    Err(err) => {
-                        warn!("Failed to bind udp socket: {}", err);
-                        continue;
+        match new_renet_client(&preferences) {
+            Ok(renet_client) => {
+                match preferences.transfer_token {
+                    Some(token) => {
+                        client.send(OutgoingReliableClientMessage {
+                            message: NetworkingClientMessage::SectorTransfer(token),
+                        });
                     }
+                    None => {}
                 }
-
-                let channels_config = vec![
-                    ChannelConfig::Reliable(ReliableChannelConfig {
-                        packet_budget: 6000,
-                        max_message_size: 5900,
-                        ..Default::default()
-                    }),
-                    ChannelConfig::Unreliable(Default::default()),
-                    ChannelConfig::Chunk(Default::default()),
-                ];
-
-                let connection_config = RenetConnectionConfig {
-                    send_channels_config: channels_config.clone(),
-                    receive_channels_config: channels_config,
-
-                    ..Default::default()
-                };
-                let current_time = SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap();
-                let client_id = current_time.as_millis() as u64;
-
-                info!("Connecting to {}...", socket_address);
-
-                match ConnectToken::generate(
-                    current_time,
-                    PROTOCOL_ID,
-                    120,
-                    client_id,
-                    120,
-                    vec![socket_address],
-                    None,
-                    &PRIV_KEY,
-                ) {
-                    Ok(connect_token) => {
-                        let renet_client = RenetClient::new(
-                            current_time,
-                            socket,
-                            connection_config,
-                            ClientAuthentication::Secure { connect_token },
-                        )
-                        .unwrap();
-
+                match preferences.resume_token {
+                    Some(token) => {
                         client.send(OutgoingReliableClientMessage {
-                            message: NetworkingClientMessage::Account(
-                                preferences.account_name.clone(),
-                            ),
+                            message: NetworkingClientMessage::ResumeToken(token),
                         });
-                        commands.insert_resource(renet_client);
-
-                        connection_state.status = ConnectionStatus::Connecting;
-                    }
-                    Err(err) => {
-                        warn!("Token generation failed: {:?}", err);
                     }
+                    None => {}
                 }
+                client.send(OutgoingReliableClientMessage {
+                    message: NetworkingClientMessage::Account(
+                        preferences.account_name.clone(),
+                        NETCODE_VERSION,
+                    ),
+                });
+                commands.insert_resource(renet_client);
+
+                connection_state.status = ConnectionStatus::Connecting;
+                connection_state.timeout = Timer::from_seconds(CONNECTION_TIMEOUT, TimerMode::Once);
             }
-            ConnectionStatus::Connecting => {
-                continue;
-            }
-            ConnectionStatus::Connected => {
-                continue;
+            Err(err) => {
+                warn!("Couldn't connect: {}", err);
+                connection_state.failed(ConnectionStatus::Failed(err));
             }
         }
     }
 }
 
+/// Client connection state.
 #[derive(Default, Resource)]
 pub struct Connection {
     pub status: ConnectionStatus,
+    /// Time the server has left to confirm the connection.
+    pub timeout: Timer,
+    /// Reconnect attempts made since the connection was lost.
+    pub reconnect_attempts: u8,
+    /// Backoff until the next reconnect attempt.
+    pub reconnect_timer: Timer,
+}
+
+impl Connection {
+    /// Schedule the next reconnect attempt if we are reconnecting and have attempts left, otherwise settle on the supplied status.
+    pub fn failed(&mut self, status: ConnectionStatus) {
+        if self.reconnect_attempts > 0 && self.reconnect_attempts < MAX_RECONNECT_ATTEMPTS {
+            self.schedule_reconnect();
+        } else {
+            self.reconnect_attempts = 0;
+            self.status = status;
+        }
+    }
+    /// Wait with exponential backoff before the next reconnect attempt.
+    pub fn schedule_reconnect(&mut self) {
+        let delay = RECONNECT_BASE_DELAY * 2f32.powi(self.reconnect_attempts as i32);
+        self.status = ConnectionStatus::Reconnecting;
+        self.reconnect_timer = Timer::from_seconds(delay, TimerMode::Once);
+        info!("Reconnecting in {} seconds.", delay);
+    }
 }
 
 #[derive(Default, Debug, Clone, Eq, PartialEq, Hash)]
@@ -170,6 +263,40 @@ pub enum ConnectionStatus {
     None,
     Connecting,
     Connected,
+    /// Lost the connection and waiting for the next reconnect attempt.
+    Reconnecting,
+    /// The server did not respond in time.
+    TimedOut,
+    /// The server refused the connection.
+    Rejected(RejectionReason),
+    /// The server removed us with the supplied reason.
+    Kicked(String),
+    /// The connection could not be set up client-side.
+    Failed(String),
+}
+
+impl ConnectionStatus {
+    /// Human readable description of why the connection ended, if it ended.
+    pub fn failure_description(&self) -> Option<String> {
+        match self {
+            ConnectionStatus::TimedOut => Some("The server did not respond in time.".to_string()),
+            ConnectionStatus::Rejected(reason) => match reason {
+                RejectionReason::ServerFull => Some("The server is full.".to_string()),
+                RejectionReason::ProtocolMismatch(version) => Some(format!(
+                    "Client version mismatch, the server runs netcode version {} while you run {}.",
+                    version, NETCODE_VERSION
+                )),
+                RejectionReason::Banned(reason) => {
+                    Some(format!("You are banned from this server: {}", reason))
+                }
+            },
+            ConnectionStatus::Kicked(reason) => {
+                Some(format!("You were kicked from the server: {}", reason))
+            }
+            ConnectionStatus::Failed(reason) => Some(reason.clone()),
+            _ => None,
+        }
+    }
 }
 
 use bevy::prelude::EventWriter;
@@ -179,6 +306,11 @@ use bevy::prelude::EventWriter;
 pub fn connected(connection: Res<Connection>) -> bool {
     matches!(connection.status, ConnectionStatus::Connected)
 }
+/// System run run_if, true while waiting for the next reconnect attempt.
+
+pub fn is_reconnecting(connection: Res<Connection>) -> bool {
+    matches!(connection.status, ConnectionStatus::Reconnecting)
+}
 /// System run run_if with iyes_loopless. The earliest server messages (for setup_ui, boarding etc.)
 /// come in while in the connecting stage.
 
@@ -433,18 +565,67 @@ use crate::server::NetworkingServerMessage;
 pub(crate) fn confirm_connection(
     mut client1: EventReader<IncomingReliableServerMessage<NetworkingServerMessage>>,
     mut connected_state: ResMut<Connection>,
+    mut preferences: ResMut<ConnectionPreferences>,
+    mut commands: Commands,
 ) {
     for message in client1.iter() {
         let player_message = message.message.clone();
         match player_message {
             NetworkingServerMessage::Awoo => {
                 connected_state.status = ConnectionStatus::Connected;
+                connected_state.reconnect_attempts = 0;
+                preferences.transfer_token = None;
                 info!("Connected.");
             }
+            NetworkingServerMessage::Redirect(address, token) => {
+                info!("Moving to sector server {}.", address);
+                preferences.server_address = address;
+                preferences.transfer_token = Some(token);
+                preferences.resume_token = None;
+                // Connect on the next attempt so outgoing messages do not reach the previous server.
+                commands.remove_resource::<RenetClient>();
+                connected_state.reconnect_attempts = 0;
+                connected_state.status = ConnectionStatus::Reconnecting;
+                connected_state.reconnect_timer = Timer::from_seconds(0., TimerMode::Once);
+            }
+            NetworkingServerMessage::ResumeToken(token) => {
+                preferences.resume_token = Some(token);
+            }
+            NetworkingServerMessage::Rejected(reason) => {
+                warn!("Server rejected connection: {:?}", reason);
+                preferences.resume_token = None;
+                connected_state.reconnect_attempts = 0;
+                connected_state.status = ConnectionStatus::Rejected(reason);
+            }
+            NetworkingServerMessage::Kicked(reason) => {
+                warn!("Kicked from server: {}", reason);
+                // The server does not hold the pawn of kicked players.
+                preferences.resume_token = None;
+                connected_state.reconnect_attempts = 0;
+                connected_state.status = ConnectionStatus::Kicked(reason);
+            }
         }
     }
 }
 
+/// Consider the connection timed out if the server did not confirm it in time.
+
+pub(crate) fn connection_timeout(
+    mut connected_state: ResMut<Connection>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    if !matches!(connected_state.status, ConnectionStatus::Connecting) {
+        return;
+    }
+    connected_state.timeout.tick(time.delta());
+    if connected_state.timeout.finished() {
+        warn!("Connection timed out.");
+        commands.remove_resource::<RenetClient>();
+        connected_state.failed(ConnectionStatus::TimedOut);
+    }
+}
+
 pub(crate) fn on_disconnect(
     client: Res<RenetClient>,
     mut connected_state: ResMut<Connection>,
@@ -453,9 +634,41 @@ pub(crate) fn on_disconnect(
     match client.disconnected() {
         Some(d) => {
             warn!("Disconnected from server: [{}]", d);
-            connected_state.status = ConnectionStatus::None;
             commands.remove_resource::<RenetClient>();
+            match connected_state.status {
+                ConnectionStatus::Connected => {
+                    connected_state.reconnect_attempts = 0;
+                    connected_state.schedule_reconnect();
+                }
+                ConnectionStatus::Connecting => {
+                    connected_state.failed(ConnectionStatus::Failed(format!(
+                        "Disconnected from server: {}",
+                        d
+                    )));
+                }
+                // Already informed of the reason by the server.
+                _ => {}
+            }
         }
         None => {}
     }
 }
+
+/// Attempt to reconnect once the backoff timer has finished.
+
+pub(crate) fn reconnect(
+    mut connected_state: ResMut<Connection>,
+    time: Res<Time>,
+    mut connect: EventWriter<ConnectToServer>,
+) {
+    connected_state.reconnect_timer.tick(time.delta());
+    if connected_state.reconnect_timer.just_finished() {
+        connected_state.reconnect_attempts += 1;
+        info!(
+            "Reconnect attempt {}/{}.",
+            connected_state.reconnect_attempts, MAX_RECONNECT_ATTEMPTS
+        );
+        connect.send(ConnectToServer);
+    }
+}
+use bevy::prelude::Time;
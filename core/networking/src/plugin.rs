@@ -2,12 +2,16 @@ use bevy::prelude::{resource_exists, App, CoreSet, IntoSystemConfig, Plugin};
 use bevy_renet::{renet::RenetClient, RenetClientPlugin, RenetServerPlugin};
 use resources::is_server::is_server;
 
-use super::server::{souls, startup_server_listen_connections};
+use super::server::{
+    disconnect_clients, process_pending_disconnects, souls, startup_server_listen_connections,
+    DisconnectClient, PendingDisconnects,
+};
 use crate::{
     client::{
-        confirm_connection, connect_to_server, connected, is_client_connected, on_disconnect,
-        receive_incoming_reliable_server_messages, receive_incoming_unreliable_server_messages,
-        ConnectToServer, Connection, ConnectionPreferences, IncomingRawReliableServerMessage,
+        confirm_connection, connect_to_server, connection_timeout, is_client_connected,
+        is_reconnecting, on_disconnect, receive_incoming_reliable_server_messages,
+        receive_incoming_unreliable_server_messages, reconnect, ConnectToServer, Connection,
+        ConnectionPreferences, IncomingRawReliableServerMessage,
         IncomingRawUnreliableServerMessage,
     },
     messaging::{
@@ -29,6 +33,10 @@ impl Plugin for NetworkingPlugin {
             app.add_plugin(RenetServerPlugin::default())
                 .insert_resource(startup_server_listen_connections())
                 .add_system(souls)
+                .add_event::<DisconnectClient>()
+                .init_resource::<PendingDisconnects>()
+                .add_system(disconnect_clients)
+                .add_system(process_pending_disconnects.after(disconnect_clients))
                 .add_event::<IncomingRawReliableClientMessage>()
                 .add_event::<IncomingRawUnreliableClientMessage>()
                 .add_system(
@@ -62,7 +70,9 @@ impl Plugin for NetworkingPlugin {
                 .add_event::<IncomingRawReliableServerMessage>()
                 .add_event::<IncomingRawUnreliableServerMessage>()
                 .add_system(confirm_connection.run_if(is_client_connected))
-                .add_system(on_disconnect.run_if(connected));
+                .add_system(connection_timeout.run_if(is_client_connected))
+                .add_system(on_disconnect.run_if(resource_exists::<RenetClient>()))
+                .add_system(reconnect.run_if(is_reconnecting));
         }
 
         app.init_resource::<Typenames>()
@@ -77,3 +87,5 @@ impl Plugin for NetworkingPlugin {
 pub const RENET_RELIABLE_CHANNEL_ID: u8 = 0;
 pub const RENET_UNRELIABLE_CHANNEL_ID: u8 = 1;
 pub const RENET_BLOCKING_CHANNEL_ID: u8 = 2;
+/// Largest message the chunk channel accepts in bytes.
+pub const RENET_BLOCKING_MAX_MESSAGE_SIZE: u64 = 1024 * 1024;
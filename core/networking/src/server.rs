@@ -5,13 +5,20 @@ use bevy::{
 use serde::{Deserialize, Serialize};
 use typename::TypeName;
 
-use std::{collections::HashMap, net::UdpSocket, time::SystemTime};
+use resources::sectors::local_sector;
+use std::{
+    collections::HashMap,
+    net::{SocketAddr, UdpSocket},
+    time::SystemTime,
+};
 
 use bevy_renet::renet::{
-    ChannelConfig, ReliableChannelConfig, RenetConnectionConfig, RenetServer, ServerAuthentication,
-    ServerConfig,
+    ChannelConfig, ChunkChannelConfig, ReliableChannelConfig, RenetConnectionConfig, RenetServer,
+    ServerAuthentication, ServerConfig,
 };
 
+use crate::plugin::RENET_BLOCKING_MAX_MESSAGE_SIZE;
+
 /// The network port the server will listen use for connections.
 
 pub const SERVER_PORT: u16 = 57713;
@@ -20,14 +27,34 @@ pub const SERVER_PORT: u16 = 57713;
 
 pub(crate) const PROTOCOL_ID: u64 = 7;
 
+/// Version of the netcode messages, checked by the server during account verification.
+/// Unlike a [PROTOCOL_ID] mismatch, which makes the connection silently time out, a mismatching netcode version gets rejected with a readable reason.
+
+pub const NETCODE_VERSION: u32 = 3;
+
+/// Maximum amount of connections the transport layer accepts.
+
+pub(crate) const MAX_CONNECTIONS: usize = 128;
+
+/// Maximum amount of players. Lower than [MAX_CONNECTIONS] so that clients of a full server can still be told why they got rejected.
+
+pub const MAX_PLAYERS: usize = 120;
+
 pub(crate) const PRIV_KEY: [u8; 32] = *b"(=^.^=)(=^.^=)(=^.^=)(=^.^=)(=^.";
 
 /// Start server and open and listen to port.
 
 pub(crate) fn startup_server_listen_connections() -> RenetServer {
-    let server_addr = (local_ipaddress::get().unwrap_or_default() + ":" + &SERVER_PORT.to_string())
-        .parse()
-        .unwrap();
+    // Servers of a galaxy cluster listen on the game address of their sector.
+    let server_addr: SocketAddr = match local_sector() {
+        Some(sector) => sector
+            .game_address
+            .parse()
+            .expect("Invalid game address of sector."),
+        None => (local_ipaddress::get().unwrap_or_default() + ":" + &SERVER_PORT.to_string())
+            .parse()
+            .unwrap(),
+    };
     let socket = UdpSocket::bind(server_addr).unwrap();
 
     let channels_config = vec![
@@ -37,7 +64,10 @@ pub(crate) fn startup_server_listen_connections() -> RenetServer {
             ..Default::default()
         }),
         ChannelConfig::Unreliable(Default::default()),
-        ChannelConfig::Chunk(Default::default()),
+        ChannelConfig::Chunk(ChunkChannelConfig {
+            max_message_size: RENET_BLOCKING_MAX_MESSAGE_SIZE,
+            ..Default::default()
+        }),
     ];
 
     let connection_config = RenetConnectionConfig {
@@ -47,7 +77,7 @@ pub(crate) fn startup_server_listen_connections() -> RenetServer {
     };
 
     let server_config = ServerConfig::new(
-        128,
+        MAX_CONNECTIONS,
         PROTOCOL_ID,
         server_addr,
         ServerAuthentication::Secure {
@@ -91,15 +121,131 @@ pub(crate) fn souls(
 
 pub enum NetworkingClientMessage {
     HeartBeat,
-    Account(String),
+    /// Account name and [NETCODE_VERSION] of the client.
+    Account(String, u32),
+    /// Transfer token handed out by the previous sector, sent before [NetworkingClientMessage::Account].
+    SectorTransfer(u64),
+    /// Resume token handed out on a previous connection, sent before [NetworkingClientMessage::Account].
+    ResumeToken(u64),
 }
 
-/// Gets serialized and sent over the net, this is the client message.
+/// Gets serialized and sent over the net, this is the server message.
 #[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
 
 pub enum NetworkingServerMessage {
     Awoo,
+    /// The connection got refused, the server will disconnect the client shortly.
+    Rejected(RejectionReason),
+    /// The client got removed from the server with the supplied reason.
+    Kicked(String),
+    /// The pawn of the client moved to another sector, reconnect to its game address with the transfer token.
+    Redirect(String, u64),
+    /// Secret token to take back control over the pawn when reconnecting after a lost connection.
+    ResumeToken(u64),
+}
+
+/// Reasons for the server to refuse a new connection.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+
+pub enum RejectionReason {
+    ServerFull,
+    /// Contains the [NETCODE_VERSION] of the server.
+    ProtocolMismatch(u32),
+    /// Contains the reason of the ban.
+    Banned(String),
+}
+
+/// Reasons for the server to disconnect a client.
+#[derive(Debug, Clone)]
+
+pub enum DisconnectReason {
+    Rejected(RejectionReason),
+    Kicked(String),
+    /// Game address of the sector the client moves to and its transfer token.
+    Redirected(String, u64),
+}
+
+/// Event to disconnect a client after informing it about the reason.
+
+pub struct DisconnectClient {
+    pub handle: u64,
+    pub reason: DisconnectReason,
+}
+
+/// Clients that have been informed about their disconnection and are about to be disconnected.
+/// The delay gives the reliable channel time to deliver the reason before the connection is closed.
+#[derive(Default, Resource)]
+
+pub struct PendingDisconnects {
+    pub list: Vec<(u64, Timer)>,
+}
+
+/// Seconds between informing a client about its disconnection and closing the connection.
+
+pub const DISCONNECT_DELAY: f32 = 0.5;
+
+/// Inform clients about their disconnection and queue them for disconnecting.
+
+pub(crate) fn disconnect_clients(
+    mut events: EventReader<DisconnectClient>,
+    mut pending: ResMut<PendingDisconnects>,
+    mut server: EventWriter<OutgoingReliableServerMessage<NetworkingServerMessage>>,
+) {
+    for event in events.iter() {
+        let message;
+        match &event.reason {
+            DisconnectReason::Rejected(reason) => {
+                info!("Rejecting [{}]: {:?}", event.handle, reason);
+                message = NetworkingServerMessage::Rejected(reason.clone());
+            }
+            DisconnectReason::Kicked(reason) => {
+                info!("Kicking [{}]: {}", event.handle, reason);
+                message = NetworkingServerMessage::Kicked(reason.clone());
+            }
+            DisconnectReason::Redirected(address, token) => {
+                info!("Redirecting [{}] to {}.", event.handle, address);
+                message = NetworkingServerMessage::Redirect(address.clone(), *token);
+            }
+        }
+        server.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message,
+        });
+        if pending
+            .list
+            .iter()
+            .any(|(handle, _)| handle == &event.handle)
+        {
+            continue;
+        }
+        pending.list.push((
+            event.handle,
+            Timer::from_seconds(DISCONNECT_DELAY, TimerMode::Once),
+        ));
+    }
+}
+
+/// Close connections of clients that were queued for disconnecting.
+
+pub(crate) fn process_pending_disconnects(
+    mut pending: ResMut<PendingDisconnects>,
+    mut server: ResMut<RenetServer>,
+    time: Res<Time>,
+) {
+    let mut finished = vec![];
+    for (handle, timer) in pending.list.iter_mut() {
+        timer.tick(time.delta());
+        if timer.finished() {
+            finished.push(*handle);
+        }
+    }
+    for handle in finished {
+        server.disconnect(handle);
+    }
+    pending.list.retain(|(_, timer)| !timer.finished());
 }
+use bevy::prelude::Time;
+use bevy::time::{Timer, TimerMode};
 
 /// This message gets sent at high intervals.
 #[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
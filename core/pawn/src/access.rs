@@ -0,0 +1,139 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use bevy::prelude::{info, Component, ResMut, Resource};
+use serde::{Deserialize, Serialize};
+
+/// An access level doors and other devices can be restricted to.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct AccessLevel {
+    pub id: String,
+    pub name: String,
+}
+
+/// A crew job, the access levels it is issued and how it spawns.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct Job {
+    pub id: String,
+    /// Job title.
+    pub name: String,
+    pub access: Vec<String>,
+    /// Radio channels the job can talk and listen on.
+    #[serde(default)]
+    pub radio_channels: Vec<String>,
+    /// Maximum amount of players with this job per round, unlimited if none.
+    #[serde(default)]
+    pub slots: Option<u16>,
+    /// Entity type identifiers of the items the job spawns with.
+    #[serde(default)]
+    pub starting_inventory: Vec<String>,
+    /// Spawn point types the job spawns at, any spawn point if empty.
+    #[serde(default)]
+    pub spawn_point_types: Vec<String>,
+}
+
+/// Resource containing the access levels and jobs of the ship, loaded from data/settings/access.ron.
+#[derive(Serialize, Deserialize, Resource, Default, Clone)]
+
+pub struct AccessConfig {
+    pub levels: Vec<AccessLevel>,
+    pub jobs: Vec<Job>,
+    /// Job assigned to pawns without a valid job.
+    pub default_job: String,
+    /// Access level required to operate ID consoles.
+    pub id_console_access: String,
+    /// Access level required to make ship-wide announcements from terminals.
+    pub announcement_access: String,
+}
+
+impl AccessConfig {
+    /// Get a job, falls back to the default job.
+    pub fn job(&self, id: &str) -> Option<&Job> {
+        match self.jobs.iter().find(|j| j.id == id) {
+            Some(j) => Some(j),
+            None => self.jobs.iter().find(|j| j.id == self.default_job),
+        }
+    }
+    pub fn level(&self, id: &str) -> Option<&AccessLevel> {
+        self.levels.iter().find(|l| l.id == id)
+    }
+    /// Job to assign for a requested job. The requested job if it has a slot left, otherwise the default job or any other job with a slot left.
+    /// None if every job is full.
+    pub fn open_job(&self, requested: &str, job_assignments: &JobAssignments) -> Option<String> {
+        for id in [requested, self.default_job.as_str()] {
+            match self.jobs.iter().find(|j| j.id == id) {
+                Some(job) => {
+                    if job_assignments.is_available(job) {
+                        return Some(job.id.clone());
+                    }
+                }
+                None => {}
+            }
+        }
+        self.jobs
+            .iter()
+            .find(|j| job_assignments.is_available(j))
+            .map(|j| j.id.clone())
+    }
+}
+
+/// Load access levels and jobs from drive.
+
+pub(crate) fn load_access_config(mut config: ResMut<AccessConfig>) {
+    let path = Path::new("data").join("settings").join("access.ron");
+    let raw_ron: String =
+        fs::read_to_string(path).expect("Error reading settings access.ron from drive.");
+    *config = ron::from_str(&raw_ron).expect("Error parsing settings access.ron String.");
+
+    info!(
+        "Loaded {} access levels and {} jobs.",
+        config.levels.len(),
+        config.jobs.len()
+    );
+}
+
+/// Resource with the amount of players assigned to each job this round.
+#[derive(Resource, Default)]
+
+pub struct JobAssignments {
+    pub filled: HashMap<String, u16>,
+}
+
+impl JobAssignments {
+    /// Whether the job has a slot left this round.
+    pub fn is_available(&self, job: &Job) -> bool {
+        match job.slots {
+            Some(slots) => self.filled.get(&job.id).copied().unwrap_or(0) < slots,
+            None => true,
+        }
+    }
+    /// Slots left of a job this round, none if unlimited.
+    pub fn slots_left(&self, job: &Job) -> Option<u16> {
+        job.slots
+            .map(|slots| slots.saturating_sub(self.filled.get(&job.id).copied().unwrap_or(0)))
+    }
+    pub fn assign(&mut self, job_id: &str) {
+        *self.filled.entry(job_id.to_string()).or_default() += 1;
+    }
+    /// Free all slots for a new round.
+    pub fn clear(&mut self) {
+        self.filled.clear();
+    }
+}
+
+/// Radio channels a pawn can talk and listen on.
+#[derive(Component, Default, Clone, Debug)]
+
+pub struct RadioChannels {
+    pub channels: Vec<String>,
+}
+
+/// ID card component, carries the access levels of its holder.
+#[derive(Component, Default, Clone, Debug)]
+
+pub struct IdCard {
+    pub owner_name: String,
+    pub job: String,
+    pub access: Vec<String>,
+}
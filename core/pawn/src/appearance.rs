@@ -0,0 +1,190 @@
+use std::{fs, path::Path};
+
+use bevy::prelude::{
+    info, Changed, Component, Entity, EventReader, EventWriter, Query, Res, ResMut, Resource,
+};
+use entity::{sensable::Sensable, showcase::Showcase, spawning_events::SpawnClientEntity};
+use networking::server::{HandleToEntity, OutgoingReliableServerMessage};
+use serde::{Deserialize, Serialize};
+
+use crate::net::PawnServerMessage;
+
+/// Appearance of a character by the ids of the options in [AppearanceConfig].
+#[derive(Serialize, Deserialize, Component, Default, Clone, Debug, PartialEq)]
+
+pub struct CharacterAppearance {
+    pub body: String,
+    pub skin_tone: String,
+    pub hair_style: String,
+    pub hair_color: String,
+}
+
+/// A body variant, swaps in the base mesh of the character.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct BodyVariant {
+    pub id: String,
+    pub name: String,
+    /// Mesh asset relative to the assets folder of the entity.
+    pub mesh: String,
+    /// Whether generated names for this body are masculine.
+    pub masculine: bool,
+}
+
+/// An appearance option that adds a mesh to the character.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct MeshOption {
+    pub id: String,
+    pub name: String,
+    /// Mesh asset relative to the assets folder of the entity, none for no mesh.
+    pub mesh: Option<String>,
+}
+
+/// An appearance option that tints materials of the character.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct ColorOption {
+    pub id: String,
+    pub name: String,
+    pub color: (f32, f32, f32),
+}
+
+/// Resource containing the character appearance options, loaded from data/settings/appearance.ron.
+#[derive(Serialize, Deserialize, Resource, Default, Clone, Debug)]
+
+pub struct AppearanceConfig {
+    pub bodies: Vec<BodyVariant>,
+    pub skin_tones: Vec<ColorOption>,
+    pub hair_styles: Vec<MeshOption>,
+    pub hair_colors: Vec<ColorOption>,
+}
+
+impl AppearanceConfig {
+    pub fn body(&self, id: &str) -> Option<&BodyVariant> {
+        self.bodies.iter().find(|o| o.id == id)
+    }
+    pub fn skin_tone(&self, id: &str) -> Option<&ColorOption> {
+        self.skin_tones.iter().find(|o| o.id == id)
+    }
+    pub fn hair_style(&self, id: &str) -> Option<&MeshOption> {
+        self.hair_styles.iter().find(|o| o.id == id)
+    }
+    pub fn hair_color(&self, id: &str) -> Option<&ColorOption> {
+        self.hair_colors.iter().find(|o| o.id == id)
+    }
+    /// Whether every part of the appearance is a known option.
+    pub fn is_valid(&self, appearance: &CharacterAppearance) -> bool {
+        self.body(&appearance.body).is_some()
+            && self.skin_tone(&appearance.skin_tone).is_some()
+            && self.hair_style(&appearance.hair_style).is_some()
+            && self.hair_color(&appearance.hair_color).is_some()
+    }
+    /// Appearance made of the first option of every part.
+    pub fn default_appearance(&self) -> CharacterAppearance {
+        CharacterAppearance {
+            body: self
+                .bodies
+                .first()
+                .map(|o| o.id.clone())
+                .unwrap_or_default(),
+            skin_tone: self
+                .skin_tones
+                .first()
+                .map(|o| o.id.clone())
+                .unwrap_or_default(),
+            hair_style: self
+                .hair_styles
+                .first()
+                .map(|o| o.id.clone())
+                .unwrap_or_default(),
+            hair_color: self
+                .hair_colors
+                .first()
+                .map(|o| o.id.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Load the character appearance options from drive.
+
+pub(crate) fn load_appearance_config(mut config: ResMut<AppearanceConfig>) {
+    let path = Path::new("data").join("settings").join("appearance.ron");
+    let raw_ron: String =
+        fs::read_to_string(path).expect("Error reading settings appearance.ron from drive.");
+    *config = ron::from_str(&raw_ron).expect("Error parsing settings appearance.ron String.");
+
+    info!(
+        "Loaded {} body variants, {} skin tones, {} hair styles and {} hair colors.",
+        config.bodies.len(),
+        config.skin_tones.len(),
+        config.hair_styles.len(),
+        config.hair_colors.len()
+    );
+}
+
+/// Send the appearance of pawns to clients that load them in.
+
+pub(crate) fn load_appearance_for_client(
+    mut load_events: EventReader<SpawnClientEntity>,
+    appearances: Query<&CharacterAppearance>,
+    mut server: EventWriter<OutgoingReliableServerMessage<PawnServerMessage>>,
+) {
+    for event in load_events.iter() {
+        match appearances.get(event.entity) {
+            Ok(appearance) => {
+                server.send(OutgoingReliableServerMessage {
+                    handle: event.loader_handle,
+                    message: PawnServerMessage::Appearance(event.entity, appearance.clone()),
+                });
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Send changed appearances to the clients sensing the pawn and to showcase owners.
+
+pub(crate) fn broadcast_changed_appearance(
+    changed: Query<
+        (
+            Entity,
+            &CharacterAppearance,
+            Option<&Sensable>,
+            Option<&Showcase>,
+        ),
+        Changed<CharacterAppearance>,
+    >,
+    handle_to_entity: Res<HandleToEntity>,
+    mut server: EventWriter<OutgoingReliableServerMessage<PawnServerMessage>>,
+) {
+    for (entity, appearance, sensable_option, showcase_option) in changed.iter() {
+        let mut handles = vec![];
+        match showcase_option {
+            Some(showcase) => {
+                handles.push(showcase.handle);
+            }
+            None => {}
+        }
+        match sensable_option {
+            Some(sensable) => {
+                for sensed_by in sensable.sensed_by.iter() {
+                    match handle_to_entity.inv_map.get(sensed_by) {
+                        Some(handle) => {
+                            handles.push(*handle);
+                        }
+                        None => {}
+                    }
+                }
+            }
+            None => {}
+        }
+        for handle in handles {
+            server.send(OutgoingReliableServerMessage {
+                handle,
+                message: PawnServerMessage::Appearance(entity, appearance.clone()),
+            });
+        }
+    }
+}
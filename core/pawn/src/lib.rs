@@ -1,7 +1,13 @@
 //! Pawns are controllable entities by either players or AI.
 
+/// Access levels, jobs and ID cards.
+pub mod access;
 /// Manage pawn actions.
 mod actions;
+/// Character appearance options and their replication.
+pub mod appearance;
+/// Networking.
+pub mod net;
 /// Pawn resources.
 pub mod pawn;
 /// The Bevy plugin of this crate.
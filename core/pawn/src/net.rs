@@ -0,0 +1,12 @@
+use bevy::prelude::Entity;
+use serde::{Deserialize, Serialize};
+use typename::TypeName;
+
+use crate::appearance::CharacterAppearance;
+
+/// Gets serialized and sent over the net, this is the server message.
+#[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
+
+pub enum PawnServerMessage {
+    Appearance(Entity, CharacterAppearance),
+}
@@ -3,27 +3,14 @@ use bevy::{
     prelude::{Component, Transform},
 };
 
-/// Ship authorizations for pawns.
-#[derive(PartialEq)]
-
-pub enum ShipAuthorizationEnum {
-    Security,
-    Common,
-}
-/// Crew jobs for pawns.
-#[derive(Default, Copy, Clone)]
-
-pub enum ShipJobsEnum {
-    #[default]
-    Security,
-    Control,
-}
 /// The component.
 #[derive(Default, Component, Clone)]
 
 pub struct Pawn {
     pub character_name: String,
-    pub job: ShipJobsEnum,
+    /// Id of the job in [crate::access::AccessConfig].
+    pub job: String,
+    pub appearance: CharacterAppearance,
     pub communicator: Communicator,
     pub facing_direction: FacingDirection,
 }
@@ -36,13 +23,6 @@ pub enum Communicator {
     Standard,
     Machine,
 }
-/// Ship authorization component.
-#[derive(Component)]
-
-pub struct ShipAuthorization {
-    pub access: Vec<ShipAuthorizationEnum>,
-}
-
 #[derive(Default, Debug, Clone)]
 
 pub enum FacingDirection {
@@ -71,6 +51,7 @@ pub fn facing_direction_to_direction(direction: &FacingDirection) -> Vec2 {
         FacingDirection::Left => Vec2::new(-1., 0.),
     }
 }
+use crate::appearance::CharacterAppearance;
 use bevy_rapier3d::na::Quaternion;
 use entity::entity_types::EntityType;
 use networking::server::ConnectedPlayer;
@@ -139,6 +120,8 @@ pub enum PawnDesignation {
 
 pub struct Spawning {
     pub transform: Transform,
+    /// Entity types to spawn with instead of the starting inventory of the job.
+    pub inventory_option: Option<Vec<String>>,
 }
 /// How far melee fists attacks can reach.
 
@@ -1,5 +1,12 @@
+use crate::access::{load_access_config, AccessConfig, JobAssignments};
 use crate::actions::{build_actions, examine, examine_prerequisite_check};
-use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use crate::appearance::{
+    broadcast_changed_appearance, load_appearance_config, load_appearance_for_client,
+    AppearanceConfig,
+};
+use crate::net::PawnServerMessage;
+use bevy::prelude::{App, CoreSet, IntoSystemConfig, Plugin};
+use networking::messaging::{register_reliable_message, MessageSender};
 use resources::is_server::is_server;
 use resources::labels::ActionsLabels;
 pub struct PawnPlugin;
@@ -21,7 +28,16 @@ impl Plugin for PawnPlugin {
                 build_actions
                     .in_set(ActionsLabels::Build)
                     .after(ActionsLabels::Init),
-            );
+            )
+            .init_resource::<AccessConfig>()
+            .init_resource::<JobAssignments>()
+            .add_startup_system(load_access_config)
+            .add_system(load_appearance_for_client)
+            .add_system(broadcast_changed_appearance.in_base_set(CoreSet::PostUpdate));
         }
+        app.init_resource::<AppearanceConfig>()
+            .add_startup_system(load_appearance_config);
+
+        register_reliable_message::<PawnServerMessage>(app, MessageSender::Server);
     }
 }
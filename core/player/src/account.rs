@@ -11,6 +11,8 @@ use bevy::prelude::{EventReader, EventWriter};
 
 pub struct Accounts {
     pub list: HashMap<u64, String>,
+    /// Resume tokens handed out to the connected handles.
+    pub resume_tokens: HashMap<u64, u64>,
 }
 use crate::connections::SendServerConfiguration;
 use crate::names::UsedNames;
@@ -26,36 +28,117 @@ pub(crate) fn account_verification(
     mut configure: EventWriter<SendServerConfiguration>,
     mut accounts: ResMut<Accounts>,
     mut used_names: ResMut<UsedNames>,
+    mut held_pawns: ResMut<HeldPawns>,
+    mut reconnecting: ResMut<ReconnectingPlayers>,
+    mut disconnect: EventWriter<DisconnectClient>,
+    bans: Res<Bans>,
+    connected_players: Query<(), With<ConnectedPlayer>>,
+    server: Res<RenetServer>,
 ) {
     use bevy::prelude::info;
 
     for message in incoming.iter() {
         match &message.message {
-            NetworkingClientMessage::Account(account_name) => {
+            NetworkingClientMessage::ResumeToken(token) => {
+                held_pawns.presented.insert(message.handle, *token);
+            }
+            NetworkingClientMessage::Account(account_name, netcode_version) => {
+                if netcode_version != &NETCODE_VERSION {
+                    disconnect.send(DisconnectClient {
+                        handle: message.handle,
+                        reason: DisconnectReason::Rejected(RejectionReason::ProtocolMismatch(
+                            NETCODE_VERSION,
+                        )),
+                    });
+                    continue;
+                }
+                if accounts.list.len() >= MAX_PLAYERS {
+                    disconnect.send(DisconnectClient {
+                        handle: message.handle,
+                        reason: DisconnectReason::Rejected(RejectionReason::ServerFull),
+                    });
+                    continue;
+                }
+
                 let mut user_name = account_name.clone();
                 if user_name.len() > 16 {
                     user_name = user_name[..16].to_string();
                 }
 
-                if used_names.used_account_names.contains(&user_name) {
-                    // Account name already exists.
+                // The disconnect queued on connection may not have been processed yet.
+                let ip_ban_option = match server.client_addr(message.handle) {
+                    Some(address) => bans.ip_ban(&address.ip()),
+                    None => None,
+                };
+                match ip_ban_option.or_else(|| bans.account_ban(&user_name)) {
+                    Some(ban) => {
+                        disconnect.send(DisconnectClient {
+                            handle: message.handle,
+                            reason: DisconnectReason::Rejected(RejectionReason::Banned(
+                                ban.reason.clone(),
+                            )),
+                        });
+                        continue;
+                    }
+                    None => {}
+                }
 
-                    let mut default_name = "Wolf".to_string() + &used_names.player_i.to_string();
-                    used_names.player_i += 1;
+                // Only the client that got the resume token of a held pawn can take it back.
+                let mut held_option = None;
+                match held_pawns.presented.remove(&message.handle) {
+                    Some(token) => match held_pawns.list.remove(&token) {
+                        Some(held) => {
+                            if connected_players.get(held.entity).is_ok() {
+                                held_option = Some(held);
+                            } else {
+                                info!(
+                                    "Held pawn {:?} of {} no longer exists.",
+                                    held.entity, held.account_name
+                                );
+                                used_names
+                                    .used_account_names
+                                    .retain(|name| name != &held.account_name);
+                            }
+                        }
+                        None => {}
+                    },
+                    None => {}
+                }
 
-                    while used_names.used_account_names.contains(&default_name) {
-                        used_names.player_i += 1;
-                        default_name = "Wolf".to_string() + &used_names.player_i.to_string();
+                match held_option {
+                    Some(held) => {
+                        // Reconnected within the grace period, resume control over the held pawn.
+                        info!(
+                            "{} reconnected as [{}], resuming pawn {:?}.",
+                            held.account_name, message.handle, held.entity
+                        );
+                        user_name = held.account_name.clone();
+                        reconnecting.map.insert(message.handle, held);
                     }
+                    None => {
+                        if used_names.used_account_names.contains(&user_name) {
+                            // Account name already exists.
 
-                    warn!(
-                        "Account name {} by [{}] already exists. Assigned account name {}.",
-                        user_name, message.handle, default_name
-                    );
+                            let mut default_name =
+                                "Wolf".to_string() + &used_names.player_i.to_string();
+                            used_names.player_i += 1;
 
-                    user_name = default_name;
+                            while used_names.used_account_names.contains(&default_name) {
+                                used_names.player_i += 1;
+                                default_name =
+                                    "Wolf".to_string() + &used_names.player_i.to_string();
+                            }
+
+                            warn!(
+                                "Account name {} by [{}] already exists. Assigned account name {}.",
+                                user_name, message.handle, default_name
+                            );
+
+                            user_name = default_name;
+                        }
+                        used_names.used_account_names.push(user_name.clone());
+                    }
                 }
-                used_names.used_account_names.push(user_name.clone());
                 accounts.list.insert(message.handle, user_name.clone());
 
                 outgoing.send(OutgoingReliableServerMessage {
@@ -63,6 +146,13 @@ pub(crate) fn account_verification(
                     message: NetworkingServerMessage::Awoo,
                 });
 
+                let resume_token: u64 = rand::random();
+                accounts.resume_tokens.insert(message.handle, resume_token);
+                outgoing.send(OutgoingReliableServerMessage {
+                    handle: message.handle,
+                    message: NetworkingServerMessage::ResumeToken(resume_token),
+                });
+
                 configure.send(SendServerConfiguration {
                     handle: message.handle,
                 });
@@ -73,3 +163,11 @@ pub(crate) fn account_verification(
         }
     }
 }
+use crate::connections::{HeldPawns, ReconnectingPlayers};
+use crate::moderation::Bans;
+use bevy::prelude::{Query, Res, With};
+use bevy_renet::renet::RenetServer;
+use networking::server::ConnectedPlayer;
+use networking::server::{
+    DisconnectClient, DisconnectReason, RejectionReason, MAX_PLAYERS, NETCODE_VERSION,
+};
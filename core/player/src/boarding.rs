@@ -10,6 +10,10 @@ pub struct BoardingPlayer {
     pub player_handle: u64,
     pub player_character_name: String,
     pub entity: Entity,
+    /// Id of the job the player boards with.
+    pub job: String,
+    /// Entity types the pawn spawns with instead of the starting inventory of its job.
+    pub inventory_option: Option<Vec<String>>,
 }
 /// Resource for slightly delayed boarding announcements.
 #[derive(Default, Resource)]
@@ -21,9 +25,11 @@ use crate::net::PlayerServerMessage;
 use crate::spawn_points::SpawnPointRon;
 
 use crate::connections::{OnBoard, SetupPhase};
+use bevy::prelude::Res;
 use bevy::prelude::{EventWriter, Transform};
 use bevy::time::TimerMode;
 use networking::server::OutgoingReliableServerMessage;
+use pawn::access::AccessConfig;
 use pawn::pawn::Spawning;
 
 /// Event that fires when a player has successfully boarded.
@@ -70,30 +76,39 @@ pub(crate) fn done_boarding(
     mut commands: Commands,
 
     mut asana_boarding_announcements: ResMut<BoardingAnnouncements>,
+    access_config: Res<AccessConfig>,
 ) {
     for boarding_player in boarding_player_event.iter() {
         let player_character_name = boarding_player.player_character_name.clone();
         let player_handle = boarding_player.player_handle;
         let entity_id = boarding_player.entity;
 
-        //let mut assigned_spawn_transform = spawn_points.list[spawn_points.i].transform;
-        let mut assigned_spawn_transform = Transform::IDENTITY;
-        assigned_spawn_transform.translation.y = 0.;
+        let point_types = match access_config.job(&boarding_player.job) {
+            Some(job) => job.spawn_point_types.clone(),
+            None => vec![],
+        };
+        let assigned_spawn_transform;
+        match spawn_points.next(&point_types) {
+            Some(transform) => {
+                assigned_spawn_transform = transform;
+            }
+            None => {
+                let mut transform = Transform::IDENTITY;
+                transform.translation.y = 0.;
+                assigned_spawn_transform = transform;
+            }
+        }
         commands
             .entity(entity_id)
             .insert((
                 OnBoard,
                 Spawning {
                     transform: assigned_spawn_transform,
+                    inventory_option: boarding_player.inventory_option.clone(),
                 },
             ))
             .remove::<(SetupPhase, SoftPlayer)>();
 
-        spawn_points.i += 1;
-
-        if spawn_points.i >= spawn_points.list.len() {
-            spawn_points.i = 0;
-        }
         server.send(OutgoingReliableServerMessage {
             handle: player_handle,
             message: PlayerServerMessage::InitGame,
@@ -168,6 +183,23 @@ pub struct SpawnPoints {
     pub list: Vec<SpawnPoint>,
     pub i: usize,
 }
+
+impl SpawnPoints {
+    /// Transform of the next spawn point of one of the given types, round-robin.
+    /// Any type matches if none are given.
+    pub fn next(&mut self, point_types: &[String]) -> Option<Transform> {
+        let length = self.list.len();
+        for offset in 0..length {
+            let index = (self.i + offset) % length;
+            let point = &self.list[index];
+            if point_types.is_empty() || point_types.contains(&point.point_type) {
+                self.i = (index + 1) % length;
+                return Some(point.transform);
+            }
+        }
+        None
+    }
+}
 use serde::Deserialize;
 /// Raw json.
 #[derive(Deserialize)]
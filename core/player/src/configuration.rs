@@ -19,6 +19,7 @@ pub(crate) fn server_new_client_configuration(
     mut commands: Commands,
     mut handle_to_entity: ResMut<HandleToEntity>,
     mut server1: EventWriter<OutgoingReliableServerMessage<PlayerServerMessage>>,
+    reconnecting: Res<ReconnectingPlayers>,
 ) {
     use resources::content::SF_CONTENT_PREFIX;
 
@@ -57,29 +58,53 @@ pub(crate) fn server_new_client_configuration(
             ),
         });
 
-        // Create the actual Bevy entity for the player , with its network handle, authid and softConnected components.
+        // Reconnected players take back their held pawn instead.
+        match reconnecting.map.get(&event.handle) {
+            Some(held) => {
+                handle_to_entity.map.insert(event.handle, held.entity);
+                handle_to_entity.inv_map.insert(held.entity, event.handle);
+                continue;
+            }
+            None => {}
+        }
 
-        let connected_player_component = ConnectedPlayer {
-            handle: event.handle,
-            authid: auth_id_i.i,
-            rcon: false,
-            ..Default::default()
-        };
-
-        let soft_connected_component = SoftPlayer;
-        auth_id_i.i += 1;
-
-        let player_entity_id = commands
-            .spawn((connected_player_component, soft_connected_component))
-            .id();
-
-        handle_to_entity.map.insert(event.handle, player_entity_id);
-        handle_to_entity
-            .inv_map
-            .insert(player_entity_id, event.handle);
+        spawn_soft_player(
+            event.handle,
+            &mut commands,
+            &mut auth_id_i,
+            &mut handle_to_entity,
+        );
     }
 }
-use crate::connections::PlayerAwaitingBoarding;
+
+/// Spawn the entity of a new player that has yet to board.
+
+pub(crate) fn spawn_soft_player(
+    handle: u64,
+    commands: &mut Commands,
+    auth_id_i: &mut AuthidI,
+    handle_to_entity: &mut HandleToEntity,
+) {
+    // Create the actual Bevy entity for the player , with its network handle, authid and softConnected components.
+
+    let connected_player_component = ConnectedPlayer {
+        handle,
+        authid: auth_id_i.i,
+        rcon: false,
+        ..Default::default()
+    };
+
+    let soft_connected_component = SoftPlayer;
+    auth_id_i.i += 1;
+
+    let player_entity_id = commands
+        .spawn((connected_player_component, soft_connected_component))
+        .id();
+
+    handle_to_entity.map.insert(handle, player_entity_id);
+    handle_to_entity.inv_map.insert(player_entity_id, handle);
+}
+use crate::connections::{PlayerAwaitingBoarding, PlayerResumed, ReconnectingPlayers};
 use networking::client::IncomingReliableServerMessage;
 
 use bevy::prelude::info;
@@ -113,14 +138,27 @@ pub(crate) fn finished_configuration(
     mut config_events: EventReader<SendServerConfiguration>,
     mut server: EventWriter<OutgoingReliableServerMessage<PlayerServerMessage>>,
     mut player_awaiting_event: EventWriter<PlayerAwaitingBoarding>,
+    mut reconnecting: ResMut<ReconnectingPlayers>,
+    mut resumed: EventWriter<PlayerResumed>,
 ) {
     for event in config_events.iter() {
         server.send(OutgoingReliableServerMessage {
             handle: event.handle,
             message: PlayerServerMessage::ConfigFinished,
         });
-        player_awaiting_event.send(PlayerAwaitingBoarding {
-            handle: event.handle,
-        });
+        match reconnecting.map.remove(&event.handle) {
+            Some(held) => {
+                resumed.send(PlayerResumed {
+                    handle: event.handle,
+                    entity: held.entity,
+                    loaded: held.loaded,
+                });
+            }
+            None => {
+                player_awaiting_event.send(PlayerAwaitingBoarding {
+                    handle: event.handle,
+                });
+            }
+        }
     }
 }
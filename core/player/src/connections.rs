@@ -10,7 +10,19 @@ use bevy_renet::renet::ServerEvent;
 
 /// Networking connect and disconnect events.
 
-pub(crate) fn server_events(mut server_events: EventReader<ServerEvent>, server: Res<RenetServer>) {
+pub(crate) fn server_events(
+    mut server_events: EventReader<ServerEvent>,
+    server: Res<RenetServer>,
+    mut accounts: ResMut<Accounts>,
+    mut used_names: ResMut<UsedNames>,
+    mut held_pawns: ResMut<HeldPawns>,
+    mut reconnecting: ResMut<ReconnectingPlayers>,
+    mut arriving: ResMut<ArrivingPlayers>,
+    pawns: Query<(Entity, &ConnectedPlayer, Option<&Senser>), With<Pawn>>,
+    bans: Res<Bans>,
+    mut disconnect: EventWriter<DisconnectClient>,
+    mut disconnecting: ResMut<DisconnectingPlayers>,
+) {
     for event in server_events.iter() {
         match event {
             ServerEvent::ClientConnected(handle, _) => {
@@ -27,15 +39,272 @@ pub(crate) fn server_events(mut server_events: EventReader<ServerEvent>, server:
                 };
 
                 info!("Incoming connection [{}] [{:?}]", handle, client_address);
+
+                match bans.ip_ban(&client_address.ip()) {
+                    Some(ban) => {
+                        disconnect.send(DisconnectClient {
+                            handle: *handle,
+                            reason: DisconnectReason::Rejected(RejectionReason::Banned(
+                                ban.reason.clone(),
+                            )),
+                        });
+                    }
+                    None => {}
+                }
             }
             ServerEvent::ClientDisconnected(handle) => {
                 info!("[{}] has disconnected.", handle);
+
+                reconnecting.map.remove(handle);
+                arriving.handles.remove(handle);
+                held_pawns.presented.remove(handle);
+                let resume_token_option = accounts.resume_tokens.remove(handle);
+                let disconnected_by_server = disconnecting.handles.remove(handle);
+
+                let account_name;
+                match accounts.list.remove(handle) {
+                    Some(name) => {
+                        account_name = name;
+                    }
+                    None => {
+                        continue;
+                    }
+                }
+
+                let mut pawn_option = None;
+                for (entity, connected_player, senser_option) in pawns.iter() {
+                    if connected_player.handle == *handle {
+                        let loaded = match senser_option {
+                            Some(senser) => senser.sensing.clone(),
+                            None => HashSet::new(),
+                        };
+                        pawn_option = Some((entity, loaded));
+                        break;
+                    }
+                }
+
+                match (pawn_option, resume_token_option) {
+                    (Some((entity, loaded)), Some(resume_token)) if !disconnected_by_server => {
+                        info!(
+                            "Holding pawn {:?} of {} for {} seconds.",
+                            entity, account_name, RECONNECT_GRACE_PERIOD
+                        );
+                        held_pawns.list.insert(
+                            resume_token,
+                            HeldPawn {
+                                entity,
+                                account_name,
+                                loaded,
+                                timer: Timer::from_seconds(RECONNECT_GRACE_PERIOD, TimerMode::Once),
+                            },
+                        );
+                    }
+                    _ => {
+                        used_names
+                            .used_account_names
+                            .retain(|name| name != &account_name);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Handles of clients the server is disconnecting, such as kicked and banned players.
+/// Their pawns are not held for them to reconnect.
+#[derive(Default, Resource)]
+
+pub struct DisconnectingPlayers {
+    pub handles: HashSet<u64>,
+}
+
+/// Remember the clients the server disconnects.
+
+pub(crate) fn record_server_disconnects(
+    mut events: EventReader<DisconnectClient>,
+    mut disconnecting: ResMut<DisconnectingPlayers>,
+) {
+    for event in events.iter() {
+        disconnecting.handles.insert(event.handle);
+    }
+}
+
+/// Seconds a disconnected player's pawn is held for them to reconnect and resume control.
+
+pub const RECONNECT_GRACE_PERIOD: f32 = 120.;
+
+/// A pawn held for its disconnected player.
+
+pub struct HeldPawn {
+    pub entity: Entity,
+    pub account_name: String,
+    /// Entities the client had loaded when it lost the connection.
+    pub loaded: HashSet<Entity>,
+    pub timer: Timer,
+}
+
+/// Pawns of disconnected players stored with the resume tokens handed to their clients.
+#[derive(Default, Resource)]
+
+pub struct HeldPawns {
+    pub list: HashMap<u64, HeldPawn>,
+    /// Resume tokens presented by connecting handles before their account verification.
+    pub presented: HashMap<u64, u64>,
+}
+
+/// Handles of reconnected players that are being configured to resume control over their held pawn.
+#[derive(Default, Resource)]
+
+pub struct ReconnectingPlayers {
+    pub map: HashMap<u64, HeldPawn>,
+}
+
+/// Handles of players arriving from another sector, they board with their transferred character and skip the setup UI.
+#[derive(Default, Resource)]
+
+pub struct ArrivingPlayers {
+    pub handles: HashSet<u64>,
+}
+
+/// Release held pawns once their grace period has passed. The pawn stays idle in the world.
+
+pub(crate) fn tick_held_pawns(
+    mut held_pawns: ResMut<HeldPawns>,
+    mut used_names: ResMut<UsedNames>,
+    time: Res<Time>,
+) {
+    let mut expired = vec![];
+    for (resume_token, held) in held_pawns.list.iter_mut() {
+        held.timer.tick(time.delta());
+        if held.timer.finished() {
+            expired.push(*resume_token);
+        }
+    }
+    for resume_token in expired {
+        match held_pawns.list.remove(&resume_token) {
+            Some(held) => {
+                info!("Released held pawn of {}.", held.account_name);
+                used_names
+                    .used_account_names
+                    .retain(|name| name != &held.account_name);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Event that fires when a reconnected player is configured and takes back control over its held pawn.
+
+pub struct PlayerResumed {
+    pub handle: u64,
+    pub entity: Entity,
+    /// Entities the client still has loaded from before it lost the connection.
+    pub loaded: HashSet<Entity>,
+}
+
+/// Event that fires when a reconnected player couldn't take back its held pawn and continues as a new player.
+
+pub struct ResumeFailed {
+    pub handle: u64,
+}
+
+/// Hand the held pawn back to the reconnected player and resend the world around it.
+
+pub(crate) fn resume_held_pawn(
+    mut events: EventReader<PlayerResumed>,
+    mut connected_players: Query<(&mut ConnectedPlayer, Option<&mut Senser>)>,
+    mut sensables: Query<&mut Sensable>,
+    mut server: EventWriter<OutgoingReliableServerMessage<PlayerServerMessage>>,
+    mut entity_server: EventWriter<OutgoingReliableServerMessage<EntityServerMessage>>,
+    mut handle_to_entity: ResMut<HandleToEntity>,
+    mut commands: Commands,
+    mut auth_id_i: ResMut<AuthidI>,
+    mut awaiting: EventWriter<PlayerAwaitingBoarding>,
+    mut resume_failed: EventWriter<ResumeFailed>,
+) {
+    for event in events.iter() {
+        match connected_players.get_mut(event.entity) {
+            Ok((mut connected_player, senser_option)) => {
+                connected_player.handle = event.handle;
+                connected_player.connected = true;
+
+                match senser_option {
+                    Some(mut senser) => {
+                        // Unload what the client still has but is gone or out of sight now.
+                        for entity in event.loaded.iter() {
+                            if !senser.sensing.contains(entity) {
+                                entity_server.send(OutgoingReliableServerMessage {
+                                    handle: event.handle,
+                                    message: EntityServerMessage::UnloadEntity(*entity),
+                                });
+                            }
+                        }
+                        // Entities sensed while disconnected never got loaded by the client.
+                        senser.sensing.clear();
+                        senser.sfx.clear();
+                    }
+                    None => {}
+                }
+                for mut sensable in sensables.iter_mut() {
+                    sensable.sensed_by.remove(&event.entity);
+                }
+            }
+            Err(_) => {
+                // The pawn got despawned during configuration, board like a new player instead.
+                // The client is configured already, it only needs the entity and setup UI of a new player.
+                warn!(
+                    "Couldnt find held pawn {:?}, continuing [{}] as a new player.",
+                    event.entity, event.handle
+                );
+                handle_to_entity.inv_map.remove(&event.entity);
+                handle_to_entity.map.remove(&event.handle);
+                spawn_soft_player(
+                    event.handle,
+                    &mut commands,
+                    &mut auth_id_i,
+                    &mut handle_to_entity,
+                );
+                awaiting.send(PlayerAwaitingBoarding {
+                    handle: event.handle,
+                });
+                resume_failed.send(ResumeFailed {
+                    handle: event.handle,
+                });
+                continue;
             }
         }
+
+        server.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message: PlayerServerMessage::PawnId(event.entity),
+        });
+        server.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message: PlayerServerMessage::Boarded,
+        });
+        server.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message: PlayerServerMessage::InitGame,
+        });
     }
 }
+use crate::account::Accounts;
+use crate::configuration::spawn_soft_player;
+use crate::moderation::Bans;
+use crate::names::UsedNames;
+use crate::net::PlayerServerMessage;
+use bevy::prelude::Commands;
 use bevy::prelude::Component;
 use bevy::prelude::Resource;
+use bevy::prelude::{Entity, EventWriter, Query, ResMut, Time, With};
+use bevy::time::{Timer, TimerMode};
+use entity::{net::EntityServerMessage, sensable::Sensable, senser::Senser};
+use networking::server::{
+    ConnectedPlayer, DisconnectClient, DisconnectReason, HandleToEntity,
+    OutgoingReliableServerMessage, RejectionReason,
+};
+use pawn::pawn::Pawn;
+use std::collections::{HashMap, HashSet};
 
 /// The component for entities int he boarding phase.
 #[derive(Component)]
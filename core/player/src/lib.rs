@@ -8,13 +8,15 @@ pub mod boarding;
 pub mod configuration;
 /// The configuration send from the server to newly connected clients.
 pub mod connections;
-/// Spawns a camera at a fixed position, useful to inspect the 3D scene.
-pub mod debug_camera;
+/// Bans and mutes.
+pub mod moderation;
 /// Generate human names.
 pub mod name_generator;
 /// Account and player names.
 pub mod names;
 pub mod net;
+/// First person, third person and spectator camera of the player.
+pub mod pawn_camera;
 /// The Bevy plugin of this crate.
 pub mod plugin;
 /// Map spawn points.
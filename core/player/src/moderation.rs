@@ -0,0 +1,224 @@
+use std::{
+    fs,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use bevy::prelude::{info, warn, EventReader, EventWriter, Res, ResMut, Resource};
+use bevy_renet::renet::RenetServer;
+use networking::server::{DisconnectClient, DisconnectReason, RejectionReason};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::account::Accounts;
+
+/// A ban on an account and the address it connected from.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct Ban {
+    pub account_name: String,
+    pub ip_option: Option<IpAddr>,
+    /// Unix timestamp in seconds at which the ban expires. Permanent if none.
+    pub expires_option: Option<u64>,
+    pub reason: String,
+}
+
+impl Ban {
+    pub fn is_expired(&self) -> bool {
+        match self.expires_option {
+            Some(expires) => unix_now() >= expires,
+            None => false,
+        }
+    }
+}
+
+/// Current unix time in seconds.
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Resource containing all bans. Persisted to [bans_path].
+#[derive(Default, Resource, Serialize, Deserialize)]
+
+pub struct Bans {
+    pub list: Vec<Ban>,
+}
+
+impl Bans {
+    /// Returns the active ban matching the account name.
+    pub fn account_ban(&self, account_name: &str) -> Option<&Ban> {
+        self.list
+            .iter()
+            .find(|ban| !ban.is_expired() && ban.account_name == account_name)
+    }
+    /// Returns the active ban matching the address.
+    pub fn ip_ban(&self, ip: &IpAddr) -> Option<&Ban> {
+        self.list
+            .iter()
+            .find(|ban| !ban.is_expired() && ban.ip_option.as_ref() == Some(ip))
+    }
+    /// Write the bans to drive.
+    pub fn save(&self) {
+        let path = bans_path();
+        match path.parent() {
+            Some(dir) => match fs::create_dir_all(dir) {
+                Ok(_) => {}
+                Err(err) => {
+                    warn!("Couldn't create directory for bans: {}", err);
+                    return;
+                }
+            },
+            None => {}
+        }
+        match ron::ser::to_string_pretty(self, PrettyConfig::default()) {
+            Ok(bans_ron) => match fs::write(path, bans_ron) {
+                Ok(_) => {}
+                Err(err) => {
+                    warn!("Couldn't write bans: {}", err);
+                }
+            },
+            Err(err) => {
+                warn!("Couldn't serialize bans: {}", err);
+            }
+        }
+    }
+}
+
+/// Path of the persisted bans.
+
+pub fn bans_path() -> PathBuf {
+    Path::new("data").join("settings").join("bans.ron")
+}
+
+/// Load persisted bans from drive.
+
+pub(crate) fn load_bans(mut bans: ResMut<Bans>) {
+    let path = bans_path();
+
+    if !path.exists() {
+        return;
+    }
+
+    match fs::read_to_string(path) {
+        Ok(bans_ron) => match ron::from_str::<Bans>(&bans_ron) {
+            Ok(b) => {
+                *bans = b;
+                bans.list.retain(|ban| !ban.is_expired());
+                info!("Loaded {} bans.", bans.list.len());
+            }
+            Err(err) => {
+                warn!("Couldn't parse bans: {}", err);
+            }
+        },
+        Err(err) => {
+            warn!("Couldn't read bans: {}", err);
+        }
+    }
+}
+
+/// Event to ban the account and address of a connected player.
+
+pub struct BanPlayer {
+    pub handle: u64,
+    /// Permanent if none.
+    pub duration_option: Option<Duration>,
+    pub reason: String,
+}
+
+/// Ban connected players and disconnect them.
+
+pub(crate) fn ban_players(
+    mut events: EventReader<BanPlayer>,
+    mut bans: ResMut<Bans>,
+    accounts: Res<Accounts>,
+    server: Res<RenetServer>,
+    mut disconnect: EventWriter<DisconnectClient>,
+) {
+    for event in events.iter() {
+        let account_name;
+        match accounts.list.get(&event.handle) {
+            Some(n) => {
+                account_name = n.clone();
+            }
+            None => {
+                warn!("Couldn't find account of [{}] to ban.", event.handle);
+                continue;
+            }
+        }
+
+        let ip_option = server.client_addr(event.handle).map(|address| address.ip());
+
+        info!(
+            "Banned {} [{:?}] for {:?}: {}",
+            account_name, ip_option, event.duration_option, event.reason
+        );
+
+        bans.list.push(Ban {
+            account_name,
+            ip_option,
+            expires_option: event
+                .duration_option
+                .map(|duration| unix_now() + duration.as_secs()),
+            reason: event.reason.clone(),
+        });
+        bans.save();
+
+        disconnect.send(DisconnectClient {
+            handle: event.handle,
+            reason: DisconnectReason::Rejected(RejectionReason::Banned(event.reason.clone())),
+        });
+    }
+}
+
+/// Chat channels a mute applies to.
+#[derive(Clone, Debug, PartialEq)]
+
+pub enum MuteScope {
+    /// The out-of-character global chat only.
+    GlobalChat,
+    /// Every chat channel, including ship-wide terminal announcements.
+    AllChat,
+}
+
+/// A mute on an account.
+
+pub struct Mute {
+    pub scope: MuteScope,
+    /// Unix timestamp in seconds at which the mute expires. Permanent if none.
+    pub expires_option: Option<u64>,
+}
+
+/// Resource containing muted accounts.
+#[derive(Default, Resource)]
+
+pub struct Mutes {
+    pub map: HashMap<String, Mute>,
+}
+
+impl Mutes {
+    /// Check if an account is muted for a chat channel. Pass [MuteScope::GlobalChat] for the global chat and [MuteScope::AllChat] for any other channel.
+    pub fn is_muted(&self, account_name: &str, scope: MuteScope) -> bool {
+        match self.map.get(account_name) {
+            Some(mute) => {
+                let expired = match mute.expires_option {
+                    Some(expires) => unix_now() >= expires,
+                    None => false,
+                };
+                if expired {
+                    return false;
+                }
+                match scope {
+                    MuteScope::GlobalChat => true,
+                    MuteScope::AllChat => mute.scope == MuteScope::AllChat,
+                }
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,419 @@
+use bevy::{
+    core_pipeline::fxaa::Fxaa,
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    prelude::{
+        Camera, Camera3dBundle, Children, Commands, DetectChangesMut, Entity, EventReader,
+        EventWriter, GlobalTransform, Input, KeyCode, Local, Name, Query, Res, ResMut, Resource,
+        Vec2, Vec3, Visibility, With,
+    },
+    window::{CursorGrabMode, PrimaryWindow, Window},
+};
+use bevy_atmosphere::prelude::AtmosphereCamera;
+use bevy_rapier3d::prelude::{CollisionGroups, Group, QueryFilter, RapierContext};
+use cameras::{
+    controllers::{
+        fps::{ActiveCamera, FpsCameraBundle, FpsCameraController},
+        orbit::{ControlEvent, OrbitCameraController},
+    },
+    LookTransform, Smoother,
+};
+use entity::spawn::{ClientEntityServerEntity, PawnEntityId};
+use graphics::settings::GraphicsSettings;
+use networking::client::IncomingReliableServerMessage;
+use physics::physics::{get_bit_masks, ColliderGroup};
+use resources::{
+    binds::{KeyBind, KeyBinds},
+    hud::HudState,
+    ui::TextInput,
+};
+
+use crate::{configuration::Boarded, net::PlayerServerMessage};
+
+pub const TOGGLE_THIRD_PERSON_BIND: &str = "toggleThirdPerson";
+
+/// Height of the eyes above the pawn origin for pawn models without a head bone.
+
+pub const PAWN_EYE_HEIGHT: f32 = 1.7;
+/// Default, minimum and maximum distance of the third person camera to the head.
+
+const THIRD_PERSON_DEFAULT_DISTANCE: f32 = 3.;
+const THIRD_PERSON_MIN_DISTANCE: f32 = 1.;
+const THIRD_PERSON_MAX_DISTANCE: f32 = 8.;
+/// Distance the third person camera keeps from walls it collides with.
+
+const CAMERA_WALL_MARGIN: f32 = 0.2;
+/// Closest the third person camera gets to the head when pushed in by walls.
+
+const CAMERA_COLLISION_MIN_DISTANCE: f32 = 0.1;
+
+/// Camera modes of the local player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+
+pub enum PawnCameraMode {
+    #[default]
+    FirstPerson,
+    ThirdPerson,
+    /// Free camera for players without a pawn to follow.
+    Spectator,
+}
+
+/// Client resource with the state of the player camera.
+#[derive(Resource)]
+
+pub struct PawnCamera {
+    /// Mode selected by the player, either first or third person.
+    pub mode: PawnCameraMode,
+    /// Mode currently applied to the camera. Spectator while there is no pawn to follow.
+    pub active_mode: Option<PawnCameraMode>,
+    /// Desired distance of the third person camera to the head.
+    pub third_person_distance: f32,
+    /// Head bone of the pawn model.
+    pub head_option: Option<Entity>,
+}
+
+impl Default for PawnCamera {
+    fn default() -> Self {
+        Self {
+            mode: PawnCameraMode::FirstPerson,
+            active_mode: None,
+            third_person_distance: THIRD_PERSON_DEFAULT_DISTANCE,
+            head_option: None,
+        }
+    }
+}
+
+pub(crate) fn register_input(mut binds: ResMut<KeyBinds>) {
+    binds.list.insert(
+        TOGGLE_THIRD_PERSON_BIND.to_string(),
+        KeyBind {
+            key_code: KeyCode::V,
+            description: "Toggles between the first and third person camera.".to_string(),
+            name: "Toggle Third Person".to_string(),
+        },
+    );
+}
+
+/// Spawn the player camera on boarding.
+
+pub(crate) fn spawn_pawn_camera(
+    mut commands: Commands,
+    mut messages: EventReader<IncomingReliableServerMessage<PlayerServerMessage>>,
+    mut spawning: Local<bool>,
+    mut state: ResMut<ActiveCamera>,
+    mut pawn_camera: ResMut<PawnCamera>,
+    settings: Res<GraphicsSettings>,
+) {
+    // Skip one frame to prevent camera ambiguity.
+    if *spawning {
+        *spawning = false;
+        let id = commands
+            .spawn(Camera3dBundle {
+                camera: Camera {
+                    msaa_writeback: settings.msaa.is_enabled(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(FpsCameraBundle::new(
+                FpsCameraController::default(),
+                Vec3::new(0., PAWN_EYE_HEIGHT, 0.),
+                Vec3::new(0., PAWN_EYE_HEIGHT, -2.),
+                Vec3::Y,
+            ))
+            .insert(OrbitCameraController {
+                enabled: false,
+                ..Default::default()
+            })
+            .insert(AtmosphereCamera::default())
+            .insert(Fxaa {
+                enabled: settings.fxaa.is_some(),
+                ..Default::default()
+            })
+            .id();
+
+        state.option = Some(id);
+        pawn_camera.active_mode = None;
+    }
+
+    for message in messages.iter() {
+        match message.message {
+            PlayerServerMessage::Boarded => {
+                // Resuming a held pawn after a reconnect keeps the existing camera.
+                if state.option.is_none() {
+                    *spawning = true;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Find the head bone in the scene of the pawn model.
+
+pub(crate) fn find_pawn_head(
+    mut pawn_camera: ResMut<PawnCamera>,
+    pawn: Res<PawnEntityId>,
+    map: Res<ClientEntityServerEntity>,
+    children_query: Query<&Children>,
+    names: Query<&Name>,
+) {
+    match pawn_camera.head_option {
+        Some(head) => {
+            if names.get(head).is_ok() {
+                return;
+            }
+            pawn_camera.head_option = None;
+        }
+        None => {}
+    }
+
+    let client_pawn;
+    match pawn
+        .option
+        .and_then(|server_pawn| map.map.get(&server_pawn))
+    {
+        Some(p) => {
+            client_pawn = *p;
+        }
+        None => {
+            return;
+        }
+    }
+
+    let mut stack = vec![client_pawn];
+    while let Some(node) = stack.pop() {
+        match names.get(node) {
+            Ok(name) => {
+                if name.as_str().to_lowercase().ends_with("head") {
+                    pawn_camera.head_option = Some(node);
+                    return;
+                }
+            }
+            Err(_) => {}
+        }
+        match children_query.get(node) {
+            Ok(children) => {
+                stack.extend(children.iter());
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+pub(crate) fn toggle_third_person(
+    keys: Res<Input<KeyCode>>,
+    binds: Res<KeyBinds>,
+    focus: Res<TextInput>,
+    mut pawn_camera: ResMut<PawnCamera>,
+) {
+    if !keys.just_pressed(binds.bind(TOGGLE_THIRD_PERSON_BIND)) || focus.focused_input.is_some() {
+        return;
+    }
+    pawn_camera.mode = match pawn_camera.mode {
+        PawnCameraMode::FirstPerson => PawnCameraMode::ThirdPerson,
+        _ => PawnCameraMode::FirstPerson,
+    };
+}
+
+/// Mouse orbits the third person camera while the cursor is grabbed, the wheel zooms.
+
+pub(crate) fn third_person_input(
+    mut events: EventWriter<ControlEvent>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    controllers: Query<&OrbitCameraController>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    hud_state: Res<HudState>,
+    mut pawn_camera: ResMut<PawnCamera>,
+) {
+    let controller = if let Some(controller) = controllers.iter().find(|c| c.enabled) {
+        controller
+    } else {
+        mouse_motion_events.clear();
+        mouse_wheel_reader.clear();
+        return;
+    };
+    let grabbed = match windows.get_single() {
+        Ok(window) => window.cursor.grab_mode == CursorGrabMode::Locked,
+        Err(_) => false,
+    };
+    if hud_state.expanded || !grabbed {
+        mouse_motion_events.clear();
+        mouse_wheel_reader.clear();
+        return;
+    }
+
+    let mut cursor_delta = Vec2::ZERO;
+    for event in mouse_motion_events.iter() {
+        cursor_delta += event.delta;
+    }
+    events.send(ControlEvent::Orbit(
+        controller.mouse_rotate_sensitivity * cursor_delta,
+    ));
+
+    for event in mouse_wheel_reader.iter() {
+        let scroll_amount = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / controller.pixels_per_line,
+        };
+        pawn_camera.third_person_distance = (pawn_camera.third_person_distance
+            * (1. - scroll_amount * controller.mouse_wheel_zoom_sensitivity))
+            .clamp(THIRD_PERSON_MIN_DISTANCE, THIRD_PERSON_MAX_DISTANCE);
+    }
+}
+
+/// Attach the camera to the head of the pawn, or let it fly freely as a spectator.
+
+pub(crate) fn update_pawn_camera(
+    mut pawn_camera: ResMut<PawnCamera>,
+    active_camera: Res<ActiveCamera>,
+    boarded: Res<Boarded>,
+    pawn: Res<PawnEntityId>,
+    map: Res<ClientEntityServerEntity>,
+    global_transforms: Query<&GlobalTransform>,
+    mut cameras: Query<(
+        &mut LookTransform,
+        &mut Smoother,
+        &mut FpsCameraController,
+        &mut OrbitCameraController,
+    )>,
+    mut visibilities: Query<&mut Visibility>,
+    rapier_context: Res<RapierContext>,
+) {
+    let camera_entity;
+    match active_camera.option {
+        Some(c) => {
+            camera_entity = c;
+        }
+        None => {
+            return;
+        }
+    }
+    let (mut look_transform, mut smoother, mut fps, mut orbit);
+    match cameras.get_mut(camera_entity) {
+        Ok((l, s, f, o)) => {
+            look_transform = l;
+            smoother = s;
+            fps = f;
+            orbit = o;
+        }
+        Err(_) => {
+            return;
+        }
+    }
+
+    let client_pawn = pawn
+        .option
+        .and_then(|server_pawn| map.map.get(&server_pawn))
+        .filter(|client_pawn| global_transforms.get(**client_pawn).is_ok());
+
+    let head_position;
+    let mode;
+    match client_pawn {
+        Some(client_pawn) => {
+            head_position = match pawn_camera
+                .head_option
+                .and_then(|head| global_transforms.get(head).ok())
+            {
+                Some(head) => head.translation(),
+                None => {
+                    global_transforms.get(*client_pawn).unwrap().translation()
+                        + Vec3::Y * PAWN_EYE_HEIGHT
+                }
+            };
+            mode = match boarded.boarded {
+                true => pawn_camera.mode,
+                false => PawnCameraMode::Spectator,
+            };
+        }
+        None => {
+            head_position = look_transform.eye;
+            mode = PawnCameraMode::Spectator;
+        }
+    }
+
+    // The own pawn model is only shown in third person.
+    match client_pawn {
+        Some(client_pawn) => match visibilities.get_mut(*client_pawn) {
+            Ok(mut visibility) => {
+                let desired = match mode {
+                    PawnCameraMode::ThirdPerson => Visibility::Inherited,
+                    _ => Visibility::Hidden,
+                };
+                if *visibility != desired {
+                    *visibility = desired;
+                }
+            }
+            Err(_) => {}
+        },
+        None => {}
+    }
+
+    let look_direction = look_transform.look_direction().unwrap_or(Vec3::NEG_Z);
+
+    if pawn_camera.active_mode != Some(mode) {
+        // Toggle the controllers without triggering their change detection, which would disable the smoother.
+        fps.bypass_change_detection().enabled = mode != PawnCameraMode::ThirdPerson;
+        orbit.bypass_change_detection().enabled = mode == PawnCameraMode::ThirdPerson;
+        smoother.set_enabled(true);
+        match mode {
+            PawnCameraMode::FirstPerson => {
+                // Smoothing would make the camera lag behind the head.
+                smoother.set_lag_weight(0.);
+                look_transform.eye = head_position;
+                look_transform.target = head_position + look_direction;
+            }
+            PawnCameraMode::ThirdPerson => {
+                smoother.set_lag_weight(orbit.smoothing_weight);
+                look_transform.target = head_position;
+                look_transform.eye =
+                    head_position - look_direction * pawn_camera.third_person_distance;
+            }
+            PawnCameraMode::Spectator => {
+                smoother.set_lag_weight(fps.smoothing_weight);
+            }
+        }
+        pawn_camera.active_mode = Some(mode);
+    }
+
+    match mode {
+        PawnCameraMode::FirstPerson => {
+            let radius = look_transform.radius();
+            look_transform.eye = head_position;
+            look_transform.target = head_position + look_direction * radius;
+        }
+        PawnCameraMode::ThirdPerson => {
+            let orbit_direction = (look_transform.eye - look_transform.target).normalize_or_zero();
+            let mut distance = pawn_camera.third_person_distance;
+
+            let masks = get_bit_masks(ColliderGroup::Standard);
+            let mut filter = QueryFilter::new().groups(CollisionGroups::new(
+                Group::from_bits(masks.0).unwrap(),
+                Group::from_bits(masks.1).unwrap(),
+            ));
+            match client_pawn {
+                Some(client_pawn) => {
+                    filter = filter.exclude_rigid_body(*client_pawn);
+                }
+                None => {}
+            }
+            // Pull the camera in front of walls between the head and the camera.
+            match rapier_context.cast_ray(
+                head_position,
+                orbit_direction,
+                distance + CAMERA_WALL_MARGIN,
+                true,
+                filter,
+            ) {
+                Some((_entity, toi)) => {
+                    distance = (toi - CAMERA_WALL_MARGIN).max(CAMERA_COLLISION_MIN_DISTANCE);
+                }
+                None => {}
+            }
+
+            look_transform.target = head_position;
+            look_transform.eye = head_position + orbit_direction * distance;
+        }
+        PawnCameraMode::Spectator => {}
+    }
+}
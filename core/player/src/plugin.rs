@@ -3,17 +3,26 @@ use crate::boarding::{player_boarded, PlayerBoarded, SpawnPoints};
 use crate::configuration::{
     client_receive_pawnid, finished_configuration, server_new_client_configuration, Boarded,
 };
-use crate::connections::{AuthidI, SendServerConfiguration};
-use crate::debug_camera::spawn_debug_camera;
+use crate::connections::{
+    record_server_disconnects, resume_held_pawn, tick_held_pawns, ArrivingPlayers, AuthidI,
+    DisconnectingPlayers, HeldPawns, PlayerResumed, ReconnectingPlayers, ResumeFailed,
+    SendServerConfiguration,
+};
+use crate::moderation::{ban_players, load_bans, BanPlayer, Bans, Mutes};
 use crate::net::PlayerServerMessage;
+use crate::pawn_camera::{
+    find_pawn_head, register_input, spawn_pawn_camera, third_person_input, toggle_third_person,
+    update_pawn_camera, PawnCamera,
+};
 use crate::{
     boarding::{done_boarding, BoardingAnnouncements, InputUIInputTransmitText},
     connections::{server_events, PlayerAwaitingBoarding},
 };
 use bevy::prelude::IntoSystemConfig;
 use bevy::prelude::{App, Plugin, SystemSet};
-use cameras::controllers::fps::FpsCameraPlugin;
-use cameras::LookTransformPlugin;
+use cameras::controllers::fps::{self, FpsCameraPlugin};
+use cameras::controllers::orbit::{self, OrbitCameraPlugin};
+use cameras::{look_transform_system, LookTransformPlugin};
 use networking::{
     messaging::{register_reliable_message, MessageSender},
     server::HandleToEntity,
@@ -50,12 +59,40 @@ impl Plugin for PlayerPlugin {
                 .add_system(account_verification)
                 .init_resource::<Accounts>()
                 .add_event::<PlayerBoarded>()
-                .add_system(player_boarded);
+                .add_system(player_boarded)
+                .init_resource::<HeldPawns>()
+                .init_resource::<ReconnectingPlayers>()
+                .init_resource::<ArrivingPlayers>()
+                .add_event::<PlayerResumed>()
+                .add_event::<ResumeFailed>()
+                .init_resource::<DisconnectingPlayers>()
+                .add_system(record_server_disconnects)
+                .add_system(tick_held_pawns)
+                .add_system(resume_held_pawn.after(ConfigurationLabel::Main))
+                .init_resource::<Bans>()
+                .init_resource::<Mutes>()
+                .add_startup_system(load_bans)
+                .add_event::<BanPlayer>()
+                .add_system(ban_players);
         } else {
             app.add_system(client_receive_pawnid)
-                .add_system(spawn_debug_camera)
+                .add_system(spawn_pawn_camera)
                 .add_plugin(LookTransformPlugin)
                 .add_plugin(FpsCameraPlugin::default())
+                .add_plugin(OrbitCameraPlugin::new(true))
+                .init_resource::<PawnCamera>()
+                .add_startup_system(register_input)
+                .add_system(find_pawn_head)
+                .add_system(toggle_third_person)
+                .add_system(third_person_input.before(orbit::control_system))
+                .add_system(
+                    update_pawn_camera
+                        .after(find_pawn_head)
+                        .after(toggle_third_person)
+                        .after(fps::control_system)
+                        .after(orbit::control_system)
+                        .before(look_transform_system),
+                )
                 .init_resource::<Boarded>();
         }
         app.init_resource::<SpawnPoints>();
@@ -0,0 +1,143 @@
+use bevy::prelude::{Query, Res, ResMut, Transform};
+use entity::examine::Examinable;
+use gridmap::examine::GridmapExamineMessages;
+use text_api::core::{ENGINEERING_TEXT_COLOR, FURTHER_ITALIC_FONT, HEALTHY_COLOR, WARNING_COLOR};
+
+use crate::{
+    network::PowerNetworks,
+    power::{PowerConsumer, PowerGenerator, PowerStorage},
+};
+
+/// Id of the power status text of examinable power devices.
+pub const POWER_EXAMINE_TEXT_ID: u32 = 12;
+
+/// Keep the power status of power devices in their examine text.
+
+pub(crate) fn update_power_examine_texts(
+    mut examinables: Query<(
+        &mut Examinable,
+        &Transform,
+        Option<&PowerConsumer>,
+        Option<&PowerGenerator>,
+        Option<&PowerStorage>,
+    )>,
+    networks: Res<PowerNetworks>,
+) {
+    for (mut examinable, transform, consumer_option, generator_option, storage_option) in
+        examinables.iter_mut()
+    {
+        let text;
+        if networks.device_network(transform.translation).is_none()
+            && (generator_option.is_some() || storage_option.is_some())
+        {
+            text = "[font=".to_string()
+                + FURTHER_ITALIC_FONT
+                + "][color="
+                + WARNING_COLOR
+                + "]It is not connected to a power cable.[/color][/font]";
+        } else {
+            match (consumer_option, generator_option, storage_option) {
+                (_, Some(generator), _) => {
+                    if generator.active {
+                        text = "[font=".to_string()
+                            + FURTHER_ITALIC_FONT
+                            + "][color="
+                            + HEALTHY_COLOR
+                            + "]It is generating "
+                            + &(generator.output as u32).to_string()
+                            + " watts.[/color][/font]";
+                    } else {
+                        text = "[font=".to_string()
+                            + FURTHER_ITALIC_FONT
+                            + "][color="
+                            + WARNING_COLOR
+                            + "]It is turned off.[/color][/font]";
+                    }
+                }
+                (_, _, Some(storage)) => {
+                    let percentage = match storage.capacity > 0. {
+                        true => (storage.charge / storage.capacity * 100.).round() as u32,
+                        false => 0,
+                    };
+                    text = "[font=".to_string()
+                        + FURTHER_ITALIC_FONT
+                        + "][color="
+                        + ENGINEERING_TEXT_COLOR
+                        + "]It is charged to "
+                        + &percentage.to_string()
+                        + "%.[/color][/font]";
+                }
+                (Some(consumer), _, _) => {
+                    if consumer.powered {
+                        text = "[font=".to_string()
+                            + FURTHER_ITALIC_FONT
+                            + "][color="
+                            + HEALTHY_COLOR
+                            + "]It is powered.[/color][/font]";
+                    } else {
+                        text = "[font=".to_string()
+                            + FURTHER_ITALIC_FONT
+                            + "][color="
+                            + WARNING_COLOR
+                            + "]It has no power.[/color][/font]";
+                    }
+                }
+                (None, None, None) => {
+                    continue;
+                }
+            }
+        }
+
+        if examinable.assigned_texts.get(&POWER_EXAMINE_TEXT_ID) != Some(&text) {
+            examinable
+                .assigned_texts
+                .insert(POWER_EXAMINE_TEXT_ID, text);
+        }
+    }
+}
+
+/// Examine the power network of a power cable cell.
+
+pub(crate) fn examine_map_power(
+    mut examine_map_events: ResMut<GridmapExamineMessages>,
+    networks: Res<PowerNetworks>,
+) {
+    for examine_event in examine_map_events.messages.iter_mut() {
+        let network;
+        match networks.cell_network(&examine_event.gridmap_cell_id) {
+            Some(n) => {
+                network = n;
+            }
+            None => {
+                continue;
+            }
+        }
+
+        let status;
+        if !network.powered {
+            status = "[color=".to_string() + WARNING_COLOR + "]The network has no power.[/color]";
+        } else if network.supply < network.load {
+            status =
+                "[color=".to_string() + WARNING_COLOR + "]The network runs on batteries.[/color]";
+        } else {
+            status = "[color=".to_string() + HEALTHY_COLOR + "]The network is powered.[/color]";
+        }
+
+        examine_event.message = examine_event.message.clone()
+            + "[font="
+            + FURTHER_ITALIC_FONT
+            + "]"
+            + &status
+            + "\n[color="
+            + ENGINEERING_TEXT_COLOR
+            + "]Supply: "
+            + &(network.supply as u32).to_string()
+            + " W, load: "
+            + &(network.load as u32).to_string()
+            + " W, stored: "
+            + &((network.stored / 1000.) as u32).to_string()
+            + "/"
+            + &((network.capacity / 1000.) as u32).to_string()
+            + " kJ.[/color][/font]\n";
+    }
+}
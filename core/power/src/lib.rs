@@ -0,0 +1,17 @@
+//! The power grid. Power cables on the details1 gridmap layer connect generators, batteries and powered devices into power networks.
+//! Devices connect to a cable in their own cell or the cell right below it. Consumers declare their load and only operate while their network can supply it.
+
+/// Power status in examine texts.
+pub mod examine;
+/// The networking module of this crate.
+pub mod net;
+/// Power networks made up by power cables and the balancing of supply and load.
+pub mod network;
+/// The power mini-map overlay.
+pub mod overlay;
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Power device components.
+pub mod power;
+/// Sync the powered status of consumers with clients.
+pub mod status;
@@ -0,0 +1,10 @@
+use bevy::prelude::Entity;
+use serde::{Deserialize, Serialize};
+use typename::TypeName;
+
+/// Gets serialized and sent over the net, this is the server message.
+#[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
+
+pub enum PowerServerMessage {
+    PoweredStatus(Entity, bool),
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use bevy::{
+    math::Vec3,
+    prelude::{info, EventReader, Query, Res, ResMut, Resource, Transform},
+    time::Time,
+};
+use gridmap::{
+    details1::{AddDetails1Tile, RemoveDetails1Tile},
+    grid::Gridmap,
+};
+use resources::math::{world_to_cell_id, Vec3Int};
+
+use crate::power::{PowerConsumer, PowerGenerator, PowerStorage};
+
+/// Name of the power cable tile on the details1 layer.
+pub const POWER_CABLE_NAME: &str = "power_cable_1";
+
+/// A set of connected power cables and the state of the devices attached to it.
+#[derive(Default, Clone)]
+
+pub struct PowerNetwork {
+    pub cells: Vec<Vec3Int>,
+    /// Power supplied by generators last tick in watts.
+    pub supply: f32,
+    /// Power demanded by consumers last tick in watts.
+    pub load: f32,
+    /// Energy stored in batteries in joules.
+    pub stored: f32,
+    /// Maximum energy stored in batteries in joules.
+    pub capacity: f32,
+    /// Whether the consumers of the network were powered last tick.
+    pub powered: bool,
+}
+
+/// Resource with the power networks made up by the power cables of the gridmap.
+#[derive(Resource)]
+
+pub struct PowerNetworks {
+    pub networks: Vec<PowerNetwork>,
+    /// Network index of each power cable cell.
+    pub cells: HashMap<Vec3Int, usize>,
+    /// Whether cables changed and the networks have to be recomputed.
+    pub dirty: bool,
+}
+
+impl Default for PowerNetworks {
+    fn default() -> Self {
+        Self {
+            networks: vec![],
+            cells: HashMap::default(),
+            dirty: true,
+        }
+    }
+}
+
+impl PowerNetworks {
+    /// The network a device at a world position is connected to.
+    /// Devices connect to a cable in their own cell or in the cell right below it.
+    pub fn device_network(&self, position: Vec3) -> Option<usize> {
+        let cell_id = world_to_cell_id(position);
+        match self.cells.get(&cell_id) {
+            Some(network) => Some(*network),
+            None => self
+                .cells
+                .get(&Vec3Int {
+                    x: cell_id.x,
+                    y: cell_id.y - 1,
+                    z: cell_id.z,
+                })
+                .copied(),
+        }
+    }
+    pub fn cell_network(&self, cell_id: &Vec3Int) -> Option<&PowerNetwork> {
+        match self.cells.get(cell_id) {
+            Some(network) => self.networks.get(*network),
+            None => None,
+        }
+    }
+}
+
+const ADJACENT_CELLS: [Vec3Int; 6] = [
+    Vec3Int { x: 1, y: 0, z: 0 },
+    Vec3Int { x: -1, y: 0, z: 0 },
+    Vec3Int { x: 0, y: 1, z: 0 },
+    Vec3Int { x: 0, y: -1, z: 0 },
+    Vec3Int { x: 0, y: 0, z: 1 },
+    Vec3Int { x: 0, y: 0, z: -1 },
+];
+
+/// Recompute the power networks when power cables get constructed or deconstructed.
+
+pub(crate) fn rebuild_power_networks(
+    mut add_events: EventReader<AddDetails1Tile>,
+    mut remove_events: EventReader<RemoveDetails1Tile>,
+    gridmap: Res<Gridmap>,
+    mut networks: ResMut<PowerNetworks>,
+) {
+    if add_events.iter().count() + remove_events.iter().count() > 0 {
+        networks.dirty = true;
+    }
+    if !networks.dirty {
+        return;
+    }
+    networks.dirty = false;
+
+    let cable_type;
+    match gridmap.details1_name_id_map.get(POWER_CABLE_NAME) {
+        Some(t) => {
+            cable_type = *t;
+        }
+        None => {
+            return;
+        }
+    }
+
+    let mut cells: HashMap<Vec3Int, usize> = HashMap::new();
+    let mut new_networks: Vec<PowerNetwork> = vec![];
+
+    for (cell_id, item) in gridmap.details1.iter() {
+        if item.tile_type != cable_type || cells.contains_key(cell_id) {
+            continue;
+        }
+        let network_index = new_networks.len();
+        let mut network = PowerNetwork::default();
+        let mut stack = vec![*cell_id];
+        cells.insert(*cell_id, network_index);
+
+        while let Some(current) = stack.pop() {
+            network.cells.push(current);
+            for offset in ADJACENT_CELLS.iter() {
+                let adjacent = Vec3Int {
+                    x: current.x + offset.x,
+                    y: current.y + offset.y,
+                    z: current.z + offset.z,
+                };
+                if cells.contains_key(&adjacent) {
+                    continue;
+                }
+                match gridmap.details1.get(&adjacent) {
+                    Some(adjacent_item) => {
+                        if adjacent_item.tile_type == cable_type {
+                            cells.insert(adjacent, network_index);
+                            stack.push(adjacent);
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+        new_networks.push(network);
+    }
+
+    info!("Computed {} power networks.", new_networks.len());
+
+    networks.networks = new_networks;
+    networks.cells = cells;
+}
+
+/// Balance supply and load of every power network. Surplus charges batteries and shortages drain them.
+
+pub(crate) fn distribute_power(
+    mut networks: ResMut<PowerNetworks>,
+    mut consumers: Query<(&Transform, &mut PowerConsumer)>,
+    generators: Query<(&Transform, &PowerGenerator)>,
+    mut storages: Query<(&Transform, &mut PowerStorage)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    if delta <= 0. {
+        return;
+    }
+
+    for network in networks.networks.iter_mut() {
+        network.supply = 0.;
+        network.load = 0.;
+        network.stored = 0.;
+        network.capacity = 0.;
+    }
+
+    for (transform, generator) in generators.iter() {
+        if !generator.active {
+            continue;
+        }
+        match networks.device_network(transform.translation) {
+            Some(index) => {
+                networks.networks[index].supply += generator.output;
+            }
+            None => {}
+        }
+    }
+    for (transform, consumer) in consumers.iter() {
+        match networks.device_network(transform.translation) {
+            Some(index) => {
+                networks.networks[index].load += consumer.load;
+            }
+            None => {}
+        }
+    }
+
+    // Surplus of each network, negative during shortages.
+    let mut surplus: Vec<f32> = networks
+        .networks
+        .iter()
+        .map(|n| n.supply - n.load)
+        .collect();
+    let mut discharge_available = vec![0.; networks.networks.len()];
+    for (transform, storage) in storages.iter() {
+        match networks.device_network(transform.translation) {
+            Some(index) => {
+                discharge_available[index] += storage.max_rate.min(storage.charge / delta);
+            }
+            None => {}
+        }
+    }
+    for (index, network) in networks.networks.iter_mut().enumerate() {
+        network.powered = surplus[index] >= 0. || discharge_available[index] >= -surplus[index];
+    }
+
+    for (transform, mut storage) in storages.iter_mut() {
+        let index;
+        match networks.device_network(transform.translation) {
+            Some(i) => {
+                index = i;
+            }
+            None => {
+                continue;
+            }
+        }
+        if surplus[index] > 0. {
+            let rate = surplus[index]
+                .min(storage.max_rate)
+                .min((storage.capacity - storage.charge) / delta);
+            storage.charge += rate * delta;
+            surplus[index] -= rate;
+        } else if surplus[index] < 0. && networks.networks[index].powered {
+            let rate = (-surplus[index])
+                .min(storage.max_rate)
+                .min(storage.charge / delta);
+            storage.charge -= rate * delta;
+            surplus[index] += rate;
+        }
+        networks.networks[index].stored += storage.charge;
+        networks.networks[index].capacity += storage.capacity;
+    }
+
+    for (transform, mut consumer) in consumers.iter_mut() {
+        let powered = match networks.device_network(transform.translation) {
+            Some(index) => networks.networks[index].powered,
+            None => false,
+        };
+        if consumer.powered != powered {
+            consumer.powered = powered;
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{EventWriter, Query, Res};
+use map::{
+    map::{get_overlay_tile_item, get_overlay_tile_priority, Map, OverlayTile},
+    net::MapServerMessage,
+};
+use networking::server::{ConnectedPlayer, OutgoingReliableServerMessage};
+use resources::math::Vec2Int;
+
+use crate::network::{PowerNetwork, PowerNetworks};
+
+/// Id of the power mini-map display mode.
+pub const POWER_DISPLAY_MODE: &str = "power";
+
+fn network_overlay_tile(network: &PowerNetwork) -> OverlayTile {
+    if !network.powered {
+        OverlayTile::Red
+    } else if network.supply < network.load {
+        OverlayTile::Yellow
+    } else {
+        OverlayTile::Green
+    }
+}
+
+/// Show power cables colored by the state of their network on mini-maps in the power display mode.
+
+pub(crate) fn power_map_overlay(
+    map_holders: Query<(&Map, &ConnectedPlayer)>,
+    networks: Res<PowerNetworks>,
+    mut server: EventWriter<OutgoingReliableServerMessage<MapServerMessage>>,
+) {
+    for (map_component, connected_player) in map_holders.iter() {
+        if !connected_player.connected
+            || map_component.display_mode != Some(POWER_DISPLAY_MODE.to_string())
+        {
+            continue;
+        }
+
+        let view_range = map_component.view_range as i16;
+        let center = Vec2Int {
+            x: map_component.camera_position.x as i16,
+            y: map_component.camera_position.y as i16,
+        };
+
+        let mut tiles: HashMap<Vec2Int, OverlayTile> = HashMap::new();
+        for (cell_id, network_index) in networks.cells.iter() {
+            if (cell_id.x - center.x).abs() > view_range
+                || (cell_id.z - center.y).abs() > view_range
+            {
+                continue;
+            }
+            let tile;
+            match networks.networks.get(*network_index) {
+                Some(network) => {
+                    tile = network_overlay_tile(network);
+                }
+                None => {
+                    continue;
+                }
+            }
+            let id = Vec2Int {
+                x: cell_id.x,
+                y: cell_id.z,
+            };
+            // Cables stacked on different floors share a mini-map tile, show the worst state.
+            match tiles.get(&id) {
+                Some(existing) => {
+                    if get_overlay_tile_priority(existing) >= get_overlay_tile_priority(&tile) {
+                        continue;
+                    }
+                }
+                None => {}
+            }
+            tiles.insert(id, tile);
+        }
+
+        server.send(OutgoingReliableServerMessage {
+            handle: connected_player.handle,
+            message: MapServerMessage::MapOverlayUpdate(
+                tiles
+                    .iter()
+                    .map(|(id, tile)| (id.x, id.y, get_overlay_tile_item(tile)))
+                    .collect(),
+            ),
+        });
+
+        match map_component.passed_mouse_cell {
+            Some((idx, idy)) => {
+                let hovered = networks
+                    .cells
+                    .iter()
+                    .find(|(cell_id, _)| cell_id.x == idx && cell_id.z == idy);
+                let text = match hovered {
+                    Some((_, network_index)) => match networks.networks.get(*network_index) {
+                        Some(network) => format!(
+                            "Power network\nSupply: {} W\nLoad: {} W",
+                            network.supply as u32, network.load as u32
+                        ),
+                        None => "".to_string(),
+                    },
+                    None => "".to_string(),
+                };
+                server.send(OutgoingReliableServerMessage {
+                    handle: connected_player.handle,
+                    message: MapServerMessage::MapOverlayHoverData(text),
+                });
+            }
+            None => {}
+        }
+    }
+}
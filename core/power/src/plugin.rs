@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::{App, CoreSet, IntoSystemConfig, Plugin},
+    time::common_conditions::on_fixed_timer,
+};
+use networking::messaging::{register_reliable_message, MessageSender};
+use resources::{
+    is_server::is_server,
+    labels::{ActionsLabels, PostUpdateLabels},
+};
+
+use crate::{
+    examine::{examine_map_power, update_power_examine_texts},
+    net::PowerServerMessage,
+    network::{distribute_power, rebuild_power_networks, PowerNetworks},
+    overlay::power_map_overlay,
+    status::{
+        apply_power_status, broadcast_changed_power_status, load_power_status_for_client,
+        receive_power_status, PendingPowerStatuses,
+    },
+};
+
+pub struct PowerPlugin;
+
+impl Plugin for PowerPlugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.init_resource::<PowerNetworks>()
+                .add_system(rebuild_power_networks.in_base_set(CoreSet::PostUpdate))
+                .add_system(distribute_power)
+                .add_system(update_power_examine_texts.after(distribute_power))
+                .add_system(examine_map_power.after(ActionsLabels::Action))
+                .add_system(power_map_overlay.run_if(on_fixed_timer(Duration::from_secs_f32(1.))))
+                .add_system(load_power_status_for_client)
+                .add_system(
+                    broadcast_changed_power_status
+                        .in_base_set(CoreSet::PostUpdate)
+                        .before(PostUpdateLabels::EntityUpdate),
+                );
+        } else {
+            app.init_resource::<PendingPowerStatuses>()
+                .add_system(receive_power_status)
+                .add_system(apply_power_status.after(receive_power_status));
+        }
+
+        register_reliable_message::<PowerServerMessage>(app, MessageSender::Server);
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::{Component, Entity, Query};
+
+/// Entities that draw power from the power network they are connected to.
+#[derive(Component, Default)]
+
+pub struct PowerConsumer {
+    /// Power drawn while operating in watts.
+    pub load: f32,
+    /// Whether the connected network could supply the load last tick.
+    pub powered: bool,
+}
+
+/// Entities that supply power to the power network they are connected to.
+#[derive(Component)]
+
+pub struct PowerGenerator {
+    /// Power supplied while active in watts.
+    pub output: f32,
+    pub active: bool,
+}
+
+/// Entities that store surplus power of their network and supply it during shortages.
+#[derive(Component)]
+
+pub struct PowerStorage {
+    /// Stored energy in joules.
+    pub charge: f32,
+    /// Maximum stored energy in joules.
+    pub capacity: f32,
+    /// Maximum charge and discharge rate in watts.
+    pub max_rate: f32,
+}
+
+/// Whether an entity can operate. Entities that do not consume power always can.
+
+pub fn is_powered(entity: Entity, consumers: &Query<&PowerConsumer>) -> bool {
+    match consumers.get(entity) {
+        Ok(consumer) => consumer.powered,
+        Err(_) => true,
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{Changed, Entity, EventReader, EventWriter, Query, Res, ResMut, Resource};
+use entity::{
+    sensable::Sensable, spawn::ClientEntityServerEntity, spawning_events::SpawnClientEntity,
+};
+use networking::{
+    client::IncomingReliableServerMessage,
+    server::{HandleToEntity, OutgoingReliableServerMessage},
+};
+
+use crate::{net::PowerServerMessage, power::PowerConsumer};
+
+/// Send the powered status of consumers to clients that load them in.
+
+pub(crate) fn load_power_status_for_client(
+    mut load_events: EventReader<SpawnClientEntity>,
+    consumers: Query<&PowerConsumer>,
+    mut server: EventWriter<OutgoingReliableServerMessage<PowerServerMessage>>,
+) {
+    for event in load_events.iter() {
+        match consumers.get(event.entity) {
+            Ok(consumer) => {
+                server.send(OutgoingReliableServerMessage {
+                    handle: event.loader_handle,
+                    message: PowerServerMessage::PoweredStatus(event.entity, consumer.powered),
+                });
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Send changed powered statuses to the clients sensing the consumer.
+
+pub(crate) fn broadcast_changed_power_status(
+    changed: Query<(Entity, &PowerConsumer, &Sensable), Changed<PowerConsumer>>,
+    handle_to_entity: Res<HandleToEntity>,
+    mut server: EventWriter<OutgoingReliableServerMessage<PowerServerMessage>>,
+) {
+    for (entity, consumer, sensable) in changed.iter() {
+        for sensed_by in sensable.sensed_by.iter() {
+            match handle_to_entity.inv_map.get(sensed_by) {
+                Some(handle) => {
+                    server.send(OutgoingReliableServerMessage {
+                        handle: *handle,
+                        message: PowerServerMessage::PoweredStatus(entity, consumer.powered),
+                    });
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// Client resource with received powered statuses of server entities that have yet to be applied.
+#[derive(Resource, Default)]
+
+pub struct PendingPowerStatuses {
+    pub map: HashMap<Entity, bool>,
+}
+
+pub(crate) fn receive_power_status(
+    mut net: EventReader<IncomingReliableServerMessage<PowerServerMessage>>,
+    mut pending: ResMut<PendingPowerStatuses>,
+) {
+    for message in net.iter() {
+        match &message.message {
+            PowerServerMessage::PoweredStatus(server_entity, powered) => {
+                pending.map.insert(*server_entity, *powered);
+            }
+        }
+    }
+}
+
+/// Apply received powered statuses once their entities are loaded in.
+
+pub(crate) fn apply_power_status(
+    mut pending: ResMut<PendingPowerStatuses>,
+    map: Res<ClientEntityServerEntity>,
+    mut consumers: Query<&mut PowerConsumer>,
+) {
+    let mut applied = vec![];
+    for (server_entity, powered) in pending.map.iter() {
+        let client_entity;
+        match map.map.get(server_entity) {
+            Some(e) => {
+                client_entity = *e;
+            }
+            None => {
+                continue;
+            }
+        }
+        match consumers.get_mut(client_entity) {
+            Ok(mut consumer) => {
+                if consumer.powered != *powered {
+                    consumer.powered = *powered;
+                }
+            }
+            Err(_) => {
+                // Consumer not built yet.
+                continue;
+            }
+        }
+        applied.push(*server_entity);
+    }
+    for server_entity in applied {
+        pending.map.remove(&server_entity);
+    }
+}
@@ -1 +1,37 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use bevy::prelude::Resource;
+
 pub const SF_CONTENT_PREFIX: &str = "sf::";
+
+/// Namespace of the content shipped with the game.
+
+pub const SF_CONTENT_NAMESPACE: &str = "sf";
+
+/// Namespace of a content identifier such as `sf::pistol_b1`.
+
+pub fn content_namespace(identifier: &str) -> &str {
+    match identifier.rsplit_once("::") {
+        Some((namespace, _)) => namespace,
+        None => SF_CONTENT_NAMESPACE,
+    }
+}
+
+/// Client resource with the downloaded content of the connected server.
+/// Files of its content packs take the place of the assets with the same path in the asset folder.
+#[derive(Resource, Default)]
+
+pub struct ContentAssets {
+    /// Cached file by content pack namespace and path inside the pack.
+    pub paths: HashMap<(String, String), PathBuf>,
+}
+
+impl ContentAssets {
+    /// Path to load an asset from, the cached server content if one of its packs provides the asset.
+    pub fn asset_path(&self, namespace: &str, path: &str) -> String {
+        match self.paths.get(&(namespace.to_string(), path.to_string())) {
+            Some(cached) => cached.to_string_lossy().to_string(),
+            None => path.to_string(),
+        }
+    }
+}
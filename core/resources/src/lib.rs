@@ -13,8 +13,14 @@ pub mod converters;
 pub mod grid;
 pub mod hud;
 pub mod is_server;
+/// Start the server from a persistent world save.
+pub mod load_save;
+/// The map that gets loaded.
+pub mod maps;
 pub mod math;
 /// The Bevy ECS plugin of this crate.
 pub mod plugin;
+/// Sectors of the galaxy cluster and the servers hosting them.
+pub mod sectors;
 pub mod set_icon;
 pub mod ui;
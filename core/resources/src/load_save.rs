@@ -0,0 +1,17 @@
+use std::env;
+
+/// Command line option followed by the name of the world save to start from.
+
+pub const LOAD_SAVE_ARG: &str = "--load-save";
+
+/// The world save name passed with `--load-save <name>`, the default map is loaded when none.
+
+pub fn load_save_name() -> Option<String> {
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == LOAD_SAVE_ARG {
+            return args.next();
+        }
+    }
+    None
+}
@@ -0,0 +1,26 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Map that is loaded when the map rotation did not pick one yet.
+
+pub const DEFAULT_MAP_NAME: &str = "bullseye";
+
+/// File with the name of the map to load, written by the map rotation at the end of a round.
+
+pub fn current_map_path() -> PathBuf {
+    Path::new("data").join("maps").join("current.ron")
+}
+
+/// Name of the map to load from data/maps.
+
+pub fn current_map_name() -> String {
+    match fs::read_to_string(current_map_path()) {
+        Ok(raw_ron) => match ron::from_str::<String>(&raw_ron) {
+            Ok(name) => name,
+            Err(_) => DEFAULT_MAP_NAME.to_string(),
+        },
+        Err(_) => DEFAULT_MAP_NAME.to_string(),
+    }
+}
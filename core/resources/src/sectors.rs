@@ -0,0 +1,77 @@
+use std::{env, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// Command line option followed by the id of the sector this server hosts.
+
+pub const SECTOR_ARG: &str = "--sector";
+
+/// The sector id passed with `--sector <id>`, the server is standalone when none.
+
+pub fn sector_name() -> Option<String> {
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == SECTOR_ARG {
+            return args.next();
+        }
+    }
+    None
+}
+
+/// Link secret shipped in data/settings/sectors.ron, sectors refuse to link until it is changed.
+
+pub const DEFAULT_LINK_SECRET: &str = "change me";
+
+/// A sub-sector of the galaxy cluster and the server hosting it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct SectorRon {
+    pub id: String,
+    /// Address clients connect to.
+    pub game_address: String,
+    /// Address peer servers hand pawns over to.
+    pub link_address: String,
+}
+
+/// The sectors of the galaxy cluster, loaded from data/settings/sectors.ron.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+
+pub struct SectorRegistryRon {
+    pub sectors: Vec<SectorRon>,
+    /// Shared between the servers of the cluster, transfers with another secret are refused.
+    pub link_secret: String,
+}
+
+impl SectorRegistryRon {
+    pub fn sector(&self, id: &str) -> Option<&SectorRon> {
+        self.sectors.iter().find(|s| s.id == id)
+    }
+    /// Whether the link secret was set to something other than empty or the shipped default.
+    pub fn has_link_secret(&self) -> bool {
+        !self.link_secret.trim().is_empty() && self.link_secret != DEFAULT_LINK_SECRET
+    }
+}
+
+/// Load the sector registry from drive, empty if the server is not part of a cluster.
+
+pub fn load_sector_registry() -> SectorRegistryRon {
+    let path = Path::new("data").join("settings").join("sectors.ron");
+    match fs::read_to_string(path) {
+        Ok(raw_ron) => ron::from_str(&raw_ron).expect("Error parsing settings sectors.ron String."),
+        Err(_) => SectorRegistryRon::default(),
+    }
+}
+
+/// The sector this server hosts, if started with `--sector` and the sector is registered.
+
+pub fn local_sector() -> Option<SectorRon> {
+    match sector_name() {
+        Some(id) => match load_sector_registry().sector(&id) {
+            Some(sector) => Some(sector.clone()),
+            None => {
+                panic!("Sector {} is not in settings sectors.ron.", id);
+            }
+        },
+        None => None,
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{App, Entity, ResMut, Resource};
+use pawn::access::{AccessConfig, JobAssignments};
+
+/// A player that boarded this round.
+#[derive(Clone, Debug)]
+
+pub struct RoundPlayer {
+    pub handle: u64,
+    pub entity: Entity,
+    pub character_name: String,
+    pub job: String,
+    pub objectives: Vec<String>,
+}
+
+/// Outcome of a round, shown in the end-of-round summary.
+#[derive(Clone, Debug)]
+
+pub struct RoundResult {
+    pub title: String,
+    pub details: Vec<String>,
+}
+
+/// A game mode decides how a round is played and won.
+/// Implement it in a plugin and register it with [register_game_mode].
+
+pub trait GameMode: Send + Sync {
+    /// Identifier used in data/settings/round.ron.
+    fn id(&self) -> String;
+    fn name(&self) -> String;
+    /// Job a boarding player gets, given the job they picked in the setup ui.
+    /// None refuses boarding, for example when every job is full.
+    fn pick_job(
+        &mut self,
+        requested: &str,
+        access_config: &AccessConfig,
+        job_assignments: &JobAssignments,
+    ) -> Option<String>;
+    /// Objectives of a player that just boarded.
+    fn assign_objectives(&mut self, player: &RoundPlayer) -> Vec<String>;
+    /// Checked every frame of the round, ends the round early when some.
+    fn check_win(&mut self, players: &HashMap<u64, RoundPlayer>) -> Option<RoundResult>;
+    /// Result when the round ran out of time.
+    fn round_timeout(&mut self, players: &HashMap<u64, RoundPlayer>) -> RoundResult;
+}
+
+/// Resource with the registered game modes and the one this round is played with.
+#[derive(Resource, Default)]
+
+pub struct GameModes {
+    pub modes: HashMap<String, Box<dyn GameMode>>,
+    pub active: String,
+}
+
+impl GameModes {
+    pub fn active_mut(&mut self) -> Option<&mut Box<dyn GameMode>> {
+        self.modes.get_mut(&self.active)
+    }
+}
+
+fn store_game_mode<T: GameMode + Default + 'static>(mut game_modes: ResMut<GameModes>) {
+    let mode = T::default();
+    game_modes.modes.insert(mode.id(), Box::new(mode));
+}
+
+/// Register a game mode so it can be picked in data/settings/round.ron.
+
+pub fn register_game_mode<T: GameMode + Default + 'static>(app: &mut App) {
+    app.add_startup_system(store_game_mode::<T>);
+}
+
+/// Identifier of the default game mode.
+
+pub const EXTENDED_MODE_ID: &str = "extended";
+
+/// Default game mode without antagonists, the crew keeps the ship running until the round times out.
+#[derive(Default)]
+
+pub struct ExtendedMode;
+
+impl GameMode for ExtendedMode {
+    fn id(&self) -> String {
+        EXTENDED_MODE_ID.to_string()
+    }
+    fn name(&self) -> String {
+        "Extended".to_string()
+    }
+    fn pick_job(
+        &mut self,
+        requested: &str,
+        access_config: &AccessConfig,
+        job_assignments: &JobAssignments,
+    ) -> Option<String> {
+        access_config.open_job(requested, job_assignments)
+    }
+    fn assign_objectives(&mut self, _player: &RoundPlayer) -> Vec<String> {
+        vec!["Keep the ship running until the end of the shift.".to_string()]
+    }
+    fn check_win(&mut self, _players: &HashMap<u64, RoundPlayer>) -> Option<RoundResult> {
+        None
+    }
+    fn round_timeout(&mut self, players: &HashMap<u64, RoundPlayer>) -> RoundResult {
+        RoundResult {
+            title: "The shift has ended.".to_string(),
+            details: vec![format!("{} crew members served aboard.", players.len())],
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Round lifecycle of the server.
+//! A round moves from the lobby through a pre-round countdown into the round itself, ends with a summary and restarts the server on the next map of the rotation.
+//! Maps are only loaded at startup, so the restart disconnects all players and exits the process, the server is expected to run under a supervisor that starts it again.
+//! Game modes plug into the round to pick jobs, assign objectives and decide when the round is won.
+
+/// Game modes and the default extended mode.
+pub mod game_mode;
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Round states, configuration and systems.
+pub mod round;
@@ -0,0 +1,37 @@
+use bevy::prelude::{App, IntoSystemAppConfig, IntoSystemConfig, OnEnter, OnUpdate, Plugin};
+use resources::is_server::is_server;
+
+use crate::{
+    game_mode::{register_game_mode, ExtendedMode, GameModes},
+    round::{
+        end_round, load_round_config, restart_round, round_player_boarded, start_pre_round,
+        start_round, tick_lobby, tick_pre_round, tick_restart, tick_round, tick_round_end,
+        ReadyPlayers, Round, RoundConfig, RoundState,
+    },
+};
+
+pub struct RoundPlugin;
+
+impl Plugin for RoundPlugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.add_state::<RoundState>()
+                .init_resource::<RoundConfig>()
+                .init_resource::<GameModes>()
+                .init_resource::<ReadyPlayers>()
+                .init_resource::<Round>()
+                .add_startup_system(load_round_config)
+                .add_system(tick_lobby.in_set(OnUpdate(RoundState::Lobby)))
+                .add_system(start_pre_round.in_schedule(OnEnter(RoundState::PreRound)))
+                .add_system(tick_pre_round.in_set(OnUpdate(RoundState::PreRound)))
+                .add_system(start_round.in_schedule(OnEnter(RoundState::InProgress)))
+                .add_system(round_player_boarded.in_set(OnUpdate(RoundState::InProgress)))
+                .add_system(tick_round.in_set(OnUpdate(RoundState::InProgress)))
+                .add_system(end_round.in_schedule(OnEnter(RoundState::RoundEnd)))
+                .add_system(tick_round_end.in_set(OnUpdate(RoundState::RoundEnd)))
+                .add_system(restart_round.in_schedule(OnEnter(RoundState::Restart)))
+                .add_system(tick_restart.in_set(OnUpdate(RoundState::Restart)));
+            register_game_mode::<ExtendedMode>(app);
+        }
+    }
+}
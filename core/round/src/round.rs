@@ -0,0 +1,471 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use bevy::{
+    app::AppExit,
+    prelude::{
+        info, warn, Color, EventReader, EventWriter, NextState, Query, Res, ResMut, Resource,
+        States,
+    },
+    time::{Time, Timer, TimerMode},
+};
+use chat::net::{ChatMessage, ChatServerMessage};
+use networking::server::{
+    ConnectedPlayer, DisconnectClient, DisconnectReason, OutgoingReliableServerMessage,
+    DISCONNECT_DELAY,
+};
+use pawn::access::JobAssignments;
+use player::boarding::BoardingPlayer;
+use resources::maps::{current_map_name, current_map_path};
+use serde::{Deserialize, Serialize};
+use ui::{
+    fonts::{Fonts, SOURCECODE_REGULAR_FONT},
+    text::{NetTextSection, COMMUNICATION_FONT_SIZE},
+};
+
+use crate::game_mode::{GameModes, RoundPlayer, RoundResult, EXTENDED_MODE_ID};
+
+/// States of the round lifecycle.
+#[derive(States, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+
+pub enum RoundState {
+    /// Waiting for enough players to be ready.
+    #[default]
+    Lobby,
+    /// Countdown before the round starts.
+    PreRound,
+    InProgress,
+    /// The round summary is shown.
+    RoundEnd,
+    /// Players are disconnected and the server shuts down to be restarted on the next map.
+    /// The map is only loaded at startup, so a new round needs a new server process started by a supervisor.
+    Restart,
+}
+
+/// Resource with the round settings, loaded from data/settings/round.ron.
+#[derive(Serialize, Deserialize, Resource, Clone)]
+
+pub struct RoundConfig {
+    /// Ready players needed to start the pre-round countdown.
+    pub min_players: usize,
+    pub pre_round_seconds: u32,
+    pub round_minutes: u32,
+    /// Seconds the round summary is shown before the server restarts.
+    pub round_end_seconds: u32,
+    /// Identifier of the game mode rounds are played with.
+    pub game_mode: String,
+    /// Map rotation, the next map is loaded after each round.
+    pub maps: Vec<String>,
+}
+
+impl Default for RoundConfig {
+    fn default() -> Self {
+        Self {
+            min_players: 1,
+            pre_round_seconds: 30,
+            round_minutes: 60,
+            round_end_seconds: 30,
+            game_mode: EXTENDED_MODE_ID.to_string(),
+            maps: vec![],
+        }
+    }
+}
+
+/// Load the round settings from drive.
+
+pub(crate) fn load_round_config(
+    mut config: ResMut<RoundConfig>,
+    mut game_modes: ResMut<GameModes>,
+) {
+    let path = Path::new("data").join("settings").join("round.ron");
+    let raw_ron: String =
+        fs::read_to_string(path).expect("Error reading settings round.ron from drive.");
+    *config = ron::from_str(&raw_ron).expect("Error parsing settings round.ron String.");
+    game_modes.active = config.game_mode.clone();
+
+    info!(
+        "Loaded round settings with game mode {} and {} maps in rotation.",
+        config.game_mode,
+        config.maps.len()
+    );
+}
+
+/// Resource with the handles of players that requested boarding, they board once the round is in progress.
+#[derive(Resource, Default)]
+
+pub struct ReadyPlayers {
+    pub handles: Vec<u64>,
+}
+
+/// Resource with the state of the current round.
+#[derive(Resource)]
+
+pub struct Round {
+    /// Timer of the current round state.
+    pub timer: Timer,
+    pub players: HashMap<u64, RoundPlayer>,
+    pub result: Option<RoundResult>,
+    /// Amount of ready players last announced in the lobby.
+    pub announced_ready: usize,
+}
+
+impl Default for Round {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(0., TimerMode::Once),
+            players: HashMap::new(),
+            result: None,
+            announced_ready: 0,
+        }
+    }
+}
+
+fn chat_message(
+    net: &mut EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    fonts: &Fonts,
+    handle: u64,
+    text: &str,
+) {
+    net.send(OutgoingReliableServerMessage {
+        handle,
+        message: ChatServerMessage::ChatMessage(ChatMessage {
+            sections: vec![NetTextSection {
+                text: text.to_string(),
+                font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+                font_size: COMMUNICATION_FONT_SIZE,
+                color: Color::WHITE,
+            }],
+        }),
+    });
+}
+
+fn broadcast(
+    net: &mut EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    connected_players: &Query<&ConnectedPlayer>,
+    fonts: &Fonts,
+    text: &str,
+) {
+    for player in connected_players.iter() {
+        if !player.connected {
+            continue;
+        }
+        chat_message(net, fonts, player.handle, text);
+    }
+}
+
+/// Drop ready players that disconnected.
+
+fn prune_ready_players(ready: &mut ReadyPlayers, connected_players: &Query<&ConnectedPlayer>) {
+    ready.handles.retain(|handle| {
+        connected_players
+            .iter()
+            .any(|player| player.handle == *handle && player.connected)
+    });
+}
+
+/// Start the pre-round countdown once enough players are ready.
+
+pub(crate) fn tick_lobby(
+    mut ready: ResMut<ReadyPlayers>,
+    mut round: ResMut<Round>,
+    config: Res<RoundConfig>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut next_state: ResMut<NextState<RoundState>>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    fonts: Res<Fonts>,
+) {
+    prune_ready_players(&mut ready, &connected_players);
+
+    if ready.handles.len() >= config.min_players {
+        next_state.set(RoundState::PreRound);
+        return;
+    }
+    if ready.handles.len() != round.announced_ready {
+        round.announced_ready = ready.handles.len();
+        broadcast(
+            &mut net,
+            &connected_players,
+            &fonts,
+            &format!(
+                "{}/{} players ready to start the round.",
+                ready.handles.len(),
+                config.min_players
+            ),
+        );
+    }
+}
+
+pub(crate) fn start_pre_round(
+    mut round: ResMut<Round>,
+    config: Res<RoundConfig>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    fonts: Res<Fonts>,
+) {
+    round.timer = Timer::from_seconds(config.pre_round_seconds as f32, TimerMode::Once);
+    broadcast(
+        &mut net,
+        &connected_players,
+        &fonts,
+        &format!("The round starts in {} seconds.", config.pre_round_seconds),
+    );
+}
+
+/// Count down to the round, back to the lobby if too many ready players left.
+
+pub(crate) fn tick_pre_round(
+    mut ready: ResMut<ReadyPlayers>,
+    mut round: ResMut<Round>,
+    config: Res<RoundConfig>,
+    time: Res<Time>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut next_state: ResMut<NextState<RoundState>>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    fonts: Res<Fonts>,
+) {
+    prune_ready_players(&mut ready, &connected_players);
+
+    if ready.handles.len() < config.min_players {
+        round.announced_ready = ready.handles.len();
+        broadcast(
+            &mut net,
+            &connected_players,
+            &fonts,
+            "Not enough players ready, the round start was cancelled.",
+        );
+        next_state.set(RoundState::Lobby);
+        return;
+    }
+
+    round.timer.tick(time.delta());
+    if round.timer.just_finished() {
+        next_state.set(RoundState::InProgress);
+    }
+}
+
+pub(crate) fn start_round(
+    mut round: ResMut<Round>,
+    config: Res<RoundConfig>,
+    mut game_modes: ResMut<GameModes>,
+    mut job_assignments: ResMut<JobAssignments>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    fonts: Res<Fonts>,
+) {
+    round.timer = Timer::from_seconds(config.round_minutes as f32 * 60., TimerMode::Once);
+    round.players.clear();
+    round.result = None;
+    job_assignments.clear();
+
+    let name;
+    match game_modes.active_mut() {
+        Some(mode) => {
+            name = mode.name();
+        }
+        None => {
+            warn!(
+                "Couldnt find game mode {}, falling back to {}.",
+                game_modes.active, EXTENDED_MODE_ID
+            );
+            game_modes.active = EXTENDED_MODE_ID.to_string();
+            name = "Extended".to_string();
+        }
+    }
+    info!("Round started with game mode {}.", name);
+    broadcast(
+        &mut net,
+        &connected_players,
+        &fonts,
+        &format!("The round has started. Game mode: {}.", name),
+    );
+}
+
+/// Hand out objectives to players that boarded.
+
+pub(crate) fn round_player_boarded(
+    mut boarding: EventReader<BoardingPlayer>,
+    mut round: ResMut<Round>,
+    mut game_modes: ResMut<GameModes>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    fonts: Res<Fonts>,
+) {
+    for event in boarding.iter() {
+        let mut player = RoundPlayer {
+            handle: event.player_handle,
+            entity: event.entity,
+            character_name: event.player_character_name.clone(),
+            job: event.job.clone(),
+            objectives: vec![],
+        };
+        match game_modes.active_mut() {
+            Some(mode) => {
+                player.objectives = mode.assign_objectives(&player);
+            }
+            None => {
+                warn!("Couldnt find active game mode.");
+            }
+        }
+        for objective in player.objectives.iter() {
+            chat_message(
+                &mut net,
+                &fonts,
+                player.handle,
+                &("Objective: ".to_string() + objective),
+            );
+        }
+        round.players.insert(player.handle, player);
+    }
+}
+
+/// End the round when the game mode is won or the round ran out of time.
+
+pub(crate) fn tick_round(
+    mut round: ResMut<Round>,
+    time: Res<Time>,
+    mut game_modes: ResMut<GameModes>,
+    mut next_state: ResMut<NextState<RoundState>>,
+) {
+    round.timer.tick(time.delta());
+
+    let mode;
+    match game_modes.active_mut() {
+        Some(m) => {
+            mode = m;
+        }
+        None => {
+            return;
+        }
+    }
+    let result;
+    match mode.check_win(&round.players) {
+        Some(r) => {
+            result = r;
+        }
+        None => {
+            if !round.timer.just_finished() {
+                return;
+            }
+            result = mode.round_timeout(&round.players);
+        }
+    }
+    round.result = Some(result);
+    next_state.set(RoundState::RoundEnd);
+}
+
+/// Broadcast the end-of-round summary.
+
+pub(crate) fn end_round(
+    mut round: ResMut<Round>,
+    config: Res<RoundConfig>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    fonts: Res<Fonts>,
+) {
+    let minutes = round.timer.elapsed().as_secs() / 60;
+    let mut summary = vec!["The round has ended.".to_string()];
+    match &round.result {
+        Some(result) => {
+            summary.push(result.title.clone());
+            for detail in result.details.iter() {
+                summary.push(detail.clone());
+            }
+        }
+        None => {}
+    }
+    summary.push(format!("Round duration: {} minutes.", minutes));
+    for player in round.players.values() {
+        summary.push(format!("{} ({})", player.character_name, player.job));
+        for objective in player.objectives.iter() {
+            summary.push("- ".to_string() + objective);
+        }
+    }
+    for line in summary.iter() {
+        broadcast(&mut net, &connected_players, &fonts, line);
+    }
+    info!("{}", summary.join("\n"));
+
+    round.timer = Timer::from_seconds(config.round_end_seconds as f32, TimerMode::Once);
+}
+
+pub(crate) fn tick_round_end(
+    mut round: ResMut<Round>,
+    time: Res<Time>,
+    mut next_state: ResMut<NextState<RoundState>>,
+) {
+    round.timer.tick(time.delta());
+    if round.timer.just_finished() {
+        next_state.set(RoundState::Restart);
+    }
+}
+
+/// Next map of the rotation after the current map.
+
+fn next_map(maps: &[String], current: &str) -> Option<String> {
+    if maps.is_empty() {
+        return None;
+    }
+    match maps.iter().position(|map| map == current) {
+        Some(index) => Some(maps[(index + 1) % maps.len()].clone()),
+        None => Some(maps[0].clone()),
+    }
+}
+
+/// Persist the next map of the rotation, reset the round and disconnect all players.
+/// The map and its entities are only loaded at startup, so the server shuts down afterwards and is expected to be restarted by its supervisor.
+
+pub(crate) fn restart_round(
+    config: Res<RoundConfig>,
+    mut round: ResMut<Round>,
+    mut ready: ResMut<ReadyPlayers>,
+    mut job_assignments: ResMut<JobAssignments>,
+    connected_players: Query<&ConnectedPlayer>,
+    mut disconnect: EventWriter<DisconnectClient>,
+) {
+    let map = next_map(&config.maps, &current_map_name()).unwrap_or(current_map_name());
+    match ron::to_string(&map) {
+        Ok(data) => match fs::write(current_map_path(), data) {
+            Ok(_) => {}
+            Err(error) => {
+                warn!("Couldnt write next map {}: {}", map, error);
+            }
+        },
+        Err(error) => {
+            warn!("Couldnt serialize next map {}: {}", map, error);
+        }
+    }
+    info!("Restarting server, next map is {}.", map);
+
+    // Nobody boards into the round that is shutting down.
+    ready.handles.clear();
+    job_assignments.clear();
+    round.players.clear();
+    round.result = None;
+    round.announced_ready = 0;
+
+    for player in connected_players.iter() {
+        if !player.connected {
+            continue;
+        }
+        disconnect.send(DisconnectClient {
+            handle: player.handle,
+            reason: DisconnectReason::Kicked(format!(
+                "The round is over, the server restarts on {}.",
+                map
+            )),
+        });
+    }
+    // Give the disconnect reasons time to arrive before the server goes down.
+    round.timer = Timer::from_seconds(DISCONNECT_DELAY * 2., TimerMode::Once);
+}
+
+/// Shut the server down once the players have been disconnected.
+
+pub(crate) fn tick_restart(
+    mut round: ResMut<Round>,
+    time: Res<Time>,
+    mut exit: EventWriter<AppExit>,
+) {
+    round.timer.tick(time.delta());
+    if round.timer.just_finished() {
+        exit.send(AppExit);
+    }
+}
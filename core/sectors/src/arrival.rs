@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{info, warn, Entity, EventReader, EventWriter, Query, Res, ResMut, Resource},
+    time::{Time, Timer, TimerMode},
+};
+use bevy_renet::renet::ServerEvent;
+use entity::{
+    entity_types::EntityTypes,
+    health::{HealthComponent, HealthContainer, HumanoidHealth},
+};
+use networking::server::{
+    HandleToEntity, IncomingReliableClientMessage, NetworkingChatServerMessage,
+    NetworkingClientMessage, OutgoingReliableServerMessage,
+};
+use pawn::access::{AccessConfig, JobAssignments};
+use player::{
+    boarding::{BoardingPlayer, PlayerBoarded},
+    connections::{ArrivingPlayers, PlayerAwaitingBoarding},
+};
+use setup_menu::core::{SetupUiUserData, SetupUiUserDataSets};
+
+use crate::link::{PawnTransferData, SectorLink};
+
+/// Seconds a handed over pawn waits for its client to claim it.
+
+pub const TRANSFER_TOKEN_LIFETIME: f32 = 60.;
+
+/// Maximum amount of inventory items a handed over pawn arrives with.
+
+pub const MAX_TRANSFER_INVENTORY: usize = 64;
+
+/// Frames to wait for an arrived pawn to be built before giving up on its transferred health.
+
+const MAX_RESTORE_FRAMES: u32 = 600;
+
+/// Resource with the pawns handed over by peer sectors.
+#[derive(Resource, Default)]
+
+pub struct PendingArrivals {
+    /// Transfers waiting for their client by token.
+    pub tokens: HashMap<u64, (PawnTransferData, Timer)>,
+    /// Transfers claimed by connected clients by handle, with the lifetime left once released again.
+    pub claimed: HashMap<u64, (PawnTransferData, Timer)>,
+    /// Handles of arriving players waiting for an open job slot to board.
+    pub awaiting: Vec<u64>,
+    /// Transferred health to apply once the pawn is built, with the frames waited.
+    pub health: Vec<(Entity, HumanoidHealth, u32)>,
+}
+
+/// Store pawns handed over by peer sectors until their client claims them.
+
+pub(crate) fn receive_arrivals(
+    link: Res<SectorLink>,
+    mut pending: ResMut<PendingArrivals>,
+    time: Res<Time>,
+    types: Res<EntityTypes>,
+) {
+    match link.incoming.lock() {
+        Ok(receiver) => {
+            while let Ok(mut transfer) = receiver.try_recv() {
+                // Peers are trusted with pawns, not with spawning whatever they like.
+                transfer.inventory.truncate(MAX_TRANSFER_INVENTORY);
+                transfer.inventory.retain(|entity_type| {
+                    let known = types.types.contains_key(entity_type);
+                    if !known {
+                        warn!(
+                            "Couldnt find entity type {} of arriving {}.",
+                            entity_type, transfer.character_name
+                        );
+                    }
+                    known
+                });
+                info!(
+                    "{} is arriving from sector {}.",
+                    transfer.character_name, transfer.from_sector
+                );
+                pending.tokens.insert(
+                    transfer.token,
+                    (
+                        transfer,
+                        Timer::from_seconds(TRANSFER_TOKEN_LIFETIME, TimerMode::Once),
+                    ),
+                );
+            }
+        }
+        Err(_) => {
+            warn!("Couldnt lock sector link.");
+        }
+    }
+
+    pending.tokens.retain(|_, (transfer, timer)| {
+        timer.tick(time.delta());
+        if timer.finished() {
+            info!(
+                "Transfer of {} from sector {} expired.",
+                transfer.character_name, transfer.from_sector
+            );
+        }
+        !timer.finished()
+    });
+}
+
+/// Match connecting clients with the pawn they claim with their transfer token.
+
+pub(crate) fn claim_transfer_tokens(
+    mut incoming: EventReader<IncomingReliableClientMessage<NetworkingClientMessage>>,
+    mut pending: ResMut<PendingArrivals>,
+    mut arriving: ResMut<ArrivingPlayers>,
+) {
+    for message in incoming.iter() {
+        match &message.message {
+            NetworkingClientMessage::SectorTransfer(token) => {
+                match pending.tokens.remove(token) {
+                    Some(claim) => {
+                        arriving.handles.insert(message.handle);
+                        pending.claimed.insert(message.handle, claim);
+                    }
+                    None => {
+                        // The client boards as a new player instead.
+                        warn!("Couldnt find transfer token of [{}].", message.handle);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Hand claimed transfers of clients that disconnected before boarding back to their token.
+
+pub(crate) fn release_disconnected_claims(
+    mut server_events: EventReader<ServerEvent>,
+    mut pending: ResMut<PendingArrivals>,
+) {
+    for event in server_events.iter() {
+        match event {
+            ServerEvent::ClientDisconnected(handle) => {
+                pending.awaiting.retain(|h| h != handle);
+                match pending.claimed.remove(handle) {
+                    Some((transfer, timer)) => {
+                        info!(
+                            "{} disconnected before arriving, the transfer can be claimed again.",
+                            transfer.character_name
+                        );
+                        pending.tokens.insert(transfer.token, (transfer, timer));
+                    }
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Board arriving players with their transferred character, job and inventory.
+/// Players wait for a slot when their job and every other job is full.
+
+pub(crate) fn board_arriving_players(
+    mut awaiting_boarding: EventReader<PlayerAwaitingBoarding>,
+    mut pending: ResMut<PendingArrivals>,
+    handle_to_entity: Res<HandleToEntity>,
+    mut setupui_datas: ResMut<SetupUiUserDataSets>,
+    access_config: Res<AccessConfig>,
+    mut job_assignments: ResMut<JobAssignments>,
+    mut boarding: EventWriter<BoardingPlayer>,
+    mut net: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+) {
+    let pending = &mut *pending;
+    let mut arrived = vec![];
+    for event in awaiting_boarding.iter() {
+        if pending.claimed.contains_key(&event.handle) {
+            pending.awaiting.push(event.handle);
+            arrived.push(event.handle);
+        }
+    }
+
+    let awaiting = std::mem::take(&mut pending.awaiting);
+    for handle in awaiting {
+        let transfer;
+        match pending.claimed.get(&handle) {
+            Some((t, _)) => {
+                transfer = t;
+            }
+            None => {
+                continue;
+            }
+        }
+        let entity;
+        match handle_to_entity.map.get(&handle) {
+            Some(e) => {
+                entity = *e;
+            }
+            None => {
+                warn!("Couldnt find entity of arriving player {}.", handle);
+                continue;
+            }
+        }
+        let job;
+        match access_config.open_job(&transfer.job, &job_assignments) {
+            Some(j) => {
+                job = j;
+            }
+            None => {
+                if arrived.contains(&handle) {
+                    net.send(OutgoingReliableServerMessage {
+                        handle,
+                        message: NetworkingChatServerMessage::ChatMessage(
+                            "[color=#ff003c]There are no open jobs left, waiting for a slot to board.[/color]"
+                                .to_string(),
+                        ),
+                    });
+                }
+                pending.awaiting.push(handle);
+                continue;
+            }
+        }
+        job_assignments.assign(&job);
+
+        setupui_datas.list.insert(
+            handle,
+            SetupUiUserData {
+                character_name: transfer.character_name.clone(),
+                appearance: transfer.appearance.clone(),
+                job: job.clone(),
+            },
+        );
+        boarding.send(BoardingPlayer {
+            player_handle: handle,
+            player_character_name: transfer.character_name.clone(),
+            entity,
+            job,
+            inventory_option: Some(transfer.inventory.clone()),
+        });
+    }
+}
+
+/// Apply the transferred health to arrived pawns once they are built.
+
+pub(crate) fn restore_arriving_health(
+    mut boarded: EventReader<PlayerBoarded>,
+    mut pending: ResMut<PendingArrivals>,
+    mut arriving: ResMut<ArrivingPlayers>,
+    mut health_query: Query<&mut HealthComponent>,
+) {
+    for event in boarded.iter() {
+        match pending.claimed.remove(&event.handle) {
+            Some((transfer, _)) => {
+                arriving.handles.remove(&event.handle);
+                match transfer.health {
+                    Some(health) => {
+                        pending.health.push((event.entity, health, 0));
+                    }
+                    None => {}
+                }
+            }
+            None => {}
+        }
+    }
+
+    pending.health.retain_mut(
+        |(entity, health, frames)| match health_query.get_mut(*entity) {
+            Ok(mut component) => {
+                component.health.health_container = HealthContainer::Humanoid(health.clone());
+                false
+            }
+            Err(_) => {
+                *frames += 1;
+                if *frames > MAX_RESTORE_FRAMES {
+                    warn!("Couldnt restore transferred health of {:?}.", entity);
+                    return false;
+                }
+                true
+            }
+        },
+    );
+}
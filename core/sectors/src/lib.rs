@@ -0,0 +1,14 @@
+//! Sector handoff between the servers of a galaxy cluster.
+//! Each server hosts one sub-sector, registered with its game and link address in `data/settings/sectors.ron` and picked with `--sector <id>`.
+//! A pawn moving to another sector gets serialized and handed to the peer server over the link, after which its client is redirected with a transfer token.
+//! The client reconnects to the peer server with the token and boards with its transferred character, skipping the setup UI.
+//! All sectors of a cluster are expected to run the same map, clients load it locally.
+
+/// Receive pawns from peer sectors.
+pub mod arrival;
+/// The inter-server link and its wire format.
+pub mod link;
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Hand pawns over to peer sectors.
+pub mod transfer;
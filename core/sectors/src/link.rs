@@ -0,0 +1,259 @@
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use bevy::prelude::{info, warn, Commands, Resource};
+use entity::health::HumanoidHealth;
+use networking::server::NETCODE_VERSION;
+use pawn::appearance::CharacterAppearance;
+use resources::sectors::{load_sector_registry, local_sector};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Maximum size in bytes of a single message over the link.
+
+pub const MAX_LINK_FRAME_SIZE: u32 = 1_048_576;
+
+/// Seconds to wait on a peer sector before a transfer fails.
+
+pub const LINK_TIMEOUT: u64 = 5;
+
+/// A pawn handed over to another sector.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct PawnTransferData {
+    /// Token the client claims the pawn with on the peer sector.
+    pub token: u64,
+    pub from_sector: String,
+    pub account_name: String,
+    pub character_name: String,
+    pub appearance: CharacterAppearance,
+    pub job: String,
+    pub health: Option<HumanoidHealth>,
+    /// Entity types of the items in the inventory of the pawn.
+    pub inventory: Vec<String>,
+}
+
+/// Sent by a sector to hand a pawn over.
+#[derive(Serialize, Deserialize)]
+
+pub struct LinkRequest {
+    pub link_secret: String,
+    /// [NETCODE_VERSION] of the sending server.
+    pub version: u32,
+    pub transfer: PawnTransferData,
+}
+
+/// Answer of the peer sector to a [LinkRequest].
+#[derive(Serialize, Deserialize, Debug)]
+
+pub enum LinkResponse {
+    Accepted,
+    Refused(String),
+}
+
+/// Write a length prefixed message to the link.
+
+pub fn write_frame<T: Serialize>(stream: &mut TcpStream, message: &T) -> Result<(), String> {
+    let bin;
+    match bincode::serialize(message) {
+        Ok(b) => {
+            bin = b;
+        }
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    if bin.len() > MAX_LINK_FRAME_SIZE as usize {
+        return Err("Link message too large.".to_string());
+    }
+    match stream.write_all(&(bin.len() as u32).to_be_bytes()) {
+        Ok(_) => {}
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    match stream.write_all(&bin) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Read a length prefixed message from the link.
+
+pub fn read_frame<T: DeserializeOwned>(stream: &mut TcpStream) -> Result<T, String> {
+    let mut length = [0u8; 4];
+    match stream.read_exact(&mut length) {
+        Ok(_) => {}
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    let length = u32::from_be_bytes(length);
+    if length > MAX_LINK_FRAME_SIZE {
+        return Err(format!("Link message of {} bytes too large.", length));
+    }
+    let mut bin = vec![0u8; length as usize];
+    match stream.read_exact(&mut bin) {
+        Ok(_) => {}
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    match bincode::deserialize(&bin) {
+        Ok(message) => Ok(message),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Hand a pawn over to the link address of a peer sector, blocks until the peer answered.
+
+pub fn send_transfer(
+    link_address: &str,
+    link_secret: &str,
+    transfer: PawnTransferData,
+) -> Result<(), String> {
+    let address: SocketAddr;
+    match link_address.parse() {
+        Ok(a) => {
+            address = a;
+        }
+        Err(_) => {
+            return Err(format!("Invalid link address {}.", link_address));
+        }
+    }
+    let mut stream;
+    match TcpStream::connect_timeout(&address, Duration::from_secs(LINK_TIMEOUT)) {
+        Ok(s) => {
+            stream = s;
+        }
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(LINK_TIMEOUT)));
+    let _ = stream.set_write_timeout(Some(Duration::from_secs(LINK_TIMEOUT)));
+
+    write_frame(
+        &mut stream,
+        &LinkRequest {
+            link_secret: link_secret.to_string(),
+            version: NETCODE_VERSION,
+            transfer,
+        },
+    )?;
+    match read_frame::<LinkResponse>(&mut stream)? {
+        LinkResponse::Accepted => Ok(()),
+        LinkResponse::Refused(reason) => Err(reason),
+    }
+}
+
+/// Outcome of a transfer to a peer sector.
+
+pub struct TransferOutcome {
+    pub handle: u64,
+    pub token: u64,
+    /// Game address of the peer sector.
+    pub game_address: String,
+    pub result: Result<(), String>,
+}
+
+/// Resource with the channels between the link threads and the game.
+#[derive(Resource)]
+
+pub struct SectorLink {
+    /// Pawns handed over by peer sectors.
+    pub incoming: Mutex<Receiver<PawnTransferData>>,
+    /// Clone into transfer threads to report their outcome.
+    pub outcome_sender: Mutex<Sender<TransferOutcome>>,
+    pub outcomes: Mutex<Receiver<TransferOutcome>>,
+}
+
+fn handle_link_request(
+    stream: &mut TcpStream,
+    link_secret: &str,
+    incoming: &Sender<PawnTransferData>,
+) -> Result<(), String> {
+    let request = read_frame::<LinkRequest>(stream)?;
+    let response;
+    if request.link_secret != link_secret {
+        response = LinkResponse::Refused("Invalid link secret.".to_string());
+    } else if request.version != NETCODE_VERSION {
+        response = LinkResponse::Refused(format!(
+            "Netcode version mismatch, the peer runs version {}.",
+            NETCODE_VERSION
+        ));
+    } else {
+        match incoming.send(request.transfer) {
+            Ok(_) => {
+                response = LinkResponse::Accepted;
+            }
+            Err(_) => {
+                response = LinkResponse::Refused("Sector is shutting down.".to_string());
+            }
+        }
+    }
+    write_frame(stream, &response)
+}
+
+/// Listen to pawns handed over by peer sectors on the link address of this sector.
+
+pub(crate) fn start_sector_link(mut commands: Commands) {
+    let (incoming_sender, incoming) = channel();
+    let (outcome_sender, outcomes) = channel();
+    commands.insert_resource(SectorLink {
+        incoming: Mutex::new(incoming),
+        outcome_sender: Mutex::new(outcome_sender),
+        outcomes: Mutex::new(outcomes),
+    });
+
+    let sector;
+    match local_sector() {
+        Some(s) => {
+            sector = s;
+        }
+        None => {
+            return;
+        }
+    }
+    let registry = load_sector_registry();
+    // Anyone knowing the secret can hand arbitrary pawns over to this sector.
+    if !registry.has_link_secret() {
+        panic!(
+            "Sector {} refuses to link, set link_secret in settings sectors.ron to a private value.",
+            sector.id
+        );
+    }
+    let link_secret = registry.link_secret;
+    let listener = TcpListener::bind(&sector.link_address).expect("Failed to bind sector link.");
+    info!(
+        "Hosting sector {}, listening to peer sectors on [{}].",
+        sector.id, sector.link_address
+    );
+
+    thread::spawn(move || {
+        for stream_result in listener.incoming() {
+            match stream_result {
+                Ok(mut stream) => {
+                    let _ = stream.set_read_timeout(Some(Duration::from_secs(LINK_TIMEOUT)));
+                    let _ = stream.set_write_timeout(Some(Duration::from_secs(LINK_TIMEOUT)));
+                    match handle_link_request(&mut stream, &link_secret, &incoming_sender) {
+                        Ok(_) => {}
+                        Err(error) => {
+                            warn!("Couldnt handle sector link request: {}", error);
+                        }
+                    }
+                }
+                Err(error) => {
+                    warn!("Couldnt accept sector link connection: {}", error);
+                }
+            }
+        }
+    });
+}
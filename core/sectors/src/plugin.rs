@@ -0,0 +1,37 @@
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use player::plugin::ConfigurationLabel;
+use resources::is_server::is_server;
+
+use crate::{
+    arrival::{
+        board_arriving_players, claim_transfer_tokens, receive_arrivals,
+        release_disconnected_claims, restore_arriving_health, PendingArrivals,
+    },
+    link::start_sector_link,
+    transfer::{
+        finish_pawn_transfers, start_pawn_transfers, OutgoingTransfers, SectorRegistry,
+        TransferPawn,
+    },
+};
+
+pub struct SectorsPlugin;
+
+impl Plugin for SectorsPlugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.add_event::<TransferPawn>()
+                .init_resource::<SectorRegistry>()
+                .init_resource::<OutgoingTransfers>()
+                .init_resource::<PendingArrivals>()
+                .add_startup_system(start_sector_link)
+                .add_system(start_pawn_transfers)
+                .add_system(finish_pawn_transfers)
+                .add_system(receive_arrivals)
+                // Claim before the client gets configured so it skips the setup UI.
+                .add_system(claim_transfer_tokens.before(ConfigurationLabel::SpawnEntity))
+                .add_system(release_disconnected_claims)
+                .add_system(board_arriving_players)
+                .add_system(restore_arriving_health);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::{collections::HashSet, thread};
+
+use bevy::prelude::{info, warn, Entity, EventReader, EventWriter, Query, Res, ResMut, Resource};
+use entity::{
+    entity_data::EntityData,
+    entity_types::EntityType,
+    health::{HealthComponent, HealthContainer},
+    spawning_events::DespawnClientEntity,
+};
+use inventory::server::inventory::Inventory;
+use networking::server::{
+    DisconnectClient, DisconnectReason, HandleToEntity, NetworkingChatServerMessage,
+    OutgoingReliableServerMessage,
+};
+use pawn::{access::IdCard, pawn::Pawn};
+use player::{account::Accounts, names::UsedNames};
+use resources::sectors::{load_sector_registry, sector_name, SectorRegistryRon};
+
+use crate::link::{send_transfer, PawnTransferData, SectorLink, TransferOutcome};
+
+/// Event to hand the pawn of a player over to another sector.
+
+pub struct TransferPawn {
+    pub handle: u64,
+    /// Id of the sector to move to.
+    pub sector: String,
+}
+
+/// Resource with the players whose pawn is being handed over.
+#[derive(Resource, Default)]
+
+pub struct OutgoingTransfers {
+    pub handles: HashSet<u64>,
+}
+
+/// Resource with the sector registry of the cluster.
+#[derive(Resource)]
+
+pub struct SectorRegistry {
+    pub registry: SectorRegistryRon,
+}
+
+impl Default for SectorRegistry {
+    fn default() -> Self {
+        Self {
+            registry: load_sector_registry(),
+        }
+    }
+}
+
+fn chat_reply(
+    net: &mut EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+    handle: u64,
+    text: String,
+) {
+    net.send(OutgoingReliableServerMessage {
+        handle,
+        message: NetworkingChatServerMessage::ChatMessage(text),
+    });
+}
+
+/// Serialize pawns and hand them over to their peer sector on a link thread.
+
+pub(crate) fn start_pawn_transfers(
+    mut events: EventReader<TransferPawn>,
+    registry: Res<SectorRegistry>,
+    link: Res<SectorLink>,
+    mut outgoing: ResMut<OutgoingTransfers>,
+    handle_to_entity: Res<HandleToEntity>,
+    accounts: Res<Accounts>,
+    pawns: Query<(&Pawn, Option<&HealthComponent>, Option<&Inventory>)>,
+    items: Query<(&EntityData, Option<&IdCard>)>,
+    mut net: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+) {
+    for event in events.iter() {
+        let from_sector;
+        match sector_name() {
+            Some(s) => {
+                from_sector = s;
+            }
+            None => {
+                chat_reply(
+                    &mut net,
+                    event.handle,
+                    "This server is not part of a galaxy cluster.".to_string(),
+                );
+                continue;
+            }
+        }
+        if event.sector == from_sector {
+            chat_reply(
+                &mut net,
+                event.handle,
+                format!("You are already in sector {}.", from_sector),
+            );
+            continue;
+        }
+        let sector;
+        match registry.registry.sector(&event.sector) {
+            Some(s) => {
+                sector = s.clone();
+            }
+            None => {
+                chat_reply(
+                    &mut net,
+                    event.handle,
+                    format!("Unknown sector {}.", event.sector),
+                );
+                continue;
+            }
+        }
+        if outgoing.handles.contains(&event.handle) {
+            continue;
+        }
+
+        let pawn_entity;
+        match handle_to_entity.map.get(&event.handle) {
+            Some(e) => {
+                pawn_entity = *e;
+            }
+            None => {
+                warn!("Couldnt find entity of player {}.", event.handle);
+                continue;
+            }
+        }
+        let account_name;
+        match accounts.list.get(&event.handle) {
+            Some(n) => {
+                account_name = n.clone();
+            }
+            None => {
+                warn!("Couldnt find account name of {}.", event.handle);
+                continue;
+            }
+        }
+        let pawn_components;
+        match pawns.get(pawn_entity) {
+            Ok(components) => {
+                pawn_components = components;
+            }
+            Err(_) => {
+                chat_reply(
+                    &mut net,
+                    event.handle,
+                    "Board before moving to another sector.".to_string(),
+                );
+                continue;
+            }
+        }
+        let (pawn, health_option, inventory_option) = pawn_components;
+
+        let mut health = None;
+        match health_option {
+            Some(component) => match &component.health.health_container {
+                HealthContainer::Humanoid(humanoid) => {
+                    health = Some(humanoid.clone());
+                }
+                _ => {}
+            },
+            None => {}
+        }
+
+        // ID cards are issued again by the peer sector for the job.
+        let mut inventory = vec![];
+        match inventory_option {
+            Some(i) => {
+                for slot in i.slots.values() {
+                    for item in slot.items.iter() {
+                        match items.get(item.entity) {
+                            Ok((_, Some(_))) => {}
+                            Ok((entity_data, None)) => {
+                                inventory.push(entity_data.entity_type.get_identity());
+                            }
+                            Err(_) => {
+                                warn!("Couldnt find inventory item {:?}.", item.entity);
+                            }
+                        }
+                    }
+                }
+            }
+            None => {}
+        }
+
+        let transfer = PawnTransferData {
+            token: rand::random::<u64>(),
+            from_sector,
+            account_name,
+            character_name: pawn.character_name.clone(),
+            appearance: pawn.appearance.clone(),
+            job: pawn.job.clone(),
+            health,
+            inventory,
+        };
+
+        let outcome_sender;
+        match link.outcome_sender.lock() {
+            Ok(sender) => {
+                outcome_sender = sender.clone();
+            }
+            Err(_) => {
+                warn!("Couldnt lock sector link.");
+                continue;
+            }
+        }
+        if !registry.registry.has_link_secret() {
+            chat_reply(
+                &mut net,
+                event.handle,
+                "The sector link secret is not configured.".to_string(),
+            );
+            continue;
+        }
+        outgoing.handles.insert(event.handle);
+        info!(
+            "Handing {} over to sector {}.",
+            transfer.character_name, sector.id
+        );
+
+        let handle = event.handle;
+        let link_secret = registry.registry.link_secret.clone();
+
+        thread::spawn(move || {
+            let token = transfer.token;
+            let result = send_transfer(&sector.link_address, &link_secret, transfer);
+            let _ = outcome_sender.send(TransferOutcome {
+                handle,
+                token,
+                game_address: sector.game_address,
+                result,
+            });
+        });
+    }
+}
+
+/// Redirect the clients of handed over pawns and remove the pawns from this sector.
+
+pub(crate) fn finish_pawn_transfers(
+    link: Res<SectorLink>,
+    mut outgoing: ResMut<OutgoingTransfers>,
+    mut handle_to_entity: ResMut<HandleToEntity>,
+    mut used_names: ResMut<UsedNames>,
+    pawns: Query<(&Pawn, Option<&Inventory>)>,
+    mut disconnect: EventWriter<DisconnectClient>,
+    mut despawn: EventWriter<DespawnClientEntity>,
+    mut net: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+) {
+    let mut outcomes = vec![];
+    match link.outcomes.lock() {
+        Ok(receiver) => {
+            while let Ok(outcome) = receiver.try_recv() {
+                outcomes.push(outcome);
+            }
+        }
+        Err(_) => {
+            warn!("Couldnt lock sector link.");
+            return;
+        }
+    }
+
+    for outcome in outcomes {
+        outgoing.handles.remove(&outcome.handle);
+        match outcome.result {
+            Ok(_) => {}
+            Err(error) => {
+                warn!("Couldnt hand over pawn of {}: {}", outcome.handle, error);
+                chat_reply(
+                    &mut net,
+                    outcome.handle,
+                    "The sector you are moving to is unreachable.".to_string(),
+                );
+                continue;
+            }
+        }
+
+        match handle_to_entity.map.remove(&outcome.handle) {
+            Some(pawn_entity) => {
+                handle_to_entity.inv_map.remove(&pawn_entity);
+                match pawns.get(pawn_entity) {
+                    Ok((pawn, inventory_option)) => {
+                        used_names.names.remove(&pawn.character_name);
+                        match inventory_option {
+                            Some(inventory) => {
+                                for slot in inventory.slots.values() {
+                                    for item in slot.items.iter() {
+                                        despawn.send(DespawnClientEntity {
+                                            entity: item.entity,
+                                        });
+                                    }
+                                }
+                            }
+                            None => {}
+                        }
+                    }
+                    Err(_) => {}
+                }
+                despawn.send(DespawnClientEntity {
+                    entity: pawn_entity,
+                });
+            }
+            None => {}
+        }
+
+        disconnect.send(DisconnectClient {
+            handle: outcome.handle,
+            reason: DisconnectReason::Redirected(outcome.game_address, outcome.token),
+        });
+    }
+}
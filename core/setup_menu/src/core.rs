@@ -1,15 +1,25 @@
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use bevy::prelude::{Added, Commands, EventReader, EventWriter, Query, Res, Resource};
+use bevy::prelude::{info, Added, Commands, EventReader, EventWriter, Query, Res, Resource};
 use networking::server::HandleToEntity;
 use player::connections::PlayerAwaitingBoarding;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
 
 /// Godot NodePath.
 pub const INPUT_NAME_PATH_FULL : &str = "setupUI::ColorRect/background/VBoxContainer/HBoxContainer/characterSettingsPopup/Control/TabContainer/Boarding Configuration/VBoxContainer/vBoxNameInput/Control/inputName";
 /// Godot NodePath.
 pub const INPUT_NAME_PATH : &str = "ColorRect/background/VBoxContainer/HBoxContainer/characterSettingsPopup/Control/TabContainer/Boarding Configuration/VBoxContainer/vBoxNameInput/Control/inputName";
 
+use crate::creator::CharacterCreatorState;
+use crate::net::{CharacterCreatorOptions, CharacterProfile, JobOption};
+use entity::showcase::Showcase;
 use motd::motd::MOTD;
+use pawn::access::{AccessConfig, JobAssignments};
+use pawn::appearance::{AppearanceConfig, CharacterAppearance};
+use player::account::Accounts;
 use player::name_generator::get_full_name;
 use player::{connections::SetupPhase, names::UsedNames};
 
@@ -17,21 +27,78 @@ use networking::server::NetworkingChatServerMessage;
 use networking::server::OutgoingReliableServerMessage;
 
 use networking::server::ConnectedPlayer;
-/// Initialize the setup UI by spawning in showcase entities etc.
 
-pub(crate) fn initialize_setupui(
+/// The character creator options with the slots left of each job.
+
+pub fn creator_options(
+    appearance_config: &AppearanceConfig,
+    access_config: &AccessConfig,
+    job_assignments: &JobAssignments,
+) -> CharacterCreatorOptions {
+    CharacterCreatorOptions {
+        appearance: appearance_config.clone(),
+        jobs: access_config
+            .jobs
+            .iter()
+            .map(|j| JobOption {
+                id: j.id.clone(),
+                name: j.name.clone(),
+                slots_left: job_assignments.slots_left(j),
+            })
+            .collect(),
+    }
+}
+
+/// Initialize the setup UI by sending the character creator options and the character profile.
+
+pub fn initialize_setupui(
     used_names: Res<UsedNames>,
     query: Query<&ConnectedPlayer, Added<SetupPhase>>,
     mut server1: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
     mut server2: EventWriter<OutgoingReliableServerMessage<SetupUiServerMessage>>,
     motd: Res<MOTD>,
     mut datas: ResMut<SetupUiUserDataSets>,
+    appearance_config: Res<AppearanceConfig>,
+    access_config: Res<AccessConfig>,
+    accounts: Res<Accounts>,
+    profiles: Res<CharacterProfiles>,
+    job_assignments: Res<JobAssignments>,
 ) {
     for connected_player_component in query.iter() {
-        let suggested_name = get_full_name(true, true, &used_names);
+        let stored_profile_option = match accounts.list.get(&connected_player_component.handle) {
+            Some(account_name) => profiles.list.get(account_name),
+            None => None,
+        };
+        let profile;
+        match stored_profile_option {
+            Some(p) => {
+                profile = p.clone();
+            }
+            None => {
+                let appearance = appearance_config.default_appearance();
+                let masculine = match appearance_config.body(&appearance.body) {
+                    Some(body) => body.masculine,
+                    None => true,
+                };
+                profile = CharacterProfile {
+                    character_name: get_full_name(masculine, true, &used_names),
+                    appearance,
+                    job: access_config.default_job.clone(),
+                };
+            }
+        }
+
+        server2.send(OutgoingReliableServerMessage {
+            handle: connected_player_component.handle,
+            message: SetupUiServerMessage::CharacterCreatorOptions(creator_options(
+                &appearance_config,
+                &access_config,
+                &job_assignments,
+            )),
+        });
         server2.send(OutgoingReliableServerMessage {
             handle: connected_player_component.handle,
-            message: SetupUiServerMessage::SuggestedCharacterName(suggested_name.clone()),
+            message: SetupUiServerMessage::CharacterProfile(profile.clone()),
         });
         server1.send(OutgoingReliableServerMessage {
             handle: connected_player_component.handle,
@@ -40,7 +107,9 @@ pub(crate) fn initialize_setupui(
         datas.list.insert(
             connected_player_component.handle,
             SetupUiUserData {
-                character_name: suggested_name,
+                character_name: profile.character_name,
+                appearance: profile.appearance,
+                job: profile.job,
             },
         );
     }
@@ -58,82 +127,364 @@ pub struct SetupUiUserDataSets {
 
 pub struct SetupUiUserData {
     pub character_name: String,
+    pub appearance: CharacterAppearance,
+    /// Id of the starting job.
+    pub job: String,
+}
+
+impl SetupUiUserData {
+    pub fn profile(&self) -> CharacterProfile {
+        CharacterProfile {
+            character_name: self.character_name.clone(),
+            appearance: self.appearance.clone(),
+            job: self.job.clone(),
+        }
+    }
+}
+
+/// Character profiles by account name, restored when players return to the setup UI.
+/// Persisted to drive so they survive server restarts.
+#[derive(Default, Resource, Serialize, Deserialize)]
+
+pub struct CharacterProfiles {
+    pub list: HashMap<String, CharacterProfile>,
 }
 
-pub(crate) fn receive_input_character_name(
+impl CharacterProfiles {
+    /// Persist the character profiles to drive.
+    pub fn save(&self) {
+        let path = character_profiles_path();
+        match path.parent() {
+            Some(dir) => match fs::create_dir_all(dir) {
+                Ok(_) => {}
+                Err(err) => {
+                    warn!("Couldn't create directory for character profiles: {}", err);
+                    return;
+                }
+            },
+            None => {}
+        }
+        match ron::ser::to_string_pretty(self, PrettyConfig::default()) {
+            Ok(profiles_ron) => match fs::write(path, profiles_ron) {
+                Ok(_) => {}
+                Err(err) => {
+                    warn!("Couldn't write character profiles: {}", err);
+                }
+            },
+            Err(err) => {
+                warn!("Couldn't serialize character profiles: {}", err);
+            }
+        }
+    }
+}
+
+/// Path of the persisted character profiles, next to the other account data.
+
+pub fn character_profiles_path() -> PathBuf {
+    Path::new("data")
+        .join("settings")
+        .join("character_profiles.ron")
+}
+
+/// Load persisted character profiles from drive.
+
+pub(crate) fn load_character_profiles(mut profiles: ResMut<CharacterProfiles>) {
+    let path = character_profiles_path();
+
+    if !path.exists() {
+        return;
+    }
+
+    match fs::read_to_string(path) {
+        Ok(profiles_ron) => match ron::from_str::<CharacterProfiles>(&profiles_ron) {
+            Ok(p) => {
+                *profiles = p;
+                info!("Loaded {} character profiles.", profiles.list.len());
+            }
+            Err(err) => {
+                warn!("Couldn't parse character profiles: {}", err);
+            }
+        },
+        Err(err) => {
+            warn!("Couldn't read character profiles: {}", err);
+        }
+    }
+}
+
+/// Maximum length of character names.
+
+pub const CHARACTER_NAME_MAX_LENGTH: usize = 26;
+
+/// Receive and validate character creator input, store it in the profile and confirm it to the client.
+
+pub(crate) fn receive_character_creator_input(
     mut server: EventReader<IncomingReliableClientMessage<SetupUiClientMessage>>,
+    mut outgoing: EventWriter<OutgoingReliableServerMessage<SetupUiServerMessage>>,
     mut datas: ResMut<SetupUiUserDataSets>,
+    mut profiles: ResMut<CharacterProfiles>,
+    accounts: Res<Accounts>,
+    appearance_config: Res<AppearanceConfig>,
+    access_config: Res<AccessConfig>,
+    job_assignments: Res<JobAssignments>,
+    mut showcases: Query<(&Showcase, &mut CharacterAppearance)>,
 ) {
     for message in server.iter() {
-        match message.message.clone() {
+        let setupui_data;
+        match datas.list.get_mut(&message.handle) {
+            Some(d) => {
+                setupui_data = d;
+            }
+            None => {
+                match message.message {
+                    SetupUiClientMessage::InputCharacterName(_)
+                    | SetupUiClientMessage::InputCharacterAppearance(_)
+                    | SetupUiClientMessage::InputJob(_) => {
+                        warn!("Could not find SetupUiData for handle {}", message.handle);
+                    }
+                    _ => (),
+                }
+                continue;
+            }
+        }
+        match &message.message {
             SetupUiClientMessage::InputCharacterName(name) => {
-                match datas.list.get_mut(&message.handle) {
-                    Some(setupui_data) => {
-                        setupui_data.character_name = name;
+                let name = name.trim();
+                if name.is_empty() || name.chars().count() > CHARACTER_NAME_MAX_LENGTH {
+                    warn!("Rejected character name of {}.", message.handle);
+                } else {
+                    setupui_data.character_name = name.to_string();
+                }
+            }
+            SetupUiClientMessage::InputCharacterAppearance(appearance) => {
+                if !appearance_config.is_valid(appearance) {
+                    warn!("Rejected character appearance of {}.", message.handle);
+                } else {
+                    setupui_data.appearance = appearance.clone();
+                    for (showcase, mut showcase_appearance) in showcases.iter_mut() {
+                        if showcase.handle == message.handle {
+                            *showcase_appearance = appearance.clone();
+                        }
+                    }
+                }
+            }
+            SetupUiClientMessage::InputJob(job) => {
+                match access_config.jobs.iter().find(|j| &j.id == job) {
+                    Some(j) => {
+                        if job_assignments.is_available(j) {
+                            setupui_data.job = job.clone();
+                        } else {
+                            warn!("Rejected full job {} of {}.", job, message.handle);
+                        }
                     }
                     None => {
-                        warn!("Could not find SetupUiData for handle {}", message.handle);
+                        warn!("Rejected job {} of {}.", job, message.handle);
                     }
                 }
             }
-            _ => (),
+            _ => {
+                continue;
+            }
         }
+
+        let profile = setupui_data.profile();
+        match accounts.list.get(&message.handle) {
+            Some(account_name) => {
+                if profiles.list.get(account_name) != Some(&profile) {
+                    profiles.list.insert(account_name.clone(), profile.clone());
+                    profiles.save();
+                }
+            }
+            None => {}
+        }
+        // Always confirm so rejected input gets reverted on the client.
+        outgoing.send(OutgoingReliableServerMessage {
+            handle: message.handle,
+            message: SetupUiServerMessage::CharacterProfile(profile),
+        });
     }
 }
 
-/// Recieve boarding UI input.
+/// Recieve boarding UI input, players board once the round is in progress.
 
 pub(crate) fn ui_input_boarding(
     mut event: EventReader<IncomingReliableClientMessage<SetupUiClientMessage>>,
-    mut boarding_player_event: EventWriter<BoardingPlayer>,
-    handle_to_entity: Res<HandleToEntity>,
-    mut query: Query<&ConnectedPlayer>,
-    setupui_datas: Res<SetupUiUserDataSets>,
+    mut ready_players: ResMut<ReadyPlayers>,
+    content_ready: Res<ContentReady>,
 ) {
     for new_event in event.iter() {
-        let player_entity = handle_to_entity
-            .map
-            .get(&new_event.handle)
-            .expect("ui_input_boarding could not find entity belonging to player handle.");
-
         match new_event.message {
             SetupUiClientMessage::RequestBoarding => {
-                let connected_player_component;
-                match query.get_mut(*player_entity) {
-                    Ok(s) => {
-                        connected_player_component = s;
-                    }
-                    Err(_rr) => {
-                        warn!("ui_input_boarding could not find components belonging to player entity: {:?}", player_entity);
-                        continue;
-                    }
+                if !content_ready.handles.contains(&new_event.handle) {
+                    warn!(
+                        "[{}] requested boarding before downloading the server content.",
+                        new_event.handle
+                    );
+                    continue;
                 }
+                if !ready_players.handles.contains(&new_event.handle) {
+                    ready_players.handles.push(new_event.handle);
+                }
+            }
+            _ => (),
+        }
+    }
+}
 
-                match setupui_datas.list.get(&connected_player_component.handle) {
-                    Some(setupui_data) => {
-                        boarding_player_event.send(BoardingPlayer {
-                            entity: *player_entity,
-                            player_handle: connected_player_component.handle,
-                            player_character_name: setupui_data.character_name.clone(),
-                        });
+use content::server::ContentReady;
+use round::{game_mode::GameModes, round::ReadyPlayers};
+
+/// Board ready players with the job the game mode picks.
+
+pub(crate) fn board_ready_players(
+    mut ready_players: ResMut<ReadyPlayers>,
+    mut boarding_player_event: EventWriter<BoardingPlayer>,
+    handle_to_entity: Res<HandleToEntity>,
+    mut query: Query<&ConnectedPlayer>,
+    mut setupui_datas: ResMut<SetupUiUserDataSets>,
+    access_config: Res<AccessConfig>,
+    mut job_assignments: ResMut<JobAssignments>,
+    mut game_modes: ResMut<GameModes>,
+    mut outgoing: EventWriter<OutgoingReliableServerMessage<SetupUiServerMessage>>,
+    mut chat: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+    appearance_config: Res<AppearanceConfig>,
+) {
+    for handle in ready_players.handles.drain(..) {
+        let player_entity;
+        match handle_to_entity.map.get(&handle) {
+            Some(e) => {
+                player_entity = *e;
+            }
+            None => {
+                warn!(
+                    "board_ready_players could not find entity belonging to player handle {}.",
+                    handle
+                );
+                continue;
+            }
+        }
+
+        let connected_player_component;
+        match query.get_mut(player_entity) {
+            Ok(s) => {
+                connected_player_component = s;
+            }
+            Err(_rr) => {
+                warn!(
+                    "board_ready_players could not find components belonging to player entity: {:?}",
+                    player_entity
+                );
+                continue;
+            }
+        }
+        if !connected_player_component.connected {
+            continue;
+        }
+
+        match setupui_datas
+            .list
+            .get_mut(&connected_player_component.handle)
+        {
+            Some(setupui_data) => {
+                // The picked job may have filled up or be unavailable in this game mode.
+                let job_option = match game_modes.active_mut() {
+                    Some(mode) => {
+                        mode.pick_job(&setupui_data.job, &access_config, &job_assignments)
+                    }
+                    None => access_config.open_job(&setupui_data.job, &job_assignments),
+                };
+                let job;
+                match job_option {
+                    Some(j) => {
+                        job = j;
                     }
                     None => {
-                        warn!("ui_input_boarding could not find setupui_datas belonging to player: {:?}", connected_player_component.handle);
+                        // Send the player back to the character creator.
+                        warn!(
+                            "Refused boarding of {}, no job available.",
+                            connected_player_component.handle
+                        );
+                        chat.send(OutgoingReliableServerMessage {
+                            handle: connected_player_component.handle,
+                            message: NetworkingChatServerMessage::ChatMessage(
+                                "[color=#ff003c]There are no open jobs left to board with.[/color]"
+                                    .to_string(),
+                            ),
+                        });
+                        outgoing.send(OutgoingReliableServerMessage {
+                            handle: connected_player_component.handle,
+                            message: SetupUiServerMessage::CharacterCreatorOptions(
+                                creator_options(
+                                    &appearance_config,
+                                    &access_config,
+                                    &job_assignments,
+                                ),
+                            ),
+                        });
+                        outgoing.send(OutgoingReliableServerMessage {
+                            handle: connected_player_component.handle,
+                            message: SetupUiServerMessage::CharacterProfile(setupui_data.profile()),
+                        });
+                        continue;
                     }
                 }
+                if job != setupui_data.job {
+                    warn!(
+                        "Job {} of {} unavailable, assigned job {}.",
+                        setupui_data.job, connected_player_component.handle, job
+                    );
+                    setupui_data.job = job;
+                    outgoing.send(OutgoingReliableServerMessage {
+                        handle: connected_player_component.handle,
+                        message: SetupUiServerMessage::CharacterProfile(setupui_data.profile()),
+                    });
+                }
+                job_assignments.assign(&setupui_data.job);
+
+                boarding_player_event.send(BoardingPlayer {
+                    entity: player_entity,
+                    player_handle: connected_player_component.handle,
+                    player_character_name: setupui_data.character_name.clone(),
+                    job: setupui_data.job.clone(),
+                    inventory_option: None,
+                });
+            }
+            None => {
+                warn!(
+                    "board_ready_players could not find setupui_datas belonging to player: {:?}",
+                    connected_player_component.handle
+                );
             }
-            _ => (),
         }
     }
 }
 
-use player::connections::SendServerConfiguration;
+use player::connections::{
+    ArrivingPlayers, ReconnectingPlayers, ResumeFailed, SendServerConfiguration,
+};
 
 pub(crate) fn configure(
     mut config_events: EventReader<SendServerConfiguration>,
+    mut resume_failed: EventReader<ResumeFailed>,
     mut server1: EventWriter<OutgoingReliableServerMessage<SetupUiServerMessage>>,
+    reconnecting: Res<ReconnectingPlayers>,
+    arriving: Res<ArrivingPlayers>,
 ) {
     for event in config_events.iter() {
+        // Reconnected players resume their held pawn and skip the setup UI.
+        if reconnecting.map.contains_key(&event.handle) {
+            continue;
+        }
+        // Players arriving from another sector board with their transferred character.
+        if arriving.handles.contains(&event.handle) {
+            continue;
+        }
+        server1.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message: SetupUiServerMessage::InitSetupUi,
+        });
+    }
+    // Reconnected players whose held pawn is gone set up a new character.
+    for event in resume_failed.iter() {
         server1.send(OutgoingReliableServerMessage {
             handle: event.handle,
             message: SetupUiServerMessage::InitSetupUi,
@@ -169,23 +520,22 @@ use networking::client::OutgoingReliableClientMessage;
 pub(crate) fn client_setup_ui(
     mut incoming_setupui_messages: EventReader<IncomingReliableServerMessage<SetupUiServerMessage>>,
     mut outgoing_setupui_messages: EventWriter<OutgoingReliableClientMessage<SetupUiClientMessage>>,
+    mut creator_state: ResMut<CharacterCreatorState>,
 ) {
     for message in incoming_setupui_messages.iter() {
         let player_message = message.message.clone();
         match player_message {
-            SetupUiServerMessage::SuggestedCharacterName(name) => {
-                outgoing_setupui_messages.send(OutgoingReliableClientMessage {
-                    message: SetupUiClientMessage::InputCharacterName(name),
-                });
-                outgoing_setupui_messages.send(OutgoingReliableClientMessage {
-                    message: SetupUiClientMessage::RequestBoarding,
-                });
-            }
             SetupUiServerMessage::InitSetupUi => {
                 outgoing_setupui_messages.send(OutgoingReliableClientMessage {
                     message: SetupUiClientMessage::SetupUiLoaded,
                 });
             }
+            SetupUiServerMessage::CharacterCreatorOptions(options) => {
+                creator_state.options_option = Some(options);
+            }
+            SetupUiServerMessage::CharacterProfile(profile) => {
+                creator_state.profile_option = Some(profile);
+            }
         }
     }
 }
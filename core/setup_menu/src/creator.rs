@@ -0,0 +1,532 @@
+use bevy::{
+    prelude::{
+        info, warn, AssetServer, BuildChildren, Button, ButtonBundle, Changed, Color, Commands,
+        Component, DespawnRecursiveExt, DetectChanges, Entity, EventWriter, NodeBundle, Query, Res,
+        ResMut, Resource, TextBundle, With,
+    },
+    text::{Text, TextStyle},
+    ui::{
+        AlignItems, FlexDirection, Interaction, JustifyContent, PositionType, Size, Style, UiRect,
+        Val,
+    },
+};
+use content::client::{ContentDownload, ContentStatus};
+use networking::client::OutgoingReliableClientMessage;
+use resources::ui::TextInput;
+use ui::{
+    button::SFButton,
+    fonts::{ARIZONE_FONT, EMPIRE_FONT},
+    text_input::{CharacterFilter, SetText, TextInputNode, INPUT_TEXT_BG},
+};
+
+use crate::net::{CharacterCreatorOptions, CharacterProfile, SetupUiClientMessage};
+
+pub const CREATOR_BG_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.9);
+pub const CREATOR_BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+
+/// Client resource with the character creator options and the profile confirmed by the server.
+#[derive(Resource, Default)]
+
+pub struct CharacterCreatorState {
+    pub options_option: Option<CharacterCreatorOptions>,
+    pub profile_option: Option<CharacterProfile>,
+    pub root_option: Option<Entity>,
+}
+
+/// The parts of a character that can be picked in the character creator.
+#[derive(Clone, Copy, PartialEq)]
+
+pub enum CreatorField {
+    Body,
+    SkinTone,
+    HairStyle,
+    HairColor,
+    Job,
+}
+
+impl CreatorField {
+    fn label(&self) -> &'static str {
+        match self {
+            CreatorField::Body => "Body",
+            CreatorField::SkinTone => "Skin",
+            CreatorField::HairStyle => "Hair",
+            CreatorField::HairColor => "Hair color",
+            CreatorField::Job => "Job",
+        }
+    }
+}
+
+const CREATOR_FIELDS: [CreatorField; 5] = [
+    CreatorField::Body,
+    CreatorField::SkinTone,
+    CreatorField::HairStyle,
+    CreatorField::HairColor,
+    CreatorField::Job,
+];
+
+/// Button that cycles through the options of a field.
+#[derive(Component)]
+
+pub struct CreatorCycleButton {
+    pub field: CreatorField,
+    pub step: i32,
+}
+
+/// Text displaying the picked option of a field.
+#[derive(Component)]
+
+pub struct CreatorValueText {
+    pub field: CreatorField,
+}
+
+/// Text input holding the character name.
+#[derive(Component)]
+
+pub struct CreatorNameInput;
+
+/// Button that submits the character name input.
+#[derive(Component)]
+
+pub struct CreatorNameButton {
+    pub input_node: Entity,
+}
+
+#[derive(Component)]
+
+pub struct CreatorBoardButton;
+
+/// Text displaying the progress or failure of the server content download.
+#[derive(Component)]
+
+pub struct CreatorContentText;
+
+/// Ids and names of the options of a field.
+
+fn field_options(options: &CharacterCreatorOptions, field: CreatorField) -> Vec<(String, String)> {
+    let appearance = &options.appearance;
+    match field {
+        CreatorField::Body => appearance
+            .bodies
+            .iter()
+            .map(|o| (o.id.clone(), o.name.clone()))
+            .collect(),
+        CreatorField::SkinTone => appearance
+            .skin_tones
+            .iter()
+            .map(|o| (o.id.clone(), o.name.clone()))
+            .collect(),
+        CreatorField::HairStyle => appearance
+            .hair_styles
+            .iter()
+            .map(|o| (o.id.clone(), o.name.clone()))
+            .collect(),
+        CreatorField::HairColor => appearance
+            .hair_colors
+            .iter()
+            .map(|o| (o.id.clone(), o.name.clone()))
+            .collect(),
+        CreatorField::Job => options
+            .jobs
+            .iter()
+            .map(|o| match o.slots_left {
+                Some(slots_left) => (o.id.clone(), format!("{} ({} left)", o.name, slots_left)),
+                None => (o.id.clone(), o.name.clone()),
+            })
+            .collect(),
+    }
+}
+
+/// Id of the option of a field picked in a profile.
+
+fn profile_value(profile: &CharacterProfile, field: CreatorField) -> &String {
+    match field {
+        CreatorField::Body => &profile.appearance.body,
+        CreatorField::SkinTone => &profile.appearance.skin_tone,
+        CreatorField::HairStyle => &profile.appearance.hair_style,
+        CreatorField::HairColor => &profile.appearance.hair_color,
+        CreatorField::Job => &profile.job,
+    }
+}
+
+/// Build the character creator once the options arrived.
+
+pub(crate) fn build_character_creator(
+    mut state: ResMut<CharacterCreatorState>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    if state.root_option.is_some() || state.options_option.is_none() {
+        return;
+    }
+
+    let arizone_font = asset_server.load(ARIZONE_FONT);
+    let empire_font = asset_server.load(EMPIRE_FONT);
+
+    let text_style = TextStyle {
+        font_size: 14.0,
+        color: Color::WHITE,
+        font: empire_font.clone(),
+    };
+
+    let root = commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Percent(5.),
+                    top: Val::Percent(20.),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Px(320.), Val::Auto),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(10.)),
+                ..Default::default()
+            },
+            background_color: CREATOR_BG_COLOR.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Character",
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::WHITE,
+                    font: arizone_font.clone(),
+                },
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.), Val::Px(28.)),
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Name", text_style.clone()));
+                    let input_node = parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(55.), Val::Px(24.)),
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: INPUT_TEXT_BG.into(),
+                            ..Default::default()
+                        })
+                        .insert((
+                            TextInputNode {
+                                character_filter_option: Some(CharacterFilter::Chat),
+                                ..Default::default()
+                            },
+                            Interaction::default(),
+                            CreatorNameInput,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section("", text_style.clone()));
+                        })
+                        .id();
+                    parent
+                        .spawn(ButtonBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(40.), Val::Px(24.)),
+                                margin: UiRect::horizontal(Val::Px(6.)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: CREATOR_BUTTON_COLOR.into(),
+                            ..Default::default()
+                        })
+                        .insert(CreatorNameButton { input_node })
+                        .insert(SFButton {
+                            color_parent: false,
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section("Set", text_style.clone()));
+                        });
+                });
+
+            for field in CREATOR_FIELDS {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(100.), Val::Px(28.)),
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(field.label(), text_style.clone()));
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                for (step, label) in [(-1, "<"), (1, ">")] {
+                                    if step == 1 {
+                                        parent
+                                            .spawn(TextBundle::from_section("", text_style.clone()))
+                                            .insert(CreatorValueText { field });
+                                    }
+                                    parent
+                                        .spawn(ButtonBundle {
+                                            style: Style {
+                                                size: Size::new(Val::Px(24.), Val::Px(24.)),
+                                                margin: UiRect::horizontal(Val::Px(6.)),
+                                                justify_content: JustifyContent::Center,
+                                                align_items: AlignItems::Center,
+                                                ..Default::default()
+                                            },
+                                            background_color: CREATOR_BUTTON_COLOR.into(),
+                                            ..Default::default()
+                                        })
+                                        .insert(CreatorCycleButton { field, step })
+                                        .insert(SFButton {
+                                            color_parent: false,
+                                            ..Default::default()
+                                        })
+                                        .with_children(|parent| {
+                                            parent.spawn(TextBundle::from_section(
+                                                label,
+                                                text_style.clone(),
+                                            ));
+                                        });
+                                }
+                            });
+                    });
+            }
+
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(60.), Val::Px(30.)),
+                        margin: UiRect::top(Val::Px(10.)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: CREATOR_BUTTON_COLOR.into(),
+                    ..Default::default()
+                })
+                .insert(CreatorBoardButton)
+                .insert(SFButton {
+                    color_parent: false,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Board",
+                        TextStyle {
+                            font_size: 16.0,
+                            color: Color::WHITE,
+                            font: arizone_font.clone(),
+                        },
+                    ));
+                });
+            parent
+                .spawn(
+                    TextBundle::from_section("", text_style.clone()).with_style(Style {
+                        max_size: Size::new(Val::Px(300.), Val::Auto),
+                        margin: UiRect::top(Val::Px(6.)),
+                        ..Default::default()
+                    }),
+                )
+                .insert(CreatorContentText);
+        })
+        .id();
+
+    state.root_option = Some(root);
+}
+
+/// Show the profile confirmed by the server in the character creator.
+/// The name input is left alone while it is being typed in.
+
+pub(crate) fn update_character_creator_text(
+    state: Res<CharacterCreatorState>,
+    mut value_texts: Query<(&mut Text, &CreatorValueText)>,
+    name_inputs: Query<(Entity, &TextInputNode), With<CreatorNameInput>>,
+    text_input: Res<TextInput>,
+    mut set_text: EventWriter<SetText>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    let options;
+    let profile;
+    match (&state.options_option, &state.profile_option) {
+        (Some(o), Some(p)) => {
+            options = o;
+            profile = p;
+        }
+        _ => {
+            return;
+        }
+    }
+
+    for (entity, node) in name_inputs.iter() {
+        if text_input.focused_input == Some(entity) || node.input == profile.character_name {
+            continue;
+        }
+        set_text.send(SetText {
+            entity,
+            text: profile.character_name.clone(),
+        });
+    }
+    for (mut text, value_text) in value_texts.iter_mut() {
+        let value = profile_value(profile, value_text.field);
+        let name = field_options(options, value_text.field)
+            .into_iter()
+            .find(|(id, _)| id == value)
+            .map(|(_, name)| name)
+            .unwrap_or_default();
+        match text.sections.get_mut(0) {
+            Some(section) => {
+                section.value = name;
+            }
+            None => {}
+        }
+    }
+}
+
+/// Text describing the server content download, empty once it is done.
+
+fn content_status_text(content: &ContentDownload) -> String {
+    match &content.status {
+        ContentStatus::None | ContentStatus::Ready => "".to_string(),
+        ContentStatus::Downloading => format!(
+            "Downloading server content {}%",
+            content.received_bytes * 100 / content.total_bytes.max(1)
+        ),
+        ContentStatus::Failed(reason) => format!(
+            "Couldn't download server content: {} Reconnect to try again.",
+            reason
+        ),
+    }
+}
+
+/// Show the progress or failure of the server content download in the character creator.
+
+pub(crate) fn update_character_creator_content_text(
+    content: Res<ContentDownload>,
+    mut texts: Query<&mut Text, With<CreatorContentText>>,
+) {
+    let status = content_status_text(&content);
+    for mut text in texts.iter_mut() {
+        match text.sections.get_mut(0) {
+            Some(section) => {
+                if section.value != status {
+                    section.value = status.clone();
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+/// Request the next or previous option of a field or the typed name. The server confirms it with the updated profile.
+
+pub(crate) fn character_creator_buttons(
+    cycle_buttons: Query<(&Interaction, &CreatorCycleButton), (Changed<Interaction>, With<Button>)>,
+    name_buttons: Query<(&Interaction, &CreatorNameButton), (Changed<Interaction>, With<Button>)>,
+    name_inputs: Query<&TextInputNode, With<CreatorNameInput>>,
+    board_buttons: Query<&Interaction, (Changed<Interaction>, With<CreatorBoardButton>)>,
+    mut state: ResMut<CharacterCreatorState>,
+    mut commands: Commands,
+    mut net: EventWriter<OutgoingReliableClientMessage<SetupUiClientMessage>>,
+    content: Res<ContentDownload>,
+) {
+    for (interaction, button) in cycle_buttons.iter() {
+        if !matches!(interaction, Interaction::Clicked) {
+            continue;
+        }
+        let options;
+        let profile;
+        match (&state.options_option, &state.profile_option) {
+            (Some(o), Some(p)) => {
+                options = o;
+                profile = p;
+            }
+            _ => {
+                continue;
+            }
+        }
+        let field_options = field_options(options, button.field);
+        if field_options.is_empty() {
+            continue;
+        }
+        let current = profile_value(profile, button.field);
+        let current_index = field_options
+            .iter()
+            .position(|(id, _)| id == current)
+            .unwrap_or(0) as i32;
+        let next_index = (current_index + button.step).rem_euclid(field_options.len() as i32);
+        let next_id = field_options[next_index as usize].0.clone();
+
+        let message = match button.field {
+            CreatorField::Job => SetupUiClientMessage::InputJob(next_id),
+            field => {
+                let mut appearance = profile.appearance.clone();
+                match field {
+                    CreatorField::Body => appearance.body = next_id,
+                    CreatorField::SkinTone => appearance.skin_tone = next_id,
+                    CreatorField::HairStyle => appearance.hair_style = next_id,
+                    CreatorField::HairColor => appearance.hair_color = next_id,
+                    CreatorField::Job => {}
+                }
+                SetupUiClientMessage::InputCharacterAppearance(appearance)
+            }
+        };
+        net.send(OutgoingReliableClientMessage { message });
+    }
+
+    for (interaction, button) in name_buttons.iter() {
+        if !matches!(interaction, Interaction::Clicked) {
+            continue;
+        }
+        match name_inputs.get(button.input_node) {
+            Ok(node) => {
+                if node.input.trim().is_empty() {
+                    continue;
+                }
+                net.send(OutgoingReliableClientMessage {
+                    message: SetupUiClientMessage::InputCharacterName(node.input.clone()),
+                });
+            }
+            Err(_) => {
+                warn!("Couldnt find character name input node.");
+            }
+        }
+    }
+
+    for interaction in board_buttons.iter() {
+        if !matches!(interaction, Interaction::Clicked) {
+            continue;
+        }
+        // The server refuses boarding until its content is downloaded.
+        if content.status != ContentStatus::Ready {
+            info!("Server content is still downloading.");
+            continue;
+        }
+        net.send(OutgoingReliableClientMessage {
+            message: SetupUiClientMessage::RequestBoarding,
+        });
+        match state.root_option.take() {
+            Some(root) => {
+                commands.entity(root).despawn_recursive();
+            }
+            None => {}
+        }
+        state.options_option = None;
+    }
+}
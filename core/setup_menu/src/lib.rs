@@ -2,5 +2,7 @@
 
 // The Bevy ECS plugin of this crate.
 pub mod core;
+/// The client character creator.
+pub mod creator;
 pub mod net;
 pub mod plugin;
@@ -1,16 +1,48 @@
+use pawn::appearance::{AppearanceConfig, CharacterAppearance};
 use serde::{Deserialize, Serialize};
 use typename::TypeName;
 
 #[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
 
 pub enum SetupUiServerMessage {
-    SuggestedCharacterName(String),
     InitSetupUi,
+    CharacterCreatorOptions(CharacterCreatorOptions),
+    CharacterProfile(CharacterProfile),
 }
 #[derive(Serialize, Deserialize, Debug, Clone, TypeName)]
 
 pub enum SetupUiClientMessage {
     InputCharacterName(String),
+    InputCharacterAppearance(CharacterAppearance),
+    InputJob(String),
     SetupUiLoaded,
     RequestBoarding,
 }
+
+/// The character a player boards with.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+
+pub struct CharacterProfile {
+    pub character_name: String,
+    pub appearance: CharacterAppearance,
+    /// Id of the starting job.
+    pub job: String,
+}
+
+/// The options players can pick from in the character creator.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+
+pub struct CharacterCreatorOptions {
+    pub appearance: AppearanceConfig,
+    pub jobs: Vec<JobOption>,
+}
+
+/// A job that can be picked in the character creator.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+
+pub struct JobOption {
+    pub id: String,
+    pub name: String,
+    /// Slots left this round, none if unlimited.
+    pub slots_left: Option<u16>,
+}
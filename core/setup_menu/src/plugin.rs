@@ -1,4 +1,4 @@
-use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use bevy::prelude::{in_state, App, IntoSystemConfig, Plugin};
 use controller::networking::InputUIInput;
 use networking::{
     client::is_client_connected,
@@ -6,12 +6,17 @@ use networking::{
 };
 use player::plugin::ConfigurationLabel;
 use resources::{is_server::is_server, labels::BuildingLabels};
+use round::round::RoundState;
 
 use crate::{
     core::{
-        client_setup_ui, configure, initialize_setupui, new_clients_enable_setupui,
-        receive_input_character_name, setupui_loaded, ui_input_boarding, SetupUiState,
-        SetupUiUserDataSets,
+        board_ready_players, client_setup_ui, configure, initialize_setupui,
+        load_character_profiles, new_clients_enable_setupui, receive_character_creator_input,
+        setupui_loaded, ui_input_boarding, CharacterProfiles, SetupUiState, SetupUiUserDataSets,
+    },
+    creator::{
+        build_character_creator, character_creator_buttons, update_character_creator_content_text,
+        update_character_creator_text, CharacterCreatorState,
     },
     net::{SetupUiClientMessage, SetupUiServerMessage},
 };
@@ -21,6 +26,11 @@ impl Plugin for SetupMenuPlugin {
     fn build(&self, app: &mut App) {
         if is_server() {
             app.add_system(ui_input_boarding)
+                .add_system(
+                    board_ready_players
+                        .after(ui_input_boarding)
+                        .run_if(in_state(RoundState::InProgress)),
+                )
                 .add_system(initialize_setupui.in_set(BuildingLabels::TriggerBuild))
                 .add_event::<InputUIInput>()
                 .add_system(
@@ -31,10 +41,17 @@ impl Plugin for SetupMenuPlugin {
                 .add_system(new_clients_enable_setupui)
                 .init_resource::<SetupUiState>()
                 .add_system(setupui_loaded)
-                .add_system(receive_input_character_name)
-                .init_resource::<SetupUiUserDataSets>();
+                .add_system(receive_character_creator_input)
+                .init_resource::<SetupUiUserDataSets>()
+                .init_resource::<CharacterProfiles>()
+                .add_startup_system(load_character_profiles);
         } else {
-            app.add_system(client_setup_ui.run_if(is_client_connected));
+            app.add_system(client_setup_ui.run_if(is_client_connected))
+                .init_resource::<CharacterCreatorState>()
+                .add_system(build_character_creator.after(client_setup_ui))
+                .add_system(update_character_creator_text.after(build_character_creator))
+                .add_system(update_character_creator_content_text.after(build_character_creator))
+                .add_system(character_creator_buttons);
         }
 
         register_reliable_message::<SetupUiServerMessage>(app, MessageSender::Server);
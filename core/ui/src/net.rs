@@ -8,6 +8,7 @@ use typename::TypeName;
 
 pub enum UiClientMessage {
     TextTreeInput(TextTreeInput),
+    TextInput(TextInputResponse),
 }
 
 /// Gets serialized and sent over the net, this is the server message.
@@ -17,7 +18,7 @@ pub enum UiServerMessage {
     TextTreeSelection(TextTreeSelection),
     UIAddNotice(String),
     UIRemoveNotice(String),
-    UIRequestInput(String),
+    UIRequestInput(TextInputRequest),
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TextTreeSelection {
@@ -32,3 +33,16 @@ pub struct TextTreeInput {
     pub id: String,
     pub entry: String,
 }
+/// Ask the client to submit a line of text.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TextInputRequest {
+    pub entity: Entity,
+    pub id: String,
+    pub text: String,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TextInputResponse {
+    pub entity: Entity,
+    pub id: String,
+    pub input: String,
+}
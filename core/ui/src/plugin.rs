@@ -10,7 +10,7 @@ use crate::{
     text_input::{
         focus_events, incoming_messages, input_characters, input_mouse_press_unfocus,
         set_text_input_node_text, ui_events, FocusTextInput, SetText, TextInputLabel,
-        TextTreeInputSelection, UnfocusTextInput,
+        TextInputSubmission, TextTreeInputSelection, UnfocusTextInput,
     },
 };
 use resources::{is_server::is_server, ui::TextInput};
@@ -19,7 +19,8 @@ impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         if is_server() {
             app.add_system(incoming_messages.in_base_set(CoreSet::PreUpdate))
-                .add_event::<TextTreeInputSelection>();
+                .add_event::<TextTreeInputSelection>()
+                .add_event::<TextInputSubmission>();
         } else {
             app.add_system(ui_events.in_set(TextInputLabel::UiEvents))
                 .add_system(
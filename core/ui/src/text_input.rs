@@ -521,6 +521,7 @@ use crate::net::UiClientMessage;
 pub(crate) fn incoming_messages(
     mut server: EventReader<IncomingReliableClientMessage<UiClientMessage>>,
     mut text_tree_input_selection: EventWriter<TextTreeInputSelection>,
+    mut text_input_submission: EventWriter<TextInputSubmission>,
 ) {
     for message in server.iter() {
         let client_message = message.message.clone();
@@ -534,6 +535,14 @@ pub(crate) fn incoming_messages(
                     entity: data.entity,
                 });
             }
+            UiClientMessage::TextInput(data) => {
+                text_input_submission.send(TextInputSubmission {
+                    handle: message.handle,
+                    id: data.id,
+                    input: data.input,
+                    entity: data.entity,
+                });
+            }
         }
     }
 }
@@ -548,3 +557,14 @@ pub struct TextTreeInputSelection {
     pub entry: String,
     pub entity: Entity,
 }
+/// Client text input submission event.
+
+pub struct TextInputSubmission {
+    /// Handle of the submitter of the input.
+    pub handle: u64,
+    /// Input request ID.
+    pub id: String,
+    /// The submitted text.
+    pub input: String,
+    pub entity: Entity,
+}
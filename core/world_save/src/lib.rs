@@ -0,0 +1,13 @@
+//! Persistent world saves of the live ship state.
+//! The server periodically and on exit, including Ctrl-C and SIGTERM, writes a snapshot of the gridmap cells and entities to `data/saves/`.
+//! Starting the server with `--load-save <name>` restores such a snapshot instead of the default map entities.
+//! Gridmap cells are restored as changes on top of the default map, so clients that load the default map stay in sync.
+
+/// Restore world saves.
+pub mod load;
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// The world save format and writing world saves.
+pub mod save;
+/// Save the world when the server process is asked to stop.
+pub mod shutdown;
@@ -0,0 +1,328 @@
+use std::{collections::HashMap, fs};
+
+use bevy::prelude::{
+    info, warn, Commands, Entity, EventWriter, Query, Res, ResMut, Resource, With,
+};
+use entity::{
+    entity_data::{EntityData, RawSpawnEvent},
+    entity_types::{EntityType, EntityTypes},
+    health::HealthComponent,
+};
+use gridmap::{
+    details1::{AddDetails1Tile, RemoveDetails1Tile},
+    grid::{AddTile, Gridmap, RemoveTile},
+};
+use inventory::{
+    item::InventoryItem,
+    server::inventory::{AddItemToSlot, Inventory},
+};
+use resources::{grid::TargetCell, load_save::load_save_name, math::Vec3Int};
+
+use crate::save::{
+    main_cells, world_save_path, SavedCellRon, SavedHealthRon, WorldSaveRon, WORLD_SAVE_VERSION,
+};
+
+/// Frames to wait for restored entities and cells to be built before giving up on their saved state.
+
+const MAX_RESTORE_FRAMES: u32 = 600;
+
+/// A restored item that goes back into an inventory slot.
+
+pub struct PendingSlotItem {
+    pub item: Entity,
+    pub holder: Entity,
+    pub slot_id: u8,
+}
+
+/// Resource with the parts of a loaded world save that are restored once the world is built.
+#[derive(Resource, Default)]
+
+pub struct PendingWorldSave {
+    pub main: Vec<SavedCellRon>,
+    pub details1: Vec<SavedCellRon>,
+    pub cells_restored: bool,
+    pub entity_health: Vec<(Entity, SavedHealthRon)>,
+    pub slot_items: Vec<PendingSlotItem>,
+    pub cell_health: Vec<(TargetCell, u16, SavedHealthRon)>,
+    pub details1_health: Vec<(Vec3Int, u16, SavedHealthRon)>,
+    pub frames: u32,
+}
+
+/// Read the world save passed with `--load-save` and spawn its entities.
+
+pub(crate) fn load_world_save(
+    mut raw_spawner: EventWriter<RawSpawnEvent>,
+    mut pending: ResMut<PendingWorldSave>,
+    mut commands: Commands,
+) {
+    let name;
+    match load_save_name() {
+        Some(n) => {
+            name = n;
+        }
+        None => {
+            return;
+        }
+    }
+
+    let world_save_raw_ron: String = fs::read_to_string(world_save_path(&name))
+        .expect("Error reading world save file from drive.");
+    let world_save: WorldSaveRon =
+        ron::from_str(&world_save_raw_ron).expect("Error parsing world save String.");
+
+    if world_save.version != WORLD_SAVE_VERSION {
+        panic!(
+            "World save {} has version {}, expected version {}.",
+            name, world_save.version, WORLD_SAVE_VERSION
+        );
+    }
+
+    let mut spawned = vec![];
+    for saved in world_save.entities.iter() {
+        let entity = commands.spawn(()).id();
+        raw_spawner.send(RawSpawnEvent {
+            raw_entity: saved.entity.clone(),
+            entity_option: Some(entity),
+        });
+        spawned.push(entity);
+    }
+
+    for (saved, entity) in world_save.entities.iter().zip(spawned.iter()) {
+        match &saved.health {
+            Some(health) => {
+                pending.entity_health.push((*entity, health.clone()));
+            }
+            None => {}
+        }
+        match &saved.slot {
+            Some(slot) => match spawned.get(slot.holder) {
+                Some(holder) => {
+                    pending.slot_items.push(PendingSlotItem {
+                        item: *entity,
+                        holder: *holder,
+                        slot_id: slot.slot_id,
+                    });
+                }
+                None => {
+                    warn!("Couldnt find saved inventory holder {}.", slot.holder);
+                }
+            },
+            None => {}
+        }
+    }
+
+    pending.main = world_save.main;
+    pending.details1 = world_save.details1;
+
+    info!(
+        "Loaded world save {} with {} entities.",
+        name,
+        spawned.len()
+    );
+}
+
+/// Turn the default map into the saved gridmap by removing and adding the cells that differ.
+/// Runs once after the default map cells were built.
+
+pub(crate) fn restore_world_save_cells(
+    mut pending: ResMut<PendingWorldSave>,
+    gridmap: Res<Gridmap>,
+    mut add_tile: EventWriter<AddTile>,
+    mut remove_tile: EventWriter<RemoveTile>,
+    mut add_details1: EventWriter<AddDetails1Tile>,
+    mut remove_details1: EventWriter<RemoveDetails1Tile>,
+    mut commands: Commands,
+) {
+    if pending.cells_restored {
+        return;
+    }
+    pending.cells_restored = true;
+
+    let mut saved_main: HashMap<TargetCell, (u16, u8)> = HashMap::new();
+    for cell in std::mem::take(&mut pending.main) {
+        match gridmap.main_name_id_map.get(&cell.tile) {
+            Some(tile_type) => {
+                let target = TargetCell {
+                    id: cell.id,
+                    face: cell.face,
+                };
+                match cell.health {
+                    Some(health) => {
+                        pending
+                            .cell_health
+                            .push((target.clone(), *tile_type, health));
+                    }
+                    None => {}
+                }
+                saved_main.insert(target, (*tile_type, cell.orientation));
+            }
+            None => {
+                warn!("Couldnt find item {}", cell.tile);
+            }
+        }
+    }
+
+    for (target, item) in main_cells(&gridmap) {
+        match saved_main.get(&target) {
+            Some((tile_type, orientation))
+                if *tile_type == item.tile_type && *orientation == item.orientation => {}
+            _ => {
+                remove_tile.send(RemoveTile { cell: target });
+            }
+        }
+    }
+    for (target, (tile_type, orientation)) in saved_main {
+        match gridmap.get_cell(target.clone()) {
+            Some(item) if item.tile_type == tile_type && item.orientation == orientation => {}
+            _ => {
+                add_tile.send(AddTile {
+                    id: target.id,
+                    tile_type,
+                    orientation,
+                    face: target.face,
+                    group_instance_id_option: None,
+                    entity: commands.spawn(()).id(),
+                    default_map_spawn: false,
+                });
+            }
+        }
+    }
+
+    let mut saved_details1: HashMap<Vec3Int, (u16, u8)> = HashMap::new();
+    for cell in std::mem::take(&mut pending.details1) {
+        match gridmap.details1_name_id_map.get(&cell.tile) {
+            Some(tile_type) => {
+                match cell.health {
+                    Some(health) => {
+                        pending.details1_health.push((cell.id, *tile_type, health));
+                    }
+                    None => {}
+                }
+                saved_details1.insert(cell.id, (*tile_type, cell.orientation));
+            }
+            None => {
+                warn!("Couldnt find details1 item {}", cell.tile);
+            }
+        }
+    }
+
+    for (id, item) in gridmap.details1.iter() {
+        match saved_details1.get(id) {
+            Some((tile_type, orientation))
+                if *tile_type == item.tile_type && *orientation == item.orientation => {}
+            _ => {
+                remove_details1.send(RemoveDetails1Tile { id: *id });
+            }
+        }
+    }
+    for (id, (tile_type, orientation)) in saved_details1 {
+        match gridmap.details1.get(&id) {
+            Some(item) if item.tile_type == tile_type && item.orientation == orientation => {}
+            _ => {
+                add_details1.send(AddDetails1Tile {
+                    id,
+                    tile_type,
+                    orientation,
+                    entity: commands.spawn(()).id(),
+                    default_map_spawn: false,
+                });
+            }
+        }
+    }
+}
+
+/// Apply saved health and inventory slots once the restored entities and cells are built.
+
+pub(crate) fn apply_world_save_states(
+    mut pending: ResMut<PendingWorldSave>,
+    mut gridmap: ResMut<Gridmap>,
+    mut health_query: Query<&mut HealthComponent>,
+    inventory_items: Query<&EntityData, With<InventoryItem>>,
+    inventories: Query<&Inventory>,
+    types: Res<EntityTypes>,
+    mut add_slot_item: EventWriter<AddItemToSlot>,
+) {
+    if !pending.cells_restored
+        || (pending.entity_health.is_empty()
+            && pending.slot_items.is_empty()
+            && pending.cell_health.is_empty()
+            && pending.details1_health.is_empty())
+    {
+        return;
+    }
+
+    pending
+        .entity_health
+        .retain(|(entity, health)| match health_query.get_mut(*entity) {
+            Ok(mut component) => {
+                health.apply(&mut component.health);
+                false
+            }
+            Err(_) => true,
+        });
+
+    pending.slot_items.retain(|slot_item| {
+        match (
+            inventory_items.get(slot_item.item),
+            inventories.get(slot_item.holder),
+        ) {
+            (Ok(entity_data), Ok(_)) => {
+                match types
+                    .netcode_types
+                    .get(&entity_data.entity_type.get_identity())
+                {
+                    Some(net_type) => {
+                        add_slot_item.send(AddItemToSlot {
+                            slot_id: slot_item.slot_id,
+                            inventory_entity: slot_item.holder,
+                            item_entity: slot_item.item,
+                            item_type_id: *net_type,
+                        });
+                    }
+                    None => {
+                        warn!("Couldnt find entity type of saved inventory item.");
+                    }
+                }
+                false
+            }
+            _ => true,
+        }
+    });
+
+    let mut cell_health = std::mem::take(&mut pending.cell_health);
+    cell_health.retain(
+        |(target, tile_type, health)| match gridmap.get_cell_mut(target.clone()) {
+            Some(item) if item.tile_type == *tile_type => {
+                health.apply(&mut item.health);
+                false
+            }
+            _ => true,
+        },
+    );
+    pending.cell_health = cell_health;
+
+    let mut details1_health = std::mem::take(&mut pending.details1_health);
+    details1_health.retain(
+        |(id, tile_type, health)| match gridmap.details1.get_mut(id) {
+            Some(item) if item.tile_type == *tile_type => {
+                health.apply(&mut item.health);
+                false
+            }
+            _ => true,
+        },
+    );
+    pending.details1_health = details1_health;
+
+    pending.frames += 1;
+    if pending.frames > MAX_RESTORE_FRAMES {
+        warn!(
+            "Couldnt restore the saved state of {} entities and {} cells.",
+            pending.entity_health.len() + pending.slot_items.len(),
+            pending.cell_health.len() + pending.details1_health.len()
+        );
+        pending.entity_health.clear();
+        pending.slot_items.clear();
+        pending.cell_health.clear();
+        pending.details1_health.clear();
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::{App, CoreSet, IntoSystemConfig, Plugin};
+use resources::{is_server::is_server, labels::StartupLabels, load_save::load_save_name};
+
+use crate::{
+    load::{apply_world_save_states, load_world_save, restore_world_save_cells, PendingWorldSave},
+    save::{save_world, WorldSave},
+    shutdown::{exit_on_shutdown_signal, start_shutdown_handler},
+};
+
+pub struct WorldSavePlugin;
+
+impl Plugin for WorldSavePlugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.init_resource::<WorldSave>()
+                .add_startup_system(start_shutdown_handler)
+                .add_system(exit_on_shutdown_signal)
+                .add_system(save_world.in_base_set(CoreSet::Last));
+            if load_save_name().is_some() {
+                app.init_resource::<PendingWorldSave>()
+                    .add_startup_system(
+                        load_world_save
+                            .after(StartupLabels::BuildGridmap)
+                            .in_set(StartupLabels::InitEntities),
+                    )
+                    // The default map cells are built in the update set of the first frame.
+                    .add_system(restore_world_save_cells.in_base_set(CoreSet::PostUpdate))
+                    .add_system(apply_world_save_states);
+            }
+        }
+    }
+}
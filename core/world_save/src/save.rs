@@ -0,0 +1,374 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    app::AppExit,
+    prelude::{
+        info, warn, Entity, EventReader, Query, Res, ResMut, Resource, Transform, With, Without,
+    },
+    time::{Time, Timer, TimerMode},
+};
+use entity::{
+    entity_data::EntityData,
+    entity_types::EntityType,
+    health::{EntityContainer, Health, HealthComponent, HealthContainer, StructureHealth},
+    init::RawEntityRon,
+    showcase::Showcase,
+};
+use gridmap::grid::{CellIndexes, CellItem, Gridmap};
+use inventory::server::inventory::Inventory;
+use pawn::pawn::Pawn;
+use resources::{
+    grid::{CellFace, TargetCell},
+    load_save::load_save_name,
+    math::Vec3Int,
+};
+use serde::{Deserialize, Serialize};
+
+/// Version of the world save format, saves of other versions are refused.
+
+pub const WORLD_SAVE_VERSION: u32 = 1;
+
+/// Seconds between periodic world saves.
+
+pub const WORLD_SAVE_INTERVAL: f32 = 300.;
+
+/// Name of the world save when the server was not started from one.
+
+pub const DEFAULT_WORLD_SAVE_NAME: &str = "autosave";
+
+/// Path of the world save with a name.
+
+pub fn world_save_path(name: &str) -> PathBuf {
+    Path::new("data")
+        .join("saves")
+        .join(name.to_string() + ".ron")
+}
+
+/// A snapshot of the live ship state.
+#[derive(Serialize, Deserialize)]
+
+pub struct WorldSaveRon {
+    pub version: u32,
+    /// Cells of the main gridmap layer.
+    pub main: Vec<SavedCellRon>,
+    /// Cells of the details1 gridmap layer.
+    pub details1: Vec<SavedCellRon>,
+    pub entities: Vec<SavedEntityRon>,
+}
+
+/// A saved gridmap cell.
+#[derive(Serialize, Deserialize, Clone)]
+
+pub struct SavedCellRon {
+    pub id: Vec3Int,
+    /// Tile type name.
+    pub tile: String,
+    pub orientation: u8,
+    pub face: CellFace,
+    pub health: Option<SavedHealthRon>,
+}
+
+/// A saved entity.
+#[derive(Serialize, Deserialize, Clone)]
+
+pub struct SavedEntityRon {
+    pub entity: RawEntityRon,
+    pub health: Option<SavedHealthRon>,
+    /// The inventory slot this entity is stored in.
+    pub slot: Option<SavedSlotRon>,
+}
+
+/// Inventory slot of a saved item.
+#[derive(Serialize, Deserialize, Clone)]
+
+pub struct SavedSlotRon {
+    /// Index of the saved entity holding the inventory.
+    pub holder: usize,
+    pub slot_id: u8,
+}
+
+/// Saved damage of a health container.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub enum SavedHealthRon {
+    Entity { brute: f32, burn: f32, toxin: f32 },
+    Structure { brute: f32, burn: f32, toxin: f32 },
+}
+
+impl SavedHealthRon {
+    /// Humanoid health belongs to pawns and is not saved.
+    pub fn new(health: &Health) -> Option<Self> {
+        match &health.health_container {
+            HealthContainer::Entity(container) => Some(Self::Entity {
+                brute: container.brute,
+                burn: container.burn,
+                toxin: container.toxin,
+            }),
+            HealthContainer::Structure(container) => Some(Self::Structure {
+                brute: container.brute,
+                burn: container.burn,
+                toxin: container.toxin,
+            }),
+            HealthContainer::Humanoid(_) => None,
+        }
+    }
+    pub fn apply(&self, health: &mut Health) {
+        match self {
+            SavedHealthRon::Entity { brute, burn, toxin } => {
+                health.health_container = HealthContainer::Entity(EntityContainer {
+                    brute: *brute,
+                    burn: *burn,
+                    toxin: *toxin,
+                });
+            }
+            SavedHealthRon::Structure { brute, burn, toxin } => {
+                health.health_container = HealthContainer::Structure(StructureHealth {
+                    brute: *brute,
+                    burn: *burn,
+                    toxin: *toxin,
+                });
+            }
+        }
+    }
+}
+
+/// Name and timer of the world save the server writes to.
+#[derive(Resource)]
+
+pub struct WorldSave {
+    pub name: String,
+    pub timer: Timer,
+}
+
+impl Default for WorldSave {
+    fn default() -> Self {
+        Self {
+            name: load_save_name().unwrap_or(DEFAULT_WORLD_SAVE_NAME.to_string()),
+            timer: Timer::from_seconds(WORLD_SAVE_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+/// All tiles of the main gridmap layer.
+
+pub fn main_cells(gridmap: &Gridmap) -> Vec<(TargetCell, CellItem)> {
+    let mut cells = vec![];
+    for (chunk_index, chunk_option) in gridmap.grid.iter().enumerate() {
+        match chunk_option {
+            Some(chunk) => {
+                for (cell_index, cell_option) in chunk.cells.iter().enumerate() {
+                    match cell_option {
+                        Some(cell) => match gridmap.get_id(CellIndexes {
+                            chunk: chunk_index,
+                            cell: cell_index,
+                        }) {
+                            Some(id) => {
+                                for (item, face) in cell.get_items() {
+                                    cells.push((TargetCell { id, face }, item));
+                                }
+                            }
+                            None => {}
+                        },
+                        None => {}
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+    cells
+}
+
+fn saved_cells(gridmap: &Gridmap) -> (Vec<SavedCellRon>, Vec<SavedCellRon>) {
+    let mut main = vec![];
+    for (target, item) in main_cells(gridmap) {
+        match gridmap.main_id_name_map.get(&item.tile_type) {
+            Some(name) => {
+                main.push(SavedCellRon {
+                    id: target.id,
+                    tile: name.clone(),
+                    orientation: item.orientation,
+                    face: target.face,
+                    health: SavedHealthRon::new(&item.health),
+                });
+            }
+            None => {
+                warn!("Couldnt find item {}", item.tile_type);
+            }
+        }
+    }
+    let mut details1 = vec![];
+    for (id, item) in gridmap.details1.iter() {
+        match gridmap.details1_id_name_map.get(&item.tile_type) {
+            Some(name) => {
+                details1.push(SavedCellRon {
+                    id: *id,
+                    tile: name.clone(),
+                    orientation: item.orientation,
+                    face: CellFace::Floor,
+                    health: SavedHealthRon::new(&item.health),
+                });
+            }
+            None => {
+                warn!("Couldnt find details1 item {}", item.tile_type);
+            }
+        }
+    }
+    (main, details1)
+}
+
+fn write_world_save(name: &str, save: &WorldSaveRon) -> Result<PathBuf, String> {
+    let path = world_save_path(name);
+    let data;
+    match ron::to_string(save) {
+        Ok(d) => {
+            data = d;
+        }
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    match path.parent() {
+        Some(directory) => match fs::create_dir_all(directory) {
+            Ok(_) => {}
+            Err(error) => {
+                return Err(error.to_string());
+            }
+        },
+        None => {}
+    }
+    // Write next to the save first so a crash while writing leaves the previous save intact.
+    let temporary = path.with_extension("ron.tmp");
+    match fs::write(&temporary, data) {
+        Ok(_) => {}
+        Err(error) => {
+            return Err(error.to_string());
+        }
+    }
+    match fs::rename(&temporary, &path) {
+        Ok(_) => Ok(path),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Periodically and on exit write the live ship state to the world save.
+/// Pawns belong to connected players and are not saved. Neither are the items in their inventories,
+/// boarding players get a fresh loadout so saving them would pile duplicate gear up on every restart.
+
+pub(crate) fn save_world(
+    mut world_save: ResMut<WorldSave>,
+    time: Res<Time>,
+    mut exit: EventReader<AppExit>,
+    gridmap: Res<Gridmap>,
+    entities: Query<
+        (Entity, &EntityData, &Transform, Option<&HealthComponent>),
+        (Without<Pawn>, Without<Showcase>),
+    >,
+    holders: Query<(Entity, &Inventory)>,
+    transforms: Query<&Transform>,
+    pawns: Query<(), With<Pawn>>,
+) {
+    world_save.timer.tick(time.delta());
+    let exiting = exit.iter().count() > 0;
+    if !world_save.timer.just_finished() && !exiting {
+        return;
+    }
+
+    let mut slots: HashMap<Entity, (Entity, u8)> = HashMap::new();
+    let mut pawn_items: HashSet<Entity> = HashSet::new();
+    for (holder, inventory) in holders.iter() {
+        let pawn_held = pawns.get(holder).is_ok();
+        for (slot_id, slot) in inventory.slots.iter() {
+            for item in slot.items.iter() {
+                if pawn_held {
+                    pawn_items.insert(item.entity);
+                } else {
+                    slots.insert(item.entity, (holder, *slot_id));
+                }
+            }
+        }
+    }
+
+    let mut indexes: HashMap<Entity, usize> = HashMap::new();
+    for (index, (entity, _, _, _)) in entities
+        .iter()
+        .filter(|(entity, _, _, _)| !pawn_items.contains(entity))
+        .enumerate()
+    {
+        indexes.insert(entity, index);
+    }
+
+    let mut saved_entities = vec![];
+    for (entity, entity_data, transform, health_option) in entities.iter() {
+        if pawn_items.contains(&entity) {
+            continue;
+        }
+        let mut translation = transform.translation;
+        let mut slot = None;
+        match slots.get(&entity) {
+            Some((holder, slot_id)) => match indexes.get(holder) {
+                Some(holder_index) => {
+                    slot = Some(SavedSlotRon {
+                        holder: *holder_index,
+                        slot_id: *slot_id,
+                    });
+                }
+                None => match transforms.get(*holder) {
+                    Ok(holder_transform) => {
+                        translation = holder_transform.translation;
+                    }
+                    Err(_) => {
+                        warn!("Couldnt find transform of inventory holder.");
+                    }
+                },
+            },
+            None => {}
+        }
+        let health;
+        match health_option {
+            Some(component) => {
+                health = SavedHealthRon::new(&component.health);
+            }
+            None => {
+                health = None;
+            }
+        }
+        saved_entities.push(SavedEntityRon {
+            entity: RawEntityRon {
+                entity_type: entity_data.entity_type.get_identity(),
+                translation,
+                rotation: transform.rotation,
+                scale: transform.scale,
+                data: "".to_string(),
+            },
+            health,
+            slot,
+        });
+    }
+
+    let (main, details1) = saved_cells(&gridmap);
+    let save = WorldSaveRon {
+        version: WORLD_SAVE_VERSION,
+        main,
+        details1,
+        entities: saved_entities,
+    };
+
+    match write_world_save(&world_save.name, &save) {
+        Ok(path) => {
+            info!(
+                "Saved world with {} cells and {} entities to {}.",
+                save.main.len() + save.details1.len(),
+                save.entities.len(),
+                path.display()
+            );
+        }
+        Err(error) => {
+            warn!("Couldnt write world save {}: {}", world_save.name, error);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use bevy::{
+    app::AppExit,
+    prelude::{info, warn, Commands, EventWriter, Local, Res, Resource},
+};
+
+/// Set once the process received Ctrl-C or SIGTERM.
+#[derive(Resource, Default)]
+
+pub struct ShutdownSignal {
+    received: Arc<AtomicBool>,
+}
+
+/// Catch Ctrl-C and SIGTERM so the server exits through [AppExit] and writes the world save.
+/// A second signal exits immediately in case the graceful shutdown hangs.
+
+pub(crate) fn start_shutdown_handler(mut commands: Commands) {
+    let signal = ShutdownSignal::default();
+    let received = signal.received.clone();
+
+    match ctrlc::set_handler(move || {
+        if received.swap(true, Ordering::SeqCst) {
+            std::process::exit(1);
+        }
+    }) {
+        Ok(_) => {}
+        Err(err) => {
+            warn!("Couldn't set shutdown signal handler: {}", err);
+        }
+    }
+
+    commands.insert_resource(signal);
+}
+
+/// Exit the app once a shutdown signal was received.
+
+pub(crate) fn exit_on_shutdown_signal(
+    signal: Res<ShutdownSignal>,
+    mut exit: EventWriter<AppExit>,
+    mut sent: Local<bool>,
+) {
+    if *sent || !signal.received.load(Ordering::SeqCst) {
+        return;
+    }
+    info!("Received shutdown signal, saving world and exiting.");
+    exit.send(AppExit);
+    *sent = true;
+}
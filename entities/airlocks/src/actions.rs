@@ -177,6 +177,14 @@ pub(crate) fn airlock_actions(
     }
 }
 
+/// Range within which airlocks can be locked and unlocked remotely.
+
+pub const REMOTE_LOCK_RANGE: f32 = 30.;
+
+/// Seconds it takes to remotely lock or unlock an airlock.
+
+pub const REMOTE_LOCK_DURATION: f32 = 1.;
+
 /// Prerequisite check of locking an airlock.
 
 pub(crate) fn lock_action_prequisite_check(
@@ -236,7 +244,8 @@ pub(crate) fn lock_action_prequisite_check(
 
                 let distance = start_pos.distance(end_pos);
 
-                match distance < 30. && examiner_data_link.links.contains(&DataLinkType::RemoteLock)
+                match distance < REMOTE_LOCK_RANGE
+                    && examiner_data_link.links.contains(&DataLinkType::RemoteLock)
                 {
                     true => {
                         action.approve();
@@ -303,3 +312,25 @@ pub(crate) fn build_actions(
         }
     }
 }
+
+use actions::timed::{TimedActionSettings, TimedActions};
+
+/// Make remotely locking and unlocking airlocks take time.
+
+pub(crate) fn register_lock_timed_actions(mut timed_actions: ResMut<TimedActions>) {
+    for id in [
+        "actions::airlocks/lockopen",
+        "actions::airlocks/lockclosed",
+        "actions::airlocks/unlock",
+    ] {
+        timed_actions.map.insert(
+            id.to_string(),
+            TimedActionSettings {
+                duration: REMOTE_LOCK_DURATION,
+                max_distance_option: Some(REMOTE_LOCK_RANGE),
+                interrupt_on_damage: true,
+                interrupt_on_item_switch: false,
+            },
+        );
+    }
+}
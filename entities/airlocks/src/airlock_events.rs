@@ -4,7 +4,9 @@ use bevy::{
 };
 use bevy_rapier3d::prelude::{CollisionGroups, Group};
 use entity::{entity_data::EntityGroup, examine::Examinable};
-use pawn::pawn::{Pawn, ShipAuthorization};
+use inventory::server::{access::has_access, inventory::Inventory};
+use pawn::access::IdCard;
+use power::power::{is_powered, PowerConsumer};
 use resources::math::{world_to_cell_id, Vec2Int};
 use sfx::{builder::sfx_builder, entity_update::SfxAutoDestroyTimers};
 use sounds::{
@@ -39,7 +41,8 @@ pub(crate) fn airlock_events(
     mut toggle_open_action: EventReader<InputAirlockToggleOpen>,
     transforms: Query<&Transform>,
     mut airlock_query: Query<(&mut Airlock, Entity, &mut Examinable, &Children)>,
-    pawn_query: Query<(&Pawn, &ShipAuthorization)>,
+    inventory_query: Query<&Inventory>,
+    id_card_query: Query<&IdCard>,
     mut auto_destroy_timers: ResMut<SfxAutoDestroyTimers>,
     mut commands: Commands,
     mut airlock_lock_open_event: EventReader<AirLockLockOpen>,
@@ -47,6 +50,7 @@ pub(crate) fn airlock_events(
     mut unlock_events: EventReader<AirlockUnlock>,
     mut server: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
     mut collision_groups: Query<&mut CollisionGroups>,
+    consumers: Query<&PowerConsumer>,
 ) {
     let mut close_requests = vec![];
     let mut open_requests = vec![];
@@ -309,6 +313,11 @@ pub(crate) fn airlock_events(
             pawn_entity = collision_event.collider1_entity;
         }
 
+        // Unpowered airlocks can only be opened manually.
+        if !is_powered(airlock_entity, &consumers) {
+            continue;
+        }
+
         open_requests.push(AirLockOpenRequest {
             opener_option: Some(pawn_entity),
             opened: airlock_entity,
@@ -355,29 +364,15 @@ pub(crate) fn airlock_events(
 
         match request.opener_option {
             Some(opener) => {
-                let pawn_space_access_component_result =
-                    pawn_query.get_component::<ShipAuthorization>(opener);
-                let pawn_space_access_component;
-
-                match pawn_space_access_component_result {
-                    Ok(result) => {
-                        pawn_space_access_component = result;
-                    }
-                    Err(_err) => {
-                        continue;
-                    }
-                }
-
-                for space_permission in &airlock_component.access_permissions {
-                    if pawn_space_access_component
-                        .access
-                        .contains(space_permission)
-                        == true
-                    {
-                        pawn_has_permission = true;
-                        break;
-                    }
+                if inventory_query.get(opener).is_err() {
+                    continue;
                 }
+                pawn_has_permission = has_access(
+                    opener,
+                    &airlock_component.access_permissions,
+                    &inventory_query,
+                    &id_card_query,
+                );
             }
             None => {
                 pawn_has_permission = true;
@@ -479,29 +474,15 @@ pub(crate) fn airlock_events(
 
                 match request.interacter_option {
                     Some(interacter) => {
-                        let pawn_space_access_component_result =
-                            pawn_query.get_component::<ShipAuthorization>(interacter);
-                        let pawn_space_access_component;
-
-                        match pawn_space_access_component_result {
-                            Ok(result) => {
-                                pawn_space_access_component = result;
-                            }
-                            Err(_err) => {
-                                continue;
-                            }
-                        }
-
-                        for space_permission in &airlock_component.access_permissions {
-                            if pawn_space_access_component
-                                .access
-                                .contains(space_permission)
-                                == true
-                            {
-                                pawn_has_permission = true;
-                                break;
-                            }
+                        if inventory_query.get(interacter).is_err() {
+                            continue;
                         }
+                        pawn_has_permission = has_access(
+                            interacter,
+                            &airlock_component.access_permissions,
+                            &inventory_query,
+                            &id_card_query,
+                        );
                     }
                     None => {
                         pawn_has_permission = true;
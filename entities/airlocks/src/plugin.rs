@@ -1,6 +1,6 @@
 use bevy::prelude::{App, CoreSet, IntoSystemConfig, Plugin, SystemSet};
 use combat::sfx::health_combat_hit_result_sfx;
-use entity::entity_types::register_entity_type;
+use entity::entity_types::{register_entity_type, register_grid_entity_type};
 use entity::spawn::build_base_entities;
 use physics::spawn::build_rigid_bodies;
 use resources::is_server::is_server;
@@ -8,7 +8,7 @@ use resources::labels::{ActionsLabels, BuildingLabels, CombatLabels, PostUpdateL
 
 use crate::{
     actions::{
-        airlock_actions, build_actions, lock_action_prequisite_check,
+        airlock_actions, build_actions, lock_action_prequisite_check, register_lock_timed_actions,
         toggle_open_action_prequisite_check,
     },
     airlock_events::{
@@ -73,11 +73,13 @@ impl Plugin for AirLocksPlugin {
                     build_actions
                         .in_set(ActionsLabels::Build)
                         .after(ActionsLabels::Init),
-                );
+                )
+                .add_startup_system(register_lock_timed_actions);
         }
         app.add_system(build_airlocks::<AirlockType>.after(BuildingLabels::TriggerBuild))
             .add_system((build_rigid_bodies::<AirlockType>).after(BuildingLabels::TriggerBuild))
             .add_system((build_base_entities::<AirlockType>).after(BuildingLabels::TriggerBuild));
         register_entity_type::<AirlockType>(app);
+        register_grid_entity_type::<AirlockType>(app);
     }
 }
@@ -1,5 +1,4 @@
 use bevy::{prelude::Component, time::Timer};
-use pawn::pawn::ShipAuthorizationEnum;
 
 use crate::airlock_events::LockedStatus;
 
@@ -12,7 +11,7 @@ pub struct Airlock {
     /// Current color of the access lights.
     pub access_lights: AccessLightsStatus,
     /// Required authorization to interact with the air lock.
-    pub access_permissions: Vec<ShipAuthorizationEnum>,
+    pub access_permissions: Vec<String>,
     /// Whether the air lock is locked.
     pub locked_status: LockedStatus,
 
@@ -41,7 +40,7 @@ impl Default for Airlock {
         Self {
             status: AirlockStatus::Closed,
             access_lights: AccessLightsStatus::Neutral,
-            access_permissions: vec![ShipAuthorizationEnum::Common],
+            access_permissions: vec!["common".to_string()],
             locked_status: LockedStatus::None,
             denied_timer_option: None,
             open_timer_option: None,
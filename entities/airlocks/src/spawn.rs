@@ -8,14 +8,13 @@ use bevy::{
 use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Friction};
 use const_format::concatcp;
 use entity::{
-    entity_data::EntityGroup,
+    entity_data::{EntityGroup, GridEntity, GridItemData},
     entity_macros::Identity,
     entity_types::EntityType,
     examine::{Examinable, RichName},
     health::Health,
     spawn::{BaseEntityBuilder, BaseEntityBundle, EntityBuildData, NoData, SpawnEntity},
 };
-use pawn::pawn::ShipAuthorizationEnum;
 use physics::spawn::{RigidBodyBuilder, RigidBodyBundle};
 use text_api::core::{FURTHER_ITALIC_FONT, HEALTHY_COLOR};
 
@@ -120,21 +119,38 @@ impl Default for AirlockType {
     }
 }
 
+impl GridEntity for AirlockType {
+    fn get_grid_item_data() -> GridItemData {
+        GridItemData {
+            transform_offset: Transform::IDENTITY,
+            can_be_built_with_grid_item: vec![],
+        }
+    }
+}
+
 pub fn build_airlocks<T: Send + Sync + 'static>(
     mut commands: Commands,
     mut airlock_spawns: EventReader<SpawnEntity<T>>,
 ) {
     for spawn_event in airlock_spawns.iter() {
-        commands
-            .entity(spawn_event.spawn_data.entity)
-            .insert(Airlock {
-                access_permissions: vec![ShipAuthorizationEnum::Security],
+        commands.entity(spawn_event.spawn_data.entity).insert((
+            Airlock {
+                access_permissions: vec!["security".to_string()],
                 ..Default::default()
-            });
+            },
+            PowerConsumer {
+                load: AIRLOCK_POWER_LOAD,
+                ..Default::default()
+            },
+        ));
     }
 }
+use power::power::PowerConsumer;
 use resources::content::SF_CONTENT_PREFIX;
 
+/// Power drawn by airlocks in watts.
+pub const AIRLOCK_POWER_LOAD: f32 = 300.;
+
 pub const SECURITY_AIRLOCK_ENTITY_NAME: &str = concatcp!(SF_CONTENT_PREFIX, "security_airlock1");
 pub const BRIDGE_AIRLOCK_ENTITY_NAME: &str = concatcp!(SF_CONTENT_PREFIX, "bridge_airlock");
 pub const GOVERNMENT_AIRLOCK_ENTITY_NAME: &str = concatcp!(SF_CONTENT_PREFIX, "government_airLock");
@@ -0,0 +1,42 @@
+use bevy::prelude::{Color, EventReader, EventWriter, Query, Res};
+
+use chat::net::{ChatMessage, ChatServerMessage};
+use networking::server::{ConnectedPlayer, OutgoingReliableServerMessage};
+use ui::{
+    fonts::{Fonts, SOURCECODE_REGULAR_FONT},
+    text::{NetTextSection, COMMUNICATION_FONT_SIZE},
+};
+
+/// Event to have ASANA announce a message to the whole ship.
+
+pub struct AsanaAnnouncement {
+    pub message: String,
+}
+
+/// Broadcast ASANA announcements to all connected players.
+
+pub(crate) fn broadcast_asana_announcements(
+    mut events: EventReader<AsanaAnnouncement>,
+    mut net: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
+    connected_players: Query<&ConnectedPlayer>,
+    fonts: Res<Fonts>,
+) {
+    for event in events.iter() {
+        for player in connected_players.iter() {
+            if !player.connected {
+                continue;
+            }
+            net.send(OutgoingReliableServerMessage {
+                handle: player.handle,
+                message: ChatServerMessage::ChatMessage(ChatMessage {
+                    sections: vec![NetTextSection {
+                        text: "ASANA: ".to_string() + &event.message,
+                        font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
+                        font_size: COMMUNICATION_FONT_SIZE,
+                        color: Color::WHITE,
+                    }],
+                }),
+            });
+        }
+    }
+}
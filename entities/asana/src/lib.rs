@@ -1,5 +1,7 @@
 //! Automated Space Announcements.
 
+/// Ship-wide ASANA announcements.
+pub mod announcements;
 /// The Bevy plugin of this crate.
 pub mod plugin;
 /// Manage ASANA boarding announcements.
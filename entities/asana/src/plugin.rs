@@ -1,14 +1,19 @@
-use bevy::prelude::{App, Plugin};
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
 use resources::is_server::is_server;
 
-use crate::tick_asana_boarding_announcements::tick_asana_boarding_announcements;
+use crate::{
+    announcements::{broadcast_asana_announcements, AsanaAnnouncement},
+    tick_asana_boarding_announcements::tick_asana_boarding_announcements,
+};
 
 pub struct AsanaPlugin;
 
 impl Plugin for AsanaPlugin {
     fn build(&self, app: &mut App) {
         if is_server() {
-            app.add_system(tick_asana_boarding_announcements);
+            app.add_event::<AsanaAnnouncement>()
+                .add_system(tick_asana_boarding_announcements)
+                .add_system(broadcast_asana_announcements.after(tick_asana_boarding_announcements));
         }
     }
 }
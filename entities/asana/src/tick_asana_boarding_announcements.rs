@@ -1,23 +1,17 @@
 use bevy::{
-    prelude::{Color, EventWriter, Query, Res, ResMut},
+    prelude::{EventWriter, Res, ResMut},
     time::Time,
 };
 
-use chat::net::{ChatMessage, ChatServerMessage};
-use networking::server::{ConnectedPlayer, OutgoingReliableServerMessage};
 use player::boarding::BoardingAnnouncements;
-use ui::{
-    fonts::{Fonts, SOURCECODE_REGULAR_FONT},
-    text::{NetTextSection, COMMUNICATION_FONT_SIZE},
-};
+
+use crate::announcements::AsanaAnnouncement;
 /// Manage asana boarding announcements.
 
 pub(crate) fn tick_asana_boarding_announcements(
     mut asana_boarding_announcements: ResMut<BoardingAnnouncements>,
     time: Res<Time>,
-    mut net: EventWriter<OutgoingReliableServerMessage<ChatServerMessage>>,
-    connected_players: Query<&ConnectedPlayer>,
-    fonts: Res<Fonts>,
+    mut announcements: EventWriter<AsanaAnnouncement>,
 ) {
     let mut done_messages: Vec<usize> = vec![];
 
@@ -27,22 +21,9 @@ pub(crate) fn tick_asana_boarding_announcements(
         &mut asana_boarding_announcements.announcements
     {
         if announcement_timer.tick(time.delta()).just_finished() {
-            for player in connected_players.iter() {
-                if !player.connected {
-                    continue;
-                }
-                net.send(OutgoingReliableServerMessage {
-                    handle: player.handle,
-                    message: ChatServerMessage::ChatMessage(ChatMessage {
-                        sections: vec![NetTextSection {
-                            text: "ASANA: ".to_string() + announcement_message,
-                            font: *fonts.inv_map.get(SOURCECODE_REGULAR_FONT).unwrap(),
-                            font_size: COMMUNICATION_FONT_SIZE,
-                            color: Color::WHITE,
-                        }],
-                    }),
-                });
-            }
+            announcements.send(AsanaAnnouncement {
+                message: announcement_message.clone(),
+            });
 
             done_messages.push(j);
         }
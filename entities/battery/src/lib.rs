@@ -0,0 +1,6 @@
+//! Batteries that store power of power networks.
+
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Battery spawner.
+pub mod spawn;
@@ -0,0 +1,19 @@
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use entity::entity_types::{register_entity_type, register_grid_entity_type};
+use entity::spawn::build_base_entities;
+use physics::spawn::build_rigid_bodies;
+use resources::labels::BuildingLabels;
+
+use crate::spawn::{build_batteries, BatteryType};
+
+pub struct BatteriesPlugin;
+
+impl Plugin for BatteriesPlugin {
+    fn build(&self, app: &mut App) {
+        register_entity_type::<BatteryType>(app);
+        register_grid_entity_type::<BatteryType>(app);
+        app.add_system(build_batteries::<BatteryType>.after(BuildingLabels::TriggerBuild))
+            .add_system((build_base_entities::<BatteryType>).after(BuildingLabels::TriggerBuild))
+            .add_system((build_rigid_bodies::<BatteryType>).after(BuildingLabels::TriggerBuild));
+    }
+}
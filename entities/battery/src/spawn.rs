@@ -0,0 +1,106 @@
+use bevy::{
+    math::Vec3,
+    prelude::{Commands, EventReader, Transform},
+};
+use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Friction};
+use entity::{
+    entity_data::{GridEntity, GridItemData},
+    entity_macros::Identity,
+    entity_types::EntityType,
+    examine::{Examinable, RichName},
+    health::Health,
+    spawn::{BaseEntityBuilder, BaseEntityBundle, EntityBuildData, NoData, SpawnEntity},
+};
+use physics::{
+    rigid_body::STANDARD_BODY_FRICTION,
+    spawn::{RigidBodyBuilder, RigidBodyBundle},
+};
+use power::power::PowerStorage;
+use resources::content::SF_CONTENT_PREFIX;
+use std::collections::BTreeMap;
+
+/// Energy stored by full batteries in joules.
+pub const BATTERY_CAPACITY: f32 = 600000.;
+/// Maximum charge and discharge rate of batteries in watts.
+pub const BATTERY_MAX_RATE: f32 = 2000.;
+
+impl BaseEntityBuilder<NoData> for BatteryType {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> BaseEntityBundle {
+        let mut examine_map = BTreeMap::new();
+        examine_map.insert(
+            0,
+            "A battery that stores surplus power of the power cable it stands on.".to_string(),
+        );
+
+        BaseEntityBundle {
+            default_transform: Transform::IDENTITY,
+            examinable: Examinable {
+                assigned_texts: examine_map,
+                name: RichName {
+                    name: "battery".to_string(),
+                    n: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            entity_type: Box::new(BatteryType::new()),
+            health: Health {
+                is_combat_obstacle: true,
+                is_reach_obstacle: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl RigidBodyBuilder<NoData> for BatteryType {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> RigidBodyBundle {
+        let mut friction = Friction::coefficient(STANDARD_BODY_FRICTION);
+        friction.combine_rule = CoefficientCombineRule::Min;
+
+        RigidBodyBundle {
+            collider: Collider::cuboid(0.3, 0.5, 0.3),
+            collider_transform: Transform::from_translation(Vec3::new(0., 0.5, 0.)),
+            collider_friction: friction,
+            rigidbody_dynamic: false,
+            collision_events: true,
+        }
+    }
+}
+
+#[derive(Clone, Identity)]
+pub struct BatteryType {
+    pub identifier: String,
+}
+impl Default for BatteryType {
+    fn default() -> Self {
+        BatteryType {
+            identifier: SF_CONTENT_PREFIX.to_owned() + "battery",
+        }
+    }
+}
+
+impl GridEntity for BatteryType {
+    fn get_grid_item_data() -> GridItemData {
+        GridItemData {
+            transform_offset: Transform::IDENTITY,
+            can_be_built_with_grid_item: vec![],
+        }
+    }
+}
+
+pub fn build_batteries<T: Send + Sync + 'static>(
+    mut commands: Commands,
+    mut spawn_events: EventReader<SpawnEntity<T>>,
+) {
+    for spawn_event in spawn_events.iter() {
+        commands
+            .entity(spawn_event.spawn_data.entity)
+            .insert(PowerStorage {
+                charge: BATTERY_CAPACITY,
+                capacity: BATTERY_CAPACITY,
+                max_rate: BATTERY_MAX_RATE,
+            });
+    }
+}
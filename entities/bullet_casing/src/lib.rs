@@ -0,0 +1,6 @@
+//! Spent casings ejected by ballistic weapons.
+
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Spawner.
+pub mod spawn;
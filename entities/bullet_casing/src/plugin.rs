@@ -0,0 +1,34 @@
+use basic_console_commands::register::{
+    register_basic_console_commands_for_inventory_item_type,
+    register_basic_console_commands_for_type,
+};
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use entity::{entity_types::register_entity_type, spawn::build_base_entities};
+use inventory::spawn_item::build_inventory_items;
+use physics::spawn::build_rigid_bodies;
+use resources::{
+    is_server::is_server,
+    labels::{BuildingLabels, CombatLabels},
+};
+
+use super::spawn::{eject_casings, BulletCasingType};
+
+pub struct BulletCasingPlugin;
+
+impl Plugin for BulletCasingPlugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.add_system(eject_casings.after(CombatLabels::WeaponHandler));
+        }
+        register_entity_type::<BulletCasingType>(app);
+        register_basic_console_commands_for_type::<BulletCasingType>(app);
+        register_basic_console_commands_for_inventory_item_type::<BulletCasingType>(app);
+        app.add_system(
+            (build_base_entities::<BulletCasingType>).after(BuildingLabels::TriggerBuild),
+        )
+        .add_system((build_rigid_bodies::<BulletCasingType>).after(BuildingLabels::TriggerBuild))
+        .add_system(
+            (build_inventory_items::<BulletCasingType>).after(BuildingLabels::TriggerBuild),
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use actions::core::{Action, ActionData, ActionRequests, BuildingActions};
+use bevy::prelude::{warn, Component, Query, Res, ResMut, Transform};
+use inventory::server::{access::worn_card_access, inventory::Inventory};
+use pawn::{
+    access::{AccessConfig, IdCard},
+    pawn::REACH_DISTANCE,
+};
+use power::power::{is_powered, PowerConsumer};
+
+/// Computers that can grant and revoke access levels of ID cards.
+#[derive(Component)]
+
+pub struct IdConsole;
+
+const GRANT_PREFIX: &str = "actions::computers/grant/";
+const REVOKE_PREFIX: &str = "actions::computers/revoke/";
+
+/// Build grant and revoke actions for ID cards held against ID consoles.
+
+pub(crate) fn build_actions(
+    mut building_action_data: ResMut<BuildingActions>,
+    consoles: Query<&IdConsole>,
+    cards: Query<&IdCard>,
+    config: Res<AccessConfig>,
+) {
+    for building_action in building_action_data.list.iter_mut() {
+        match building_action.target_entity_option {
+            Some(examined_entity) => {
+                if consoles.get(examined_entity).is_err() {
+                    continue;
+                }
+            }
+            None => {
+                continue;
+            }
+        }
+        let card;
+        match building_action.action_taker_item {
+            Some(item) => match cards.get(item) {
+                Ok(c) => {
+                    card = c;
+                }
+                Err(_) => {
+                    continue;
+                }
+            },
+            None => {
+                continue;
+            }
+        }
+
+        let mut priority = 90;
+        for level in config.levels.iter() {
+            let data;
+            if card.access.contains(&level.id) {
+                data = Action {
+                    id: REVOKE_PREFIX.to_string() + &level.id,
+                    text: format!("Revoke {}", level.name),
+                    tab_list_priority: priority,
+                };
+            } else {
+                data = Action {
+                    id: GRANT_PREFIX.to_string() + &level.id,
+                    text: format!("Grant {}", level.name),
+                    tab_list_priority: priority,
+                };
+            }
+            building_action.actions.push(ActionData {
+                data,
+                approved: None,
+            });
+            priority = priority.saturating_sub(1);
+        }
+    }
+}
+
+/// Prerequisite check of editing an ID card. Requires the ID console access level on the worn card.
+
+pub(crate) fn id_console_prequisite_check(
+    mut building_action_data: ResMut<BuildingActions>,
+    transforms: Query<&Transform>,
+    inventories: Query<&Inventory>,
+    cards: Query<&IdCard>,
+    config: Res<AccessConfig>,
+    consumers: Query<&PowerConsumer>,
+) {
+    for building in building_action_data.list.iter_mut() {
+        for action in building.actions.iter_mut() {
+            if !action.data.id.starts_with(GRANT_PREFIX)
+                && !action.data.id.starts_with(REVOKE_PREFIX)
+            {
+                continue;
+            }
+            let examiner_transform;
+            match transforms.get(building.action_taker) {
+                Ok(t) => {
+                    examiner_transform = t;
+                }
+                Err(_rr) => {
+                    warn!("Couldnt find transform of examining entity!");
+                    continue;
+                }
+            }
+            let target;
+            let target_transform;
+            match building.target_entity_option {
+                Some(t) => match transforms.get(t) {
+                    Ok(tr) => {
+                        target = t;
+                        target_transform = tr;
+                    }
+                    Err(_) => {
+                        continue;
+                    }
+                },
+                None => {
+                    continue;
+                }
+            }
+            // Unpowered computers refuse input.
+            if !is_powered(target, &consumers) {
+                action.do_not_approve();
+                continue;
+            }
+
+            let distance = target_transform
+                .translation
+                .distance(examiner_transform.translation);
+            let access = worn_card_access(building.action_taker, &inventories, &cards);
+
+            match distance < REACH_DISTANCE && access.contains(&config.id_console_access) {
+                true => {
+                    action.approve();
+                }
+                false => {
+                    action.do_not_approve();
+                }
+            }
+        }
+    }
+}
+
+/// Grant or revoke access levels of the held ID card.
+
+pub(crate) fn id_console_actions(
+    building_action: Res<BuildingActions>,
+    action_requests: Res<ActionRequests>,
+    mut cards: Query<&mut IdCard>,
+) {
+    for building in building_action.list.iter() {
+        let building_action_id;
+        match action_requests.list.get(&building.incremented_i) {
+            Some(action_request) => {
+                building_action_id = action_request.get_id().clone();
+            }
+            None => {
+                continue;
+            }
+        }
+        for action_data in building.actions.iter() {
+            if !action_data.is_approved() || action_data.data.id != building_action_id {
+                continue;
+            }
+            let card_entity;
+            match building.action_taker_item {
+                Some(e) => {
+                    card_entity = e;
+                }
+                None => {
+                    continue;
+                }
+            }
+            let mut card;
+            match cards.get_mut(card_entity) {
+                Ok(c) => {
+                    card = c;
+                }
+                Err(_) => {
+                    warn!("Couldnt find ID card.");
+                    continue;
+                }
+            }
+            match action_data.data.id.strip_prefix(GRANT_PREFIX) {
+                Some(level) => {
+                    if !card.access.iter().any(|l| l == level) {
+                        card.access.push(level.to_string());
+                    }
+                }
+                None => {}
+            }
+            match action_data.data.id.strip_prefix(REVOKE_PREFIX) {
+                Some(level) => {
+                    card.access.retain(|l| l != level);
+                }
+                None => {}
+            }
+        }
+    }
+}
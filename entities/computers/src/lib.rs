@@ -2,7 +2,11 @@
 
 /// Manage computer events.
 pub mod computer;
+/// ID console actions.
+pub mod id_console;
 /// The Bevy plugin of this crate.
 pub mod plugin;
 /// Computer spawner.
 pub mod spawn;
+/// Server-driven terminal programs.
+pub mod terminal;
@@ -1,18 +1,24 @@
 use bevy::prelude::{App, IntoSystemConfig, Plugin};
 use combat::sfx::health_combat_hit_result_sfx;
-use entity::entity_types::register_entity_type;
+use entity::entity_types::{register_entity_type, register_grid_entity_type};
 use entity::spawn::build_base_entities;
 use physics::spawn::build_rigid_bodies;
 use resources::{
     is_server::is_server,
-    labels::{BuildingLabels, CombatLabels},
+    labels::{ActionsLabels, BuildingLabels, CombatLabels, UpdateLabels},
 };
 
 use crate::computer::Computer;
 
 use super::{
     computer::computer_added,
+    id_console::{build_actions, id_console_actions, id_console_prequisite_check},
     spawn::{build_computers, ComputerType},
+    terminal::{
+        build_terminal_actions, terminal_actions, terminal_airlock_control_input,
+        terminal_announcement_input, terminal_input, terminal_prequisite_check,
+        AirlockControlListings, AnnouncementCooldowns,
+    },
 };
 
 pub struct ComputersPlugin;
@@ -20,11 +26,51 @@ pub struct ComputersPlugin;
 impl Plugin for ComputersPlugin {
     fn build(&self, app: &mut App) {
         if is_server() {
-            app.add_system(computer_added).add_system(
-                health_combat_hit_result_sfx::<Computer>.after(CombatLabels::FinalizeApplyDamage),
-            );
+            app.add_system(computer_added)
+                .add_system(
+                    health_combat_hit_result_sfx::<Computer>
+                        .after(CombatLabels::FinalizeApplyDamage),
+                )
+                .add_system(
+                    build_actions
+                        .in_set(ActionsLabels::Build)
+                        .after(ActionsLabels::Init),
+                )
+                .add_system(
+                    id_console_prequisite_check
+                        .in_set(ActionsLabels::Approve)
+                        .after(ActionsLabels::Build),
+                )
+                .add_system(
+                    id_console_actions
+                        .in_set(ActionsLabels::Action)
+                        .after(ActionsLabels::Approve),
+                )
+                .add_system(
+                    build_terminal_actions
+                        .in_set(ActionsLabels::Build)
+                        .after(ActionsLabels::Init),
+                )
+                .add_system(
+                    terminal_prequisite_check
+                        .in_set(ActionsLabels::Approve)
+                        .after(ActionsLabels::Build),
+                )
+                .add_system(
+                    terminal_actions
+                        .in_set(ActionsLabels::Action)
+                        .after(ActionsLabels::Approve),
+                )
+                .add_system(terminal_input.in_set(UpdateLabels::TextTreeInputSelection))
+                .add_system(
+                    terminal_airlock_control_input.in_set(UpdateLabels::TextTreeInputSelection),
+                )
+                .add_system(terminal_announcement_input)
+                .init_resource::<AnnouncementCooldowns>()
+                .init_resource::<AirlockControlListings>();
         }
         register_entity_type::<ComputerType>(app);
+        register_grid_entity_type::<ComputerType>(app);
         app.add_system(build_computers::<ComputerType>.after(BuildingLabels::TriggerBuild))
             .add_system((build_base_entities::<ComputerType>).after(BuildingLabels::TriggerBuild))
             .add_system((build_rigid_bodies::<ComputerType>).after(BuildingLabels::TriggerBuild));
@@ -4,6 +4,7 @@ use bevy::{
 };
 use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Friction};
 use entity::{
+    entity_data::{GridEntity, GridItemData},
     entity_macros::Identity,
     entity_types::EntityType,
     examine::{Examinable, RichName},
@@ -79,16 +80,35 @@ impl Default for ComputerType {
     }
 }
 
+impl GridEntity for ComputerType {
+    fn get_grid_item_data() -> GridItemData {
+        GridItemData {
+            transform_offset: Transform::IDENTITY,
+            can_be_built_with_grid_item: vec![],
+        }
+    }
+}
+
 pub fn build_computers<T: Send + Sync + 'static>(
     mut commands: Commands,
     mut spawn_events: EventReader<SpawnEntity<T>>,
 ) {
     for spawn_event in spawn_events.iter() {
-        commands
-            .entity(spawn_event.spawn_data.entity)
-            .insert(Computer);
+        commands.entity(spawn_event.spawn_data.entity).insert((
+            Computer,
+            IdConsole,
+            Terminal,
+            PowerConsumer {
+                load: COMPUTER_POWER_LOAD,
+                ..Default::default()
+            },
+        ));
     }
 }
+use power::power::PowerConsumer;
 use resources::content::SF_CONTENT_PREFIX;
 
-use super::computer::Computer;
+/// Power drawn by computers in watts.
+pub const COMPUTER_POWER_LOAD: f32 = 200.;
+
+use super::{computer::Computer, id_console::IdConsole, terminal::Terminal};
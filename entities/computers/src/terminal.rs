@@ -0,0 +1,674 @@
+use actions::core::{Action, ActionData, ActionRequests, BuildingActions};
+use airlocks::{
+    airlock_events::{AirlockLockClosed, AirlockUnlock, LockedStatus},
+    resources::Airlock,
+};
+use asana::announcements::AsanaAnnouncement;
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{
+        warn, Component, Entity, EventReader, EventWriter, Query, Res, ResMut, Resource, Transform,
+    },
+    time::Time,
+};
+use entity::examine::Examinable;
+use gridmap::destruction::HullBreaches;
+use inventory::server::{access::worn_card_access, inventory::Inventory};
+use networking::server::{HandleToEntity, OutgoingReliableServerMessage};
+use pawn::{
+    access::{AccessConfig, IdCard},
+    pawn::{DataLink, DataLinkType, Pawn, REACH_DISTANCE},
+};
+use player::{
+    account::Accounts,
+    moderation::{MuteScope, Mutes},
+};
+use power::{
+    network::PowerNetworks,
+    power::{is_powered, PowerConsumer},
+};
+use resources::math::world_to_cell_id;
+use ui::{
+    net::{TextInputRequest, TextTreeSelection, UiServerMessage},
+    text_input::{TextInputSubmission, TextTreeInputSelection},
+};
+
+/// Computers that run server-driven terminal programs.
+#[derive(Component)]
+
+pub struct Terminal;
+
+pub const TERMINAL_ACTION_ID: &str = "actions::computers/terminal";
+
+pub const TERMINAL_MENU_ID: &str = "ui::computers/terminal";
+pub const CREW_MANIFEST_ID: &str = "ui::computers/crewmanifest";
+pub const ANNOUNCEMENT_INPUT_ID: &str = "ui::computers/announcement";
+pub const AIRLOCK_CONTROL_ID: &str = "ui::computers/airlockcontrol";
+pub const SHIP_STATUS_ID: &str = "ui::computers/shipstatus";
+
+const PROGRAM_CREW_MANIFEST: &str = "Crew Manifest";
+const PROGRAM_ANNOUNCEMENT: &str = "Ship-wide Announcement";
+const PROGRAM_AIRLOCK_CONTROL: &str = "Airlock Control";
+const PROGRAM_SHIP_STATUS: &str = "Ship Status";
+const BACK_ENTRY: &str = "Back";
+
+/// Maximum length of terminal announcements in characters.
+pub const MAX_ANNOUNCEMENT_LENGTH: usize = 200;
+/// Seconds an account has to wait between terminal announcements.
+pub const ANNOUNCEMENT_COOLDOWN: f64 = 30.;
+
+/// Resource with the time of the last terminal announcement of each account.
+#[derive(Resource, Default)]
+
+pub struct AnnouncementCooldowns {
+    pub last: HashMap<String, f64>,
+}
+
+/// Resource with the airlocks of the airlock control listing last sent to each client, in listed order.
+#[derive(Resource, Default)]
+
+pub struct AirlockControlListings {
+    pub listings: HashMap<u64, Vec<Entity>>,
+}
+
+/// Build the use terminal action.
+
+pub(crate) fn build_terminal_actions(
+    mut building_action_data: ResMut<BuildingActions>,
+    terminals: Query<&Terminal>,
+) {
+    for building_action in building_action_data.list.iter_mut() {
+        match building_action.target_entity_option {
+            Some(examined_entity) => {
+                if terminals.get(examined_entity).is_err() {
+                    continue;
+                }
+            }
+            None => {
+                continue;
+            }
+        }
+        building_action.actions.push(ActionData {
+            data: Action {
+                id: TERMINAL_ACTION_ID.to_string(),
+                text: "Use Terminal".to_string(),
+                tab_list_priority: 100,
+            },
+            approved: None,
+        });
+    }
+}
+
+/// Whether a pawn can operate a terminal. Terminals have to be powered and within reach.
+
+fn can_use_terminal(
+    user: Entity,
+    terminal: Entity,
+    transforms: &Query<&Transform>,
+    consumers: &Query<&PowerConsumer>,
+) -> bool {
+    if !is_powered(terminal, consumers) {
+        return false;
+    }
+    match (transforms.get(user), transforms.get(terminal)) {
+        (Ok(user_transform), Ok(terminal_transform)) => {
+            user_transform
+                .translation
+                .distance(terminal_transform.translation)
+                < REACH_DISTANCE
+        }
+        _ => false,
+    }
+}
+
+/// Prerequisite check of using a terminal.
+
+pub(crate) fn terminal_prequisite_check(
+    mut building_action_data: ResMut<BuildingActions>,
+    transforms: Query<&Transform>,
+    consumers: Query<&PowerConsumer>,
+) {
+    for building in building_action_data.list.iter_mut() {
+        for action in building.actions.iter_mut() {
+            if action.data.id != TERMINAL_ACTION_ID {
+                continue;
+            }
+            match building.target_entity_option {
+                Some(terminal) => {
+                    match can_use_terminal(building.action_taker, terminal, &transforms, &consumers)
+                    {
+                        true => {
+                            action.approve();
+                        }
+                        false => {
+                            action.do_not_approve();
+                        }
+                    }
+                }
+                None => {
+                    action.do_not_approve();
+                }
+            }
+        }
+    }
+}
+
+fn main_menu(terminal: Entity) -> TextTreeSelection {
+    TextTreeSelection {
+        entity: terminal,
+        id: TERMINAL_MENU_ID.to_string(),
+        entries: vec![
+            PROGRAM_CREW_MANIFEST.to_string(),
+            PROGRAM_ANNOUNCEMENT.to_string(),
+            PROGRAM_AIRLOCK_CONTROL.to_string(),
+            PROGRAM_SHIP_STATUS.to_string(),
+        ],
+        text: "Terminal".to_string(),
+    }
+}
+
+/// Open the terminal main menu.
+
+pub(crate) fn terminal_actions(
+    building_action: Res<BuildingActions>,
+    action_requests: Res<ActionRequests>,
+    handle_to_entity: Res<HandleToEntity>,
+    mut net: EventWriter<OutgoingReliableServerMessage<UiServerMessage>>,
+) {
+    for building in building_action.list.iter() {
+        let building_action_id;
+        match action_requests.list.get(&building.incremented_i) {
+            Some(action_request) => {
+                building_action_id = action_request.get_id().clone();
+            }
+            None => {
+                continue;
+            }
+        }
+        for action_data in building.actions.iter() {
+            if !action_data.is_approved()
+                || action_data.data.id != TERMINAL_ACTION_ID
+                || action_data.data.id != building_action_id
+            {
+                continue;
+            }
+            let terminal;
+            match building.target_entity_option {
+                Some(t) => {
+                    terminal = t;
+                }
+                None => {
+                    continue;
+                }
+            }
+            match handle_to_entity.inv_map.get(&building.action_taker) {
+                Some(handle) => {
+                    net.send(OutgoingReliableServerMessage {
+                        handle: *handle,
+                        message: UiServerMessage::TextTreeSelection(main_menu(terminal)),
+                    });
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+fn crew_manifest(
+    terminal: Entity,
+    pawns: &Query<&Pawn>,
+    config: &AccessConfig,
+) -> TextTreeSelection {
+    let mut crew = vec![];
+    for pawn in pawns.iter() {
+        let job_name = match config.job(&pawn.job) {
+            Some(job) => job.name.clone(),
+            None => pawn.job.clone(),
+        };
+        crew.push((job_name, pawn.character_name.clone()));
+    }
+    crew.sort();
+    let mut entries: Vec<String> = crew
+        .into_iter()
+        .map(|(job_name, name)| format!("{} - {}", name, job_name))
+        .collect();
+    entries.push(BACK_ENTRY.to_string());
+    TextTreeSelection {
+        entity: terminal,
+        id: CREW_MANIFEST_ID.to_string(),
+        entries,
+        text: "Crew Manifest".to_string(),
+    }
+}
+
+fn ship_status(
+    terminal: Entity,
+    networks: &PowerNetworks,
+    breaches: &HullBreaches,
+) -> TextTreeSelection {
+    let mut entries = vec![];
+    let powered = networks.networks.iter().filter(|n| n.powered).count();
+    entries.push(format!(
+        "Power networks: {} ({} powered)",
+        networks.networks.len(),
+        powered
+    ));
+    let mut i = 1;
+    for network in networks.networks.iter() {
+        let charge = match network.capacity > 0. {
+            true => format!("{:.0}%", network.stored / network.capacity * 100.),
+            false => "no storage".to_string(),
+        };
+        entries.push(format!(
+            "Network {}: {:.1} kW supply, {:.1} kW load, {}, {}",
+            i,
+            network.supply / 1000.,
+            network.load / 1000.,
+            charge,
+            match network.powered {
+                true => "powered",
+                false => "unpowered",
+            }
+        ));
+        i += 1;
+    }
+    entries.push(format!("Hull breaches: {}", breaches.cells.len()));
+    for breach in breaches.cells.iter() {
+        entries.push(format!("Breach at ({}, {})", breach.id.x, breach.id.z));
+    }
+    entries.push(BACK_ENTRY.to_string());
+    TextTreeSelection {
+        entity: terminal,
+        id: SHIP_STATUS_ID.to_string(),
+        entries,
+        text: "Ship Status".to_string(),
+    }
+}
+
+fn airlock_entry(name: &str, transform: &Transform, locked: bool) -> String {
+    let cell = world_to_cell_id(transform.translation);
+    format!(
+        "[{}] {} ({}, {})",
+        match locked {
+            true => "LOCKED",
+            false => "UNLOCKED",
+        },
+        name,
+        cell.x,
+        cell.z
+    )
+}
+
+fn is_locked(airlock: &Airlock) -> bool {
+    !matches!(airlock.locked_status, LockedStatus::None)
+}
+
+/// Listed position of a numbered airlock control entry.
+
+fn airlock_entry_index(entry: &str) -> Option<usize> {
+    match entry.split_once(". ") {
+        Some((number, _)) => match number.parse::<usize>() {
+            Ok(n) => n.checked_sub(1),
+            Err(_) => None,
+        },
+        None => None,
+    }
+}
+
+/// Airlock control listing with the listed airlocks in order. Entries are numbered so selections map back to their airlock.
+/// Toggled airlocks are listed with their new state as the toggle applies later this frame.
+
+fn airlock_control(
+    terminal: Entity,
+    airlocks: &Query<(Entity, &Airlock, &Examinable, &Transform)>,
+    toggled: Option<Entity>,
+) -> (TextTreeSelection, Vec<Entity>) {
+    let mut listed = vec![];
+    for (entity, airlock, examinable, transform) in airlocks.iter() {
+        let mut locked = is_locked(airlock);
+        if toggled == Some(entity) {
+            locked = !locked;
+        }
+        listed.push((
+            airlock_entry(&examinable.name.get_name(), transform, locked),
+            entity,
+        ));
+    }
+    listed.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut entries: Vec<String> = listed
+        .iter()
+        .enumerate()
+        .map(|(i, (text, _))| format!("{}. {}", i + 1, text))
+        .collect();
+    entries.push(BACK_ENTRY.to_string());
+    (
+        TextTreeSelection {
+            entity: terminal,
+            id: AIRLOCK_CONTROL_ID.to_string(),
+            entries,
+            text: "Airlock Control".to_string(),
+        },
+        listed.into_iter().map(|(_, entity)| entity).collect(),
+    )
+}
+
+fn notice(terminal: Entity, text: &str) -> TextTreeSelection {
+    TextTreeSelection {
+        entity: terminal,
+        id: TERMINAL_MENU_ID.to_string(),
+        entries: vec![BACK_ENTRY.to_string()],
+        text: text.to_string(),
+    }
+}
+
+/// Run terminal programs selected by clients.
+
+pub(crate) fn terminal_input(
+    mut input_events: EventReader<TextTreeInputSelection>,
+    handle_to_entity: Res<HandleToEntity>,
+    terminals: Query<&Terminal>,
+    transforms: Query<&Transform>,
+    consumers: Query<&PowerConsumer>,
+    data_links: Query<&DataLink>,
+    pawns: Query<&Pawn>,
+    inventories: Query<&Inventory>,
+    cards: Query<&IdCard>,
+    config: Res<AccessConfig>,
+    networks: Res<PowerNetworks>,
+    breaches: Res<HullBreaches>,
+    airlocks: Query<(Entity, &Airlock, &Examinable, &Transform)>,
+    mut listings: ResMut<AirlockControlListings>,
+    mut net: EventWriter<OutgoingReliableServerMessage<UiServerMessage>>,
+) {
+    for event in input_events.iter() {
+        if event.id != TERMINAL_MENU_ID
+            && event.id != CREW_MANIFEST_ID
+            && event.id != AIRLOCK_CONTROL_ID
+            && event.id != SHIP_STATUS_ID
+        {
+            continue;
+        }
+        if event.id == AIRLOCK_CONTROL_ID && event.entry != BACK_ENTRY {
+            // Handled by terminal_airlock_control_input.
+            continue;
+        }
+        if terminals.get(event.entity).is_err() {
+            warn!("Terminal input for non-terminal {:?}.", event.entity);
+            continue;
+        }
+        let user;
+        match handle_to_entity.map.get(&event.handle) {
+            Some(e) => {
+                user = *e;
+            }
+            None => {
+                continue;
+            }
+        }
+        if !can_use_terminal(user, event.entity, &transforms, &consumers) {
+            continue;
+        }
+
+        let screen;
+        if event.entry == BACK_ENTRY {
+            screen = UiServerMessage::TextTreeSelection(main_menu(event.entity));
+        } else if event.id == TERMINAL_MENU_ID {
+            match event.entry.as_str() {
+                PROGRAM_CREW_MANIFEST => {
+                    screen = UiServerMessage::TextTreeSelection(crew_manifest(
+                        event.entity,
+                        &pawns,
+                        &config,
+                    ));
+                }
+                PROGRAM_ANNOUNCEMENT => {
+                    match worn_card_access(user, &inventories, &cards)
+                        .contains(&config.announcement_access)
+                    {
+                        true => {
+                            screen = UiServerMessage::UIRequestInput(TextInputRequest {
+                                entity: event.entity,
+                                id: ANNOUNCEMENT_INPUT_ID.to_string(),
+                                text: "Ship-wide Announcement".to_string(),
+                            });
+                        }
+                        false => {
+                            screen = UiServerMessage::TextTreeSelection(notice(
+                                event.entity,
+                                "Insufficient access for announcements.",
+                            ));
+                        }
+                    }
+                }
+                PROGRAM_AIRLOCK_CONTROL => {
+                    let has_remote_lock = match data_links.get(user) {
+                        Ok(data_link) => data_link.links.contains(&DataLinkType::RemoteLock),
+                        Err(_) => false,
+                    };
+                    match has_remote_lock {
+                        true => {
+                            let (selection, listed) =
+                                airlock_control(event.entity, &airlocks, None);
+                            listings.listings.insert(event.handle, listed);
+                            screen = UiServerMessage::TextTreeSelection(selection);
+                        }
+                        false => {
+                            screen = UiServerMessage::TextTreeSelection(notice(
+                                event.entity,
+                                "Remote lock data link required.",
+                            ));
+                        }
+                    }
+                }
+                PROGRAM_SHIP_STATUS => {
+                    screen = UiServerMessage::TextTreeSelection(ship_status(
+                        event.entity,
+                        &networks,
+                        &breaches,
+                    ));
+                }
+                _ => {
+                    continue;
+                }
+            }
+        } else {
+            // Readouts only offer going back.
+            continue;
+        }
+        net.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message: screen,
+        });
+    }
+}
+
+/// Toggle the lock of airlocks selected in the airlock control program.
+
+pub(crate) fn terminal_airlock_control_input(
+    mut input_events: EventReader<TextTreeInputSelection>,
+    handle_to_entity: Res<HandleToEntity>,
+    terminals: Query<&Terminal>,
+    transforms: Query<&Transform>,
+    consumers: Query<&PowerConsumer>,
+    data_links: Query<&DataLink>,
+    airlocks: Query<(Entity, &Airlock, &Examinable, &Transform)>,
+    mut listings: ResMut<AirlockControlListings>,
+    mut net: EventWriter<OutgoingReliableServerMessage<UiServerMessage>>,
+    mut lock_events: EventWriter<AirlockLockClosed>,
+    mut unlock_events: EventWriter<AirlockUnlock>,
+) {
+    for event in input_events.iter() {
+        if event.id != AIRLOCK_CONTROL_ID || event.entry == BACK_ENTRY {
+            continue;
+        }
+        if terminals.get(event.entity).is_err() {
+            warn!("Terminal input for non-terminal {:?}.", event.entity);
+            continue;
+        }
+        let user;
+        match handle_to_entity.map.get(&event.handle) {
+            Some(e) => {
+                user = *e;
+            }
+            None => {
+                continue;
+            }
+        }
+        if !can_use_terminal(user, event.entity, &transforms, &consumers) {
+            continue;
+        }
+        match data_links.get(user) {
+            Ok(data_link) => {
+                if !data_link.links.contains(&DataLinkType::RemoteLock) {
+                    continue;
+                }
+            }
+            Err(_) => {
+                continue;
+            }
+        }
+
+        let selected = match (
+            listings.listings.get(&event.handle),
+            airlock_entry_index(&event.entry),
+        ) {
+            (Some(listed), Some(index)) => listed.get(index).copied(),
+            _ => None,
+        };
+        let mut toggled = None;
+        match selected {
+            Some(selected_entity) => match airlocks.get(selected_entity) {
+                Ok((entity, airlock, _, _)) => {
+                    match is_locked(airlock) {
+                        true => {
+                            unlock_events.send(AirlockUnlock {
+                                handle_option: Some(event.handle),
+                                locked: entity,
+                                locker: user,
+                            });
+                        }
+                        false => {
+                            lock_events.send(AirlockLockClosed {
+                                handle_option: Some(event.handle),
+                                locked: entity,
+                                locker: user,
+                            });
+                        }
+                    }
+                    toggled = Some(entity);
+                }
+                Err(_) => {
+                    warn!("Couldnt find selected airlock {:?}.", selected_entity);
+                }
+            },
+            None => {
+                warn!("Couldnt find airlock control selection {}.", event.entry);
+            }
+        }
+        let (selection, listed) = airlock_control(event.entity, &airlocks, toggled);
+        listings.listings.insert(event.handle, listed);
+        net.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message: UiServerMessage::TextTreeSelection(selection),
+        });
+    }
+}
+
+/// Announce text submitted to the announcement program through ASANA.
+
+pub(crate) fn terminal_announcement_input(
+    mut input_events: EventReader<TextInputSubmission>,
+    handle_to_entity: Res<HandleToEntity>,
+    terminals: Query<&Terminal>,
+    transforms: Query<&Transform>,
+    consumers: Query<&PowerConsumer>,
+    pawns: Query<&Pawn>,
+    inventories: Query<&Inventory>,
+    cards: Query<&IdCard>,
+    config: Res<AccessConfig>,
+    accounts: Res<Accounts>,
+    mutes: Res<Mutes>,
+    mut cooldowns: ResMut<AnnouncementCooldowns>,
+    time: Res<Time>,
+    mut announcements: EventWriter<AsanaAnnouncement>,
+    mut net: EventWriter<OutgoingReliableServerMessage<UiServerMessage>>,
+) {
+    for event in input_events.iter() {
+        if event.id != ANNOUNCEMENT_INPUT_ID {
+            continue;
+        }
+        if terminals.get(event.entity).is_err() {
+            warn!("Terminal input for non-terminal {:?}.", event.entity);
+            continue;
+        }
+        let user;
+        match handle_to_entity.map.get(&event.handle) {
+            Some(e) => {
+                user = *e;
+            }
+            None => {
+                continue;
+            }
+        }
+        if !can_use_terminal(user, event.entity, &transforms, &consumers) {
+            continue;
+        }
+        if !worn_card_access(user, &inventories, &cards).contains(&config.announcement_access) {
+            continue;
+        }
+        let account_name;
+        match accounts.list.get(&event.handle) {
+            Some(a) => {
+                account_name = a;
+            }
+            None => {
+                warn!("Couldnt find account of announcer.");
+                continue;
+            }
+        }
+        if mutes.is_muted(account_name, MuteScope::AllChat) {
+            continue;
+        }
+        let now = time.elapsed_seconds_f64();
+        match cooldowns.last.get(account_name) {
+            Some(last) => {
+                if now - last < ANNOUNCEMENT_COOLDOWN {
+                    net.send(OutgoingReliableServerMessage {
+                        handle: event.handle,
+                        message: UiServerMessage::TextTreeSelection(notice(
+                            event.entity,
+                            "Announcement system is cooling down.",
+                        )),
+                    });
+                    continue;
+                }
+            }
+            None => {}
+        }
+        let message: String = event
+            .input
+            .trim()
+            .chars()
+            .take(MAX_ANNOUNCEMENT_LENGTH)
+            .collect();
+        if message.is_empty() {
+            continue;
+        }
+        let announcer = match pawns.get(user) {
+            Ok(pawn) => match config.job(&pawn.job) {
+                Some(job) => format!("{} ({})", pawn.character_name, job.name),
+                None => pawn.character_name.clone(),
+            },
+            Err(_) => "Unknown".to_string(),
+        };
+        cooldowns.last.insert(account_name.clone(), now);
+        announcements.send(AsanaAnnouncement {
+            message: format!("Announcement by {}: {}", announcer, message),
+        });
+        net.send(OutgoingReliableServerMessage {
+            handle: event.handle,
+            message: UiServerMessage::TextTreeSelection(main_menu(event.entity)),
+        });
+    }
+}
@@ -1,6 +1,10 @@
 use actions::core::{Action, ActionData, ActionRequests, BuildingActions};
 use bevy::prelude::{warn, EventReader, EventWriter, Query, Res, ResMut, With};
-use gridmap::{grid::Gridmap, net::GridmapServerMessage};
+use entity::entity_data::GridEntityTypes;
+use gridmap::{
+    grid::{Gridmap, GridmapLayer},
+    net::GridmapServerMessage,
+};
 use inventory::item::InventoryItem;
 
 use crate::construction_tool::{ConstructionTool, InputConstructionOptions, InputDeconstruct};
@@ -103,7 +107,11 @@ pub fn open_input_construction_options_ui(
     mut events: EventReader<InputConstructionOptions>,
     mut net: EventWriter<OutgoingReliableServerMessage<UiServerMessage>>,
     gridmap: Res<Gridmap>,
+    grid_entity_types: Res<GridEntityTypes>,
 ) {
+    let mut entity_types: Vec<String> = grid_entity_types.map.keys().cloned().collect();
+    entity_types.sort();
+
     for event in events.iter() {
         match event.handle_option {
             Some(handle) => {
@@ -112,7 +120,13 @@ pub fn open_input_construction_options_ui(
                     message: UiServerMessage::TextTreeSelection(TextTreeSelection {
                         entity: event.entity,
                         id: CONSTRUCTION_OPTIONS_TEXT_LIST_ID.to_string(),
-                        entries: gridmap.ordered_main_names.clone(),
+                        entries: gridmap
+                            .ordered_main_names
+                            .iter()
+                            .chain(gridmap.ordered_details1_names.iter())
+                            .chain(entity_types.iter())
+                            .cloned()
+                            .collect(),
                         text: "Select Construction".to_string(),
                     }),
                 });
@@ -260,23 +274,52 @@ pub(crate) fn construction_tool_select_construction_option(
     mut query: Query<&mut ConstructionTool>,
     mut net: EventWriter<OutgoingReliableServerMessage<GridmapServerMessage>>,
     gridmap: Res<Gridmap>,
+    grid_entity_types: Res<GridEntityTypes>,
 ) {
     for event in input_events.iter() {
         if event.id == CONSTRUCTION_OPTIONS_TEXT_LIST_ID {
             match query.get_mut(event.entity) {
-                Ok(mut c) => match gridmap.main_name_id_map.get(&event.entry) {
-                    Some(type_id) => {
-                        c.construction_option = Some(*type_id);
+                Ok(mut c) => {
+                    if grid_entity_types.map.contains_key(&event.entry) {
+                        c.construction_option = None;
+                        c.construction_entity_option = Some(event.entry.clone());
 
                         net.send(OutgoingReliableServerMessage {
                             handle: event.handle,
-                            message: GridmapServerMessage::GhostCellType(*type_id),
+                            message: GridmapServerMessage::GhostEntityType(event.entry.clone()),
                         });
+                        continue;
                     }
-                    None => {
-                        warn!("couldnt find tile id.");
+                    match gridmap.main_name_id_map.get(&event.entry) {
+                        Some(type_id) => {
+                            c.construction_option = Some(*type_id);
+                            c.construction_layer = GridmapLayer::Main;
+                            c.construction_entity_option = None;
+
+                            net.send(OutgoingReliableServerMessage {
+                                handle: event.handle,
+                                message: GridmapServerMessage::GhostCellType(*type_id),
+                            });
+                            continue;
+                        }
+                        None => {}
                     }
-                },
+                    match gridmap.details1_name_id_map.get(&event.entry) {
+                        Some(type_id) => {
+                            c.construction_option = Some(*type_id);
+                            c.construction_layer = GridmapLayer::Details1;
+                            c.construction_entity_option = None;
+
+                            net.send(OutgoingReliableServerMessage {
+                                handle: event.handle,
+                                message: GridmapServerMessage::GhostDetails1CellType(*type_id),
+                            });
+                        }
+                        None => {
+                            warn!("couldnt find tile id.");
+                        }
+                    }
+                }
                 Err(_) => {
                     warn!("Couldnt find construction tool {:?}.", event.entity);
                 }
@@ -284,3 +327,29 @@ pub(crate) fn construction_tool_select_construction_option(
         }
     }
 }
+
+use actions::timed::{TimedActionSettings, TimedActions};
+use pawn::pawn::REACH_DISTANCE;
+
+/// Make constructing and deconstructing take time.
+
+pub(crate) fn register_construction_timed_actions(mut timed_actions: ResMut<TimedActions>) {
+    timed_actions.map.insert(
+        CONSTRUCTION_ACTION_ID.to_string(),
+        TimedActionSettings {
+            duration: 1.5,
+            max_distance_option: Some(REACH_DISTANCE),
+            interrupt_on_damage: true,
+            interrupt_on_item_switch: true,
+        },
+    );
+    timed_actions.map.insert(
+        DECONSTRUCTION_ACTION_ID.to_string(),
+        TimedActionSettings {
+            duration: 3.,
+            max_distance_option: Some(REACH_DISTANCE),
+            interrupt_on_damage: true,
+            interrupt_on_item_switch: true,
+        },
+    );
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::{Component, Entity};
+use gridmap::grid::GridmapLayer;
 use resources::grid::TargetCell;
 
 /// The component.
@@ -7,6 +8,10 @@ use resources::grid::TargetCell;
 pub struct ConstructionTool {
     /// Currently selected construction cell option.
     pub construction_option: Option<u16>,
+    /// Gridmap layer of the selected construction cell option.
+    pub construction_layer: GridmapLayer,
+    /// Currently selected entity type to place instead of a cell.
+    pub construction_entity_option: Option<String>,
 }
 
 /// Player requested input event.
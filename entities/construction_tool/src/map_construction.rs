@@ -1,12 +1,26 @@
-use bevy::prelude::{warn, Commands, EventReader, EventWriter, Query, Res};
-use entity::spawn::ClientEntityServerEntity;
+use bevy::prelude::{
+    warn, Commands, Entity, EventReader, EventWriter, Query, Res, ResMut, Transform, Vec3, With,
+};
+use entity::{
+    entity_data::{DefaultMapEntity, EntityData, GridEntityTypes, RawSpawnEvent},
+    entity_types::EntityType,
+    init::RawEntityRon,
+    spawn::ClientEntityServerEntity,
+    spawning_events::DespawnClientEntity,
+};
 use gridmap::{
-    construction::{GridmapConstructionState, ShowYLevelPlane},
-    grid::{AddTile, RemoveTile},
+    construction::{grid_entity_transform, GridmapConstructionState, ShowYLevelPlane},
+    details1::{AddDetails1Tile, RemoveDetails1Tile},
+    editor::{
+        copy_region, fill_edits, flood_fill_edits, paste_edits, ApplyMapEdits, MapEditorHistories,
+        RedoMapEdit, UndoMapEdit,
+    },
+    grid::{AddTile, Gridmap, GridmapLayer, RemoveTile},
     net::GridmapClientMessage,
 };
 use inventory::server::inventory::Inventory;
 use networking::server::{HandleToEntity, IncomingReliableClientMessage};
+use resources::math::{cell_id_to_world, Vec3Int};
 
 use crate::construction_tool::ConstructionTool;
 pub(crate) fn construction_tool_enable_select_cell_in_front_camera(
@@ -52,6 +66,8 @@ pub(crate) fn mouse_click_input(
     construction_tool_query: Query<&ConstructionTool>,
     mut add_events: EventWriter<AddTile>,
     mut remove_events: EventWriter<RemoveTile>,
+    mut add_details1_events: EventWriter<AddDetails1Tile>,
+    mut remove_details1_events: EventWriter<RemoveDetails1Tile>,
     mut commands: Commands,
 ) {
     for message in net.iter() {
@@ -106,22 +122,302 @@ pub(crate) fn mouse_click_input(
                     }
                 }
 
-                add_events.send(AddTile {
-                    id: construct.cell.id,
-                    tile_type: type_id,
-                    orientation: construct.orientation,
-                    face: construct.cell.face.clone(),
-                    group_instance_id_option: None,
-                    entity: commands.spawn(()).id(),
-                    default_map_spawn: false,
-                });
+                match construction_tool_component.construction_layer {
+                    GridmapLayer::Main => {
+                        add_events.send(AddTile {
+                            id: construct.cell.id,
+                            tile_type: type_id,
+                            orientation: construct.orientation,
+                            face: construct.cell.face.clone(),
+                            group_instance_id_option: None,
+                            entity: commands.spawn(()).id(),
+                            default_map_spawn: false,
+                        });
+                    }
+                    GridmapLayer::Details1 => {
+                        add_details1_events.send(AddDetails1Tile {
+                            id: construct.cell.id,
+                            tile_type: type_id,
+                            orientation: construct.orientation,
+                            entity: commands.spawn(()).id(),
+                            default_map_spawn: false,
+                        });
+                    }
+                }
             }
             GridmapClientMessage::DeconstructCell(deconstruct) => {
-                remove_events.send(RemoveTile {
-                    cell: deconstruct.cell.clone(),
-                });
+                match construction_tool_component.construction_layer {
+                    GridmapLayer::Main => {
+                        remove_events.send(RemoveTile {
+                            cell: deconstruct.cell.clone(),
+                        });
+                    }
+                    GridmapLayer::Details1 => {
+                        remove_details1_events.send(RemoveDetails1Tile {
+                            id: deconstruct.cell.id,
+                        });
+                    }
+                }
             }
             _ => (),
         }
     }
 }
+
+/// Handle map editor requests of players holding a construction tool.
+
+pub(crate) fn map_editor_input(
+    mut net: EventReader<IncomingReliableClientMessage<GridmapClientMessage>>,
+    inventory_query: Query<&Inventory>,
+    handle_to_entity: Res<HandleToEntity>,
+    construction_tool_query: Query<&ConstructionTool>,
+    gridmap: Res<Gridmap>,
+    mut histories: ResMut<MapEditorHistories>,
+    mut apply: EventWriter<ApplyMapEdits>,
+    mut undo: EventWriter<UndoMapEdit>,
+    mut redo: EventWriter<RedoMapEdit>,
+) {
+    for message in net.iter() {
+        match &message.message {
+            GridmapClientMessage::EditorFill(_)
+            | GridmapClientMessage::EditorFloodFill(_)
+            | GridmapClientMessage::EditorCopy(_)
+            | GridmapClientMessage::EditorPaste(_)
+            | GridmapClientMessage::EditorUndo
+            | GridmapClientMessage::EditorRedo => {}
+            _ => {
+                continue;
+            }
+        }
+
+        let client_entity;
+        match handle_to_entity.map.get(&message.handle) {
+            Some(entity) => {
+                client_entity = *entity;
+            }
+            None => {
+                warn!("Couldnt get entity from map.");
+                continue;
+            }
+        }
+
+        let construction_tool_component;
+        match inventory_query.get(client_entity) {
+            Ok(inventory) => match inventory.active_item {
+                Some(active_item_entity) => match construction_tool_query.get(active_item_entity) {
+                    Ok(component) => {
+                        construction_tool_component = component;
+                    }
+                    Err(_) => {
+                        continue;
+                    }
+                },
+                None => {
+                    continue;
+                }
+            },
+            Err(_) => {
+                continue;
+            }
+        }
+
+        let main_tile_type = match construction_tool_component.construction_layer {
+            GridmapLayer::Main => construction_tool_component.construction_option,
+            GridmapLayer::Details1 => None,
+        };
+
+        let edits_result;
+        match &message.message {
+            GridmapClientMessage::EditorFill(fill) => match (fill.remove, main_tile_type) {
+                (true, _) => {
+                    edits_result = fill_edits(&gridmap, fill, 0);
+                }
+                (false, Some(tile_type)) => {
+                    edits_result = fill_edits(&gridmap, fill, tile_type);
+                }
+                (false, None) => {
+                    warn!("Map editor fills require a main layer construction option.");
+                    continue;
+                }
+            },
+            GridmapClientMessage::EditorFloodFill(fill) => match main_tile_type {
+                Some(tile_type) => {
+                    edits_result = flood_fill_edits(&gridmap, fill, tile_type);
+                }
+                None => {
+                    warn!("Map editor flood fills require a main layer construction option.");
+                    continue;
+                }
+            },
+            GridmapClientMessage::EditorCopy(copy) => {
+                match copy_region(&gridmap, copy.from, copy.to) {
+                    Ok(clipboard) => {
+                        histories.map.entry(message.handle).or_default().clipboard = clipboard;
+                    }
+                    Err(error) => {
+                        warn!("{}", error);
+                    }
+                }
+                continue;
+            }
+            GridmapClientMessage::EditorPaste(paste) => match histories.map.get(&message.handle) {
+                Some(history) => {
+                    edits_result = Ok(paste_edits(&gridmap, &history.clipboard, paste));
+                }
+                None => {
+                    continue;
+                }
+            },
+            GridmapClientMessage::EditorUndo => {
+                undo.send(UndoMapEdit {
+                    handle: message.handle,
+                });
+                continue;
+            }
+            GridmapClientMessage::EditorRedo => {
+                redo.send(RedoMapEdit {
+                    handle: message.handle,
+                });
+                continue;
+            }
+            _ => {
+                continue;
+            }
+        }
+
+        match edits_result {
+            Ok(edits) => {
+                apply.send(ApplyMapEdits {
+                    handle: message.handle,
+                    edits,
+                });
+            }
+            Err(error) => {
+                warn!("{}", error);
+            }
+        }
+    }
+}
+
+/// Map entities that were placed on a cell, found through the grid item data of their entity type.
+
+fn map_entities_on_cell(
+    cell: Vec3Int,
+    grid_entity_types: &GridEntityTypes,
+    map_entities: &Query<(Entity, &EntityData, &Transform), With<DefaultMapEntity>>,
+) -> Vec<Entity> {
+    let cell_position = cell_id_to_world(cell);
+    let mut entities = vec![];
+    for (entity, entity_data, transform) in map_entities.iter() {
+        match grid_entity_types
+            .map
+            .get(&entity_data.entity_type.get_identity())
+        {
+            Some(data) => {
+                let (origin, _) = data.placed_origin(transform);
+                if origin.distance(cell_position) < 0.5 {
+                    entities.push(entity);
+                }
+            }
+            None => {}
+        }
+    }
+    entities
+}
+
+/// Place and remove map entities for players holding a construction tool.
+
+pub(crate) fn map_entity_input(
+    mut net: EventReader<IncomingReliableClientMessage<GridmapClientMessage>>,
+    inventory_query: Query<&Inventory>,
+    handle_to_entity: Res<HandleToEntity>,
+    construction_tool_query: Query<&ConstructionTool>,
+    grid_entity_types: Res<GridEntityTypes>,
+    map_entities: Query<(Entity, &EntityData, &Transform), With<DefaultMapEntity>>,
+    mut spawn: EventWriter<RawSpawnEvent>,
+    mut despawn: EventWriter<DespawnClientEntity>,
+) {
+    for message in net.iter() {
+        match &message.message {
+            GridmapClientMessage::PlaceEntity(_) | GridmapClientMessage::RemoveEntity(_) => {}
+            _ => {
+                continue;
+            }
+        }
+
+        let client_entity;
+        match handle_to_entity.map.get(&message.handle) {
+            Some(entity) => {
+                client_entity = *entity;
+            }
+            None => {
+                warn!("Couldnt get entity from map.");
+                continue;
+            }
+        }
+
+        let construction_tool_component;
+        match inventory_query.get(client_entity) {
+            Ok(inventory) => match inventory.active_item {
+                Some(active_item_entity) => match construction_tool_query.get(active_item_entity) {
+                    Ok(component) => {
+                        construction_tool_component = component;
+                    }
+                    Err(_) => {
+                        continue;
+                    }
+                },
+                None => {
+                    continue;
+                }
+            },
+            Err(_) => {
+                continue;
+            }
+        }
+
+        match &message.message {
+            GridmapClientMessage::PlaceEntity(place) => {
+                let entity_type;
+                match &construction_tool_component.construction_entity_option {
+                    Some(t) => {
+                        entity_type = t;
+                    }
+                    None => {
+                        continue;
+                    }
+                }
+                let data;
+                match grid_entity_types.map.get(entity_type) {
+                    Some(d) => {
+                        data = d;
+                    }
+                    None => {
+                        warn!("Couldnt find grid entity type {}.", entity_type);
+                        continue;
+                    }
+                }
+                if !map_entities_on_cell(place.cell, &grid_entity_types, &map_entities).is_empty() {
+                    continue;
+                }
+                let transform = grid_entity_transform(data, place.cell, place.orientation);
+                spawn.send(RawSpawnEvent {
+                    raw_entity: RawEntityRon {
+                        entity_type: entity_type.clone(),
+                        translation: transform.translation,
+                        rotation: transform.rotation,
+                        scale: Vec3::ONE,
+                        data: "".to_string(),
+                    },
+                    entity_option: None,
+                });
+            }
+            GridmapClientMessage::RemoveEntity(cell) => {
+                for entity in map_entities_on_cell(*cell, &grid_entity_types, &map_entities) {
+                    despawn.send(DespawnClientEntity { entity });
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -24,10 +24,12 @@ use crate::action::{
     build_actions, construct_action_prequisite_check, construction_tool_actions,
     construction_tool_inventory_prequisite_check, construction_tool_select_construction_option,
     deconstruct_action_prequisite_check, open_input_construction_options_ui,
+    register_construction_timed_actions,
 };
 use crate::construction_tool::ConstructionTool;
 use crate::map_construction::{
-    construction_tool_enable_select_cell_in_front_camera, mouse_click_input,
+    construction_tool_enable_select_cell_in_front_camera, map_editor_input, map_entity_input,
+    mouse_click_input,
 };
 
 use super::{
@@ -87,7 +89,10 @@ impl Plugin for ConstructionToolAdminPlugin {
                         .in_set(UpdateLabels::TextTreeInputSelection),
                 )
                 .add_system(open_input_construction_options_ui)
-                .add_system(mouse_click_input);
+                .add_system(mouse_click_input)
+                .add_system(map_editor_input)
+                .add_system(map_entity_input)
+                .add_startup_system(register_construction_timed_actions);
         } else {
             app.add_system(
                 update_inventory_hud_add_item_to_slot::<ConstructionToolType>
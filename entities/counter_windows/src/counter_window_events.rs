@@ -8,9 +8,11 @@ use bevy::{
 };
 use bevy_rapier3d::prelude::{Collider, CollisionGroups, Group};
 use entity::{entity_data::EntityGroup, examine::Examinable};
+use inventory::server::{access::has_access, inventory::Inventory};
 use networking::server::NetworkingChatServerMessage;
-use pawn::pawn::{Pawn, ShipAuthorization, ShipAuthorizationEnum};
+use pawn::access::IdCard;
 use physics::physics::{get_bit_masks, ColliderGroup};
+use power::power::{is_powered, PowerConsumer};
 use sfx::{builder::sfx_builder, entity_update::SfxAutoDestroyTimers};
 use sounds::{
     counter_window::{
@@ -45,13 +47,15 @@ pub(crate) fn counter_window_events(
     )>,
     mut counter_window_colliders: Query<&mut CollisionGroups, With<Collider>>,
     counter_window_sensor_query: Query<&CounterWindowSensor>,
-    pawn_query: Query<(&Pawn, &ShipAuthorization)>,
+    inventory_query: Query<&Inventory>,
+    id_card_query: Query<&IdCard>,
     mut auto_destroy_timers: ResMut<SfxAutoDestroyTimers>,
     mut commands: Commands,
     mut counter_window_lock_open_events: EventReader<CounterWindowLockOpen>,
     mut counter_window_lock_close_events: EventReader<CounterWindowLockClosed>,
     mut unlock_events: EventReader<CounterWindowUnlock>,
     mut server: EventWriter<OutgoingReliableServerMessage<NetworkingChatServerMessage>>,
+    consumers: Query<&PowerConsumer>,
 ) {
     let mut close_requests = vec![];
     let mut open_requests = vec![];
@@ -345,6 +349,11 @@ pub(crate) fn counter_window_events(
             }
         }
 
+        // Unpowered counter windows can only be opened manually.
+        if !is_powered(counter_window_entity, &consumers) {
+            continue;
+        }
+
         open_requests.push(CounterWindowOpenRequest {
             opener_option: Some(pawn_entity),
             opened: counter_window_entity,
@@ -442,29 +451,15 @@ pub(crate) fn counter_window_events(
 
         match request.opener_option {
             Some(opener) => {
-                let pawn_space_access_component_result =
-                    pawn_query.get_component::<ShipAuthorization>(opener);
-                let pawn_space_access_component;
-
-                match pawn_space_access_component_result {
-                    Ok(result) => {
-                        pawn_space_access_component = result;
-                    }
-                    Err(_err) => {
-                        continue;
-                    }
-                }
-
-                for space_permission in &counter_window_component.access_permissions {
-                    if pawn_space_access_component
-                        .access
-                        .contains(space_permission)
-                        == true
-                    {
-                        pawn_has_permission = true;
-                        break;
-                    }
+                if inventory_query.get(opener).is_err() {
+                    continue;
                 }
+                pawn_has_permission = has_access(
+                    opener,
+                    &counter_window_component.access_permissions,
+                    &inventory_query,
+                    &id_card_query,
+                );
             }
             None => {
                 pawn_has_permission = true;
@@ -568,29 +563,15 @@ pub(crate) fn counter_window_events(
 
                 match request.interacter_option {
                     Some(interacter) => {
-                        let pawn_space_access_component_result =
-                            pawn_query.get_component::<ShipAuthorization>(interacter);
-                        let pawn_space_access_component;
-
-                        match pawn_space_access_component_result {
-                            Ok(result) => {
-                                pawn_space_access_component = result;
-                            }
-                            Err(_err) => {
-                                continue;
-                            }
-                        }
-
-                        for space_permission in &counter_window_component.access_permissions {
-                            if pawn_space_access_component
-                                .access
-                                .contains(space_permission)
-                                == true
-                            {
-                                pawn_has_permission = true;
-                                break;
-                            }
+                        if inventory_query.get(interacter).is_err() {
+                            continue;
                         }
+                        pawn_has_permission = has_access(
+                            interacter,
+                            &counter_window_component.access_permissions,
+                            &inventory_query,
+                            &id_card_query,
+                        );
                     }
                     None => {
                         pawn_has_permission = true;
@@ -634,7 +615,7 @@ pub struct CounterWindow {
     /// State of access lights.
     pub access_lights: CounterWindowAccessLightsStatus,
     /// Authorization required to interact.
-    pub access_permissions: Vec<ShipAuthorizationEnum>,
+    pub access_permissions: Vec<String>,
     /// Lock state of counter window.
     pub locked_status: LockedStatus,
 
@@ -659,7 +640,7 @@ impl Default for CounterWindow {
         Self {
             status: CounterWindowStatus::Closed,
             access_lights: CounterWindowAccessLightsStatus::Neutral,
-            access_permissions: vec![ShipAuthorizationEnum::Common],
+            access_permissions: vec!["common".to_string()],
             locked_status: LockedStatus::None,
             denied_timer: None,
             open_timer: None,
@@ -1,6 +1,6 @@
 use bevy::prelude::{App, CoreSet, IntoSystemConfig, Plugin};
 use combat::sfx::health_combat_hit_result_sfx;
-use entity::entity_types::register_entity_type;
+use entity::entity_types::{register_entity_type, register_grid_entity_type};
 use entity::spawn::build_base_entities;
 use physics::spawn::build_rigid_bodies;
 use resources::{
@@ -73,6 +73,7 @@ impl Plugin for CounterWindowsPlugin {
                 );
         }
         register_entity_type::<CounterWindowType>(app);
+        register_grid_entity_type::<CounterWindowType>(app);
         app.add_system(
             build_counter_windows::<CounterWindowType>.after(BuildingLabels::TriggerBuild),
         )
@@ -7,21 +7,24 @@ use bevy::{
 };
 use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Friction, Group};
 use entity::{
-    entity_data::{EntityData, EntityGroup},
+    entity_data::{EntityData, EntityGroup, GridEntity, GridItemData},
     entity_macros::Identity,
     entity_types::EntityType,
     examine::{Examinable, RichName},
     health::Health,
     spawn::{BaseEntityBuilder, BaseEntityBundle, EntityBuildData, NoData, SpawnEntity},
 };
-use pawn::pawn::ShipAuthorizationEnum;
 use physics::physics::{get_bit_masks, ColliderGroup};
 use physics::spawn::{RigidBodyBuilder, RigidBodyBundle};
+use power::power::PowerConsumer;
 use resources::content::SF_CONTENT_PREFIX;
 use text_api::core::{FURTHER_ITALIC_FONT, HEALTHY_COLOR};
 
 use super::counter_window_events::{CounterWindow, CounterWindowSensor};
 
+/// Power drawn by counter windows in watts.
+pub const COUNTER_WINDOW_POWER_LOAD: f32 = 150.;
+
 pub fn get_default_transform() -> Transform {
     Transform::IDENTITY
 }
@@ -116,6 +119,15 @@ impl Default for CounterWindowType {
     }
 }
 
+impl GridEntity for CounterWindowType {
+    fn get_grid_item_data() -> GridItemData {
+        GridItemData {
+            transform_offset: Transform::IDENTITY,
+            can_be_built_with_grid_item: vec![],
+        }
+    }
+}
+
 pub fn build_counter_windows<T: Send + Sync + 'static>(
     mut commands: Commands,
     mut spawn_events: EventReader<SpawnEntity<T>>,
@@ -123,12 +135,16 @@ pub fn build_counter_windows<T: Send + Sync + 'static>(
     use entity::entity_data::BlankEntityType;
 
     for spawn_event in spawn_events.iter() {
-        commands
-            .entity(spawn_event.spawn_data.entity)
-            .insert(CounterWindow {
-                access_permissions: vec![ShipAuthorizationEnum::Security],
+        commands.entity(spawn_event.spawn_data.entity).insert((
+            CounterWindow {
+                access_permissions: vec!["security".to_string()],
                 ..Default::default()
-            });
+            },
+            PowerConsumer {
+                load: COUNTER_WINDOW_POWER_LOAD,
+                ..Default::default()
+            },
+        ));
 
         let rigid_body = RigidBody::Fixed;
 
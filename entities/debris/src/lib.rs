@@ -0,0 +1,6 @@
+//! Metal debris left behind by destroyed walls and floors.
+
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Spawner.
+pub mod spawn;
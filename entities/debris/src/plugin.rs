@@ -0,0 +1,29 @@
+use basic_console_commands::register::{
+    register_basic_console_commands_for_inventory_item_type,
+    register_basic_console_commands_for_type,
+};
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use entity::{entity_types::register_entity_type, spawn::build_base_entities};
+use inventory::spawn_item::build_inventory_items;
+use physics::spawn::build_rigid_bodies;
+use resources::{is_server::is_server, labels::BuildingLabels};
+
+use super::spawn::{spawn_debris, MetalDebrisType};
+
+pub struct DebrisPlugin;
+
+impl Plugin for DebrisPlugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.add_system(spawn_debris);
+        }
+        register_entity_type::<MetalDebrisType>(app);
+        register_basic_console_commands_for_type::<MetalDebrisType>(app);
+        register_basic_console_commands_for_inventory_item_type::<MetalDebrisType>(app);
+        app.add_system(
+            (build_base_entities::<MetalDebrisType>).after(BuildingLabels::TriggerBuild),
+        )
+        .add_system((build_rigid_bodies::<MetalDebrisType>).after(BuildingLabels::TriggerBuild))
+        .add_system((build_inventory_items::<MetalDebrisType>).after(BuildingLabels::TriggerBuild));
+    }
+}
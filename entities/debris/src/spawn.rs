@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use bevy::math::Vec3;
+use bevy::prelude::Commands;
+use bevy::prelude::EventReader;
+use bevy::prelude::EventWriter;
+use bevy::prelude::ResMut;
+use bevy::prelude::Transform;
+use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Friction};
+use entity::entity_macros::Identity;
+use entity::entity_types::EntityType;
+use entity::examine::Examinable;
+use entity::examine::RichName;
+use entity::spawn::spawn_entity;
+use entity::spawn::BaseEntityBuilder;
+use entity::spawn::BaseEntityBundle;
+use entity::spawn::EntityBuildData;
+use entity::spawn::NoData;
+use entity::spawn::SpawnEntity;
+use gridmap::destruction::TileBroken;
+use inventory::item::InventoryItem;
+use inventory::server::inventory::SlotType;
+use inventory::spawn_item::InventoryItemBuilder;
+use inventory::spawn_item::InventoryItemBundle;
+use physics::rigid_body::STANDARD_BODY_FRICTION;
+use physics::spawn::RigidBodyBuilder;
+use physics::spawn::RigidBodyBundle;
+use resources::content::SF_CONTENT_PREFIX;
+use resources::math::Vec2Int;
+use sfx::{builder::sfx_builder, entity_update::SfxAutoDestroyTimers};
+use sounds::{construction::deconstruct1_sfx::Deconstruct1SfxBundle, shared::sfx_auto_destroy};
+
+pub fn get_default_transform() -> Transform {
+    Transform::IDENTITY
+}
+
+impl BaseEntityBuilder<NoData> for MetalDebrisType {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> BaseEntityBundle {
+        let mut examine_map = BTreeMap::new();
+        examine_map.insert(0, "Twisted scraps of metal torn from the hull.".to_string());
+        BaseEntityBundle {
+            default_transform: get_default_transform(),
+            examinable: Examinable {
+                assigned_texts: examine_map,
+                name: RichName {
+                    name: "metal debris".to_string(),
+                    n: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            entity_type: Box::new(MetalDebrisType::new()),
+            ..Default::default()
+        }
+    }
+}
+
+impl InventoryItemBuilder for MetalDebrisType {
+    fn get_bundle(&self, spawn_data: &EntityBuildData) -> InventoryItemBundle {
+        let mut attachment_transforms = HashMap::new();
+
+        attachment_transforms.insert(
+            "left_hand".to_string(),
+            Transform::from_translation(Vec3::new(0., -0.003, -0.108)),
+        );
+        attachment_transforms.insert(
+            "right_hand".to_string(),
+            Transform::from_translation(Vec3::new(0.064, -0.019, 0.065)),
+        );
+
+        InventoryItemBundle {
+            inventory_item: InventoryItem {
+                in_inventory_of_entity: spawn_data.holder_entity_option,
+                attachment_transforms: attachment_transforms,
+                drop_transform: get_default_transform(),
+                slot_type: SlotType::Generic,
+                slot_size: Vec2Int { x: 2, y: 2 },
+                throw_force_factor: 1.,
+                ..Default::default()
+            },
+            melee_combat: Default::default(),
+            projectile_combat_option: None,
+        }
+    }
+}
+
+impl RigidBodyBuilder<NoData> for MetalDebrisType {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> RigidBodyBundle {
+        let mut friction = Friction::coefficient(STANDARD_BODY_FRICTION);
+        friction.combine_rule = CoefficientCombineRule::Multiply;
+
+        RigidBodyBundle {
+            collider: Collider::cuboid(0.2, 0.05, 0.2),
+            collider_transform: Transform::from_translation(Vec3::new(0., 0.05, 0.)),
+            collider_friction: friction,
+
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Identity)]
+pub struct MetalDebrisType {
+    pub identifier: String,
+}
+impl Default for MetalDebrisType {
+    fn default() -> Self {
+        Self {
+            identifier: SF_CONTENT_PREFIX.to_string() + "metal_debris",
+        }
+    }
+}
+
+/// Spawn debris and play the sound of tiles that broke.
+
+pub(crate) fn spawn_debris(
+    mut events: EventReader<TileBroken>,
+    mut commands: Commands,
+    mut spawner: EventWriter<SpawnEntity<MetalDebrisType>>,
+    mut auto_destroy_timers: ResMut<SfxAutoDestroyTimers>,
+) {
+    for event in events.iter() {
+        let transform = Transform::from_translation(event.position);
+        spawn_entity(
+            MetalDebrisType::default(),
+            transform,
+            &mut commands,
+            false,
+            None,
+            None,
+            None,
+            &mut spawner,
+        );
+        let sfx_entity = sfx_builder(
+            &mut commands,
+            transform,
+            Box::new(Deconstruct1SfxBundle::new),
+        );
+        sfx_auto_destroy(sfx_entity, &mut auto_destroy_timers);
+    }
+}
@@ -0,0 +1,6 @@
+//! Generators that supply power to power networks.
+
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Generator spawner.
+pub mod spawn;
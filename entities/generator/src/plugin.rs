@@ -0,0 +1,19 @@
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use entity::entity_types::{register_entity_type, register_grid_entity_type};
+use entity::spawn::build_base_entities;
+use physics::spawn::build_rigid_bodies;
+use resources::labels::BuildingLabels;
+
+use crate::spawn::{build_generators, GeneratorType};
+
+pub struct GeneratorsPlugin;
+
+impl Plugin for GeneratorsPlugin {
+    fn build(&self, app: &mut App) {
+        register_entity_type::<GeneratorType>(app);
+        register_grid_entity_type::<GeneratorType>(app);
+        app.add_system(build_generators::<GeneratorType>.after(BuildingLabels::TriggerBuild))
+            .add_system((build_base_entities::<GeneratorType>).after(BuildingLabels::TriggerBuild))
+            .add_system((build_rigid_bodies::<GeneratorType>).after(BuildingLabels::TriggerBuild));
+    }
+}
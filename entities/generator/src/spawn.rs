@@ -0,0 +1,103 @@
+use bevy::{
+    math::Vec3,
+    prelude::{Commands, EventReader, Transform},
+};
+use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Friction};
+use entity::{
+    entity_data::{GridEntity, GridItemData},
+    entity_macros::Identity,
+    entity_types::EntityType,
+    examine::{Examinable, RichName},
+    health::Health,
+    spawn::{BaseEntityBuilder, BaseEntityBundle, EntityBuildData, NoData, SpawnEntity},
+};
+use physics::{
+    rigid_body::STANDARD_BODY_FRICTION,
+    spawn::{RigidBodyBuilder, RigidBodyBundle},
+};
+use power::power::PowerGenerator;
+use resources::content::SF_CONTENT_PREFIX;
+use std::collections::BTreeMap;
+
+/// Power supplied by generators in watts.
+pub const GENERATOR_OUTPUT: f32 = 5000.;
+
+impl BaseEntityBuilder<NoData> for GeneratorType {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> BaseEntityBundle {
+        let mut examine_map = BTreeMap::new();
+        examine_map.insert(
+            0,
+            "A generator that supplies power to the power cable it stands on.".to_string(),
+        );
+
+        BaseEntityBundle {
+            default_transform: Transform::IDENTITY,
+            examinable: Examinable {
+                assigned_texts: examine_map,
+                name: RichName {
+                    name: "generator".to_string(),
+                    n: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            entity_type: Box::new(GeneratorType::new()),
+            health: Health {
+                is_combat_obstacle: true,
+                is_reach_obstacle: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl RigidBodyBuilder<NoData> for GeneratorType {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> RigidBodyBundle {
+        let mut friction = Friction::coefficient(STANDARD_BODY_FRICTION);
+        friction.combine_rule = CoefficientCombineRule::Min;
+
+        RigidBodyBundle {
+            collider: Collider::cuboid(0.5, 0.5, 0.5),
+            collider_transform: Transform::from_translation(Vec3::new(0., 0.5, 0.)),
+            collider_friction: friction,
+            rigidbody_dynamic: false,
+            collision_events: true,
+        }
+    }
+}
+
+#[derive(Clone, Identity)]
+pub struct GeneratorType {
+    pub identifier: String,
+}
+impl Default for GeneratorType {
+    fn default() -> Self {
+        GeneratorType {
+            identifier: SF_CONTENT_PREFIX.to_owned() + "generator",
+        }
+    }
+}
+
+impl GridEntity for GeneratorType {
+    fn get_grid_item_data() -> GridItemData {
+        GridItemData {
+            transform_offset: Transform::IDENTITY,
+            can_be_built_with_grid_item: vec![],
+        }
+    }
+}
+
+pub fn build_generators<T: Send + Sync + 'static>(
+    mut commands: Commands,
+    mut spawn_events: EventReader<SpawnEntity<T>>,
+) {
+    for spawn_event in spawn_events.iter() {
+        commands
+            .entity(spawn_event.spawn_data.entity)
+            .insert(PowerGenerator {
+                output: GENERATOR_OUTPUT,
+                active: true,
+            });
+    }
+}
@@ -0,0 +1,6 @@
+//! Gravity generators that create artificial gravity around them while powered.
+
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Gravity generator spawner.
+pub mod spawn;
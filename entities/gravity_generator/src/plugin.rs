@@ -0,0 +1,25 @@
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use entity::entity_types::{register_entity_type, register_grid_entity_type};
+use entity::spawn::build_base_entities;
+use physics::spawn::build_rigid_bodies;
+use resources::labels::BuildingLabels;
+
+use crate::spawn::{build_gravity_generators, GravityGeneratorType};
+
+pub struct GravityGeneratorsPlugin;
+
+impl Plugin for GravityGeneratorsPlugin {
+    fn build(&self, app: &mut App) {
+        register_entity_type::<GravityGeneratorType>(app);
+        register_grid_entity_type::<GravityGeneratorType>(app);
+        app.add_system(
+            build_gravity_generators::<GravityGeneratorType>.after(BuildingLabels::TriggerBuild),
+        )
+        .add_system(
+            (build_base_entities::<GravityGeneratorType>).after(BuildingLabels::TriggerBuild),
+        )
+        .add_system(
+            (build_rigid_bodies::<GravityGeneratorType>).after(BuildingLabels::TriggerBuild),
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{
+        AssetServer, Assets, BuildChildren, Children, Color, Commands, Component,
+        DespawnRecursiveExt, Entity, EventReader, Handle, Name, Parent, Query, Res, ResMut,
+        Resource, StandardMaterial, With,
+    },
+    scene::{Scene, SceneBundle, SceneInstance, SceneSpawner},
+};
+use entity::{base_mesh::entity_asset_path, spawn::ClientEntityServerEntity};
+use networking::client::IncomingReliableServerMessage;
+use pawn::{
+    appearance::{AppearanceConfig, CharacterAppearance},
+    net::PawnServerMessage,
+};
+use resources::content::ContentAssets;
+
+use crate::spawn::HumanMaleType;
+
+/// Client resource with received appearances of server entities that have yet to be applied.
+#[derive(Resource, Default)]
+
+pub struct PendingAppearances {
+    pub map: HashMap<Entity, CharacterAppearance>,
+}
+
+/// Hair mesh child of a human male.
+#[derive(Component)]
+
+pub struct HairMesh;
+
+/// Human males whose materials have yet to be tinted to their appearance.
+#[derive(Component)]
+
+pub struct AppearanceTintPending;
+
+pub(crate) fn receive_appearance(
+    mut net: EventReader<IncomingReliableServerMessage<PawnServerMessage>>,
+    mut pending: ResMut<PendingAppearances>,
+) {
+    for message in net.iter() {
+        match &message.message {
+            PawnServerMessage::Appearance(server_entity, appearance) => {
+                pending.map.insert(*server_entity, appearance.clone());
+            }
+        }
+    }
+}
+
+/// Swap in the body and hair meshes of received appearances once their entities are loaded in.
+
+pub(crate) fn apply_appearance(
+    mut pending: ResMut<PendingAppearances>,
+    map: Res<ClientEntityServerEntity>,
+    mut scenes: Query<&mut Handle<Scene>>,
+    children_query: Query<&Children>,
+    hair_meshes: Query<Entity, With<HairMesh>>,
+    config: Res<AppearanceConfig>,
+    asset_server: Res<AssetServer>,
+    content: Res<ContentAssets>,
+    mut commands: Commands,
+) {
+    let mut applied = vec![];
+    for (server_entity, appearance) in pending.map.iter() {
+        let client_entity;
+        match map.map.get(server_entity) {
+            Some(e) => {
+                client_entity = *e;
+            }
+            None => {
+                continue;
+            }
+        }
+        let mut scene;
+        match scenes.get_mut(client_entity) {
+            Ok(s) => {
+                scene = s;
+            }
+            Err(_) => {
+                // Base mesh not linked yet.
+                continue;
+            }
+        }
+        applied.push(*server_entity);
+
+        match config.body(&appearance.body) {
+            Some(body) => {
+                let body_scene = asset_server.load(
+                    entity_asset_path(&content, &HumanMaleType::default(), &body.mesh) + "#Scene0",
+                );
+                if *scene != body_scene {
+                    *scene = body_scene;
+                }
+            }
+            None => {}
+        }
+
+        match children_query.get(client_entity) {
+            Ok(children) => {
+                for child in children.iter() {
+                    if hair_meshes.get(*child).is_ok() {
+                        commands.entity(*child).despawn_recursive();
+                    }
+                }
+            }
+            Err(_) => {}
+        }
+        match config.hair_style(&appearance.hair_style) {
+            Some(hair_style) => match &hair_style.mesh {
+                Some(mesh) => {
+                    commands.entity(client_entity).with_children(|parent| {
+                        parent
+                            .spawn(SceneBundle {
+                                scene: asset_server.load(
+                                    entity_asset_path(&content, &HumanMaleType::default(), mesh)
+                                        + "#Scene0",
+                                ),
+                                ..Default::default()
+                            })
+                            .insert(HairMesh);
+                    });
+                }
+                None => {}
+            },
+            None => {}
+        }
+
+        commands
+            .entity(client_entity)
+            .insert((appearance.clone(), AppearanceTintPending));
+    }
+    for server_entity in applied {
+        pending.map.remove(&server_entity);
+    }
+}
+
+/// Tint the skin and hair materials of human males once their scenes are spawned.
+
+pub(crate) fn tint_appearance(
+    pending: Query<(Entity, &CharacterAppearance), With<AppearanceTintPending>>,
+    instances: Query<&SceneInstance>,
+    scene_spawner: Res<SceneSpawner>,
+    children_query: Query<&Children>,
+    hair_meshes: Query<Entity, With<HairMesh>>,
+    names: Query<&Name>,
+    parents: Query<&Parent>,
+    mut material_handles: Query<&mut Handle<StandardMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<AppearanceConfig>,
+    mut commands: Commands,
+) {
+    for (entity, appearance) in pending.iter() {
+        let mut scene_roots = vec![entity];
+        match children_query.get(entity) {
+            Ok(children) => {
+                for child in children.iter() {
+                    if hair_meshes.get(*child).is_ok() {
+                        scene_roots.push(*child);
+                    }
+                }
+            }
+            Err(_) => {}
+        }
+        let ready = scene_roots.iter().all(|root| match instances.get(*root) {
+            Ok(instance) => scene_spawner.instance_is_ready(**instance),
+            Err(_) => false,
+        });
+        if !ready {
+            continue;
+        }
+
+        let skin_color = config
+            .skin_tone(&appearance.skin_tone)
+            .map(|o| Color::rgb(o.color.0, o.color.1, o.color.2));
+        let hair_color = config
+            .hair_color(&appearance.hair_color)
+            .map(|o| Color::rgb(o.color.0, o.color.1, o.color.2));
+
+        let mut stack = vec![(entity, false)];
+        while let Some((node, is_hair)) = stack.pop() {
+            match children_query.get(node) {
+                Ok(children) => {
+                    for child in children.iter() {
+                        stack.push((*child, is_hair || hair_meshes.get(*child).is_ok()));
+                    }
+                }
+                Err(_) => {}
+            }
+
+            let tint;
+            if is_hair {
+                tint = hair_color;
+            } else {
+                let mut is_skin = is_skin_node(&names, node);
+                match parents.get(node) {
+                    Ok(parent) => {
+                        is_skin = is_skin || is_skin_node(&names, parent.get());
+                    }
+                    Err(_) => {}
+                }
+                tint = match is_skin {
+                    true => skin_color,
+                    false => None,
+                };
+            }
+
+            match (tint, material_handles.get_mut(node)) {
+                (Some(color), Ok(mut handle)) => match materials.get(&handle) {
+                    Some(material) => {
+                        // Copy the material so other pawns sharing it keep their appearance.
+                        let mut tinted = material.clone();
+                        tinted.base_color = color;
+                        *handle = materials.add(tinted);
+                    }
+                    None => {}
+                },
+                _ => {}
+            }
+        }
+
+        commands.entity(entity).remove::<AppearanceTintPending>();
+    }
+}
+
+/// Skin materials belong to mesh nodes with "skin" in their name.
+
+fn is_skin_node(names: &Query<&Name>, entity: Entity) -> bool {
+    match names.get(entity) {
+        Ok(name) => name.as_str().to_lowercase().contains("skin"),
+        Err(_) => false,
+    }
+}
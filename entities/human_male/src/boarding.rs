@@ -1,12 +1,12 @@
 use bevy::prelude::{Added, Commands, Entity, EventWriter, Query, ResMut};
 
-use construction_tool::spawn::ConstructionToolType;
-use entity::entity_types::EntityType;
+use entity::entity_types::{EntityType, EntityTypes};
 use networking::server::{ConnectedPlayer, HandleToEntity};
 use player::names::UsedNames;
 
 use bevy::prelude::warn;
 use bevy::prelude::Res;
+use pawn::access::{AccessConfig, RadioChannels};
 use pawn::pawn::Spawning;
 use pawn::pawn::{PawnDesignation, SpawnPawnData};
 use player::account::Accounts;
@@ -16,11 +16,11 @@ use setup_menu::core::SetupUiUserDataSets;
 
 use entity::spawn::SpawnEntity;
 
-use crate::spawn::HumanMaleType;
+use crate::spawn::{HeldItemSpawners, HumanMaleType};
 use pawn::pawn::Pawn;
 use player::boarding::PlayerBoarded;
 
-/// Spawn player as human male with preset inventory.
+/// Spawn player as human male with the starting inventory of their job, or the inventory they arrived with from another sector.
 
 pub(crate) fn spawn_boarding_player(
     query: Query<(Entity, &Spawning, &ConnectedPlayer), Added<Spawning>>,
@@ -31,6 +31,9 @@ pub(crate) fn spawn_boarding_player(
     accounts: Res<Accounts>,
     setup_ui_datas: Res<SetupUiUserDataSets>,
     mut boarded: EventWriter<PlayerBoarded>,
+    access_config: Res<AccessConfig>,
+    entity_types: Res<EntityTypes>,
+    held_item_spawners: Res<HeldItemSpawners>,
 ) {
     for (entity_id, spawning_component, connected_player_component) in query.iter() {
         let setup_data;
@@ -48,10 +51,44 @@ pub(crate) fn spawn_boarding_player(
             }
         }
 
-        let new_human_entity = commands.spawn(()).id();
+        let job;
+        match access_config.job(&setup_data.job) {
+            Some(j) => {
+                job = j;
+            }
+            None => {
+                warn!("Couldnt find job {}.", setup_data.job);
+                continue;
+            }
+        }
 
-        let passed_inventory_setup: Vec<Box<dyn EntityType>> =
-            vec![Box::new(ConstructionToolType::default())];
+        let inventory = match &spawning_component.inventory_option {
+            Some(items) => items,
+            None => &job.starting_inventory,
+        };
+        let mut passed_inventory_setup: Vec<Box<dyn EntityType>> = vec![];
+        for item_type in inventory.iter() {
+            if !held_item_spawners.identities.contains(item_type) {
+                warn!(
+                    "Couldnt find spawner for starting inventory item {}.",
+                    item_type
+                );
+                continue;
+            }
+            match entity_types.types.get(item_type) {
+                Some(t) => {
+                    passed_inventory_setup.push(t.clone());
+                }
+                None => {
+                    warn!("Unknown starting inventory entity type {}.", item_type);
+                }
+            }
+        }
+
+        let new_human_entity = commands.spawn(()).id();
+        commands.entity(new_human_entity).insert(RadioChannels {
+            channels: job.radio_channels.clone(),
+        });
 
         spawn_human.send(SpawnEntity {
             spawn_data: EntityBuildData {
@@ -63,6 +100,8 @@ pub(crate) fn spawn_boarding_player(
                 spawn_pawn_data: SpawnPawnData {
                     pawn_component: Pawn {
                         character_name: setup_data.character_name.clone(),
+                        job: job.id.clone(),
+                        appearance: setup_data.appearance.clone(),
                         ..Default::default()
                     },
                     connected_player_option: Some(connected_player_component.clone()),
@@ -1,6 +1,8 @@
 //! A humanoid.
 //! This entity is always a humanoid and always a pawn.
 
+/// Apply character appearances on the client.
+pub mod appearance;
 /// Process player boarding. From setup_ui to spawned into the game.
 pub mod boarding;
 /// Handler for bare hand combat.
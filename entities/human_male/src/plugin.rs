@@ -4,17 +4,25 @@ use construction_tool::spawn::ConstructionToolType;
 use entity::{base_mesh::link_base_mesh, entity_types::register_entity_type, loading::load_entity};
 
 use inventory::server::inventory::SpawnItemLabel;
+use magazine_b1::spawn::MagazineB1Type;
+use magnetic_boots::spawn::MagneticBootsType;
 use physics::spawn::build_rigid_bodies;
+use pistol_b1::spawn::PistolB1Type;
 use resources::{
     is_server::is_server,
     labels::{BuildingLabels, CombatLabels},
 };
+use setup_menu::core::initialize_setupui;
 
 use crate::{
+    appearance::{apply_appearance, receive_appearance, tint_appearance, PendingAppearances},
     boarding::spawn_boarding_player,
     hands_attack_handler::hands_attack_handler,
     setup_ui_showcase::human_male_setup_ui,
-    spawn::{build_base_human_males, build_human_males, spawn_held_item, HumanMaleType},
+    spawn::{
+        add_human_male_inventory, build_base_human_males, build_human_males,
+        register_held_item_spawner, spawn_held_item, spawn_id_card, HumanMaleType,
+    },
 };
 pub struct HumanMalePlugin;
 
@@ -26,11 +34,28 @@ impl Plugin for HumanMalePlugin {
                     .in_set(CombatLabels::WeaponHandler)
                     .after(CombatLabels::CacheAttack),
             )
-            .add_system(human_male_setup_ui.in_set(BuildingLabels::TriggerBuild))
-            .add_system(spawn_boarding_player.in_base_set(CoreSet::PostUpdate));
+            .add_system(
+                human_male_setup_ui
+                    .in_set(BuildingLabels::TriggerBuild)
+                    .after(initialize_setupui),
+            )
+            .add_system(spawn_boarding_player.in_base_set(CoreSet::PostUpdate))
+            .add_system(
+                spawn_id_card
+                    .in_set(SpawnItemLabel::SpawnHeldItem)
+                    .after(spawn_held_item::<ConstructionToolType>),
+            );
         } else {
             app.add_system(link_base_mesh::<HumanMaleType>)
-                .add_system(load_entity::<HumanMaleType>);
+                .add_system(load_entity::<HumanMaleType>)
+                .init_resource::<PendingAppearances>()
+                .add_system(receive_appearance)
+                .add_system(
+                    apply_appearance
+                        .after(receive_appearance)
+                        .after(link_base_mesh::<HumanMaleType>),
+                )
+                .add_system(tint_appearance.before(apply_appearance));
         }
         register_entity_type::<HumanMaleType>(app);
         register_basic_console_commands_for_type::<HumanMaleType>(app);
@@ -41,6 +66,10 @@ impl Plugin for HumanMalePlugin {
         )
         .add_system((build_base_human_males::<HumanMaleType>).after(BuildingLabels::TriggerBuild))
         .add_system((build_rigid_bodies::<HumanMaleType>).after(BuildingLabels::TriggerBuild))
-        .add_system(spawn_held_item::<ConstructionToolType>.in_set(SpawnItemLabel::SpawnHeldItem));
+        .add_system(add_human_male_inventory.in_set(SpawnItemLabel::SpawnHeldItem));
+        register_held_item_spawner::<ConstructionToolType>(app);
+        register_held_item_spawner::<MagneticBootsType>(app);
+        register_held_item_spawner::<PistolB1Type>(app);
+        register_held_item_spawner::<MagazineB1Type>(app);
     }
 }
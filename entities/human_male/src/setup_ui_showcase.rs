@@ -1,4 +1,4 @@
-use bevy::prelude::{Added, Commands, EventWriter, Query};
+use bevy::prelude::{warn, Added, Commands, EventWriter, Query, Res};
 use entity::showcase::ShowcaseData;
 use entity::spawn::{EntityBuildData, SpawnEntity};
 
@@ -8,20 +8,37 @@ use pawn::pawn::Pawn;
 use pawn::pawn::PawnDesignation;
 use pawn::pawn::SpawnPawnData;
 use player::connections::SetupPhase;
+use setup_menu::core::SetupUiUserDataSets;
 
 /// Initialize the setup UI by spawning in showcase entities etc.
+/// The showcase pawn previews the character picked in the character creator.
 
 pub(crate) fn human_male_setup_ui(
-    query: Query<(&ConnectedPlayer, &Pawn), Added<SetupPhase>>,
+    query: Query<&ConnectedPlayer, Added<SetupPhase>>,
     mut spawn_human_male: EventWriter<SpawnEntity<HumanMaleType>>,
     mut commands: Commands,
+    setup_ui_datas: Res<SetupUiUserDataSets>,
 ) {
-    for (connected_player_component, persistent_player_data_component) in query.iter() {
+    for connected_player_component in query.iter() {
         /*let passed_inventory_setup: Vec<(String, Box<dyn EntityType>)> = vec![
             ("jumpsuit".to_string(), Box::new(JumpsuitType::default())),
             ("holster".to_string(), Box::new(PistolL1Type::default())),
         ];*/
 
+        let setup_data;
+        match setup_ui_datas.list.get(&connected_player_component.handle) {
+            Some(data) => {
+                setup_data = data;
+            }
+            None => {
+                warn!(
+                    "Could not find setup data for {}",
+                    connected_player_component.handle
+                );
+                continue;
+            }
+        }
+
         let human_male_entity = commands.spawn(()).id();
 
         spawn_human_male.send(SpawnEntity {
@@ -34,7 +51,12 @@ pub(crate) fn human_male_setup_ui(
             },
             entity_type: HumanMaleType {
                 spawn_pawn_data: SpawnPawnData {
-                    pawn_component: persistent_player_data_component.clone(),
+                    pawn_component: Pawn {
+                        character_name: setup_data.character_name.clone(),
+                        job: setup_data.job.clone(),
+                        appearance: setup_data.appearance.clone(),
+                        ..Default::default()
+                    },
                     connected_player_option: Some(connected_player_component.clone()),
                     designation: PawnDesignation::Showcase,
                     ..Default::default()
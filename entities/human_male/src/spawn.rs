@@ -1,6 +1,8 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use bevy::prelude::{warn, Commands, EventReader, EventWriter, Transform, Vec3};
+use bevy::prelude::{
+    warn, App, Commands, EventReader, EventWriter, IntoSystemConfig, Resource, Transform, Vec3,
+};
 use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Dominance, Friction, LockedAxes};
 use entity::{
     entity_data::{WorldMode, WorldModes},
@@ -16,13 +18,15 @@ use entity::{
     },
 };
 use humanoid::humanoid::{Humanoid, HUMAN_MALE_ENTITY_NAME};
+use id_card::spawn::IdCardType;
 use inventory::server::{
     combat::{DamageModel, MeleeCombat},
-    inventory::{AddItemToSlot, AddSlot, Inventory, Slot},
+    inventory::{AddItemToSlot, AddSlot, Inventory, Slot, SlotType, SpawnItemLabel},
 };
 use map::map::Map;
+use pawn::access::{AccessConfig, IdCard};
 use pawn::pawn::{DataLink, DataLinkType, PawnBuilder};
-use pawn::pawn::{PawnDesignation, ShipAuthorization, ShipAuthorizationEnum, SpawnPawnData};
+use pawn::pawn::{PawnDesignation, SpawnPawnData};
 use physics::physics::CHARACTER_FLOOR_FRICTION;
 use physics::spawn::{RigidBodyBuilder, RigidBodyBundle};
 use resources::math::Vec2Int;
@@ -197,9 +201,6 @@ pub fn build_human_males(
 
             spawner.insert((
                 Senser::default(),
-                ShipAuthorization {
-                    access: vec![ShipAuthorizationEnum::Security],
-                },
                 pawn_component,
                 ControllerInput::default(),
             ));
@@ -236,6 +237,7 @@ pub fn build_human_males(
                                     "Atmospherics Pressure".to_string(),
                                     "atmospherics_pressure".to_string(),
                                 ),
+                                ("Power".to_string(), "power".to_string()),
                             ],
                             ..Default::default()
                         },
@@ -245,6 +247,8 @@ pub fn build_human_males(
             }
         }
 
+        spawner.insert(spawn_pawn_data.pawn_component.appearance.clone());
+
         let mut first_damage_flags = HashMap::new();
         first_damage_flags.insert(0, DamageFlag::SoftDamage);
         spawner.insert((
@@ -268,12 +272,85 @@ pub fn build_human_males(
     }
 }
 
-pub fn spawn_held_item<T: Send + Sync + Default + 'static>(
+/// The inventory slots of human males. Slots are added in this order, their slot id is their index.
+
+pub fn human_male_slots() -> Vec<Slot> {
+    let mut test_slot = Slot::default();
+    test_slot.name = "Backpack".to_string();
+    test_slot.size = Vec2Int { x: 16, y: 8 };
+
+    let mut id_slot = Slot::default();
+    id_slot.name = "ID".to_string();
+    id_slot.slot_type = SlotType::Id;
+    id_slot.size = Vec2Int { x: 2, y: 2 };
+
+    let mut boots_slot = Slot::default();
+    boots_slot.name = "Boots".to_string();
+    boots_slot.slot_type = SlotType::Boots;
+    boots_slot.size = Vec2Int { x: 2, y: 2 };
+
+    vec![test_slot, id_slot, boots_slot]
+}
+
+/// Slot id of the first human male inventory slot of a slot type.
+
+pub fn human_male_slot_id(slot_type: SlotType) -> Option<u8> {
+    human_male_slots()
+        .iter()
+        .position(|slot| slot.slot_type == slot_type)
+        .map(|index| index as u8)
+}
+
+/// Give human males their inventory and its slots.
+
+pub(crate) fn add_human_male_inventory(
+    mut commands: Commands,
+    mut spawn_events: EventReader<SpawnEntity<HumanMaleType>>,
+    mut add_slot: EventWriter<AddSlot>,
+) {
+    for spawn_event in spawn_events.iter() {
+        let mut spawner = commands.entity(spawn_event.spawn_data.entity);
+
+        for slot in human_male_slots() {
+            add_slot.send(AddSlot {
+                inventory_entity: spawn_event.spawn_data.entity,
+                slot,
+            });
+        }
+
+        spawner.insert(Inventory::default());
+    }
+}
+
+/// Identities of the entity types with a registered [spawn_held_item] system.
+#[derive(Resource, Default)]
+
+pub struct HeldItemSpawners {
+    pub identities: HashSet<String>,
+}
+
+/// Register [spawn_held_item] for an entity type so it can be in a starting inventory.
+
+pub fn register_held_item_spawner<T: EntityType + Send + Sync + Default + 'static>(app: &mut App) {
+    app.add_system(
+        spawn_held_item::<T>
+            .in_set(SpawnItemLabel::SpawnHeldItem)
+            .after(add_human_male_inventory),
+    );
+    app.world
+        .get_resource_or_insert_with(HeldItemSpawners::default)
+        .identities
+        .insert(T::default().get_identity());
+}
+
+/// Spawn the items of type T in the inventory setup of human males.
+/// Register with [register_held_item_spawner] for every entity type that can be in a starting inventory.
+
+pub fn spawn_held_item<T: EntityType + Send + Sync + Default + 'static>(
     mut commands: Commands,
     mut default_spawner: EventWriter<SpawnEntity<T>>,
     mut spawn_events: EventReader<SpawnEntity<HumanMaleType>>,
     mut add_slot_item: EventWriter<AddItemToSlot>,
-    mut add_slot: EventWriter<AddSlot>,
     types: Res<EntityTypes>,
 ) {
     for spawn_event in spawn_events.iter() {
@@ -282,6 +359,9 @@ pub fn spawn_held_item<T: Send + Sync + Default + 'static>(
         let mut slot_entities = vec![];
 
         for item_name in spawn_pawn_data.inventory_setup.iter() {
+            if !item_name.is_type(T::default().get_identity()) {
+                continue;
+            }
             let return_entity = commands.spawn(()).id();
             default_spawner.send(SpawnEntity {
                 spawn_data: EntityBuildData {
@@ -299,19 +379,6 @@ pub fn spawn_held_item<T: Send + Sync + Default + 'static>(
             slot_entities.push((return_entity, item_name.get_identity()));
         }
 
-        let mut spawner = commands.entity(spawn_event.spawn_data.entity);
-
-        let mut test_slot = Slot::default();
-        test_slot.name = "Backpack".to_string();
-        test_slot.size = Vec2Int { x: 16, y: 8 };
-
-        add_slot.send(AddSlot {
-            inventory_entity: spawn_event.spawn_data.entity,
-            slot: test_slot,
-        });
-
-        spawner.insert(Inventory::default());
-
         for (item, identity) in slot_entities {
             let net_type;
             match types.netcode_types.get(&identity) {
@@ -332,3 +399,89 @@ pub fn spawn_held_item<T: Send + Sync + Default + 'static>(
         }
     }
 }
+
+/// Spawn an ID card with the access of the job of player pawns into their ID slot.
+
+pub(crate) fn spawn_id_card(
+    mut commands: Commands,
+    mut card_spawner: EventWriter<SpawnEntity<IdCardType>>,
+    mut spawn_events: EventReader<SpawnEntity<HumanMaleType>>,
+    mut add_slot_item: EventWriter<AddItemToSlot>,
+    types: Res<EntityTypes>,
+    config: Res<AccessConfig>,
+) {
+    for spawn_event in spawn_events.iter() {
+        let id_slot;
+        match human_male_slot_id(SlotType::Id) {
+            Some(s) => {
+                id_slot = s;
+            }
+            None => {
+                warn!("Couldnt find ID slot of human males.");
+                continue;
+            }
+        }
+        if spawn_event.spawn_data.showcase_data_option.is_some() {
+            continue;
+        }
+        let spawn_pawn_data = spawn_event.entity_type.get_spawn_pawn_data();
+        match spawn_pawn_data.designation {
+            PawnDesignation::Player => {}
+            _ => {
+                continue;
+            }
+        }
+
+        let pawn_component = &spawn_pawn_data.pawn_component;
+        let card;
+        match config.job(&pawn_component.job) {
+            Some(job) => {
+                card = IdCard {
+                    owner_name: pawn_component.character_name.clone(),
+                    job: job.name.clone(),
+                    access: job.access.clone(),
+                };
+            }
+            None => {
+                warn!("Couldnt find job {}.", pawn_component.job);
+                continue;
+            }
+        }
+
+        let card_type = IdCardType {
+            card,
+            ..Default::default()
+        };
+        let net_type;
+        match types.netcode_types.get(&card_type.get_identity()) {
+            Some(t) => {
+                net_type = *t;
+            }
+            None => {
+                warn!("Couldnt find id card entity type.");
+                continue;
+            }
+        }
+
+        let card_entity = commands.spawn(()).id();
+        card_spawner.send(SpawnEntity {
+            spawn_data: EntityBuildData {
+                entity_transform: Transform::IDENTITY,
+                correct_transform: false,
+                holder_entity_option: Some(spawn_event.spawn_data.entity),
+                default_map_spawn: false,
+                raw_entity_option: None,
+                showcase_data_option: None,
+                entity: card_entity,
+                held_entity_option: Some(card_entity),
+            },
+            entity_type: card_type,
+        });
+        add_slot_item.send(AddItemToSlot {
+            slot_id: id_slot,
+            inventory_entity: spawn_event.spawn_data.entity,
+            item_entity: card_entity,
+            item_type_id: net_type,
+        });
+    }
+}
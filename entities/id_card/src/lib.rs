@@ -0,0 +1,6 @@
+//! ID cards granting access to doors and devices.
+
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Spawner.
+pub mod spawn;
@@ -0,0 +1,25 @@
+use basic_console_commands::register::{
+    register_basic_console_commands_for_inventory_item_type,
+    register_basic_console_commands_for_type,
+};
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use entity::{entity_types::register_entity_type, spawn::build_base_entities};
+use inventory::spawn_item::build_inventory_items;
+use physics::spawn::build_rigid_bodies;
+use resources::labels::BuildingLabels;
+
+use super::spawn::{build_id_cards, IdCardType};
+
+pub struct IdCardsPlugin;
+
+impl Plugin for IdCardsPlugin {
+    fn build(&self, app: &mut App) {
+        register_entity_type::<IdCardType>(app);
+        register_basic_console_commands_for_type::<IdCardType>(app);
+        register_basic_console_commands_for_inventory_item_type::<IdCardType>(app);
+        app.add_system(build_id_cards.after(BuildingLabels::TriggerBuild))
+            .add_system((build_base_entities::<IdCardType>).after(BuildingLabels::TriggerBuild))
+            .add_system((build_rigid_bodies::<IdCardType>).after(BuildingLabels::TriggerBuild))
+            .add_system((build_inventory_items::<IdCardType>).after(BuildingLabels::TriggerBuild));
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use bevy::math::Vec3;
+use bevy::prelude::Commands;
+use bevy::prelude::EventReader;
+use bevy::prelude::Transform;
+use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Friction};
+use entity::entity_macros::Identity;
+use entity::entity_types::EntityType;
+use entity::examine::Examinable;
+use entity::examine::RichName;
+use entity::spawn::BaseEntityBuilder;
+use entity::spawn::BaseEntityBundle;
+use entity::spawn::EntityBuildData;
+use entity::spawn::NoData;
+use entity::spawn::SpawnEntity;
+use inventory::item::InventoryItem;
+use inventory::server::inventory::SlotType;
+use inventory::spawn_item::InventoryItemBuilder;
+use inventory::spawn_item::InventoryItemBundle;
+use pawn::access::IdCard;
+use physics::rigid_body::STANDARD_BODY_FRICTION;
+use physics::spawn::RigidBodyBuilder;
+use physics::spawn::RigidBodyBundle;
+use resources::content::SF_CONTENT_PREFIX;
+use resources::math::Vec2Int;
+
+pub fn get_default_transform() -> Transform {
+    Transform::IDENTITY
+}
+
+impl BaseEntityBuilder<NoData> for IdCardType {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> BaseEntityBundle {
+        let mut examine_map = BTreeMap::new();
+        let text;
+        if self.card.owner_name.is_empty() {
+            text = "A blank ID card.".to_string();
+        } else {
+            text = format!(
+                "An ID card issued to {}, {}.",
+                self.card.owner_name, self.card.job
+            );
+        }
+        examine_map.insert(0, text);
+        BaseEntityBundle {
+            default_transform: get_default_transform(),
+            examinable: Examinable {
+                assigned_texts: examine_map,
+                name: RichName {
+                    name: "ID card".to_string(),
+                    n: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            entity_type: Box::new(self.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+impl InventoryItemBuilder for IdCardType {
+    fn get_bundle(&self, spawn_data: &EntityBuildData) -> InventoryItemBundle {
+        let mut attachment_transforms = HashMap::new();
+
+        attachment_transforms.insert(
+            "left_hand".to_string(),
+            Transform::from_translation(Vec3::new(0., -0.003, -0.108)),
+        );
+        attachment_transforms.insert(
+            "right_hand".to_string(),
+            Transform::from_translation(Vec3::new(0.064, -0.019, 0.065)),
+        );
+
+        InventoryItemBundle {
+            inventory_item: InventoryItem {
+                in_inventory_of_entity: spawn_data.holder_entity_option,
+                attachment_transforms: attachment_transforms,
+                drop_transform: get_default_transform(),
+                slot_type: SlotType::Id,
+                slot_size: Vec2Int { x: 1, y: 1 },
+                throw_force_factor: 1.,
+                ..Default::default()
+            },
+            melee_combat: Default::default(),
+            projectile_combat_option: None,
+        }
+    }
+}
+
+impl RigidBodyBuilder<NoData> for IdCardType {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> RigidBodyBundle {
+        let mut friction = Friction::coefficient(STANDARD_BODY_FRICTION);
+        friction.combine_rule = CoefficientCombineRule::Multiply;
+
+        RigidBodyBundle {
+            collider: Collider::cuboid(0.043, 0.003, 0.027),
+            collider_friction: friction,
+
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Identity)]
+pub struct IdCardType {
+    pub identifier: String,
+    /// Holder, job and access levels the card is issued with.
+    pub card: IdCard,
+}
+impl Default for IdCardType {
+    fn default() -> Self {
+        Self {
+            identifier: SF_CONTENT_PREFIX.to_string() + "id_card",
+            card: IdCard::default(),
+        }
+    }
+}
+
+/// Insert the [IdCard] component.
+
+pub fn build_id_cards(
+    mut commands: Commands,
+    mut spawn_events: EventReader<SpawnEntity<IdCardType>>,
+) {
+    for spawn_event in spawn_events.iter() {
+        commands
+            .entity(spawn_event.spawn_data.entity)
+            .insert(spawn_event.entity_type.card.clone());
+    }
+}
@@ -16,51 +16,32 @@ pub(crate) fn entity_console_commands(
     mut spawn_event: EventWriter<SpawnEntity<LineArrowType>>,
 ) {
     for command in queue.iter() {
-        if command.input.command == "pointArrow" && command.input.args.len() == 4 {
-            let x;
-            let y;
-            let z;
-
-            match command.input.args[0].parse::<f32>() {
-                Ok(v) => x = v,
-                Err(_) => continue,
-            }
-
-            match command.input.args[1].parse::<f32>() {
-                Ok(v) => y = v,
-                Err(_) => continue,
-            }
-
-            match command.input.args[2].parse::<f32>() {
-                Ok(v) => z = v,
-                Err(_) => continue,
-            }
-
-            let duration;
-
-            match command.input.args[3].parse::<i64>() {
-                Ok(v) => duration = v,
-                Err(_) => continue,
-            }
+        if command.input.command != "pointArrow" {
+            continue;
+        }
+        let args = &command.args;
+        let x = args[0].float().unwrap_or_default();
+        let y = args[1].float().unwrap_or_default();
+        let z = args[2].float().unwrap_or_default();
+        let duration = args[3].int().unwrap_or_default();
 
-            let translation = Vec3::new(x, y, z);
+        let translation = Vec3::new(x, y, z);
 
-            let mut passed_transform = Transform::IDENTITY;
-            passed_transform.translation = translation;
+        let mut passed_transform = Transform::IDENTITY;
+        passed_transform.translation = translation;
 
-            spawn_event.send(SpawnEntity {
-                spawn_data: EntityBuildData {
-                    entity_transform: passed_transform,
-                    correct_transform: false,
-                    entity: commands.spawn(()).id(),
-                    ..Default::default()
-                },
-                entity_type: LineArrowType {
-                    duration: duration as f32,
-                    ..Default::default()
-                },
-            });
-        }
+        spawn_event.send(SpawnEntity {
+            spawn_data: EntityBuildData {
+                entity_transform: passed_transform,
+                correct_transform: false,
+                entity: commands.spawn(()).id(),
+                ..Default::default()
+            },
+            entity_type: LineArrowType {
+                duration: duration as f32,
+                ..Default::default()
+            },
+        });
     }
 }
 use entity::spawning_events::DespawnClientEntity;
@@ -1,6 +1,8 @@
 use basic_console_commands::register::register_basic_console_commands_for_type;
 use bevy::prelude::{App, IntoSystemConfig, Plugin, ResMut};
-use console_commands::commands::{AllConsoleCommands, ConsoleCommand, ConsoleCommandsLabels};
+use console_commands::commands::{
+    AllConsoleCommands, ConsoleArg, ConsoleCommand, ConsoleCommandsLabels,
+};
 use entity::{entity_types::register_entity_type, spawn::build_base_entities};
 use networking::server::ConsoleArgVariant;
 use resources::{
@@ -53,10 +55,10 @@ pub fn initialize_console_commands(mut commands: ResMut<AllConsoleCommands>) {
             .to_string(),
 
         args: vec![
-            ("x".to_string(), ConsoleArgVariant::Float),
-            ("y".to_string(), ConsoleArgVariant::Float),
-            ("z".to_string(), ConsoleArgVariant::Float),
-            ("duration".to_string(), ConsoleArgVariant::Int),
+            ConsoleArg::new("x", ConsoleArgVariant::Float),
+            ConsoleArg::new("y", ConsoleArgVariant::Float),
+            ConsoleArg::new("z", ConsoleArgVariant::Float),
+            ConsoleArg::new("duration", ConsoleArgVariant::Int),
         ],
     });
 }
@@ -0,0 +1,6 @@
+//! Magazines that feed ballistic pistols.
+
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Spawner.
+pub mod spawn;
@@ -0,0 +1,27 @@
+use basic_console_commands::register::{
+    register_basic_console_commands_for_inventory_item_type,
+    register_basic_console_commands_for_type,
+};
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use entity::{entity_types::register_entity_type, spawn::build_base_entities};
+use inventory::spawn_item::build_inventory_items;
+use physics::spawn::build_rigid_bodies;
+use resources::labels::BuildingLabels;
+
+use super::spawn::{build_magazines_b1, MagazineB1Type};
+
+pub struct MagazineB1Plugin;
+
+impl Plugin for MagazineB1Plugin {
+    fn build(&self, app: &mut App) {
+        register_entity_type::<MagazineB1Type>(app);
+        register_basic_console_commands_for_type::<MagazineB1Type>(app);
+        register_basic_console_commands_for_inventory_item_type::<MagazineB1Type>(app);
+        app.add_system(build_magazines_b1::<MagazineB1Type>.after(BuildingLabels::TriggerBuild))
+            .add_system((build_base_entities::<MagazineB1Type>).after(BuildingLabels::TriggerBuild))
+            .add_system((build_rigid_bodies::<MagazineB1Type>).after(BuildingLabels::TriggerBuild))
+            .add_system(
+                (build_inventory_items::<MagazineB1Type>).after(BuildingLabels::TriggerBuild),
+            );
+    }
+}
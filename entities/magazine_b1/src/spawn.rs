@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use bevy::math::Vec3;
+use bevy::prelude::Commands;
+use bevy::prelude::EventReader;
+use bevy::prelude::Transform;
+use bevy_rapier3d::prelude::{CoefficientCombineRule, Collider, Friction};
+use combat::ballistics::{Magazine, CALIBER_9MM};
+use entity::entity_macros::Identity;
+use entity::entity_types::EntityType;
+use entity::examine::Examinable;
+use entity::examine::RichName;
+use entity::spawn::BaseEntityBuilder;
+use entity::spawn::BaseEntityBundle;
+use entity::spawn::EntityBuildData;
+use entity::spawn::NoData;
+use entity::spawn::SpawnEntity;
+use inventory::item::InventoryItem;
+use inventory::server::inventory::SlotType;
+use inventory::spawn_item::InventoryItemBuilder;
+use inventory::spawn_item::InventoryItemBundle;
+use physics::rigid_body::STANDARD_BODY_FRICTION;
+use physics::spawn::RigidBodyBuilder;
+use physics::spawn::RigidBodyBundle;
+use resources::content::SF_CONTENT_PREFIX;
+use resources::math::Vec2Int;
+
+pub fn get_default_transform() -> Transform {
+    Transform::IDENTITY
+}
+
+impl BaseEntityBuilder<NoData> for MagazineB1Type {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> BaseEntityBundle {
+        let mut examine_map = BTreeMap::new();
+        examine_map.insert(
+            0,
+            "A magazine of 9mm rounds for ballistic pistols.".to_string(),
+        );
+        BaseEntityBundle {
+            default_transform: get_default_transform(),
+            examinable: Examinable {
+                assigned_texts: examine_map,
+                name: RichName {
+                    name: "pistol magazine".to_string(),
+                    n: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            entity_type: Box::new(MagazineB1Type::new()),
+            ..Default::default()
+        }
+    }
+}
+
+impl InventoryItemBuilder for MagazineB1Type {
+    fn get_bundle(&self, spawn_data: &EntityBuildData) -> InventoryItemBundle {
+        let mut attachment_transforms = HashMap::new();
+
+        attachment_transforms.insert(
+            "left_hand".to_string(),
+            Transform::from_translation(Vec3::new(0., -0.003, -0.108)),
+        );
+        attachment_transforms.insert(
+            "right_hand".to_string(),
+            Transform::from_translation(Vec3::new(0.064, -0.019, 0.065)),
+        );
+
+        InventoryItemBundle {
+            inventory_item: InventoryItem {
+                in_inventory_of_entity: spawn_data.holder_entity_option,
+                attachment_transforms: attachment_transforms,
+                drop_transform: get_default_transform(),
+                slot_type: SlotType::Generic,
+                slot_size: Vec2Int { x: 1, y: 2 },
+                throw_force_factor: 1.,
+                ..Default::default()
+            },
+            melee_combat: Default::default(),
+            projectile_combat_option: None,
+        }
+    }
+}
+
+impl RigidBodyBuilder<NoData> for MagazineB1Type {
+    fn get_bundle(&self, _spawn_data: &EntityBuildData, _entity_data: NoData) -> RigidBodyBundle {
+        let mut friction = Friction::coefficient(STANDARD_BODY_FRICTION);
+        friction.combine_rule = CoefficientCombineRule::Multiply;
+
+        RigidBodyBundle {
+            collider: Collider::cuboid(0.02, 0.06, 0.015),
+            collider_transform: Transform::from_translation(Vec3::new(0., 0.06, 0.)),
+            collider_friction: friction,
+
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Identity)]
+pub struct MagazineB1Type {
+    pub identifier: String,
+}
+impl Default for MagazineB1Type {
+    fn default() -> Self {
+        Self {
+            identifier: SF_CONTENT_PREFIX.to_string() + "magazine_b1",
+        }
+    }
+}
+
+/// Rounds of a full magazine.
+pub const MAGAZINE_B1_CAPACITY: u16 = 12;
+
+pub fn build_magazines_b1<T: Send + Sync + 'static>(
+    mut commands: Commands,
+    mut spawn_events: EventReader<SpawnEntity<T>>,
+) {
+    for spawn_event in spawn_events.iter() {
+        commands
+            .entity(spawn_event.spawn_data.entity)
+            .insert(Magazine::new_full(CALIBER_9MM, MAGAZINE_B1_CAPACITY));
+    }
+}
@@ -0,0 +1,6 @@
+//! Magnetic boots that restore walking on hull floors in zero-G.
+
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Spawner.
+pub mod spawn;
@@ -0,0 +1,29 @@
+use basic_console_commands::register::{
+    register_basic_console_commands_for_inventory_item_type,
+    register_basic_console_commands_for_type,
+};
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use entity::{entity_types::register_entity_type, spawn::build_base_entities};
+use inventory::spawn_item::build_inventory_items;
+use physics::spawn::build_rigid_bodies;
+use resources::labels::BuildingLabels;
+
+use super::spawn::{build_magnetic_boots, MagneticBootsType};
+
+pub struct MagneticBootsPlugin;
+
+impl Plugin for MagneticBootsPlugin {
+    fn build(&self, app: &mut App) {
+        register_entity_type::<MagneticBootsType>(app);
+        register_basic_console_commands_for_type::<MagneticBootsType>(app);
+        register_basic_console_commands_for_inventory_item_type::<MagneticBootsType>(app);
+        app.add_system(
+            build_magnetic_boots::<MagneticBootsType>.after(BuildingLabels::TriggerBuild),
+        )
+        .add_system((build_base_entities::<MagneticBootsType>).after(BuildingLabels::TriggerBuild))
+        .add_system((build_rigid_bodies::<MagneticBootsType>).after(BuildingLabels::TriggerBuild))
+        .add_system(
+            (build_inventory_items::<MagneticBootsType>).after(BuildingLabels::TriggerBuild),
+        );
+    }
+}
@@ -0,0 +1,8 @@
+//! A ballistic pistol that fires rounds from magazines.
+
+/// Component.
+pub mod pistol_b1;
+/// The Bevy plugin of this crate.
+pub mod plugin;
+/// Spawner.
+pub mod spawn;
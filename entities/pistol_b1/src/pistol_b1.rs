@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+/// The component.
+#[derive(Component)]
+
+pub struct PistolB1;
@@ -0,0 +1,55 @@
+use basic_console_commands::register::{
+    register_basic_console_commands_for_inventory_item_type,
+    register_basic_console_commands_for_type,
+};
+use bevy::prelude::{App, IntoSystemConfig, Plugin};
+use combat::{
+    melee_queries::melee_attack_handler,
+    projectile_queries::projectile_attack_handler,
+    sfx::{attack_sfx, health_combat_hit_result_sfx},
+};
+use entity::{entity_types::register_entity_type, spawn::build_base_entities};
+use inventory::spawn_item::build_inventory_items;
+use physics::spawn::build_rigid_bodies;
+use resources::{
+    is_server::is_server,
+    labels::{BuildingLabels, CombatLabels},
+};
+
+use crate::pistol_b1::PistolB1;
+
+use super::spawn::{build_pistols_b1, PistolB1Type};
+
+pub struct PistolB1Plugin;
+
+impl Plugin for PistolB1Plugin {
+    fn build(&self, app: &mut App) {
+        if is_server() {
+            app.add_system(
+                melee_attack_handler::<PistolB1>
+                    .in_set(CombatLabels::WeaponHandler)
+                    .after(CombatLabels::CacheAttack),
+            )
+            .add_system(
+                projectile_attack_handler::<PistolB1>
+                    .in_set(CombatLabels::WeaponHandler)
+                    .after(CombatLabels::CacheAttack),
+            )
+            .add_system(
+                attack_sfx::<PistolB1>
+                    .after(CombatLabels::WeaponHandler)
+                    .after(CombatLabels::Query),
+            )
+            .add_system(
+                health_combat_hit_result_sfx::<PistolB1>.after(CombatLabels::FinalizeApplyDamage),
+            );
+        }
+        register_entity_type::<PistolB1Type>(app);
+        register_basic_console_commands_for_type::<PistolB1Type>(app);
+        register_basic_console_commands_for_inventory_item_type::<PistolB1Type>(app);
+        app.add_system((build_base_entities::<PistolB1Type>).after(BuildingLabels::TriggerBuild))
+            .add_system((build_rigid_bodies::<PistolB1Type>).after(BuildingLabels::TriggerBuild))
+            .add_system((build_inventory_items::<PistolB1Type>).after(BuildingLabels::TriggerBuild))
+            .add_system(build_pistols_b1::<PistolB1Type>.after(BuildingLabels::TriggerBuild));
+    }
+}
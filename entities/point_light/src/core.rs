@@ -1 +1,40 @@
+use bevy::prelude::{Changed, Component, PointLight, Query};
+use entity::sensable::Sensable;
+use power::power::PowerConsumer;
 
+/// Power drawn by point lights in watts.
+pub const POINT_LIGHT_POWER_LOAD: f32 = 60.;
+
+/// Intensity of a point light while it is powered.
+#[derive(Component)]
+
+pub struct PoweredIntensity {
+    pub intensity: f32,
+}
+
+/// Turn point lights dark while they have no power.
+
+pub(crate) fn point_light_power(
+    mut lights: Query<(&mut PointLight, &PoweredIntensity, &PowerConsumer), Changed<PowerConsumer>>,
+) {
+    for (mut light, powered_intensity, consumer) in lights.iter_mut() {
+        match consumer.powered {
+            true => {
+                light.intensity = powered_intensity.intensity;
+            }
+            false => {
+                light.intensity = 0.;
+            }
+        }
+    }
+}
+
+/// Dark point lights can no longer be sensed from afar.
+
+pub(crate) fn point_light_sensing(
+    mut lights: Query<(&mut Sensable, &PowerConsumer), Changed<PowerConsumer>>,
+) {
+    for (mut sensable, consumer) in lights.iter_mut() {
+        sensable.is_light = consumer.powered;
+    }
+}
@@ -1,14 +1,23 @@
 use bevy::prelude::{App, IntoSystemConfig, Plugin};
-use entity::entity_types::register_entity_type;
-use resources::labels::BuildingLabels;
+use entity::entity_types::{register_entity_type, register_grid_entity_type};
+use resources::{is_server::is_server, labels::BuildingLabels};
 
-use crate::spawn::{build_point_lights, PointLightType};
+use crate::{
+    core::{point_light_power, point_light_sensing},
+    spawn::{build_point_lights, PointLightType},
+};
 
 pub struct PointLightPlugin;
 
 impl Plugin for PointLightPlugin {
     fn build(&self, app: &mut App) {
+        if is_server() {
+            app.add_system(point_light_sensing);
+        } else {
+            app.add_system(point_light_power);
+        }
         app.add_system((build_point_lights::<PointLightType>).after(BuildingLabels::TriggerBuild));
         register_entity_type::<PointLightType>(app);
+        register_grid_entity_type::<PointLightType>(app);
     }
 }
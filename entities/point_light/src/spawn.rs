@@ -1,13 +1,19 @@
-use bevy::prelude::{Commands, EventReader, PointLight, PointLightBundle, Transform};
+use bevy::prelude::{Commands, EventReader, PointLight, PointLightBundle, Transform, Vec3};
 use entity::{
-    entity_data::{EntityData, EntityGroup, EntityUpdates, WorldMode, WorldModes},
+    entity_data::{
+        DefaultMapEntity, EntityData, EntityGroup, EntityUpdates, GridEntity, GridItemData,
+        WorldMode, WorldModes,
+    },
     entity_macros::Identity,
     entity_types::EntityType,
     sensable::Sensable,
     spawn::{EntityBuildData, SpawnEntity},
 };
+use power::power::PowerConsumer;
 use resources::content::SF_CONTENT_PREFIX;
 
+use crate::core::{PoweredIntensity, POINT_LIGHT_POWER_LOAD};
+
 pub struct PointLightBuilderBundle;
 
 impl PointLightBuilderBundle {
@@ -52,6 +58,19 @@ impl Default for PointLightType {
     }
 }
 
+/// Height of a placed point light above the floor of its cell.
+
+pub const POINT_LIGHT_Y: f32 = 1.9;
+
+impl GridEntity for PointLightType {
+    fn get_grid_item_data() -> GridItemData {
+        GridItemData {
+            transform_offset: Transform::from_translation(Vec3::new(0., POINT_LIGHT_Y, 0.)),
+            can_be_built_with_grid_item: vec![],
+        }
+    }
+}
+
 pub fn build_point_lights<T: PointLightBuilder + 'static>(
     mut spawn_events: EventReader<SpawnEntity<T>>,
     mut commands: Commands,
@@ -69,7 +88,7 @@ pub trait PointLightBuilder: Send + Sync {
 
 impl PointLightBuilder for PointLightType {
     fn spawn(&self, spawn_data: &EntityBuildData, commands: &mut Commands) {
-        commands.spawn((
+        commands.entity(spawn_data.entity).insert((
             PointLightBundle {
                 point_light: self.light.clone(),
                 transform: spawn_data.entity_transform,
@@ -87,7 +106,17 @@ impl PointLightBuilder for PointLightType {
             WorldMode {
                 mode: WorldModes::Static,
             },
+            PowerConsumer {
+                load: POINT_LIGHT_POWER_LOAD,
+                ..Default::default()
+            },
+            PoweredIntensity {
+                intensity: self.light.intensity,
+            },
         ));
+        if spawn_data.default_map_spawn {
+            commands.entity(spawn_data.entity).insert(DefaultMapEntity);
+        }
     }
 }
 